@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::{get_return_data, invoke, invoke_signed},
+};
+
+use crate::{
+    error::AdapterInterfaceError, instruction, AdapterDescription, DeliveryQuote, GetPeerArgs,
+    QuoteDeliveryArgs, ReceiveOutboundArgs,
+};
+
+/// Calls `receive_outbound` on `adapter_program`, signed by the outbound authority with
+/// `signer_seeds`
+pub fn receive_outbound<'info>(
+    adapter_program: &AccountInfo<'info>,
+    outbound_authority: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    args: &ReceiveOutboundArgs,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let ix = instruction::receive_outbound(
+        adapter_program.key(),
+        outbound_authority.key(),
+        account_metas(accounts),
+        args,
+    );
+
+    let mut account_infos = vec![outbound_authority.clone()];
+    account_infos.extend_from_slice(accounts);
+    account_infos.push(adapter_program.clone());
+
+    invoke_signed(&ix, &account_infos, signer_seeds).map_err(Into::into)
+}
+
+/// Calls `quote_delivery` on `adapter_program` and returns its quote
+pub fn quote_delivery<'info>(
+    adapter_program: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    args: &QuoteDeliveryArgs,
+) -> Result<DeliveryQuote> {
+    let ix = instruction::quote_delivery(adapter_program.key(), account_metas(accounts), args);
+    invoke_view(ix, adapter_program, accounts)
+}
+
+/// Calls `describe` on `adapter_program` and returns its description
+pub fn describe<'info>(
+    adapter_program: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+) -> Result<AdapterDescription> {
+    let ix = instruction::describe(adapter_program.key(), account_metas(accounts));
+    invoke_view(ix, adapter_program, accounts)
+}
+
+/// Calls `get_peer` on `adapter_program` and returns its peer on the chain, if any
+pub fn get_peer<'info>(
+    adapter_program: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    args: &GetPeerArgs,
+) -> Result<Option<[u8; 32]>> {
+    let ix = instruction::get_peer(adapter_program.key(), account_metas(accounts), args);
+    invoke_view(ix, adapter_program, accounts)
+}
+
+/// The metas of `accounts`, keeping their signer and writable flags
+fn account_metas(accounts: &[AccountInfo]) -> Vec<AccountMeta> {
+    accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        })
+        .collect()
+}
+
+/// Invokes `ix` and deserializes the return data set by `adapter_program`
+fn invoke_view<'info, T: AnchorDeserialize>(
+    ix: Instruction,
+    adapter_program: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+) -> Result<T> {
+    let mut account_infos = accounts.to_vec();
+    account_infos.push(adapter_program.clone());
+    invoke(&ix, &account_infos)?;
+
+    match get_return_data() {
+        Some((program_id, data)) if program_id == adapter_program.key() => {
+            T::try_from_slice(&data).map_err(Into::into)
+        }
+        _ => err!(AdapterInterfaceError::MissingReturnData),
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Errors of the adapter interface client
+///
+/// These are returned inside the endpoint program, so they start at 7000 to stay clear of the
+/// endpoint's own error codes, which start at 6000.
+#[error_code(offset = 7000)]
+pub enum AdapterInterfaceError {
+    #[msg("Adapter did not return data")]
+    MissingReturnData,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(u32::from(AdapterInterfaceError::MissingReturnData), 7000);
+    }
+}
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+
+use crate::{GetPeerArgs, QuoteDeliveryArgs, ReceiveOutboundArgs};
+
+/// Discriminator of `receive_outbound`
+///
+/// Accounts:
+/// 0. `[signer]` The endpoint's outbound authority for `args.src_addr`, which the adapter must
+///    check
+/// 1. .. The adapter's own accounts
+pub const RECEIVE_OUTBOUND: [u8; 8] = [186, 41, 227, 11, 17, 193, 81, 230];
+
+/// Discriminator of `quote_delivery`, which returns a `DeliveryQuote`
+/// The adapter fails the instruction if it cannot deliver to the chain at the consistency level
+///
+/// Accounts:
+/// 0. .. The adapter's own accounts, read-only
+pub const QUOTE_DELIVERY: [u8; 8] = [106, 135, 89, 199, 233, 247, 248, 209];
+
+/// Discriminator of `describe`, which returns an `AdapterDescription`
+///
+/// Accounts:
+/// 0. .. The adapter's own accounts, read-only
+pub const DESCRIBE: [u8; 8] = [230, 44, 148, 126, 188, 197, 69, 232];
+
+/// Discriminator of `get_peer`, which returns the universal address of the adapter on a chain,
+/// if it has one, as an `Option<[u8; 32]>`
+///
+/// Accounts:
+/// 0. .. The adapter's own accounts, read-only
+pub const GET_PEER: [u8; 8] = [1, 18, 69, 119, 168, 135, 133, 21];
+
+/// Builds a `receive_outbound` instruction
+pub fn receive_outbound(
+    adapter_program_id: Pubkey,
+    outbound_authority: Pubkey,
+    accounts: Vec<AccountMeta>,
+    args: &ReceiveOutboundArgs,
+) -> Instruction {
+    let mut account_metas = vec![AccountMeta::new_readonly(outbound_authority, true)];
+    account_metas.extend(accounts);
+    build(adapter_program_id, RECEIVE_OUTBOUND, account_metas, args)
+}
+
+/// Builds a `quote_delivery` instruction
+pub fn quote_delivery(
+    adapter_program_id: Pubkey,
+    accounts: Vec<AccountMeta>,
+    args: &QuoteDeliveryArgs,
+) -> Instruction {
+    build(adapter_program_id, QUOTE_DELIVERY, accounts, args)
+}
+
+/// Builds a `describe` instruction
+pub fn describe(adapter_program_id: Pubkey, accounts: Vec<AccountMeta>) -> Instruction {
+    build(adapter_program_id, DESCRIBE, accounts, &())
+}
+
+/// Builds a `get_peer` instruction
+pub fn get_peer(
+    adapter_program_id: Pubkey,
+    accounts: Vec<AccountMeta>,
+    args: &GetPeerArgs,
+) -> Instruction {
+    build(adapter_program_id, GET_PEER, accounts, args)
+}
+
+fn build<T: AnchorSerialize>(
+    program_id: Pubkey,
+    discriminator: [u8; 8],
+    accounts: Vec<AccountMeta>,
+    args: &T,
+) -> Instruction {
+    let mut data = discriminator.to_vec();
+    args.serialize(&mut data).unwrap();
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+
+    fn sighash(name: &str) -> [u8; 8] {
+        hash(format!("global:{name}").as_bytes()).to_bytes()[..8]
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_discriminators() {
+        assert_eq!(RECEIVE_OUTBOUND, sighash("receive_outbound"));
+        assert_eq!(QUOTE_DELIVERY, sighash("quote_delivery"));
+        assert_eq!(DESCRIBE, sighash("describe"));
+        assert_eq!(GET_PEER, sighash("get_peer"));
+    }
+
+    #[test]
+    fn test_receive_outbound_accounts() {
+        let outbound_authority = Pubkey::new_unique();
+        let adapter_account = AccountMeta::new(Pubkey::new_unique(), false);
+        let args = ReceiveOutboundArgs {
+            src_addr: [1; 32],
+            sequence: 2,
+            dst_chain: 3,
+            dst_addr: [4; 32],
+            payload_hash: [5; 32],
+            consistency_level: 1,
+            expiry: None,
+        };
+
+        let ix = receive_outbound(
+            Pubkey::new_unique(),
+            outbound_authority,
+            vec![adapter_account.clone()],
+            &args,
+        );
+
+        assert_eq!(
+            ix.accounts,
+            vec![
+                AccountMeta::new_readonly(outbound_authority, true),
+                adapter_account
+            ]
+        );
+        assert_eq!(ix.data[..8], RECEIVE_OUTBOUND);
+        assert_eq!(
+            ReceiveOutboundArgs::try_from_slice(&ix.data[8..]).unwrap(),
+            args
+        );
+    }
+}
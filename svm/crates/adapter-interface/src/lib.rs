@@ -0,0 +1,52 @@
+//! The instructions that SVM adapters implement, so that the endpoint, relayers and integrators
+//! can call any adapter the same way.
+//!
+//! Every instruction has a fixed discriminator, which is the Anchor discriminator of an
+//! instruction with the same name. An Anchor adapter implements the interface by declaring
+//! `receive_outbound`, `quote_delivery`, `describe` and `get_peer` in its `#[program]` module,
+//! taking and returning the types of this crate. The first accounts of each instruction are
+//! fixed by the interface, and the adapter may expect its own accounts after them.
+//!
+//! In the other direction, adapters sign the endpoint's `pick_up_message` and `attest_message`
+//! instructions with their `adapter_pda`.
+
+use anchor_lang::prelude::*;
+
+pub mod cpi;
+pub mod error;
+pub mod instruction;
+mod message_expiry;
+mod types;
+
+pub use message_expiry::*;
+pub use types::*;
+
+/// The version of the interface implemented by adapters built against this crate
+pub const INTERFACE_VERSION: u8 = 1;
+
+/// Seed of the PDA that an adapter program signs the endpoint's adapter instructions with
+pub const ADAPTER_PDA_SEED: &[u8] = b"adapter_pda";
+
+/// Seed of the endpoint PDA that signs `receive_outbound`, followed by the sending integrator's
+/// program ID
+pub const OUTBOUND_AUTHORITY_SEED: &[u8] = b"outbound_authority";
+
+/// Returns the PDA that the adapter program signs the endpoint's adapter instructions with
+pub fn adapter_pda(adapter_program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ADAPTER_PDA_SEED], adapter_program_id)
+}
+
+/// Returns the PDA that the endpoint program signs `receive_outbound` with for messages sent by
+/// `integrator_program_id`
+///
+/// Adapters must check that the signer is the outbound authority of `args.src_addr`, so that one
+/// integrator cannot push messages in the name of another.
+pub fn outbound_authority(
+    endpoint_program_id: &Pubkey,
+    integrator_program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OUTBOUND_AUTHORITY_SEED, integrator_program_id.as_ref()],
+        endpoint_program_id,
+    )
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+/// A sender-chosen deadline after which a message can no longer be received.
+///
+/// The expiry is committed into the message hash (see the endpoint's
+/// `AttestationInfo::compute_message_hash`), so it is carried to the destination chain by the
+/// adapters and cannot be changed in transit.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageExpiry {
+    /// The last unix timestamp (in seconds) at which the message can be received
+    Timestamp(u64),
+
+    /// The last destination chain block number at which the message can be received
+    /// On Solana this is the slot. Intended for same-chain flows, where the sender knows the
+    /// destination chain's block production rate.
+    Slot(u64),
+}
+
+impl MessageExpiry {
+    /// Kind byte committed into the message hash for `Timestamp`
+    pub const KIND_TIMESTAMP: u8 = 1;
+
+    /// Kind byte committed into the message hash for `Slot`
+    pub const KIND_SLOT: u8 = 2;
+
+    /// The kind byte committed into the message hash
+    pub fn kind(&self) -> u8 {
+        match self {
+            MessageExpiry::Timestamp(_) => Self::KIND_TIMESTAMP,
+            MessageExpiry::Slot(_) => Self::KIND_SLOT,
+        }
+    }
+
+    /// The deadline committed into the message hash
+    pub fn deadline(&self) -> u64 {
+        match self {
+            MessageExpiry::Timestamp(deadline) | MessageExpiry::Slot(deadline) => *deadline,
+        }
+    }
+
+    /// Returns `true` once `clock` is past the deadline
+    pub fn has_expired(&self, clock: &Clock) -> bool {
+        match self {
+            MessageExpiry::Timestamp(deadline) => {
+                u64::try_from(clock.unix_timestamp).unwrap_or(0) > *deadline
+            }
+            MessageExpiry::Slot(deadline) => clock.slot > *deadline,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(slot: u64, unix_timestamp: i64) -> Clock {
+        Clock {
+            slot,
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn test_timestamp_expiry() {
+        let expiry = MessageExpiry::Timestamp(1_000);
+        assert!(!expiry.has_expired(&clock(5_000, 999)));
+        assert!(!expiry.has_expired(&clock(5_000, 1_000)));
+        assert!(expiry.has_expired(&clock(0, 1_001)));
+    }
+
+    #[test]
+    fn test_slot_expiry() {
+        let expiry = MessageExpiry::Slot(1_000);
+        assert!(!expiry.has_expired(&clock(1_000, 5_000)));
+        assert!(expiry.has_expired(&clock(1_001, 0)));
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::MessageExpiry;
+
+/// The arguments of `receive_outbound`, describing the message being sent
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReceiveOutboundArgs {
+    pub src_addr: [u8; 32],
+    pub sequence: u64,
+    pub dst_chain: u16,
+    pub dst_addr: [u8; 32],
+    pub payload_hash: [u8; 32],
+    pub consistency_level: u8,
+    pub expiry: Option<MessageExpiry>,
+}
+
+/// The arguments of `quote_delivery`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct QuoteDeliveryArgs {
+    pub dst_chain: u16,
+    pub consistency_level: u8,
+}
+
+/// The result of `quote_delivery`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DeliveryQuote {
+    /// The fee in lamports charged by the adapter to deliver a message
+    pub fee: u64,
+}
+
+/// The result of `describe`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AdapterDescription {
+    /// The version of the interface implemented by the adapter
+    pub interface_version: u8,
+
+    /// The consistency levels the adapter can honour, or `None` if it accepts any level
+    pub supported_consistency_levels: Option<Vec<u8>>,
+
+    /// Whether the adapter implements `receive_outbound`, so that messages can be pushed to it
+    pub supports_push: bool,
+}
+
+/// The arguments of `get_peer`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GetPeerArgs {
+    pub chain_id: u16,
+}
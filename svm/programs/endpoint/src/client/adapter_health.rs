@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    AdapterStats, AttestationInfo, IntegratorChainConfig, IntegratorConfig, OutboxMessage,
+};
+
+/// An adapter that has work waiting on it but has not acted recently
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdleAdapter {
+    /// Public key of the adapter's address
+    pub adapter_program_id: Pubkey,
+
+    /// Slot of the adapter's last pick up or attestation, or 0 if it never acted
+    pub last_active_slot: u64,
+
+    /// Number of messages currently waiting on the adapter
+    pub pending_messages: usize,
+}
+
+/// Returns the send adapters that still have outbox messages to pick up but have not picked
+/// up any message within `max_idle_slots` of `current_slot`.
+///
+/// # Arguments
+///
+/// * `integrator_config` - The integrator's config, used to map bitmap indices to adapters
+/// * `stats` - The `AdapterStats` accounts of the integrator's adapters
+/// * `outbox_messages` - The integrator's open `OutboxMessage` accounts
+/// * `current_slot` - The slot to measure idleness against
+/// * `max_idle_slots` - How many slots an adapter may go without picking up a message
+pub fn idle_send_adapters(
+    integrator_config: &IntegratorConfig,
+    stats: &[AdapterStats],
+    outbox_messages: &[OutboxMessage],
+    current_slot: u64,
+    max_idle_slots: u64,
+) -> Vec<IdleAdapter> {
+    let src_addr = integrator_config.integrator_program_id.to_bytes();
+
+    idle_adapters(
+        integrator_config,
+        stats,
+        current_slot,
+        max_idle_slots,
+        |stats| stats.last_pickup_slot,
+        |index| {
+            outbox_messages
+                .iter()
+                .filter(|message| message.src_addr == src_addr)
+                .filter(|message| message.outstanding_adapters.get(index).unwrap_or(false))
+                .count()
+        },
+    )
+}
+
+/// Returns the receive adapters that are enabled for the source chain of an unexecuted
+/// message they have not attested to, but have not attested to any message within
+/// `max_idle_slots` of `current_slot`.
+///
+/// # Arguments
+///
+/// * `integrator_config` - The integrator's config, used to map bitmap indices to adapters
+/// * `chain_configs` - The integrator's `IntegratorChainConfig` accounts
+/// * `stats` - The `AdapterStats` accounts of the integrator's adapters
+/// * `attestation_infos` - The integrator's `AttestationInfo` accounts, each paired with the
+///   source chain of its message (as emitted in its `MessageAttestedTo` events)
+/// * `current_slot` - The slot to measure idleness against
+/// * `max_idle_slots` - How many slots an adapter may go without attesting to a message
+pub fn idle_recv_adapters(
+    integrator_config: &IntegratorConfig,
+    chain_configs: &[IntegratorChainConfig],
+    stats: &[AdapterStats],
+    attestation_infos: &[(u16, AttestationInfo)],
+    current_slot: u64,
+    max_idle_slots: u64,
+) -> Vec<IdleAdapter> {
+    idle_adapters(
+        integrator_config,
+        stats,
+        current_slot,
+        max_idle_slots,
+        |stats| stats.last_attestation_slot,
+        |index| {
+            attestation_infos
+                .iter()
+                .filter(|(_, info)| !info.executed)
+                .filter(|(_, info)| !info.attested_adapters.get(index).unwrap_or(false))
+                .filter(|(src_chain, _)| {
+                    chain_configs.iter().any(|config| {
+                        config.chain_id == *src_chain
+                            && config.recv_adapter_bitmap.get(index).unwrap_or(false)
+                    })
+                })
+                .count()
+        },
+    )
+}
+
+fn idle_adapters(
+    integrator_config: &IntegratorConfig,
+    stats: &[AdapterStats],
+    current_slot: u64,
+    max_idle_slots: u64,
+    last_active_slot: impl Fn(&AdapterStats) -> u64,
+    pending_messages: impl Fn(u8) -> usize,
+) -> Vec<IdleAdapter> {
+    integrator_config
+        .adapter_infos
+        .iter()
+        .enumerate()
+        .filter_map(|(index, adapter_program_id)| {
+            let pending_messages = pending_messages(index as u8);
+            if pending_messages == 0 {
+                return None;
+            }
+
+            let last_active_slot = stats
+                .iter()
+                .find(|stats| stats.adapter_program_id == *adapter_program_id)
+                .map(&last_active_slot)
+                .unwrap_or(0);
+
+            if current_slot.saturating_sub(last_active_slot) <= max_idle_slots {
+                return None;
+            }
+
+            Some(IdleAdapter {
+                adapter_program_id: *adapter_program_id,
+                last_active_slot,
+                pending_messages,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::bitmap::Bitmap;
+
+    fn integrator_config(adapters: Vec<Pubkey>) -> IntegratorConfig {
+        IntegratorConfig {
+            bump: 0,
+            integrator_program_id: Pubkey::new_unique(),
+            admin: None,
+            pending_admin: None,
+            adapter_infos: adapters,
+            audit_log_len: 0,
+            config_digest: [0; 32],
+            admin_nonce: 0,
+        }
+    }
+
+    fn stats(integrator: &IntegratorConfig, adapter: Pubkey, last_slot: u64) -> AdapterStats {
+        AdapterStats {
+            bump: 0,
+            integrator_program_id: integrator.integrator_program_id,
+            adapter_program_id: adapter,
+            messages_picked_up: 1,
+            messages_attested: 1,
+            last_pickup_slot: last_slot,
+            last_attestation_slot: last_slot,
+            outbox_messages_closed: 0,
+        }
+    }
+
+    #[test]
+    fn test_idle_send_adapters() {
+        let active = Pubkey::new_unique();
+        let idle = Pubkey::new_unique();
+        let config = integrator_config(vec![active, idle]);
+        let stats = vec![stats(&config, active, 990), stats(&config, idle, 100)];
+
+        let outbox_message = OutboxMessage {
+            src_addr: config.integrator_program_id.to_bytes(),
+            sequence: 0,
+            dst_chain: 2,
+            dst_addr: [0; 32],
+            payload_hash: [0; 32],
+            consistency_level: 0,
+            expiry: None,
+            initial_adapters: Bitmap::from_value(0b11),
+            outstanding_adapters: Bitmap::from_value(0b11),
+            refund_recipient: Pubkey::default(),
+        };
+
+        let result = idle_send_adapters(&config, &stats, &[outbox_message], 1000, 50);
+        assert_eq!(
+            result,
+            vec![IdleAdapter {
+                adapter_program_id: idle,
+                last_active_slot: 100,
+                pending_messages: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_idle_send_adapters_without_pending_messages() {
+        let idle = Pubkey::new_unique();
+        let config = integrator_config(vec![idle]);
+        let stats = vec![stats(&config, idle, 0)];
+
+        assert!(idle_send_adapters(&config, &stats, &[], 1000, 50).is_empty());
+    }
+
+    #[test]
+    fn test_idle_recv_adapters() {
+        let attested = Pubkey::new_unique();
+        let idle = Pubkey::new_unique();
+        let disabled = Pubkey::new_unique();
+        let config = integrator_config(vec![attested, idle, disabled]);
+        let stats = vec![
+            stats(&config, attested, 100),
+            stats(&config, idle, 100),
+            stats(&config, disabled, 100),
+        ];
+
+        let chain_config = IntegratorChainConfig {
+            bump: 0,
+            integrator_program_id: config.integrator_program_id,
+            chain_id: 2,
+            send_adapter_bitmap: Bitmap::new(),
+            recv_adapter_bitmap: Bitmap::from_value(0b011),
+            config_epoch: 1,
+            ordered_delivery: false,
+            replay_windows: false,
+        };
+
+        let message_hash = AttestationInfo::compute_message_hash(
+            2,
+            [1; 32],
+            0,
+            crate::CHAIN_ID,
+            config.integrator_program_id.to_bytes(),
+            [2; 32],
+            None,
+        );
+        let mut attestation_info = AttestationInfo::new(0, message_hash, None, Pubkey::default());
+        attestation_info.attested_adapters.set(0, true).unwrap();
+
+        let result = idle_recv_adapters(
+            &config,
+            &[chain_config],
+            &stats,
+            &[(2, attestation_info)],
+            1000,
+            50,
+        );
+        assert_eq!(
+            result,
+            vec![IdleAdapter {
+                adapter_program_id: idle,
+                last_active_slot: 100,
+                pending_messages: 1,
+            }]
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use spl_concurrent_merkle_tree::{hash::hash_to_parent, node::empty_node};
+
+use crate::event::AttestationLeafUpdated;
+use crate::state::{AttestationTree, CompressedAttestation};
+
+/// A proof of a leaf of an `AttestationTree`, as expected by `attest_message_compressed` and
+/// `recv_message_compressed`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttestationTreeProof {
+    /// The root the proof was built against
+    pub root: [u8; 32],
+
+    /// The index of the leaf
+    pub leaf_index: u32,
+
+    /// The sibling nodes from the leaf up, without the trailing nodes of empty subtrees
+    pub proof: Vec<[u8; 32]>,
+}
+
+impl AttestationTreeProof {
+    /// The proof nodes as the instruction's `remaining_accounts`
+    pub fn remaining_accounts(&self) -> Vec<AccountMeta> {
+        self.proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(Pubkey::new_from_array(*node), false))
+            .collect()
+    }
+}
+
+/// The leaves of an `AttestationTree`, rebuilt from its `AttestationLeafUpdated` events
+#[derive(Debug, Clone, Default)]
+pub struct AttestationTreeIndex {
+    leaves: Vec<CompressedAttestation>,
+}
+
+impl AttestationTreeIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies an `AttestationLeafUpdated` event
+    /// Events must be applied in the order they were emitted
+    pub fn apply(&mut self, event: &AttestationLeafUpdated) {
+        self.set_leaf(event.leaf_index, event.leaf);
+    }
+
+    /// Sets the leaf at `leaf_index`, which must be an existing leaf or the next one to append
+    pub fn set_leaf(&mut self, leaf_index: u32, leaf: CompressedAttestation) {
+        let leaf_index = leaf_index as usize;
+        assert!(leaf_index <= self.leaves.len(), "Leaf index out of order");
+        if leaf_index == self.leaves.len() {
+            self.leaves.push(leaf);
+        } else {
+            self.leaves[leaf_index] = leaf;
+        }
+    }
+
+    /// Returns the leaf at `leaf_index`
+    pub fn leaf(&self, leaf_index: u32) -> Option<&CompressedAttestation> {
+        self.leaves.get(leaf_index as usize)
+    }
+
+    /// Returns the number of leaves appended to the tree
+    pub fn leaf_count(&self) -> u32 {
+        self.leaves.len() as u32
+    }
+
+    /// Returns the index and leaf of the message with `message_hash`
+    pub fn find(&self, message_hash: [u8; 32]) -> Option<(u32, &CompressedAttestation)> {
+        self.leaves
+            .iter()
+            .enumerate()
+            .find(|(_, leaf)| leaf.message_hash == message_hash)
+            .map(|(index, leaf)| (index as u32, leaf))
+    }
+
+    /// Returns the current root of the tree
+    pub fn root(&self) -> [u8; 32] {
+        self.layers().pop().unwrap()[0]
+    }
+
+    /// Returns a proof of the leaf at `leaf_index` against the current root
+    pub fn proof(&self, leaf_index: u32) -> Option<AttestationTreeProof> {
+        if leaf_index as usize >= self.leaves.len() {
+            return None;
+        }
+
+        let mut layers = self.layers();
+        let root = layers.pop().unwrap()[0];
+        let mut proof: Vec<[u8; 32]> = layers
+            .iter()
+            .enumerate()
+            .map(|(level, layer)| {
+                let sibling = (leaf_index as usize >> level) ^ 1;
+                layer
+                    .get(sibling)
+                    .copied()
+                    .unwrap_or_else(|| empty_node(level as u32))
+            })
+            .collect();
+
+        // Missing nodes are filled in with empty subtrees on chain
+        while !proof.is_empty() && proof.last() == Some(&empty_node(proof.len() as u32 - 1)) {
+            proof.pop();
+        }
+
+        Some(AttestationTreeProof {
+            root,
+            leaf_index,
+            proof,
+        })
+    }
+
+    /// The non-empty nodes of each level of the tree, from the leaves up to the root
+    fn layers(&self) -> Vec<Vec<[u8; 32]>> {
+        let mut layers = vec![self
+            .leaves
+            .iter()
+            .map(|leaf| leaf.hash())
+            .collect::<Vec<_>>()];
+        for level in 0..AttestationTree::MAX_DEPTH {
+            let parents = layers[level]
+                .chunks(2)
+                .map(|pair| {
+                    let mut node = pair[0];
+                    let sibling = pair.get(1).copied().unwrap_or(empty_node(level as u32));
+                    hash_to_parent(&mut node, &sibling, true);
+                    node
+                })
+                .collect::<Vec<_>>();
+            layers.push(parents);
+        }
+        if layers[AttestationTree::MAX_DEPTH].is_empty() {
+            layers[AttestationTree::MAX_DEPTH].push(empty_node(AttestationTree::MAX_DEPTH as u32));
+        }
+        layers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AttestationMerkleTree;
+    use crate::utils::bitmap::Bitmap;
+
+    fn leaf(seed: u8) -> CompressedAttestation {
+        CompressedAttestation::new([seed; 32], 0, Bitmap::from_value(1))
+    }
+
+    fn tree() -> Box<AttestationMerkleTree> {
+        let mut tree = Box::<AttestationMerkleTree>::default();
+        tree.initialize().unwrap();
+        tree
+    }
+
+    #[test]
+    fn test_root_matches_tree() {
+        let mut tree = tree();
+        let mut index = AttestationTreeIndex::new();
+        assert_eq!(index.root(), tree.get_root());
+
+        for seed in 1..=5 {
+            tree.append(leaf(seed).hash()).unwrap();
+            index.set_leaf(seed as u32 - 1, leaf(seed));
+            assert_eq!(index.root(), tree.get_root());
+        }
+    }
+
+    #[test]
+    fn test_proof_updates_tree() {
+        let mut tree = tree();
+        let mut index = AttestationTreeIndex::new();
+        for seed in 1..=3 {
+            tree.append(leaf(seed).hash()).unwrap();
+            index.set_leaf(seed as u32 - 1, leaf(seed));
+        }
+
+        let previous = *index.leaf(1).unwrap();
+        let mut updated = previous;
+        updated.executed = true;
+
+        let proof = index.proof(1).unwrap();
+        assert!(proof.proof.len() < AttestationTree::MAX_DEPTH);
+        tree.set_leaf(
+            proof.root,
+            previous.hash(),
+            updated.hash(),
+            &proof.proof,
+            proof.leaf_index,
+        )
+        .unwrap();
+
+        index.set_leaf(1, updated);
+        assert_eq!(index.root(), tree.get_root());
+        assert_eq!(index.find([2; 32]).unwrap().0, 1);
+    }
+
+    #[test]
+    fn test_proof_out_of_range() {
+        let mut index = AttestationTreeIndex::new();
+        assert!(index.proof(0).is_none());
+        index.set_leaf(0, leaf(1));
+        assert!(index.proof(0).is_some());
+        assert!(index.proof(1).is_none());
+    }
+}
@@ -0,0 +1,7 @@
+//! Off-chain helpers for clients, relayers and monitoring tools.
+//!
+//! Nothing in here is used by the on-chain program.
+
+pub mod adapter_health;
+pub mod attestation_tree;
+pub mod outbound_accumulator;
@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+
+use crate::event::MessageSent;
+use crate::state::{AttestationInfo, SequenceTracker};
+use crate::CHAIN_ID;
+
+/// Why a `MessageSent` event could not be applied to an `OutboundAccumulator`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutboundAccumulatorError {
+    /// The event was emitted for another integrator
+    WrongSender,
+
+    /// The event is not for the next sequence, so a message is missing or repeated
+    SequenceGap { expected: u64, found: u64 },
+
+    /// The accumulator in the event does not match the one recomputed from earlier events
+    Mismatch { sequence: u64 },
+}
+
+/// An integrator's outbound accumulator, recomputed from its `MessageSent` events
+///
+/// Once every event has been applied, `verify` checks the list of sent messages against the
+/// integrator's `SequenceTracker`. A match proves that the list is complete, with no message
+/// missing, repeated or altered.
+#[derive(Debug, Clone)]
+pub struct OutboundAccumulator {
+    sender: [u8; 32],
+    next_sequence: u64,
+    value: [u8; 32],
+}
+
+impl OutboundAccumulator {
+    pub fn new(integrator_program_id: &Pubkey) -> Self {
+        Self {
+            sender: integrator_program_id.to_bytes(),
+            next_sequence: 0,
+            value: [0; 32],
+        }
+    }
+
+    /// Applies a `MessageSent` event
+    /// Events must be applied in sequence order, starting from sequence 0
+    pub fn apply(
+        &mut self,
+        event: &MessageSent,
+    ) -> std::result::Result<(), OutboundAccumulatorError> {
+        if event.sender != self.sender {
+            return Err(OutboundAccumulatorError::WrongSender);
+        }
+        if event.sequence != self.next_sequence {
+            return Err(OutboundAccumulatorError::SequenceGap {
+                expected: self.next_sequence,
+                found: event.sequence,
+            });
+        }
+
+        let message_hash = AttestationInfo::compute_message_hash(
+            CHAIN_ID,
+            event.sender,
+            event.sequence,
+            event.recipient_chain,
+            event.recipient,
+            event.payload_digest,
+            event.expiry,
+        );
+        let value = SequenceTracker::fold_outbound_accumulator(self.value, message_hash);
+        if value != event.outbound_accumulator {
+            return Err(OutboundAccumulatorError::Mismatch {
+                sequence: event.sequence,
+            });
+        }
+
+        self.value = value;
+        self.next_sequence += 1;
+        Ok(())
+    }
+
+    /// Returns the recomputed accumulator
+    pub fn value(&self) -> [u8; 32] {
+        self.value
+    }
+
+    /// Returns the number of messages applied
+    pub fn message_count(&self) -> u64 {
+        self.next_sequence
+    }
+
+    /// Returns whether the applied events are every message recorded by `sequence_tracker`
+    pub fn verify(&self, sequence_tracker: &SequenceTracker) -> bool {
+        sequence_tracker.integrator_program_id.to_bytes() == self.sender
+            && sequence_tracker.sequence == self.next_sequence
+            && sequence_tracker.outbound_accumulator == self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::bitmap::Bitmap;
+
+    fn message_sent(
+        sender: &Pubkey,
+        sequence: u64,
+        outbound_accumulator: [u8; 32],
+    ) -> (MessageSent, [u8; 32]) {
+        let event = MessageSent {
+            sender: sender.to_bytes(),
+            sequence,
+            recipient: [1; 32],
+            recipient_chain: 2,
+            payload_digest: [sequence as u8; 32],
+            consistency_level: 0,
+            expiry: None,
+            outbound_accumulator: [0; 32],
+        };
+        let message_hash = AttestationInfo::compute_message_hash(
+            CHAIN_ID,
+            event.sender,
+            event.sequence,
+            event.recipient_chain,
+            event.recipient,
+            event.payload_digest,
+            event.expiry,
+        );
+        let outbound_accumulator =
+            SequenceTracker::fold_outbound_accumulator(outbound_accumulator, message_hash);
+        (
+            MessageSent {
+                outbound_accumulator,
+                ..event
+            },
+            outbound_accumulator,
+        )
+    }
+
+    #[test]
+    fn test_outbound_accumulator() {
+        let integrator = Pubkey::new_unique();
+        let mut accumulator = OutboundAccumulator::new(&integrator);
+
+        let mut value = [0; 32];
+        for sequence in 0..3 {
+            let (event, next_value) = message_sent(&integrator, sequence, value);
+            accumulator.apply(&event).unwrap();
+            value = next_value;
+        }
+        assert_eq!(accumulator.value(), value);
+        assert_eq!(accumulator.message_count(), 3);
+
+        let mut sequence_tracker = SequenceTracker {
+            bump: 0,
+            integrator_program_id: integrator,
+            sequence: 3,
+            outbox_messages: 0,
+            outbound_accumulator: value,
+            queued_adapters: Bitmap::new(),
+        };
+        assert!(accumulator.verify(&sequence_tracker));
+
+        // A message the accumulator has not seen
+        sequence_tracker.sequence = 4;
+        assert!(!accumulator.verify(&sequence_tracker));
+    }
+
+    #[test]
+    fn test_outbound_accumulator_rejects_invalid_events() {
+        let integrator = Pubkey::new_unique();
+        let mut accumulator = OutboundAccumulator::new(&integrator);
+
+        let (event, _) = message_sent(&Pubkey::new_unique(), 0, [0; 32]);
+        assert_eq!(
+            accumulator.apply(&event),
+            Err(OutboundAccumulatorError::WrongSender)
+        );
+
+        let (event, _) = message_sent(&integrator, 1, [0; 32]);
+        assert_eq!(
+            accumulator.apply(&event),
+            Err(OutboundAccumulatorError::SequenceGap {
+                expected: 0,
+                found: 1,
+            })
+        );
+
+        let (mut event, _) = message_sent(&integrator, 0, [0; 32]);
+        event.payload_digest = [9; 32];
+        assert_eq!(
+            accumulator.apply(&event),
+            Err(OutboundAccumulatorError::Mismatch { sequence: 0 })
+        );
+        assert_eq!(accumulator.message_count(), 0);
+    }
+}
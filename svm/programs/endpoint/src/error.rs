@@ -47,4 +47,106 @@ pub enum EndpointError {
 
     #[msg("Invalid Destination Chain Id")]
     InvalidDestinationChain,
+
+    #[msg("Conflicting attestations exist for this sequence")]
+    SequenceEquivocated,
+
+    #[msg("Attestation metadata is too long")]
+    MetadataTooLong,
+
+    #[msg("Attestation metadata account is required when attaching metadata")]
+    MetadataAccountMissing,
+
+    #[msg("Adapter does not support the message's consistency level")]
+    UnsupportedConsistencyLevel,
+
+    #[msg("Message has expired")]
+    MessageExpired,
+
+    #[msg("Sequence is not the next sequence to be delivered")]
+    OutOfOrderSequence,
+
+    #[msg("Inbound cursor account is required for ordered delivery")]
+    InboundCursorMissing,
+
+    #[msg("Sequence has already been delivered or skipped")]
+    SequenceAlreadyDelivered,
+
+    #[msg("Replay window accounts are required when replay windows are enabled")]
+    ReplayWindowMissing,
+
+    #[msg("The sequence's replay window has been closed")]
+    ReplayWindowClosed,
+
+    #[msg("Attestation tree account is invalid")]
+    InvalidAttestationTree,
+
+    #[msg("Attestation tree proof is invalid")]
+    InvalidAttestationProof,
+
+    #[msg("Attestation tree is full")]
+    AttestationTreeFull,
+
+    #[msg("Replay windows must be enabled to receive compressed attestations")]
+    ReplayWindowsRequired,
+
+    #[msg("Batch accounts do not match the messages")]
+    InvalidBatchAccounts,
+
+    #[msg("Pushed adapter accounts are invalid")]
+    InvalidPushAccounts,
+
+    #[msg("Outbox message must be passed if and only if some send adapters were not pushed")]
+    InvalidOutboxMessage,
+
+    #[msg("Integrator has been deregistered")]
+    IntegratorDeregistered,
+
+    #[msg("Integrator has outbox messages that have not been picked up")]
+    OutboxMessagesOutstanding,
+
+    #[msg("Adapter has been retired")]
+    AdapterRetired,
+
+    #[msg("Integrator chain config does not belong to the integrator")]
+    InvalidIntegratorChainConfig,
+
+    #[msg("Admin authorisation signature is invalid")]
+    InvalidAdminAuthorization,
+
+    #[msg("Admin authorisation has expired")]
+    AdminAuthorizationExpired,
+
+    #[msg("Admin authorisation nonce is invalid")]
+    InvalidAdminNonce,
+
+    #[msg("Instruction cannot be authorised by signature")]
+    AdminInstructionNotSignable,
+
+    #[msg("Message does not match the record of the sent message")]
+    SentMessageMismatch,
+
+    #[msg("Adapter queue accounts are invalid")]
+    InvalidAdapterQueue,
+
+    #[msg("Sequence is the last possible sequence and cannot be delivered or skipped")]
+    SequenceOverflow,
+
+    #[msg("Refund recipient does not match the account being closed")]
+    InvalidRefundRecipient,
+
+    #[msg("Message leaf is not the one recorded for it")]
+    InvalidAttestationLeaf,
+
+    #[msg("Adapter queue is missing")]
+    AdapterQueueMissing,
+
+    #[msg("Adapter stats accounts are invalid")]
+    InvalidAdapterStats,
+
+    #[msg("Message was cancelled and cannot be re-picked up")]
+    SentMessageCancelled,
+
+    #[msg("Message expiry is only supported for messages to this chain")]
+    ExpiryNotSupported,
 }
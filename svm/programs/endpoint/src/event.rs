@@ -1,6 +1,8 @@
 use anchor_lang::event;
 use anchor_lang::prelude::*;
 
+use crate::state::{CompressedAttestation, MessageExpiry};
+
 /// Event emitted when a new integrator is registered
 #[event]
 pub struct IntegratorRegistered {
@@ -8,6 +10,14 @@ pub struct IntegratorRegistered {
     pub admin: Pubkey,
 }
 
+/// Event emitted when an integrator is deregistered
+#[event]
+pub struct IntegratorDeregistered {
+    pub integrator: Pubkey,
+    pub rent_recipient: Pubkey,
+    pub final_sequence: u64,
+}
+
 /// Event emitted when an integrator's admin is updated
 #[event]
 pub struct AdminUpdated {
@@ -32,6 +42,10 @@ pub struct MessageSent {
     pub recipient: [u8; 32],
     pub recipient_chain: u16,
     pub payload_digest: [u8; 32],
+    pub consistency_level: u8,
+    pub expiry: Option<MessageExpiry>,
+    /// The integrator's outbound accumulator after this message
+    pub outbound_accumulator: [u8; 32],
 }
 
 /// Event emitted when a message is picked up by an adapter
@@ -44,6 +58,33 @@ pub struct MessagePickedUp {
     pub payload_hash: [u8; 32],
     pub adapter: Pubkey,
     pub remaining_adapters: u128,
+    pub consistency_level: u8,
+    pub expiry: Option<MessageExpiry>,
+}
+
+/// Event emitted when a message that was already sent is handed to send adapters again
+#[event]
+pub struct MessageRepickupRequested {
+    pub src_addr: [u8; 32],
+    pub sequence: u64,
+    pub dst_chain: u16,
+    pub dst_addr: [u8; 32],
+    pub payload_hash: [u8; 32],
+    pub adapters: u128,
+    pub consistency_level: u8,
+    pub expiry: Option<MessageExpiry>,
+    pub outbox_message: Pubkey,
+}
+
+/// Event emitted when an integrator cancels a message before any adapter picked it up
+#[event]
+pub struct MessageCancelled {
+    pub src_addr: [u8; 32],
+    pub sequence: u64,
+    pub dst_chain: u16,
+    pub dst_addr: [u8; 32],
+    pub payload_hash: [u8; 32],
+    pub outbox_message: Pubkey,
 }
 
 /// Event emitted when a message is attested to by an adapter
@@ -58,6 +99,20 @@ pub struct MessageAttestedTo {
     pub payload_hash: [u8; 32],
     pub attested_bitmap: u128,
     pub attesting_adapter: [u8; 32],
+    pub metadata: Vec<u8>,
+}
+
+/// Event emitted when an adapter attests to a payload that differs from the first payload
+/// attested for the same source sequence
+#[event]
+pub struct EquivocationDetected {
+    pub integrator: Pubkey,
+    pub src_chain: u16,
+    pub src_addr: [u8; 32],
+    pub sequence: u64,
+    pub first_payload_hash: [u8; 32],
+    pub conflicting_payload_hash: [u8; 32],
+    pub attesting_adapter: [u8; 32],
 }
 
 /// Event emitted when a message is received
@@ -72,6 +127,9 @@ pub struct MessageReceived {
     pub payload_hash: [u8; 32],
     pub enabled_bitmap: u128,
     pub attested_bitmap: u128,
+    pub config_epoch: u64,
+    pub snapshot_enabled_bitmap: u128,
+    pub snapshot_config_epoch: u64,
 }
 
 /// Event emitted when a message is executed
@@ -94,6 +152,15 @@ pub struct AdapterAdded {
     pub adapters_num: u8,
 }
 
+/// Event emitted when an adapter is retired by an integrator
+#[event]
+pub struct AdapterRetired {
+    pub integrator: Pubkey,
+    pub adapter: Pubkey,
+    /// The chains the adapter was disabled on
+    pub chains: Vec<u16>,
+}
+
 /// Event emitted when a send adapter is enabled for a specific chain
 #[event]
 pub struct SendAdapterEnabledForChain {
@@ -131,3 +198,70 @@ pub struct RecvAdapterDisabledForChain {
 pub struct AdminDiscarded {
     pub integrator: Pubkey,
 }
+
+/// Event emitted when ordered delivery is turned on or off for a specific chain
+#[event]
+pub struct OrderedDeliveryUpdated {
+    pub integrator: Pubkey,
+    pub chain: u16,
+    pub enabled: bool,
+}
+
+/// Event emitted when an admin skips undelivered sequences from a source address
+#[event]
+pub struct SequencesSkipped {
+    pub integrator: Pubkey,
+    pub src_chain: u16,
+    pub src_addr: [u8; 32],
+    pub next_sequence: u64,
+}
+
+/// Event emitted when replay windows are enabled for a specific chain
+#[event]
+pub struct ReplayWindowsEnabled {
+    pub integrator: Pubkey,
+    pub chain: u16,
+}
+
+/// Event emitted when an admin closes a replay window of a source address
+#[event]
+pub struct ReplayWindowClosed {
+    pub integrator: Pubkey,
+    pub src_chain: u16,
+    pub src_addr: [u8; 32],
+    pub window_index: u64,
+    pub first_open_window: u64,
+}
+
+/// Event emitted when an attestation tree is initialized for a specific chain
+#[event]
+pub struct AttestationTreeInitialized {
+    pub integrator: Pubkey,
+    pub chain: u16,
+    pub tree: Pubkey,
+}
+
+/// Event emitted when a pending-message queue is created for an adapter
+#[event]
+pub struct AdapterQueueInitialized {
+    pub integrator: Pubkey,
+    pub adapter: Pubkey,
+    pub queue: Pubkey,
+}
+
+/// Event emitted when a leaf of an attestation tree is appended or updated
+/// Clients rebuild the tree from these events to prove its leaves
+#[event]
+pub struct AttestationLeafUpdated {
+    pub tree: Pubkey,
+    pub leaf_index: u32,
+    pub leaf: CompressedAttestation,
+}
+
+/// Event emitted when an admin instruction authorised by an off-chain signature is executed
+#[event]
+pub struct SignedAdminExecuted {
+    pub integrator: Pubkey,
+    pub signer: Pubkey,
+    pub nonce: u64,
+}
@@ -1,6 +1,8 @@
 use crate::{
     event::AdapterAdded,
-    state::{AdapterInfo, IntegratorConfig},
+    state::{
+        AdapterIndex, AdapterInfo, AdapterStats, AuditLogPage, ConfigAction, IntegratorConfig,
+    },
 };
 use anchor_lang::prelude::*;
 
@@ -52,6 +54,49 @@ pub struct AddAdapter<'info> {
     )]
     pub adapter_info: Account<'info, AdapterInfo>,
 
+    /// The account tracking the adapter's activity for this integrator
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AdapterStats::INIT_SPACE,
+        seeds = [
+            AdapterStats::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump
+    )]
+    pub adapter_stats: Account<'info, AdapterStats>,
+
+    /// The reverse index entry from the adapter to this integrator
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AdapterIndex::space_for(0),
+        seeds = [
+            AdapterIndex::SEED_PREFIX,
+            args.adapter_program_id.as_ref(),
+            args.integrator_program_id.as_ref(),
+        ],
+        bump
+    )]
+    pub adapter_index: Account<'info, AdapterIndex>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
     /// The system program
     pub system_program: Program<'info, System>,
 }
@@ -68,6 +113,9 @@ impl<'info> AddAdapter<'info> {
 /// 1. Checks if the maximum number of adapters has been reached.
 /// 2. Adds the new adapter to the list of registered adapters in IntegratorConfig
 /// 3. Initializes the AdapterInfo account with the provided information.
+/// 4. Initializes the AdapterStats account for the adapter.
+/// 5. Initializes the AdapterIndex account from the adapter to the integrator.
+/// 6. Appends an entry to the integrator's audit log.
 ///
 /// # Arguments
 ///
@@ -99,8 +147,44 @@ pub fn add_adapter(ctx: Context<AddAdapter>, args: AddAdapterArgs) -> Result<()>
         index,
         integrator_program_id: args.integrator_program_id,
         adapter_program_id: args.adapter_program_id,
+        retired: false,
+        has_queue: false,
+    });
+
+    // Initialize AdapterStats
+    ctx.accounts.adapter_stats.set_inner(AdapterStats {
+        bump: ctx.bumps.adapter_stats,
+        integrator_program_id: args.integrator_program_id,
+        adapter_program_id: args.adapter_program_id,
+        messages_picked_up: 0,
+        messages_attested: 0,
+        last_pickup_slot: 0,
+        last_attestation_slot: 0,
+        outbox_messages_closed: 0,
     });
 
+    // Initialize AdapterIndex
+    ctx.accounts.adapter_index.set_inner(AdapterIndex {
+        bump: ctx.bumps.adapter_index,
+        adapter_program_id: args.adapter_program_id,
+        integrator_program_id: args.integrator_program_id,
+        chains: Vec::new(),
+    });
+
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::AdapterAdded {
+            adapter: args.adapter_program_id,
+        },
+        Clock::get()?.slot,
+    );
+
     emit_cpi!(AdapterAdded {
         integrator: args.integrator_program_id,
         adapter: args.adapter_program_id,
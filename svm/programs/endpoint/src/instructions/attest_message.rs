@@ -1,9 +1,14 @@
+use adapter_interface::ADAPTER_PDA_SEED;
 use anchor_lang::prelude::*;
 
 use crate::{
     error::EndpointError,
     event::MessageAttestedTo,
-    state::{AdapterInfo, AttestationInfo, IntegratorChainConfig},
+    state::{
+        AdapterInfo, AdapterStats, AttestationInfo, AttestationMetadata, IntegratorChainConfig,
+        MessageExpiry, ReplayWindow, SequenceRecord,
+    },
+    utils::account::grow_account,
     CHAIN_ID,
 };
 
@@ -17,6 +22,10 @@ pub struct AttestMessageArgs {
     pub dst_chain: u16,
     pub integrator_program_id: Pubkey,
     pub payload_hash: [u8; 32],
+    /// The deadline committed into the message by the sender, if any
+    pub expiry: Option<MessageExpiry>,
+    /// Opaque metadata about the attestation, at most `AttestationMetadata::MAX_LEN` bytes
+    pub metadata: Vec<u8>,
 }
 
 #[event_cpi]
@@ -34,13 +43,27 @@ pub struct AttestMessage<'info> {
             args.adapter_program_id.as_ref(),
         ],
         bump = adapter_info.bump,
+        constraint = !adapter_info.retired @ EndpointError::AdapterRetired,
     )]
     pub adapter_info: Account<'info, AdapterInfo>,
 
+    /// The adapter stats account
+    /// The attestation counter and last attestation slot are updated here
+    #[account(
+        mut,
+        seeds = [
+            AdapterStats::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_stats.bump,
+    )]
+    pub adapter_stats: Account<'info, AdapterStats>,
+
     /// The adapter PDA signing account.
     /// This check makes sure that only the adapter program is authorised to call this message
     #[account(
-        seeds = ["adapter_pda".as_bytes()],
+        seeds = [ADAPTER_PDA_SEED],
         bump = args.adapter_pda_bump,
         seeds::program = args.adapter_program_id
     )]
@@ -70,13 +93,70 @@ pub struct AttestMessage<'info> {
                 args.sequence,
                 args.dst_chain,
                 args.integrator_program_id.to_bytes(),
-                args.payload_hash
+                args.payload_hash,
+                args.expiry
             )
         ],
         bump
     )]
     pub attestation_info: Account<'info, AttestationInfo>,
 
+    /// The sequence record account
+    /// Holds the first payload hash attested for this source sequence so that conflicting
+    /// attestations can be detected
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + SequenceRecord::INIT_SPACE,
+        seeds = [
+            SequenceRecord::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+            args.sequence.to_be_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub sequence_record: Account<'info, SequenceRecord>,
+
+    /// The replay window covering the sequence
+    /// When replay windows are enabled for the source chain, this shows whether the sequence has
+    /// already been received or executed. It may not exist
+    /// CHECK: The seeds constraint checks the address, and the account is only read if it exists
+    #[account(
+        seeds = [
+            ReplayWindow::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+            ReplayWindow::window_index(args.sequence).to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_window: UncheckedAccount<'info>,
+
+    /// The attestation metadata account
+    /// This is only required when the adapter attaches metadata to its attestation
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AttestationMetadata::empty_space(),
+        seeds = [
+            AttestationMetadata::SEED_PREFIX,
+            &AttestationInfo::compute_message_hash(
+                args.src_chain,
+                args.src_addr,
+                args.sequence,
+                args.dst_chain,
+                args.integrator_program_id.to_bytes(),
+                args.payload_hash,
+                args.expiry
+            )
+        ],
+        bump
+    )]
+    pub attestation_metadata: Option<Account<'info, AttestationMetadata>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -84,10 +164,18 @@ pub struct AttestMessage<'info> {
 ///
 /// This function allows an adapter to attest to a message. It performs the following steps:
 /// 1. Checks if the adapter is enabled for receiving messages from the source chain.
-/// 2. Initializes the attestation info account if it's newly created.
+/// 2. Initializes the attestation info account if it's newly created, recording the current
+///    config epoch and receive adapter bitmap of the source chain.
 /// 3. Checks if the adapter has already attested to this message.
 /// 4. Marks the adapter as having attested to the message.
 /// 5. Increases the number of attested in `attestation_info`.
+/// 6. Records the attestation in the adapter's `AdapterStats`.
+/// 7. Records the payload hash for the source sequence, flagging the sequence as equivocated if
+///    it differs from the first payload hash attested for it.
+/// 8. Stores the adapter's metadata in the `AttestationMetadata` account, if any was attached.
+/// 9. If replay windows are enabled and the sequence has already been received or executed,
+///    closes the attestation info and sequence record again if they were created by this
+///    attestation, refunding their rent to the payer.
 ///
 /// # Arguments
 ///
@@ -100,12 +188,17 @@ pub struct AttestMessage<'info> {
 ///   - `dst_chain`: The destination chain ID
 ///   - `dst_addr`: The destination address ([u8; 32])
 ///   - `payload_hash`: The hash of the message payload
+///   - `expiry`: The deadline committed into the message by the sender, if any
+///   - `metadata`: Opaque metadata about the attestation
 ///
 /// # Errors
 ///
 /// This function will return an error if:
 /// * The adapter is not enabled for receiving messages from the source chain
 /// * The adapter has already attested to this message
+/// * The metadata is longer than `AttestationMetadata::MAX_LEN` (EndpointError::MetadataTooLong)
+/// * Metadata is attached but no `attestation_metadata` account is passed
+///   (EndpointError::MetadataAccountMissing)
 ///
 /// # Returns
 ///
@@ -113,7 +206,8 @@ pub struct AttestMessage<'info> {
 ///
 /// # Events
 ///
-/// Emits a `MessageAttestedTo` event
+/// Emits a `MessageAttestedTo` event, and an `EquivocationDetected` event if the payload hash
+/// conflicts with an earlier attestation for the same source sequence
 pub fn attest_message(ctx: Context<AttestMessage>, args: AttestMessageArgs) -> Result<()> {
     // Validate that the destination chain is this program's chain
     require!(
@@ -135,36 +229,84 @@ pub fn attest_message(ctx: Context<AttestMessage>, args: AttestMessageArgs) -> R
     );
 
     // If the attestation_info is newly created, initialize it
-    // It is fine to check for initialization using `src_chain == 0` as
-    // `IntegratorChainConfig` and `AttestationInfo` can never have chain_id that is 0
-    if attestation_info.src_chain == 0 {
+    let created = attestation_info.is_uninitialized();
+    if created {
+        require!(args.src_chain != 0, EndpointError::InvalidChainId);
+
         attestation_info.set_inner(AttestationInfo::new(
             ctx.bumps.attestation_info,
+            AttestationInfo::compute_message_hash(
+                args.src_chain,
+                args.src_addr,
+                args.sequence,
+                args.dst_chain,
+                args.integrator_program_id.to_bytes(),
+                args.payload_hash,
+                args.expiry,
+            ),
+            args.expiry,
+            ctx.accounts.payer.key(),
+        ));
+
+        // Snapshot the receive configuration the message is first attested under
+        attestation_info.snapshot_config(
+            integrator_chain_config.config_epoch,
+            integrator_chain_config.recv_adapter_bitmap,
+        );
+    }
+
+    // Mark the Adapter as having attested to the message, which it must not have yet
+    attestation_info.attest(adapter_info.index)?;
+
+    ctx.accounts
+        .adapter_stats
+        .record_attestation(Clock::get()?.slot);
+
+    // Initialize the sequence record with the first payload hash seen, otherwise check the
+    // payload hash against it. Conflicting attestations are still accepted, but flagged so
+    // that `recv_message` refuses the sequence.
+    let sequence_record = &mut ctx.accounts.sequence_record;
+    let sequence_record_created = sequence_record.src_chain == 0;
+    if sequence_record_created {
+        sequence_record.set_inner(SequenceRecord::new(
+            ctx.bumps.sequence_record,
+            args.integrator_program_id,
             args.src_chain,
             args.src_addr,
             args.sequence,
-            args.dst_chain,
-            args.integrator_program_id.to_bytes(),
             args.payload_hash,
-        )?);
+        ));
+    } else if let Some(equivocation) =
+        sequence_record.record(args.payload_hash, args.adapter_program_id)
+    {
+        emit_cpi!(equivocation);
     }
 
-    // Check if the Adapter has already attested
-    require!(
-        !attestation_info
-            .attested_adapters
-            .get(adapter_info.index)
-            .unwrap_or(false),
-        EndpointError::DuplicateMessageAttestation
-    );
+    // Initialize the metadata account if it has just been created, even when no metadata is
+    // attached, so that it can always be read back with its bump and message hash
+    if let Some(attestation_metadata) = ctx.accounts.attestation_metadata.as_mut() {
+        if attestation_metadata.message_hash == [0; 32] {
+            attestation_metadata.bump = ctx.bumps.attestation_metadata.unwrap();
+            attestation_metadata.message_hash = attestation_info.message_hash;
+        }
+    }
 
-    // Mark the Adapter as having attested to the message
-    attestation_info
-        .attested_adapters
-        .set(adapter_info.index, true)?;
+    // Store the metadata against the adapter's index
+    if !args.metadata.is_empty() {
+        let attestation_metadata = ctx
+            .accounts
+            .attestation_metadata
+            .as_mut()
+            .ok_or(EndpointError::MetadataAccountMissing)?;
 
-    // Increment the number of attestations (saturates at 255)
-    attestation_info.num_attested = attestation_info.num_attested.saturating_add(1);
+        attestation_metadata.push(adapter_info.index, args.metadata.clone())?;
+        grow_account(
+            &attestation_metadata.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + attestation_metadata.space(),
+        )?;
+    }
 
     emit_cpi!(MessageAttestedTo {
         message_hash: attestation_info.message_hash,
@@ -176,7 +318,31 @@ pub fn attest_message(ctx: Context<AttestMessage>, args: AttestMessageArgs) -> R
         payload_hash: args.payload_hash,
         attested_bitmap: attestation_info.attested_adapters.as_value(),
         attesting_adapter: args.adapter_program_id.to_bytes(),
+        metadata: args.metadata,
     });
 
+    // With replay windows, a received sequence can never be received again, so the accounts
+    // recreated by a late attestation are closed right away instead of being left behind
+    if ctx.accounts.integrator_chain_config.replay_windows
+        && ReplayWindow::is_executed_in(
+            &ctx.accounts.replay_window,
+            &args.integrator_program_id,
+            args.src_chain,
+            args.src_addr,
+            args.sequence,
+        )?
+    {
+        if created {
+            ctx.accounts
+                .attestation_info
+                .close(ctx.accounts.payer.to_account_info())?;
+        }
+        if sequence_record_created {
+            ctx.accounts
+                .sequence_record
+                .close(ctx.accounts.payer.to_account_info())?;
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,215 @@
+use adapter_interface::ADAPTER_PDA_SEED;
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::EndpointError,
+    event::MessageAttestedTo,
+    state::{
+        AdapterInfo, AdapterStats, AttestationInfo, AttestationMetadata, IntegratorChainConfig,
+        SequenceRecord,
+    },
+    utils::account::grow_account,
+    CHAIN_ID,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AttestMessageByHashArgs {
+    pub adapter_program_id: Pubkey,
+    pub adapter_pda_bump: u8,
+    /// The hash of a message that already has an `AttestationInfo`
+    pub message_hash: [u8; 32],
+    /// Opaque metadata about the attestation, at most `AttestationMetadata::MAX_LEN` bytes
+    pub metadata: Vec<u8>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: AttestMessageByHashArgs)]
+pub struct AttestMessageByHash<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The attestation info account
+    /// This throws when the message has not been attested to yet
+    #[account(
+        mut,
+        seeds = [AttestationInfo::SEED_PREFIX, &args.message_hash],
+        bump = attestation_info.bump,
+    )]
+    pub attestation_info: Account<'info, AttestationInfo>,
+
+    /// The sequence record of the message
+    /// The message's fields, and the integrator and source chain of the other accounts, are
+    /// taken from it
+    #[account(
+        constraint = sequence_record.first_message_hash(attestation_info.expiry)
+            == args.message_hash @ EndpointError::InvalidMessageHash,
+    )]
+    pub sequence_record: Account<'info, SequenceRecord>,
+
+    /// The adapter info account
+    #[account(
+        seeds = [
+            AdapterInfo::SEED_PREFIX,
+            sequence_record.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_info.bump,
+        constraint = !adapter_info.retired @ EndpointError::AdapterRetired,
+    )]
+    pub adapter_info: Account<'info, AdapterInfo>,
+
+    /// The adapter stats account
+    /// The attestation counter and last attestation slot are updated here
+    #[account(
+        mut,
+        seeds = [
+            AdapterStats::SEED_PREFIX,
+            sequence_record.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_stats.bump,
+    )]
+    pub adapter_stats: Account<'info, AdapterStats>,
+
+    /// The adapter PDA signing account.
+    /// This check makes sure that only the adapter program is authorised to call this message
+    #[account(
+        seeds = [ADAPTER_PDA_SEED],
+        bump = args.adapter_pda_bump,
+        seeds::program = args.adapter_program_id
+    )]
+    pub adapter_pda: Signer<'info>,
+
+    /// The integrator chain config account
+    #[account(
+        seeds = [
+            IntegratorChainConfig::SEED_PREFIX,
+            sequence_record.integrator_program_id.as_ref(),
+            sequence_record.src_chain.to_be_bytes().as_ref()
+        ],
+        bump = integrator_chain_config.bump,
+    )]
+    pub integrator_chain_config: Account<'info, IntegratorChainConfig>,
+
+    /// The attestation metadata account
+    /// This is only required when the adapter attaches metadata to its attestation
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AttestationMetadata::empty_space(),
+        seeds = [AttestationMetadata::SEED_PREFIX, &args.message_hash],
+        bump
+    )]
+    pub attestation_metadata: Option<Account<'info, AttestationMetadata>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction handler for attest_message_by_hash
+///
+/// This is a cheaper form of `attest_message` for adapters attesting to a message that another
+/// adapter has already attested to. Only the message hash is passed, and the message's fields
+/// are read from the `SequenceRecord` of its sequence.
+///
+/// The first attestation of a message must go through `attest_message`, which creates the
+/// `AttestationInfo` and records the payload hash for the source sequence. Only the message
+/// carrying that first payload can be attested to by hash. A message with a conflicting payload
+/// must go through `attest_message`, which flags the sequence as equivocated.
+///
+/// It performs the following steps:
+/// 1. Checks if the adapter is enabled for receiving messages from the source chain.
+/// 2. Checks if the adapter has already attested to this message.
+/// 3. Marks the adapter as having attested to the message.
+/// 4. Increases the number of attested in `attestation_info`.
+/// 5. Records the attestation in the adapter's `AdapterStats`.
+/// 6. Stores the adapter's metadata in the `AttestationMetadata` account, if any was attached.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, containing:
+///   - `adapter_pda_bump`: The bump seed for the adapter's PDA
+///   - `message_hash`: The hash of the message
+///   - `metadata`: Opaque metadata about the attestation
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The message has no `AttestationInfo` (ErrorCode::AccountNotInitialized)
+/// * The `sequence_record` does not hold the message's payload (EndpointError::InvalidMessageHash)
+/// * The adapter is not enabled for receiving messages from the source chain
+/// * The adapter has already attested to this message
+/// * The metadata is longer than `AttestationMetadata::MAX_LEN` (EndpointError::MetadataTooLong)
+/// * Metadata is attached but no `attestation_metadata` account is passed
+///   (EndpointError::MetadataAccountMissing)
+///
+/// # Events
+///
+/// Emits a `MessageAttestedTo` event
+pub fn attest_message_by_hash(
+    ctx: Context<AttestMessageByHash>,
+    args: AttestMessageByHashArgs,
+) -> Result<()> {
+    let adapter_info = &ctx.accounts.adapter_info;
+    let attestation_info = &mut ctx.accounts.attestation_info;
+    let sequence_record = &ctx.accounts.sequence_record;
+
+    // Check if the Adapter is an enabled receive Adapter for the Integrator and source chain
+    require!(
+        ctx.accounts
+            .integrator_chain_config
+            .recv_adapter_bitmap
+            .get(adapter_info.index)
+            .unwrap_or(false),
+        EndpointError::AdapterNotEnabled
+    );
+
+    // Mark the Adapter as having attested to the message, which it must not have yet
+    attestation_info.attest(adapter_info.index)?;
+
+    ctx.accounts
+        .adapter_stats
+        .record_attestation(Clock::get()?.slot);
+
+    // Initialize the metadata account if it has just been created, even when no metadata is
+    // attached, so that it can always be read back with its bump and message hash
+    if let Some(attestation_metadata) = ctx.accounts.attestation_metadata.as_mut() {
+        if attestation_metadata.message_hash == [0; 32] {
+            attestation_metadata.bump = ctx.bumps.attestation_metadata.unwrap();
+            attestation_metadata.message_hash = args.message_hash;
+        }
+    }
+
+    // Store the metadata against the adapter's index
+    if !args.metadata.is_empty() {
+        let attestation_metadata = ctx
+            .accounts
+            .attestation_metadata
+            .as_mut()
+            .ok_or(EndpointError::MetadataAccountMissing)?;
+
+        attestation_metadata.push(adapter_info.index, args.metadata.clone())?;
+        grow_account(
+            &attestation_metadata.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + attestation_metadata.space(),
+        )?;
+    }
+
+    emit_cpi!(MessageAttestedTo {
+        message_hash: args.message_hash,
+        src_chain: sequence_record.src_chain,
+        src_addr: sequence_record.src_addr,
+        sequence: sequence_record.sequence,
+        dst_chain: CHAIN_ID,
+        dst_addr: sequence_record.integrator_program_id.to_bytes(),
+        payload_hash: sequence_record.first_payload_hash,
+        attested_bitmap: ctx.accounts.attestation_info.attested_adapters.as_value(),
+        attesting_adapter: args.adapter_program_id.to_bytes(),
+        metadata: args.metadata,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,356 @@
+use adapter_interface::ADAPTER_PDA_SEED;
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::EndpointError,
+    event::{AttestationLeafUpdated, MessageAttestedTo},
+    state::{
+        tree_error, AdapterInfo, AdapterStats, AttestationInfo, AttestationLeaf, AttestationTree,
+        CompressedAttestation, IntegratorChainConfig, MessageExpiry, ReplayWindow, SequenceRecord,
+    },
+    CHAIN_ID,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AttestMessageCompressedArgs {
+    pub adapter_program_id: Pubkey,
+    pub adapter_pda_bump: u8,
+    pub src_chain: u16,
+    pub src_addr: [u8; 32],
+    pub sequence: u64,
+    pub dst_chain: u16,
+    pub integrator_program_id: Pubkey,
+    pub payload_hash: [u8; 32],
+    /// The deadline committed into the message by the sender, if any
+    pub expiry: Option<MessageExpiry>,
+    /// The root the proof in `remaining_accounts` was built against
+    pub root: [u8; 32],
+    /// The index of the message's leaf, ignored when appending a new leaf
+    pub leaf_index: u32,
+    /// The message's current leaf, or `None` to append a leaf for the first attestation
+    pub previous_leaf: Option<CompressedAttestation>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: AttestMessageCompressedArgs)]
+pub struct AttestMessageCompressed<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The adapter info account
+    #[account(
+        seeds = [
+            AdapterInfo::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_info.bump,
+        constraint = !adapter_info.retired @ EndpointError::AdapterRetired,
+    )]
+    pub adapter_info: Account<'info, AdapterInfo>,
+
+    /// The adapter stats account
+    /// The attestation counter and last attestation slot are updated here
+    #[account(
+        mut,
+        seeds = [
+            AdapterStats::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_stats.bump,
+    )]
+    pub adapter_stats: Account<'info, AdapterStats>,
+
+    /// The adapter PDA signing account.
+    /// This check makes sure that only the adapter program is authorised to call this message
+    #[account(
+        seeds = [ADAPTER_PDA_SEED],
+        bump = args.adapter_pda_bump,
+        seeds::program = args.adapter_program_id
+    )]
+    pub adapter_pda: Signer<'info>,
+
+    /// The integrator chain config account
+    #[account(
+        seeds = [
+            IntegratorChainConfig::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref()
+        ],
+        bump = integrator_chain_config.bump,
+    )]
+    pub integrator_chain_config: Account<'info, IntegratorChainConfig>,
+
+    /// The attestation tree holding the message's leaf
+    #[account(
+        mut,
+        constraint = attestation_tree.integrator_program_id == args.integrator_program_id
+            && attestation_tree.chain_id == args.src_chain @ EndpointError::InvalidAttestationTree,
+    )]
+    pub attestation_tree: Account<'info, AttestationTree>,
+
+    /// The record of the message's leaf
+    /// This account is initialized when the message's first leaf is appended
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AttestationLeaf::INIT_SPACE,
+        seeds = [
+            AttestationLeaf::SEED_PREFIX,
+            &AttestationInfo::compute_message_hash(
+                args.src_chain,
+                args.src_addr,
+                args.sequence,
+                args.dst_chain,
+                args.integrator_program_id.to_bytes(),
+                args.payload_hash,
+                args.expiry
+            )
+        ],
+        bump
+    )]
+    pub attestation_leaf: Account<'info, AttestationLeaf>,
+
+    /// The sequence record account
+    /// Holds the first payload hash attested for this source sequence so that conflicting
+    /// attestations can be detected
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + SequenceRecord::INIT_SPACE,
+        seeds = [
+            SequenceRecord::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+            args.sequence.to_be_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub sequence_record: Box<Account<'info, SequenceRecord>>,
+
+    /// The replay window covering the sequence
+    /// This shows whether the sequence has already been received. It may not exist
+    /// CHECK: The seeds constraint checks the address, and the account is only read if it exists
+    #[account(
+        seeds = [
+            ReplayWindow::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+            ReplayWindow::window_index(args.sequence).to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_window: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction handler for attest_message_compressed
+///
+/// This is the counterpart of `attest_message` for integrators that keep attestations in an
+/// `AttestationTree` instead of `AttestationInfo` accounts, so no rent is paid per message.
+/// The proof of the message's current leaf is passed as `remaining_accounts`, one node per
+/// account, ordered from the leaf up. Trailing nodes of empty subtrees may be omitted.
+///
+/// It performs the following steps:
+/// 1. Checks if the adapter is enabled for receiving messages from the source chain, and that
+///    the sequence has not been received yet.
+/// 2. Without a `previous_leaf`, checks that the message has no leaf yet and appends a new leaf
+///    recording the current config epoch and receive adapter bitmap of the source chain.
+/// 3. Otherwise, checks that `previous_leaf` belongs to the message and is the leaf recorded in
+///    its `AttestationLeaf`, and that the adapter has not attested to it yet, and replaces it
+///    with a leaf that marks the adapter as attested.
+/// 4. Records the position of the leaf in the message's `AttestationLeaf`.
+/// 5. Records the attestation in the adapter's `AdapterStats`.
+/// 6. Records the payload hash for the source sequence, flagging the sequence as equivocated if
+///    it differs from the first payload hash attested for it.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, containing message details along with:
+///   - `root`: The root the proof was built against
+///   - `leaf_index`: The index of the message's leaf
+///   - `previous_leaf`: The message's current leaf, if it has one
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The adapter is not enabled for receiving messages from the source chain
+/// * The sequence has already been received (EndpointError::AlreadyExecuted)
+/// * `previous_leaf` belongs to another message (EndpointError::InvalidMessageHash)
+/// * The message already has a leaf and no `previous_leaf` is passed, or it is at another
+///   position, or it has no leaf and a `previous_leaf` is passed
+///   (EndpointError::InvalidAttestationLeaf)
+/// * The adapter has already attested to this message
+/// * The proof does not match the tree (EndpointError::InvalidAttestationProof)
+/// * The tree is full (EndpointError::AttestationTreeFull)
+///
+/// # Events
+///
+/// Emits a `MessageAttestedTo` and an `AttestationLeafUpdated` event, and an
+/// `EquivocationDetected` event if the payload hash conflicts with an earlier attestation for the
+/// same source sequence
+pub fn attest_message_compressed(
+    ctx: Context<AttestMessageCompressed>,
+    args: AttestMessageCompressedArgs,
+) -> Result<()> {
+    // Validate that the destination chain is this program's chain
+    require!(
+        args.dst_chain == CHAIN_ID,
+        EndpointError::InvalidDestinationChain
+    );
+    require!(args.src_chain != 0, EndpointError::InvalidChainId);
+
+    let adapter_info = &ctx.accounts.adapter_info;
+    let integrator_chain_config = &ctx.accounts.integrator_chain_config;
+
+    // Check if the Adapter is an enabled receive Adapter for the Integrator and source chain
+    require!(
+        integrator_chain_config
+            .recv_adapter_bitmap
+            .get(adapter_info.index)
+            .unwrap_or(false),
+        EndpointError::AdapterNotEnabled
+    );
+
+    // A received sequence is never received again, so its message does not need a leaf. This
+    // also keeps a second leaf from being appended once the message's `AttestationLeaf` is closed
+    require!(
+        !ReplayWindow::is_executed_in(
+            &ctx.accounts.replay_window,
+            &args.integrator_program_id,
+            args.src_chain,
+            args.src_addr,
+            args.sequence,
+        )?,
+        EndpointError::AlreadyExecuted
+    );
+
+    let message_hash = AttestationInfo::compute_message_hash(
+        args.src_chain,
+        args.src_addr,
+        args.sequence,
+        args.dst_chain,
+        args.integrator_program_id.to_bytes(),
+        args.payload_hash,
+        args.expiry,
+    );
+
+    let mut leaf = match args.previous_leaf {
+        Some(previous_leaf) => {
+            require!(
+                previous_leaf.message_hash == message_hash,
+                EndpointError::InvalidMessageHash
+            );
+            previous_leaf
+        }
+        // Snapshot the receive configuration the message is first attested under
+        None => CompressedAttestation::new(
+            message_hash,
+            integrator_chain_config.config_epoch,
+            integrator_chain_config.recv_adapter_bitmap,
+        ),
+    };
+
+    // A message has a single leaf, recorded when it is appended
+    let attestation_tree_key = ctx.accounts.attestation_tree.key();
+    let attestation_leaf = &mut ctx.accounts.attestation_leaf;
+    require!(
+        if attestation_leaf.is_uninitialized() {
+            args.previous_leaf.is_none()
+        } else {
+            args.previous_leaf.is_some()
+                && attestation_leaf.tree == attestation_tree_key
+                && attestation_leaf.leaf_index == args.leaf_index
+        },
+        EndpointError::InvalidAttestationLeaf
+    );
+
+    // Mark the Adapter as having attested to the message, which it must not have yet
+    leaf.attest(adapter_info.index)?;
+
+    let attestation_tree = ctx.accounts.attestation_tree.to_account_info();
+    let mut data = attestation_tree.data.borrow_mut();
+    let tree = AttestationTree::load_tree_mut(&mut data)?;
+    let leaf_index = match args.previous_leaf {
+        Some(previous_leaf) => {
+            let proof: Vec<[u8; 32]> = ctx
+                .remaining_accounts
+                .iter()
+                .map(|node| node.key.to_bytes())
+                .collect();
+            tree.set_leaf(
+                args.root,
+                previous_leaf.hash(),
+                leaf.hash(),
+                &proof,
+                args.leaf_index,
+            )
+            .map_err(tree_error)?;
+            args.leaf_index
+        }
+        None => {
+            tree.append(leaf.hash()).map_err(tree_error)?;
+            tree.rightmost_proof.index - 1
+        }
+    };
+    drop(data);
+
+    if attestation_leaf.is_uninitialized() {
+        attestation_leaf.set_inner(AttestationLeaf {
+            bump: ctx.bumps.attestation_leaf,
+            tree: attestation_tree_key,
+            leaf_index,
+            refund_recipient: ctx.accounts.payer.key(),
+        });
+    }
+
+    ctx.accounts
+        .adapter_stats
+        .record_attestation(Clock::get()?.slot);
+
+    // Initialize the sequence record with the first payload hash seen, otherwise check the
+    // payload hash against it. Conflicting attestations are still accepted, but flagged so
+    // that `recv_message_compressed` refuses the sequence.
+    let sequence_record = &mut ctx.accounts.sequence_record;
+    if sequence_record.src_chain == 0 {
+        sequence_record.set_inner(SequenceRecord::new(
+            ctx.bumps.sequence_record,
+            args.integrator_program_id,
+            args.src_chain,
+            args.src_addr,
+            args.sequence,
+            args.payload_hash,
+        ));
+    } else if let Some(equivocation) =
+        sequence_record.record(args.payload_hash, args.adapter_program_id)
+    {
+        emit_cpi!(equivocation);
+    }
+
+    emit_cpi!(MessageAttestedTo {
+        message_hash,
+        src_chain: args.src_chain,
+        src_addr: args.src_addr,
+        sequence: args.sequence,
+        dst_chain: args.dst_chain,
+        dst_addr: args.integrator_program_id.to_bytes(),
+        payload_hash: args.payload_hash,
+        attested_bitmap: leaf.attested_adapters.as_value(),
+        attesting_adapter: args.adapter_program_id.to_bytes(),
+        metadata: vec![],
+    });
+
+    emit_cpi!(AttestationLeafUpdated {
+        tree: attestation_tree.key(),
+        leaf_index,
+        leaf,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,300 @@
+use adapter_interface::ADAPTER_PDA_SEED;
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::EndpointError,
+    event::MessageAttestedTo,
+    instructions::BatchMode,
+    state::{
+        AdapterInfo, AdapterStats, AttestationInfo, IntegratorChainConfig, MessageExpiry,
+        ReplayWindow, SequenceRecord,
+    },
+    utils::account::{create_pda_account, is_uncreated},
+    CHAIN_ID,
+};
+
+/// A message attested to by `attest_messages`
+/// The source chain and integrator are shared by the whole batch, and the destination chain
+/// is this chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchedMessage {
+    pub src_addr: [u8; 32],
+    pub sequence: u64,
+    pub payload_hash: [u8; 32],
+    /// The deadline committed into the message by the sender, if any
+    pub expiry: Option<MessageExpiry>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AttestMessagesArgs {
+    pub adapter_program_id: Pubkey,
+    pub adapter_pda_bump: u8,
+    pub src_chain: u16,
+    pub integrator_program_id: Pubkey,
+    pub messages: Vec<BatchedMessage>,
+    /// What to do with messages the adapter has already attested to
+    pub mode: BatchMode,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: AttestMessagesArgs)]
+pub struct AttestMessages<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The adapter info account
+    #[account(
+        seeds = [
+            AdapterInfo::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_info.bump,
+        constraint = !adapter_info.retired @ EndpointError::AdapterRetired,
+    )]
+    pub adapter_info: Account<'info, AdapterInfo>,
+
+    /// The adapter stats account
+    /// The attestation counter and last attestation slot are updated here
+    #[account(
+        mut,
+        seeds = [
+            AdapterStats::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_stats.bump,
+    )]
+    pub adapter_stats: Account<'info, AdapterStats>,
+
+    /// The adapter PDA signing account.
+    /// This check makes sure that only the adapter program is authorised to call this message
+    #[account(
+        seeds = [ADAPTER_PDA_SEED],
+        bump = args.adapter_pda_bump,
+        seeds::program = args.adapter_program_id
+    )]
+    pub adapter_pda: Signer<'info>,
+
+    /// The integrator chain config account
+    #[account(
+        seeds = [
+            IntegratorChainConfig::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref()
+        ],
+        bump = integrator_chain_config.bump,
+    )]
+    pub integrator_chain_config: Account<'info, IntegratorChainConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction handler for attest_messages
+///
+/// This is the batch form of `attest_message`, for adapters relaying several messages from one
+/// source chain to one integrator. The adapter and its signer are checked once for the batch.
+///
+/// For each message, `remaining_accounts` must hold its `AttestationInfo` and its
+/// `SequenceRecord`, both writable, followed by the `ReplayWindow` covering its sequence. The
+/// first two are created if they don't exist, and the window may not exist.
+///
+/// Each message is attested to as by `attest_message`, except that no metadata can be attached.
+/// With `BatchMode::SkipDone`, messages the adapter has already attested to are left out.
+/// With `BatchMode::AllOrNothing`, any of them fails the whole batch.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, containing:
+///   - `adapter_pda_bump`: The bump seed for the adapter's PDA
+///   - `src_chain`: The source chain ID of every message
+///   - `messages`: The messages to attest to
+///   - `mode`: What to do with messages the adapter has already attested to
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The accounts in `remaining_accounts` do not match the messages
+///   (EndpointError::InvalidBatchAccounts)
+/// * The adapter is not enabled for receiving messages from the source chain
+/// * The adapter has already attested to a message and the mode is `BatchMode::AllOrNothing`
+///
+/// # Events
+///
+/// Emits a `MessageAttestedTo` event for each message attested to, and an
+/// `EquivocationDetected` event for each conflicting payload hash
+pub fn attest_messages<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AttestMessages<'info>>,
+    args: AttestMessagesArgs,
+) -> Result<()> {
+    require!(args.src_chain != 0, EndpointError::InvalidChainId);
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        args.messages.len() * 3,
+        EndpointError::InvalidBatchAccounts
+    );
+
+    let adapter_index = ctx.accounts.adapter_info.index;
+    let integrator_chain_config = &ctx.accounts.integrator_chain_config;
+
+    // Check if the Adapter is an enabled receive Adapter for the Integrator and source chain
+    require!(
+        integrator_chain_config
+            .recv_adapter_bitmap
+            .get(adapter_index)
+            .unwrap_or(false),
+        EndpointError::AdapterNotEnabled
+    );
+
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let slot = Clock::get()?.slot;
+
+    for (message, accounts) in args.messages.iter().zip(ctx.remaining_accounts.chunks(3)) {
+        let message_hash = AttestationInfo::compute_message_hash(
+            args.src_chain,
+            message.src_addr,
+            message.sequence,
+            CHAIN_ID,
+            args.integrator_program_id.to_bytes(),
+            message.payload_hash,
+            message.expiry,
+        );
+
+        // Create the attestation info if needed, recording the receive configuration the
+        // message is first attested under
+        let attestation_info_account = &accounts[0];
+        let (attestation_info_key, attestation_info_bump) = AttestationInfo::pda(message_hash);
+        require_keys_eq!(
+            attestation_info_account.key(),
+            attestation_info_key,
+            EndpointError::InvalidBatchAccounts
+        );
+        let created = is_uncreated(attestation_info_account);
+        if created {
+            create_pda_account(
+                attestation_info_account,
+                &payer,
+                &system_program,
+                8 + AttestationInfo::INIT_SPACE,
+                &[
+                    AttestationInfo::SEED_PREFIX,
+                    &message_hash,
+                    &[attestation_info_bump],
+                ],
+            )?;
+            let mut attestation_info = AttestationInfo::new(
+                attestation_info_bump,
+                message_hash,
+                message.expiry,
+                payer.key(),
+            );
+            attestation_info.snapshot_config(
+                integrator_chain_config.config_epoch,
+                integrator_chain_config.recv_adapter_bitmap,
+            );
+            attestation_info
+                .try_serialize(&mut &mut attestation_info_account.try_borrow_mut_data()?[..])?;
+        }
+        let mut attestation_info = Account::<AttestationInfo>::try_from(attestation_info_account)?;
+
+        // Check if the Adapter has already attested
+        if attestation_info.has_attested(adapter_index) {
+            require!(
+                args.mode == BatchMode::SkipDone,
+                EndpointError::DuplicateMessageAttestation
+            );
+            continue;
+        }
+
+        // Mark the Adapter as having attested to the message
+        attestation_info.attest(adapter_index)?;
+        attestation_info.exit(&crate::ID)?;
+
+        ctx.accounts.adapter_stats.record_attestation(slot);
+
+        // Record the payload hash for the source sequence, as in `attest_message`
+        let sequence_record_account = &accounts[1];
+        let (sequence_record_key, sequence_record_bump) = SequenceRecord::pda(
+            &args.integrator_program_id,
+            args.src_chain,
+            message.src_addr,
+            message.sequence,
+        );
+        require_keys_eq!(
+            sequence_record_account.key(),
+            sequence_record_key,
+            EndpointError::InvalidBatchAccounts
+        );
+        let sequence_record_created = is_uncreated(sequence_record_account);
+        if sequence_record_created {
+            create_pda_account(
+                sequence_record_account,
+                &payer,
+                &system_program,
+                8 + SequenceRecord::INIT_SPACE,
+                &[
+                    SequenceRecord::SEED_PREFIX,
+                    args.integrator_program_id.as_ref(),
+                    args.src_chain.to_be_bytes().as_ref(),
+                    message.src_addr.as_ref(),
+                    message.sequence.to_be_bytes().as_ref(),
+                    &[sequence_record_bump],
+                ],
+            )?;
+            SequenceRecord::new(
+                sequence_record_bump,
+                args.integrator_program_id,
+                args.src_chain,
+                message.src_addr,
+                message.sequence,
+                message.payload_hash,
+            )
+            .try_serialize(&mut &mut sequence_record_account.try_borrow_mut_data()?[..])?;
+        } else {
+            let mut sequence_record = Account::<SequenceRecord>::try_from(sequence_record_account)?;
+            if let Some(equivocation) =
+                sequence_record.record(message.payload_hash, args.adapter_program_id)
+            {
+                sequence_record.exit(&crate::ID)?;
+                emit_cpi!(equivocation);
+            }
+        }
+
+        emit_cpi!(MessageAttestedTo {
+            message_hash,
+            src_chain: args.src_chain,
+            src_addr: message.src_addr,
+            sequence: message.sequence,
+            dst_chain: CHAIN_ID,
+            dst_addr: args.integrator_program_id.to_bytes(),
+            payload_hash: message.payload_hash,
+            attested_bitmap: attestation_info.attested_adapters.as_value(),
+            attesting_adapter: args.adapter_program_id.to_bytes(),
+            metadata: vec![],
+        });
+
+        // Close the accounts recreated for a received sequence, as in `attest_message`
+        if integrator_chain_config.replay_windows
+            && ReplayWindow::is_executed_in(
+                &accounts[2],
+                &args.integrator_program_id,
+                args.src_chain,
+                message.src_addr,
+                message.sequence,
+            )?
+        {
+            if created {
+                attestation_info.close(payer.clone())?;
+            }
+            if sequence_record_created {
+                Account::<SequenceRecord>::try_from(sequence_record_account)?
+                    .close(payer.clone())?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::EndpointError,
+    event::MessageCancelled,
+    state::{AdapterQueue, OutboxMessage, SentMessageRecord, SequenceTracker},
+    utils::account::is_uncreated,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CancelMessageArgs {
+    pub integrator_program_id: Pubkey,
+    pub integrator_program_pda_bump: u8,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: CancelMessageArgs)]
+pub struct CancelMessage<'info> {
+    /// The PDA of the integrator program.
+    /// This makes sure that only the integrator program is authorized to use this ix
+    #[account(
+        seeds = [b"endpoint_integrator"],
+        bump = args.integrator_program_pda_bump,
+        seeds::program = args.integrator_program_id
+    )]
+    pub integrator_program_pda: Signer<'info>,
+
+    /// The outbox message to cancel
+    /// It must have been sent by the integrator, and is closed to the refund recipient
+    #[account(
+        mut,
+        close = refund_recipient,
+        has_one = refund_recipient,
+        constraint = outbox_message.src_addr == args.integrator_program_id.to_bytes()
+            @ EndpointError::InvalidOutboxMessage,
+    )]
+    pub outbox_message: Account<'info, OutboxMessage>,
+
+    /// The sequence tracker of the integrator
+    /// The count of its outbox messages is updated here
+    #[account(
+        mut,
+        seeds = [
+            SequenceTracker::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = sequence_tracker.bump,
+    )]
+    pub sequence_tracker: Account<'info, SequenceTracker>,
+
+    /// The record of the message, if it was sent with one
+    /// It is marked as cancelled here, so that the message cannot be re-picked up
+    /// CHECK: The seeds constraint checks the address, and the account is only written if it
+    /// exists
+    #[account(
+        mut,
+        seeds = [
+            SentMessageRecord::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            outbox_message.sequence.to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub sent_message_record: UncheckedAccount<'info>,
+
+    /// The account that will receive the rent from closing the outbox message account
+    #[account(mut)]
+    /// CHECK: This is an account for receiving the rent refund
+    pub refund_recipient: AccountInfo<'info>,
+}
+
+/// Cancels an outbox message before any adapter has picked it up.
+///
+/// The outbox message is closed and its rent refunded to the account that paid for it.
+/// Adapters and indexers should skip messages that were cancelled. The message keeps its
+/// sequence, which is not reused. If the message has a `SentMessageRecord`, it is marked as
+/// cancelled so that `request_repickup` refuses the message, including when the cancelled outbox
+/// message was itself created by `request_repickup`.
+///
+/// `remaining_accounts` must hold the `AdapterQueue`s (writable) of every adapter the message is
+/// outstanding for that has a queue, in increasing adapter index. The message is removed from
+/// each of them.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts involved.
+/// * `args` - The arguments for the instruction, including:
+///   * `integrator_program_id`: The program ID of the integrator.
+///   * `integrator_program_pda_bump`: The bump seed for the integrator program PDA.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The outbox message was not sent by the integrator (EndpointError::InvalidOutboxMessage).
+/// * An adapter has picked up the message, or it was pushed to an adapter when it was sent
+///   (EndpointError::MessageAlreadyPickedUp).
+/// * The adapter queues are not those of the outstanding adapters that have one, in increasing
+///   adapter index (EndpointError::InvalidAdapterQueue).
+///
+/// # Events
+///
+/// Emits a `MessageCancelled` event
+pub fn cancel_message<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelMessage<'info>>,
+    args: CancelMessageArgs,
+) -> Result<()> {
+    let outbox_message = &ctx.accounts.outbox_message;
+    require!(
+        outbox_message.outstanding_adapters.as_value()
+            == outbox_message.initial_adapters.as_value(),
+        EndpointError::MessageAlreadyPickedUp
+    );
+
+    ctx.accounts.sequence_tracker.record_outbox_cancelled();
+
+    // Stop the message from being re-picked up
+    let sent_message_record = &ctx.accounts.sent_message_record;
+    if !is_uncreated(sent_message_record) {
+        require_keys_eq!(
+            *sent_message_record.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let mut record =
+            SentMessageRecord::try_deserialize(&mut &sent_message_record.try_borrow_data()?[..])?;
+        record.cancelled = true;
+        record.try_serialize(&mut &mut sent_message_record.try_borrow_mut_data()?[..])?;
+    }
+
+    AdapterQueue::remove_all(
+        ctx.remaining_accounts,
+        &args.integrator_program_id,
+        ctx.accounts
+            .sequence_tracker
+            .queued(outbox_message.outstanding_adapters),
+        &outbox_message.key(),
+    )?;
+
+    emit_cpi!(MessageCancelled {
+        src_addr: outbox_message.src_addr,
+        sequence: outbox_message.sequence,
+        dst_chain: outbox_message.dst_chain,
+        dst_addr: outbox_message.dst_addr,
+        payload_hash: outbox_message.payload_hash,
+        outbox_message: outbox_message.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use crate::state::{AdapterIndex, AdapterInfo, AdapterQueue, AdapterStats, IntegratorTombstone};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CloseDeregisteredAdapterArgs {
+    /// The Pubkey of the deregistered integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The Pubkey of the adapter program
+    pub adapter_program_id: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CloseDeregisteredAdapterArgs)]
+pub struct CloseDeregisteredAdapter<'info> {
+    /// The tombstone of the deregistered integrator
+    #[account(
+        seeds = [
+            IntegratorTombstone::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = integrator_tombstone.bump,
+    )]
+    pub integrator_tombstone: Account<'info, IntegratorTombstone>,
+
+    /// The adapter info account to close
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            AdapterInfo::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_info.bump,
+    )]
+    pub adapter_info: Account<'info, AdapterInfo>,
+
+    /// The adapter stats account to close
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            AdapterStats::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_stats.bump,
+    )]
+    pub adapter_stats: Account<'info, AdapterStats>,
+
+    /// The adapter's queue of pending messages to close, if it has one
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            AdapterQueue::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_queue.bump,
+    )]
+    pub adapter_queue: Option<Box<Account<'info, AdapterQueue>>>,
+
+    /// The reverse index entry from the adapter to the integrator to close
+    /// Adapters added before `add_adapter` created it may not have one
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            AdapterIndex::SEED_PREFIX,
+            args.adapter_program_id.as_ref(),
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = adapter_index.bump,
+    )]
+    pub adapter_index: Option<Account<'info, AdapterIndex>>,
+
+    /// The rent recipient chosen when the integrator was deregistered
+    #[account(mut, address = integrator_tombstone.rent_recipient)]
+    /// CHECK: Checked against the tombstone
+    pub rent_recipient: AccountInfo<'info>,
+}
+
+/// Closes the accounts of an adapter of a deregistered integrator
+///
+/// Anyone can call this once the integrator has been deregistered. The `AdapterInfo` and
+/// `AdapterStats` accounts, and the `AdapterQueue` and `AdapterIndex` if passed, are closed, with
+/// their rent going to the recipient recorded in the integrator's tombstone.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts involved
+/// * `args` - The arguments for the instruction, containing:
+///   - `integrator_program_id`: The public key of the deregistered integrator program
+///   - `adapter_program_id`: The public key of the adapter program
+pub fn close_deregistered_adapter(
+    _ctx: Context<CloseDeregisteredAdapter>,
+    _args: CloseDeregisteredAdapterArgs,
+) -> Result<()> {
+    Ok(())
+}
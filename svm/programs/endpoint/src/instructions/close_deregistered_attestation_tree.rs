@@ -0,0 +1,54 @@
+use crate::error::EndpointError;
+use crate::state::{AttestationTree, IntegratorTombstone};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CloseDeregisteredAttestationTreeArgs {
+    /// The Pubkey of the deregistered integrator program
+    pub integrator_program_id: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CloseDeregisteredAttestationTreeArgs)]
+pub struct CloseDeregisteredAttestationTree<'info> {
+    /// The tombstone of the deregistered integrator
+    #[account(
+        seeds = [
+            IntegratorTombstone::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = integrator_tombstone.bump,
+    )]
+    pub integrator_tombstone: Account<'info, IntegratorTombstone>,
+
+    /// The attestation tree to close
+    #[account(
+        mut,
+        close = rent_recipient,
+        constraint = attestation_tree.integrator_program_id == args.integrator_program_id
+            @ EndpointError::InvalidAttestationTree,
+    )]
+    pub attestation_tree: Account<'info, AttestationTree>,
+
+    /// The rent recipient chosen when the integrator was deregistered
+    #[account(mut, address = integrator_tombstone.rent_recipient)]
+    /// CHECK: Checked against the tombstone
+    pub rent_recipient: AccountInfo<'info>,
+}
+
+/// Closes an attestation tree of a deregistered integrator
+///
+/// Anyone can call this once the integrator has been deregistered. The rent goes to the
+/// recipient recorded in the integrator's tombstone.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts involved
+/// * `args` - The arguments for the instruction, containing:
+///   - `integrator_program_id`: The public key of the deregistered integrator program
+pub fn close_deregistered_attestation_tree(
+    _ctx: Context<CloseDeregisteredAttestationTree>,
+    _args: CloseDeregisteredAttestationTreeArgs,
+) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::state::{AuditLogPage, IntegratorTombstone};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CloseDeregisteredAuditLogPageArgs {
+    /// The Pubkey of the deregistered integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The index of the audit log page to close
+    pub page_index: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CloseDeregisteredAuditLogPageArgs)]
+pub struct CloseDeregisteredAuditLogPage<'info> {
+    /// The tombstone of the deregistered integrator
+    #[account(
+        seeds = [
+            IntegratorTombstone::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = integrator_tombstone.bump,
+    )]
+    pub integrator_tombstone: Account<'info, IntegratorTombstone>,
+
+    /// The audit log page to close
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.page_index.to_be_bytes().as_ref(),
+        ],
+        bump = audit_log_page.bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    /// The rent recipient chosen when the integrator was deregistered
+    #[account(mut, address = integrator_tombstone.rent_recipient)]
+    /// CHECK: Checked against the tombstone
+    pub rent_recipient: AccountInfo<'info>,
+}
+
+/// Closes an audit log page of a deregistered integrator
+///
+/// Anyone can call this once the integrator has been deregistered. The rent goes to the
+/// recipient recorded in the integrator's tombstone.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts involved
+/// * `args` - The arguments for the instruction, containing:
+///   - `integrator_program_id`: The public key of the deregistered integrator program
+///   - `page_index`: The index of the audit log page
+pub fn close_deregistered_audit_log_page(
+    _ctx: Context<CloseDeregisteredAuditLogPage>,
+    _args: CloseDeregisteredAuditLogPageArgs,
+) -> Result<()> {
+    Ok(())
+}
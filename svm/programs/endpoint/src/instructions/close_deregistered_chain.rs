@@ -0,0 +1,61 @@
+use crate::state::{IntegratorChainConfig, IntegratorTombstone};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CloseDeregisteredChainArgs {
+    /// The Pubkey of the deregistered integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The chain ID of the integrator chain config to close
+    pub chain_id: u16,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CloseDeregisteredChainArgs)]
+pub struct CloseDeregisteredChain<'info> {
+    /// The tombstone of the deregistered integrator
+    #[account(
+        seeds = [
+            IntegratorTombstone::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = integrator_tombstone.bump,
+    )]
+    pub integrator_tombstone: Account<'info, IntegratorTombstone>,
+
+    /// The integrator chain config account to close
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            IntegratorChainConfig::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.chain_id.to_be_bytes().as_ref(),
+        ],
+        bump = integrator_chain_config.bump,
+    )]
+    pub integrator_chain_config: Account<'info, IntegratorChainConfig>,
+
+    /// The rent recipient chosen when the integrator was deregistered
+    #[account(mut, address = integrator_tombstone.rent_recipient)]
+    /// CHECK: Checked against the tombstone
+    pub rent_recipient: AccountInfo<'info>,
+}
+
+/// Closes an integrator chain config of a deregistered integrator
+///
+/// Anyone can call this once the integrator has been deregistered. The rent goes to the
+/// recipient recorded in the integrator's tombstone.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts involved
+/// * `args` - The arguments for the instruction, containing:
+///   - `integrator_program_id`: The public key of the deregistered integrator program
+///   - `chain_id`: The chain ID of the integrator chain config
+pub fn close_deregistered_chain(
+    _ctx: Context<CloseDeregisteredChain>,
+    _args: CloseDeregisteredChainArgs,
+) -> Result<()> {
+    Ok(())
+}
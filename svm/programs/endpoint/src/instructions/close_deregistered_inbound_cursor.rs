@@ -0,0 +1,66 @@
+use crate::state::{InboundCursor, IntegratorTombstone};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CloseDeregisteredInboundCursorArgs {
+    /// The Pubkey of the deregistered integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The source chain of the cursor
+    pub src_chain: u16,
+
+    /// The source address of the cursor
+    pub src_addr: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(args: CloseDeregisteredInboundCursorArgs)]
+pub struct CloseDeregisteredInboundCursor<'info> {
+    /// The tombstone of the deregistered integrator
+    #[account(
+        seeds = [
+            IntegratorTombstone::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = integrator_tombstone.bump,
+    )]
+    pub integrator_tombstone: Account<'info, IntegratorTombstone>,
+
+    /// The inbound cursor to close
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            InboundCursor::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+        ],
+        bump = inbound_cursor.bump,
+    )]
+    pub inbound_cursor: Account<'info, InboundCursor>,
+
+    /// The rent recipient chosen when the integrator was deregistered
+    #[account(mut, address = integrator_tombstone.rent_recipient)]
+    /// CHECK: Checked against the tombstone
+    pub rent_recipient: AccountInfo<'info>,
+}
+
+/// Closes an inbound cursor of a deregistered integrator
+///
+/// Anyone can call this once the integrator has been deregistered. The rent goes to the
+/// recipient recorded in the integrator's tombstone.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts involved
+/// * `args` - The arguments for the instruction, containing:
+///   - `integrator_program_id`: The public key of the deregistered integrator program
+///   - `src_chain`: The source chain of the cursor
+///   - `src_addr`: The source address of the cursor
+pub fn close_deregistered_inbound_cursor(
+    _ctx: Context<CloseDeregisteredInboundCursor>,
+    _args: CloseDeregisteredInboundCursorArgs,
+) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use crate::state::{IntegratorTombstone, ReplayFloor, ReplayWindow};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CloseDeregisteredReplayWindowArgs {
+    /// The Pubkey of the deregistered integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The source chain of the window
+    pub src_chain: u16,
+
+    /// The source address of the window
+    pub src_addr: [u8; 32],
+
+    /// The index of the window
+    pub window_index: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CloseDeregisteredReplayWindowArgs)]
+pub struct CloseDeregisteredReplayWindow<'info> {
+    /// The tombstone of the deregistered integrator
+    #[account(
+        seeds = [
+            IntegratorTombstone::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = integrator_tombstone.bump,
+    )]
+    pub integrator_tombstone: Account<'info, IntegratorTombstone>,
+
+    /// The replay window to close
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            ReplayWindow::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+            args.window_index.to_be_bytes().as_ref(),
+        ],
+        bump = replay_window.bump,
+    )]
+    pub replay_window: Box<Account<'info, ReplayWindow>>,
+
+    /// The replay floor of the source address, if it should be closed as well
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            ReplayFloor::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+        ],
+        bump = replay_floor.bump,
+    )]
+    pub replay_floor: Option<Account<'info, ReplayFloor>>,
+
+    /// The rent recipient chosen when the integrator was deregistered
+    #[account(mut, address = integrator_tombstone.rent_recipient)]
+    /// CHECK: Checked against the tombstone
+    pub rent_recipient: AccountInfo<'info>,
+}
+
+/// Closes a replay window of a deregistered integrator, and optionally the replay floor of
+/// its source address
+///
+/// Anyone can call this once the integrator has been deregistered. The rent goes to the
+/// recipient recorded in the integrator's tombstone.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts involved
+/// * `args` - The arguments for the instruction, containing:
+///   - `integrator_program_id`: The public key of the deregistered integrator program
+///   - `src_chain`: The source chain of the window
+///   - `src_addr`: The source address of the window
+///   - `window_index`: The index of the window
+pub fn close_deregistered_replay_window(
+    _ctx: Context<CloseDeregisteredReplayWindow>,
+    _args: CloseDeregisteredReplayWindowArgs,
+) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::state::{IntegratorTombstone, SentMessageRecord};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CloseDeregisteredSentMessageArgs {
+    /// The Pubkey of the deregistered integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The sequence of the recorded message
+    pub sequence: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CloseDeregisteredSentMessageArgs)]
+pub struct CloseDeregisteredSentMessage<'info> {
+    /// The tombstone of the deregistered integrator
+    #[account(
+        seeds = [
+            IntegratorTombstone::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = integrator_tombstone.bump,
+    )]
+    pub integrator_tombstone: Account<'info, IntegratorTombstone>,
+
+    /// The sent message record to close
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer,
+        seeds = [
+            SentMessageRecord::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.sequence.to_be_bytes().as_ref(),
+        ],
+        bump = sent_message_record.bump,
+    )]
+    pub sent_message_record: Account<'info, SentMessageRecord>,
+
+    /// The account that paid for the record, which receives its rent
+    #[account(mut)]
+    /// CHECK: Checked against the record's `payer`
+    pub payer: AccountInfo<'info>,
+}
+
+/// Closes a sent message record of a deregistered integrator
+///
+/// Anyone can call this once the integrator has been deregistered. As with
+/// `close_sent_message_record`, the rent goes to the account that paid for the record.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts involved
+/// * `args` - The arguments for the instruction, containing:
+///   - `integrator_program_id`: The public key of the deregistered integrator program
+///   - `sequence`: The sequence of the recorded message
+pub fn close_deregistered_sent_message(
+    _ctx: Context<CloseDeregisteredSentMessage>,
+    _args: CloseDeregisteredSentMessageArgs,
+) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use crate::error::EndpointError;
+use crate::event::ReplayWindowClosed;
+use crate::state::{AuditLogPage, ConfigAction, IntegratorConfig, ReplayFloor, ReplayWindow};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CloseReplayWindowArgs {
+    /// The Pubkey of the integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// Source chain ID
+    pub src_chain: u16,
+
+    /// Source address (32 bytes)
+    pub src_addr: [u8; 32],
+
+    /// The index of the window to close
+    pub window_index: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: CloseReplayWindowArgs)]
+pub struct CloseReplayWindow<'info> {
+    /// The account that pays for the replay floor, if it does not exist yet, and for a new audit
+    /// log page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The admin account that has the authority to configure the integrator
+    pub admin: Signer<'info>,
+
+    /// The integrator config account
+    /// The account constraints here make sure that the one signing this transaction is the admin
+    /// of the config
+    #[account(
+        mut,
+        seeds = [IntegratorConfig::SEED_PREFIX, args.integrator_program_id.as_ref()],
+        bump = integrator_config.bump,
+    )]
+    pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The replay floor of the source address
+    /// This account is initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayFloor::INIT_SPACE,
+        seeds = [
+            ReplayFloor::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_floor: Account<'info, ReplayFloor>,
+
+    /// The replay window to close
+    #[account(
+        mut,
+        close = recipient,
+        seeds = [
+            ReplayWindow::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+            args.window_index.to_be_bytes().as_ref(),
+        ],
+        bump = replay_window.bump,
+    )]
+    pub replay_window: Box<Account<'info, ReplayWindow>>,
+
+    /// The account that will receive the rent from closing the replay window
+    #[account(mut)]
+    /// CHECK: This is an account for receiving the rent refund
+    pub recipient: AccountInfo<'info>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CloseReplayWindow<'info> {
+    pub fn validate(&self, args: &CloseReplayWindowArgs) -> Result<()> {
+        self.integrator_config.check_admin(&self.admin)?;
+
+        // Ensure src_chain is not zero
+        require!(args.src_chain != 0, EndpointError::InvalidChainId);
+
+        Ok(())
+    }
+}
+
+/// Closes a replay window of a source address and reclaims its rent
+///
+/// Every window up to and including the closed one is treated as closed from then on, and
+/// `recv_message` and `exec_message` reject their sequences, whether or not they were executed.
+/// Windows should therefore only be closed once no message they cover is still in flight.
+///
+/// The closure is appended to the integrator's audit log.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, containing:
+///   * `integrator_program_id` - The program ID of the integrator
+///   * `src_chain` - The source chain ID
+///   * `src_addr` - The source address
+///   * `window_index` - The index of the window to close
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * `src_chain` is 0 (EndpointError::InvalidChainId)
+///
+/// # Events
+///
+/// Emits a `ReplayWindowClosed` event
+#[access_control(CloseReplayWindow::validate(&ctx.accounts, &args))]
+pub fn close_replay_window(
+    ctx: Context<CloseReplayWindow>,
+    args: CloseReplayWindowArgs,
+) -> Result<()> {
+    let replay_floor = &mut ctx.accounts.replay_floor;
+
+    // If the replay_floor is newly created, initialize it
+    if replay_floor.src_chain == 0 {
+        replay_floor.set_inner(ReplayFloor::new(
+            ctx.bumps.replay_floor,
+            args.integrator_program_id,
+            args.src_chain,
+            args.src_addr,
+        ));
+    }
+
+    replay_floor.close_through(args.window_index);
+
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        ctx.accounts.admin.key(),
+        ConfigAction::ReplayWindowClosed {
+            src_chain: args.src_chain,
+            src_addr: args.src_addr,
+            window_index: args.window_index,
+        },
+        Clock::get()?.slot,
+    );
+
+    emit_cpi!(ReplayWindowClosed {
+        integrator: args.integrator_program_id,
+        src_chain: args.src_chain,
+        src_addr: args.src_addr,
+        window_index: args.window_index,
+        first_open_window: replay_floor.first_open_window,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::state::SentMessageRecord;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CloseSentMessageRecordArgs {
+    pub integrator_program_id: Pubkey,
+    pub integrator_program_pda_bump: u8,
+    /// The sequence of the recorded message
+    pub sequence: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CloseSentMessageRecordArgs)]
+pub struct CloseSentMessageRecord<'info> {
+    /// The PDA of the integrator program.
+    /// This makes sure that only the integrator program is authorized to use this ix
+    #[account(
+        seeds = [b"endpoint_integrator"],
+        bump = args.integrator_program_pda_bump,
+        seeds::program = args.integrator_program_id
+    )]
+    pub integrator_program_pda: Signer<'info>,
+
+    /// The record to close
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer,
+        seeds = [
+            SentMessageRecord::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.sequence.to_be_bytes().as_ref(),
+        ],
+        bump = sent_message_record.bump,
+    )]
+    pub sent_message_record: Account<'info, SentMessageRecord>,
+
+    /// The account that paid for the record, which receives its rent
+    #[account(mut)]
+    /// CHECK: Checked against the record's `payer`
+    pub payer: AccountInfo<'info>,
+}
+
+/// Closes the record of a sent message, refunding its rent to the account that paid for it.
+///
+/// The message can no longer be handed to adapters again with `request_repickup` afterwards.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts involved.
+/// * `args` - The arguments for the instruction, including:
+///   * `integrator_program_id`: The program ID of the integrator.
+///   * `integrator_program_pda_bump`: The bump seed for the integrator program PDA.
+///   * `sequence`: The sequence of the recorded message.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * `payer` is not the account that paid for the record (ErrorCode::ConstraintHasOne).
+pub fn close_sent_message_record(
+    _ctx: Context<CloseSentMessageRecord>,
+    _args: CloseSentMessageRecordArgs,
+) -> Result<()> {
+    Ok(())
+}
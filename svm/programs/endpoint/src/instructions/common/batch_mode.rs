@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+/// How a batch instruction treats messages that the adapter has already handled
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchMode {
+    /// Fail the whole batch, as the single-message instruction would
+    AllOrNothing,
+
+    /// Leave the message out and carry on with the rest of the batch
+    SkipDone,
+}
@@ -1,3 +1,5 @@
 pub mod adapter_info_args;
+pub mod batch_mode;
 
 pub use adapter_info_args::*;
+pub use batch_mode::*;
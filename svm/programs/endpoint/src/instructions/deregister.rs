@@ -0,0 +1,186 @@
+use crate::{
+    error::EndpointError,
+    event::IntegratorDeregistered,
+    state::{
+        AdapterStats, AuditLogPage, ConfigAction, IntegratorConfig, IntegratorTombstone,
+        SequenceTracker,
+    },
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DeregisterArgs {
+    /// The Pubkey of the integrator program
+    pub integrator_program_id: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: DeregisterArgs)]
+pub struct Deregister<'info> {
+    /// The account that pays for the tombstone and for a new audit log page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The admin registered on IntegratorConfig
+    pub admin: Signer<'info>,
+
+    /// The integrator config account, which is closed
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [IntegratorConfig::SEED_PREFIX, args.integrator_program_id.as_ref()],
+        bump = integrator_config.bump,
+    )]
+    pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The sequence tracker account, which is closed
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [SequenceTracker::SEED_PREFIX, args.integrator_program_id.as_ref()],
+        bump = sequence_tracker.bump,
+    )]
+    pub sequence_tracker: Account<'info, SequenceTracker>,
+
+    /// The tombstone left in place of the integrator's configuration
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + IntegratorTombstone::INIT_SPACE,
+        seeds = [
+            IntegratorTombstone::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+        ],
+        bump
+    )]
+    pub integrator_tombstone: Account<'info, IntegratorTombstone>,
+
+    /// The account that will receive the rent from closing the integrator's accounts
+    #[account(mut)]
+    /// CHECK: This is an account for receiving the rent refund
+    pub rent_recipient: AccountInfo<'info>,
+
+    /// The current page of the integrator's audit log, which the deregistration is appended to
+    /// The page outlives the integrator config and is closed with `close_deregistered_audit_log_page`
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Deregister<'info> {
+    pub fn validate(&self, adapter_stats: &[AccountInfo]) -> Result<()> {
+        self.integrator_config.check_admin(&self.admin)?;
+
+        let adapters = &self.integrator_config.adapter_infos;
+        require_eq!(
+            adapter_stats.len(),
+            adapters.len(),
+            EndpointError::InvalidAdapterStats
+        );
+        let mut outbox_messages_closed = 0u64;
+        for (account, adapter) in adapter_stats.iter().zip(adapters) {
+            outbox_messages_closed =
+                outbox_messages_closed.saturating_add(AdapterStats::outbox_messages_closed(
+                    account,
+                    &self.integrator_config.integrator_program_id,
+                    adapter,
+                )?);
+        }
+
+        // Outbox messages still waiting for adapters would never be delivered
+        require!(
+            self.sequence_tracker.outbox_messages <= outbox_messages_closed,
+            EndpointError::OutboxMessagesOutstanding
+        );
+
+        Ok(())
+    }
+}
+
+/// Deregisters an integrator program from the endpoint
+///
+/// This function performs the following steps:
+/// 1. Closes the IntegratorConfig and SequenceTracker accounts, sending their rent to the
+///    rent recipient.
+/// 2. Initializes an IntegratorTombstone account, which stops the integrator program from being
+///    registered again.
+/// 3. Appends a last entry to the integrator's audit log, whose pages are left in place.
+/// 4. Emits an IntegratorDeregistered event.
+///
+/// The integrator's `AdapterInfo`, `AdapterStats` and `IntegratorChainConfig` accounts are closed
+/// afterwards with `close_deregistered_adapter` and `close_deregistered_chain`, and its audit log
+/// pages, sent message records, inbound cursors, replay windows and attestation trees with the
+/// other `close_deregistered_*` instructions. Anyone can call these, and they also send the rent
+/// to the rent recipient, except for sent message records, which refund the account that paid
+/// for them.
+///
+/// `remaining_accounts` must hold the `AdapterStats` account of each of the integrator's
+/// adapters, in the order of `IntegratorConfig::adapter_infos`. Every outbox message that was
+/// not cancelled must have been closed by a pick up, as counted in these accounts.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts involved
+/// * `args` - The arguments for the instruction, containing:
+///   - `integrator_program_id`: The public key of the integrator program
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The signer is not the admin of the integrator
+/// * `remaining_accounts` does not hold the adapter stats of the integrator's adapters
+///   (EndpointError::InvalidAdapterStats)
+/// * Any outbox message of the integrator has not been picked up by all of its adapters
+///   (EndpointError::OutboxMessagesOutstanding)
+///
+/// # Events
+///
+/// Emits an `IntegratorDeregistered` event
+#[access_control(Deregister::validate(&ctx.accounts, ctx.remaining_accounts))]
+pub fn deregister(ctx: Context<Deregister>, args: DeregisterArgs) -> Result<()> {
+    let final_sequence = ctx.accounts.sequence_tracker.sequence;
+
+    ctx.accounts
+        .integrator_tombstone
+        .set_inner(IntegratorTombstone {
+            bump: ctx.bumps.integrator_tombstone,
+            integrator_program_id: args.integrator_program_id,
+            rent_recipient: ctx.accounts.rent_recipient.key(),
+            final_sequence,
+        });
+
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::Deregistered {
+            rent_recipient: ctx.accounts.rent_recipient.key(),
+        },
+        Clock::get()?.slot,
+    );
+
+    emit_cpi!(IntegratorDeregistered {
+        integrator: args.integrator_program_id,
+        rent_recipient: ctx.accounts.rent_recipient.key(),
+        final_sequence,
+    });
+
+    Ok(())
+}
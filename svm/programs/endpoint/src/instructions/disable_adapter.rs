@@ -1,13 +1,20 @@
 use crate::error::EndpointError;
 use crate::event::{RecvAdapterDisabledForChain, SendAdapterDisabledForChain};
 use crate::instructions::common::AdapterInfoArgs;
-use crate::state::{AdapterInfo, IntegratorChainConfig, IntegratorConfig};
+use crate::state::{
+    AdapterDirection, AdapterIndex, AdapterInfo, AuditLogPage, ConfigAction, IntegratorChainConfig,
+    IntegratorConfig,
+};
 use anchor_lang::prelude::*;
 
 #[event_cpi]
 #[derive(Accounts)]
 #[instruction(args: AdapterInfoArgs)]
 pub struct DisableAdapter<'info> {
+    /// The account that pays for a new audit log page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     /// The admin account that has the authority to disable adapters
     pub admin: Signer<'info>,
 
@@ -15,6 +22,7 @@ pub struct DisableAdapter<'info> {
     /// The account constraints here make sure that the one signing this transaction is the admin
     /// of the config
     #[account(
+        mut,
         seeds = [IntegratorConfig::SEED_PREFIX, args.integrator_program_id.as_ref()],
         bump = integrator_config.bump,
     )]
@@ -45,6 +53,36 @@ pub struct DisableAdapter<'info> {
         bump = adapter_info.bump,
     )]
     pub adapter_info: Account<'info, AdapterInfo>,
+
+    /// The reverse index entry from the adapter to this integrator
+    /// Disabling never grows the account, so no payer is needed for it
+    #[account(
+        mut,
+        seeds = [
+            AdapterIndex::SEED_PREFIX,
+            args.adapter_program_id.as_ref(),
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = adapter_index.bump,
+    )]
+    pub adapter_index: Account<'info, AdapterIndex>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> DisableAdapter<'info> {
@@ -55,6 +93,9 @@ impl<'info> DisableAdapter<'info> {
 
 /// Disables a receive adapter
 ///
+/// The direction is also removed from the adapter's AdapterIndex, along with the chain once
+/// neither direction is enabled.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context of the request
@@ -80,10 +121,33 @@ pub fn disable_recv_adapter(ctx: Context<DisableAdapter>, args: AdapterInfoArgs)
         return Err(EndpointError::AdapterAlreadyDisabled.into());
     }
 
-    // Disable the adapter in the bitmap
+    // Disable the adapter in the bitmap and advance the config epoch
     integrator_chain_config
         .recv_adapter_bitmap
         .set(adapter_info.index, false)?;
+    integrator_chain_config.advance_config_epoch();
+
+    ctx.accounts
+        .adapter_index
+        .set_direction(args.chain_id, AdapterDirection::Recv, false);
+
+    let update = ctx.accounts.integrator_chain_config.digest_update();
+    ctx.accounts.integrator_config.update_config_digest(update);
+
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::RecvAdapterDisabled {
+            adapter: args.adapter_program_id,
+            chain_id: args.chain_id,
+        },
+        Clock::get()?.slot,
+    );
 
     emit_cpi!(RecvAdapterDisabledForChain {
         integrator: args.integrator_program_id,
@@ -96,6 +160,9 @@ pub fn disable_recv_adapter(ctx: Context<DisableAdapter>, args: AdapterInfoArgs)
 
 /// Disables a send adapter
 ///
+/// The direction is also removed from the adapter's AdapterIndex, along with the chain once
+/// neither direction is enabled.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context of the request
@@ -121,10 +188,33 @@ pub fn disable_send_adapter(ctx: Context<DisableAdapter>, args: AdapterInfoArgs)
         return Err(EndpointError::AdapterAlreadyDisabled.into());
     }
 
-    // Disable the adapter in the bitmap
+    // Disable the adapter in the bitmap and advance the config epoch
     integrator_chain_config
         .send_adapter_bitmap
         .set(adapter_info.index, false)?;
+    integrator_chain_config.advance_config_epoch();
+
+    ctx.accounts
+        .adapter_index
+        .set_direction(args.chain_id, AdapterDirection::Send, false);
+
+    let update = ctx.accounts.integrator_chain_config.digest_update();
+    ctx.accounts.integrator_config.update_config_digest(update);
+
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::SendAdapterDisabled {
+            adapter: args.adapter_program_id,
+            chain_id: args.chain_id,
+        },
+        Clock::get()?.slot,
+    );
 
     emit_cpi!(SendAdapterDisabledForChain {
         integrator: args.integrator_program_id,
@@ -1,9 +1,16 @@
-use crate::{event::AdminDiscarded, state::IntegratorConfig};
+use crate::{
+    event::AdminDiscarded,
+    state::{AuditLogPage, ConfigAction, IntegratorConfig},
+};
 use anchor_lang::prelude::*;
 
 #[event_cpi]
 #[derive(Accounts)]
 pub struct DiscardAdmin<'info> {
+    /// The account that pays for a new audit log page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     /// The current admin of the IntegratorConfig account
     pub admin: Signer<'info>,
 
@@ -17,6 +24,23 @@ pub struct DiscardAdmin<'info> {
         bump = integrator_config.bump,
     )]
     pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            integrator_config.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> DiscardAdmin<'info> {
@@ -45,7 +69,22 @@ impl<'info> DiscardAdmin<'info> {
 /// Emits an `AdminDiscarded` event with the integrator's public key.
 #[access_control(DiscardAdmin::validate(&ctx.accounts))]
 pub fn discard_admin(ctx: Context<DiscardAdmin>) -> Result<()> {
+    // Resolved before the admin changes, as it may be the admin's admin authority
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+
     ctx.accounts.integrator_config.admin = None;
+    ctx.accounts.integrator_config.update_admin_digest();
+
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::AdminDiscarded,
+        Clock::get()?.slot,
+    );
 
     emit_cpi!(AdminDiscarded {
         integrator: ctx.accounts.integrator_config.integrator_program_id,
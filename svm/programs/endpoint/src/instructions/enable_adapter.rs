@@ -1,7 +1,11 @@
 use crate::error::EndpointError;
 use crate::event::{RecvAdapterEnabledForChain, SendAdapterEnabledForChain};
 use crate::instructions::common::AdapterInfoArgs;
-use crate::state::{AdapterInfo, IntegratorChainConfig, IntegratorConfig};
+use crate::state::{
+    AdapterDirection, AdapterIndex, AdapterInfo, AuditLogPage, ConfigAction, IntegratorChainConfig,
+    IntegratorConfig,
+};
+use crate::utils::account::grow_account;
 use crate::utils::bitmap::Bitmap;
 use anchor_lang::prelude::*;
 
@@ -21,6 +25,7 @@ pub struct EnableAdapter<'info> {
     /// of the config
     /// The `has_one` constraint checks if admin signer is the current admin of the config
     #[account(
+        mut,
         seeds = [IntegratorConfig::SEED_PREFIX, args.integrator_program_id.as_ref()],
         bump = integrator_config.bump,
     )]
@@ -51,9 +56,38 @@ pub struct EnableAdapter<'info> {
             args.adapter_program_id.as_ref(),
         ],
         bump = adapter_info.bump,
+        constraint = !adapter_info.retired @ EndpointError::AdapterRetired,
     )]
     pub adapter_info: Account<'info, AdapterInfo>,
 
+    /// The reverse index entry from the adapter to this integrator
+    /// This is grown if the adapter is enabled on a chain it was not enabled on before
+    #[account(
+        mut,
+        seeds = [
+            AdapterIndex::SEED_PREFIX,
+            args.adapter_program_id.as_ref(),
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = adapter_index.bump,
+    )]
+    pub adapter_index: Account<'info, AdapterIndex>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
     /// The System Program
     pub system_program: Program<'info, System>,
 }
@@ -67,6 +101,19 @@ impl<'info> EnableAdapter<'info> {
 
         Ok(())
     }
+
+    /// Records the enabled direction in the adapter's AdapterIndex, growing it if needed
+    fn index_chain(&mut self, args: &AdapterInfoArgs, direction: AdapterDirection) -> Result<()> {
+        self.adapter_index
+            .set_direction(args.chain_id, direction, true);
+
+        grow_account(
+            &self.adapter_index.to_account_info(),
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+            8 + self.adapter_index.space(),
+        )
+    }
 }
 
 /// Enables a receive adapter for the integrator chain configuration
@@ -74,8 +121,10 @@ impl<'info> EnableAdapter<'info> {
 /// This function performs the following steps:
 /// 1. Initializes the IntegratorChainConfig if it's not already set up.
 /// 2. Checks if the adapter is already enabled.
-/// 3. Enables the adapter in the receive adapter bitmap.
-/// 4. Emits a RecvAdapterEnabledForChain event.
+/// 3. Enables the adapter in the receive adapter bitmap and advances the config epoch.
+/// 4. Records the enabled direction in the adapter's AdapterIndex.
+/// 5. Appends an entry to the integrator's audit log.
+/// 6. Emits a RecvAdapterEnabledForChain event.
 ///
 /// # Arguments
 ///
@@ -110,6 +159,9 @@ pub fn enable_recv_adapter(ctx: Context<EnableAdapter>, args: AdapterInfoArgs) -
             integrator_program_id: args.integrator_program_id,
             send_adapter_bitmap: Bitmap::new(),
             recv_adapter_bitmap: Bitmap::new(),
+            config_epoch: 0,
+            ordered_delivery: false,
+            replay_windows: false,
         });
     }
 
@@ -123,6 +175,27 @@ pub fn enable_recv_adapter(ctx: Context<EnableAdapter>, args: AdapterInfoArgs) -
     integrator_chain_config
         .recv_adapter_bitmap
         .set(adapter_info.index, true)?;
+    integrator_chain_config.advance_config_epoch();
+
+    ctx.accounts.index_chain(&args, AdapterDirection::Recv)?;
+
+    let update = ctx.accounts.integrator_chain_config.digest_update();
+    ctx.accounts.integrator_config.update_config_digest(update);
+
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::RecvAdapterEnabled {
+            adapter: args.adapter_program_id,
+            chain_id: args.chain_id,
+        },
+        Clock::get()?.slot,
+    );
 
     emit_cpi!(RecvAdapterEnabledForChain {
         integrator: args.integrator_program_id,
@@ -138,8 +211,10 @@ pub fn enable_recv_adapter(ctx: Context<EnableAdapter>, args: AdapterInfoArgs) -
 /// This function performs the following steps:
 /// 1. Initializes the IntegratorChainConfig if it's not already set up.
 /// 2. Checks if the adapter is already enabled.
-/// 3. Enables the adapter in the send adapter bitmap.
-/// 4. Emits a SendAdapterEnabledForChain event.
+/// 3. Enables the adapter in the send adapter bitmap and advances the config epoch.
+/// 4. Records the enabled direction in the adapter's AdapterIndex.
+/// 5. Appends an entry to the integrator's audit log.
+/// 6. Emits a SendAdapterEnabledForChain event.
 ///
 /// # Arguments
 ///
@@ -174,6 +249,9 @@ pub fn enable_send_adapter(ctx: Context<EnableAdapter>, args: AdapterInfoArgs) -
             integrator_program_id: args.integrator_program_id,
             send_adapter_bitmap: Bitmap::new(),
             recv_adapter_bitmap: Bitmap::new(),
+            config_epoch: 0,
+            ordered_delivery: false,
+            replay_windows: false,
         });
     }
 
@@ -187,6 +265,27 @@ pub fn enable_send_adapter(ctx: Context<EnableAdapter>, args: AdapterInfoArgs) -
     integrator_chain_config
         .send_adapter_bitmap
         .set(adapter_info.index, true)?;
+    integrator_chain_config.advance_config_epoch();
+
+    ctx.accounts.index_chain(&args, AdapterDirection::Send)?;
+
+    let update = ctx.accounts.integrator_chain_config.digest_update();
+    ctx.accounts.integrator_config.update_config_digest(update);
+
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::SendAdapterEnabled {
+            adapter: args.adapter_program_id,
+            chain_id: args.chain_id,
+        },
+        Clock::get()?.slot,
+    );
 
     emit_cpi!(SendAdapterEnabledForChain {
         integrator: args.integrator_program_id,
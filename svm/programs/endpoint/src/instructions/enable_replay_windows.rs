@@ -0,0 +1,123 @@
+use crate::event::ReplayWindowsEnabled;
+use crate::state::{AuditLogPage, ConfigAction, IntegratorChainConfig, IntegratorConfig};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EnableReplayWindowsArgs {
+    /// The Pubkey of the integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The ID of the source chain
+    pub chain_id: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: EnableReplayWindowsArgs)]
+pub struct EnableReplayWindows<'info> {
+    /// The account that pays for a new audit log page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The admin account that has the authority to configure the integrator
+    pub admin: Signer<'info>,
+
+    /// The integrator config account
+    /// The account constraints here make sure that the one signing this transaction is the admin
+    /// of the config
+    #[account(
+        mut,
+        seeds = [IntegratorConfig::SEED_PREFIX, args.integrator_program_id.as_ref()],
+        bump = integrator_config.bump,
+    )]
+    pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The integrator chain config account
+    /// This is created by enabling an adapter for the chain. Else, it will throw
+    /// `AccountNotInitialized`
+    #[account(
+        mut,
+        seeds = [
+            IntegratorChainConfig::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.chain_id.to_be_bytes().as_ref(),
+        ],
+        bump = integrator_chain_config.bump,
+    )]
+    pub integrator_chain_config: Account<'info, IntegratorChainConfig>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EnableReplayWindows<'info> {
+    pub fn validate(&self) -> Result<()> {
+        self.integrator_config.check_admin(&self.admin)
+    }
+}
+
+/// Enables replay windows for messages from a chain
+///
+/// From then on, `recv_message` and `exec_message` record executed sequences in the source
+/// address's `ReplayWindow` accounts and close the message's `AttestationInfo` once it is
+/// delivered.
+///
+/// This cannot be undone: the closed `AttestationInfo` accounts no longer record that their
+/// messages were executed, so turning replay windows off again would allow replays.
+/// Messages executed before replay windows were enabled keep their `AttestationInfo` and
+/// remain protected by it.
+///
+/// The change is appended to the integrator's audit log.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, containing:
+///   * `integrator_program_id` - The program ID of the integrator
+///   * `chain_id` - The source chain ID
+///
+/// # Events
+///
+/// Emits a `ReplayWindowsEnabled` event
+#[access_control(EnableReplayWindows::validate(&ctx.accounts))]
+pub fn enable_replay_windows(
+    ctx: Context<EnableReplayWindows>,
+    args: EnableReplayWindowsArgs,
+) -> Result<()> {
+    ctx.accounts.integrator_chain_config.replay_windows = true;
+
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::ReplayWindowsEnabled {
+            chain_id: args.chain_id,
+        },
+        Clock::get()?.slot,
+    );
+
+    emit_cpi!(ReplayWindowsEnabled {
+        integrator: args.integrator_program_id,
+        chain: args.chain_id,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,13 @@
 use anchor_lang::prelude::*;
 
-use crate::{error::EndpointError, event::MessageExecuted, state::AttestationInfo};
+use crate::{
+    error::EndpointError,
+    event::MessageExecuted,
+    state::{
+        AttestationInfo, InboundCursor, IntegratorChainConfig, MessageExpiry, ReplayFloor,
+        ReplayWindow, SequenceRecord,
+    },
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct ExecMessageArgs {
@@ -11,6 +18,8 @@ pub struct ExecMessageArgs {
     pub dst_chain: u16,
     pub integrator_program_id: Pubkey,
     pub payload_hash: [u8; 32],
+    /// The deadline committed into the message by the sender, if any
+    pub expiry: Option<MessageExpiry>,
 }
 
 #[event_cpi]
@@ -43,23 +52,123 @@ pub struct ExecMessage<'info> {
                 args.sequence,
                 args.dst_chain,
                 args.integrator_program_id.to_bytes(),
-                args.payload_hash
+                args.payload_hash,
+                args.expiry
             )
         ],
         bump
     )]
     pub attestation_info: Account<'info, AttestationInfo>,
 
+    /// The sequence record account
+    /// This is initialized if it doesn't exist, so that a later `recv_message` of the same
+    /// message fails with `AlreadyExecuted`
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + SequenceRecord::INIT_SPACE,
+        seeds = [
+            SequenceRecord::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+            args.sequence.to_be_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub sequence_record: Account<'info, SequenceRecord>,
+
+    /// The integrator chain config account of the source chain
+    /// This is used to check whether ordered delivery or replay windows are enabled for the
+    /// source chain. It must always be passed, and is only read if it exists: for a source chain
+    /// without a config, neither is applied
+    /// CHECK: The address is checked by the seeds, and the account by
+    /// `IntegratorChainConfig::inbound_options`
+    #[account(
+        seeds = [
+            IntegratorChainConfig::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub integrator_chain_config: UncheckedAccount<'info>,
+
+    /// The inbound cursor of the source address
+    /// This is only required when ordered delivery is enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + InboundCursor::INIT_SPACE,
+        seeds = [
+            InboundCursor::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+        ],
+        bump,
+    )]
+    pub inbound_cursor: Option<Account<'info, InboundCursor>>,
+
+    /// The replay floor of the source address
+    /// This is only required when replay windows are enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayFloor::INIT_SPACE,
+        seeds = [
+            ReplayFloor::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_floor: Option<Account<'info, ReplayFloor>>,
+
+    /// The replay window covering the sequence
+    /// This is only required when replay windows are enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayWindow::INIT_SPACE,
+        seeds = [
+            ReplayWindow::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+            ReplayWindow::window_index(args.sequence).to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_window: Option<Box<Account<'info, ReplayWindow>>>,
+
+    /// The recipient of the attestation info's rent, if it is not the payer
+    /// This is only required when replay windows are enabled for the source chain, which closes
+    /// the attestation info and sequence record, refunding both to it
+    /// CHECK: Checked against the attestation info's `refund_recipient`
+    #[account(mut)]
+    pub refund_recipient: Option<UncheckedAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
 /// Executes a message in the endpoint program
 ///
 /// This function is responsible for marking a message as executed. It performs the following steps:
-/// 1. Checks if the message has already been executed.
-/// 2. Initializes the attestation info if it's newly created.
-/// 3. Marks the message as executed.
-/// 4. Emits a MessageExecuted event.
+/// 1. Checks if the message, or another message with its sequence, has already been executed.
+/// 2. If ordered delivery is enabled for the source chain, checks that the sequence is
+///    the next one from the source address and advances the `InboundCursor` past it.
+/// 3. If replay windows are enabled for the source chain, checks that the sequence's window is
+///    still open and marks the sequence as executed in it.
+/// 4. Initializes the attestation info and sequence record if they're newly created.
+/// 5. Marks the message and its sequence as executed.
+/// 6. Emits a MessageExecuted event.
+/// 7. If replay windows are enabled, closes the `AttestationInfo`, refunding its rent to the
+///    account that paid for it, and the `SequenceRecord`, refunding its rent to the same account.
 ///
 /// # Arguments
 ///
@@ -72,6 +181,7 @@ pub struct ExecMessage<'info> {
 ///   - `dst_chain`: The destination chain ID
 ///   - `dst_addr`: The destination address ([u8; 32])
 ///   - `payload_hash`: The hash of the message payload
+///   - `expiry`: The deadline committed into the message by the sender, if any
 ///
 /// # Returns
 ///
@@ -80,7 +190,25 @@ pub struct ExecMessage<'info> {
 /// # Errors
 ///
 /// This function will return an error if:
-/// * The message has already been executed (EndpointError::AlreadyExecuted)
+/// * The message, or another message with its sequence, has already been executed
+///   (EndpointError::AlreadyExecuted)
+/// * Ordered delivery is enabled and no `inbound_cursor` is passed
+///   (EndpointError::InboundCursorMissing)
+/// * Ordered delivery is enabled and the sequence is not the next one from the source address
+///   (EndpointError::OutOfOrderSequence)
+/// * Replay windows are enabled and `replay_floor` or `replay_window` is not passed
+///   (EndpointError::ReplayWindowMissing)
+/// * Replay windows are enabled and the sequence's window has been closed
+///   (EndpointError::ReplayWindowClosed)
+/// * Replay windows are enabled and the sequence has already been executed
+///   (EndpointError::AlreadyExecuted)
+/// * Replay windows are enabled and the attestation info's refund recipient is neither the payer
+///   nor the `refund_recipient` passed (EndpointError::InvalidRefundRecipient)
+///
+/// # Notes
+///
+/// Unlike `recv_message`, this does not check the message's expiry, so an integrator can
+/// still consume an expired message to prevent it from being received later.
 ///
 /// # Events
 ///
@@ -88,24 +216,100 @@ pub struct ExecMessage<'info> {
 pub fn exec_message(ctx: Context<ExecMessage>, args: ExecMessageArgs) -> Result<()> {
     let attestation_info = &mut ctx.accounts.attestation_info;
 
-    // Check if the message has already been executed
+    // Check if the message, or another message with its sequence, has already been executed.
+    // The sequence record catches messages whose attestation info has been closed
     require!(!attestation_info.executed, EndpointError::AlreadyExecuted);
+    require!(
+        !ctx.accounts.sequence_record.executed,
+        EndpointError::AlreadyExecuted
+    );
+
+    let (ordered_delivery, replay_windows) =
+        IntegratorChainConfig::inbound_options(&ctx.accounts.integrator_chain_config)?;
+
+    // Only deliver the next sequence from the source address if ordered delivery is enabled
+    if ordered_delivery {
+        ctx.accounts
+            .inbound_cursor
+            .as_mut()
+            .ok_or(EndpointError::InboundCursorMissing)?
+            .init_and_deliver(
+                ctx.bumps.inbound_cursor.unwrap(),
+                args.integrator_program_id,
+                args.src_chain,
+                args.src_addr,
+                args.sequence,
+            )?;
+    }
+
+    // Record the sequence in its replay window if replay windows are enabled
+    if replay_windows {
+        ctx.accounts
+            .replay_floor
+            .as_mut()
+            .ok_or(EndpointError::ReplayWindowMissing)?
+            .init_and_check_open(
+                ctx.bumps.replay_floor.unwrap(),
+                args.integrator_program_id,
+                args.src_chain,
+                args.src_addr,
+                args.sequence,
+            )?;
+        ctx.accounts
+            .replay_window
+            .as_mut()
+            .ok_or(EndpointError::ReplayWindowMissing)?
+            .init_and_mark_executed(
+                ctx.bumps.replay_window.unwrap(),
+                args.integrator_program_id,
+                args.src_chain,
+                args.src_addr,
+                args.sequence,
+            )?;
+    }
 
     // If the attestation_info is newly created, initialize it
-    if attestation_info.src_chain == 0 {
+    if attestation_info.is_uninitialized() {
+        require!(
+            args.src_chain != 0 && args.dst_chain != 0,
+            EndpointError::InvalidChainId
+        );
+
         attestation_info.set_inner(AttestationInfo::new(
             ctx.bumps.attestation_info,
-            args.src_chain,
-            args.src_addr,
-            args.sequence,
-            args.dst_chain,
-            args.integrator_program_id.to_bytes(),
-            args.payload_hash,
-        )?);
+            AttestationInfo::compute_message_hash(
+                args.src_chain,
+                args.src_addr,
+                args.sequence,
+                args.dst_chain,
+                args.integrator_program_id.to_bytes(),
+                args.payload_hash,
+                args.expiry,
+            ),
+            args.expiry,
+            ctx.accounts.payer.key(),
+        ));
     }
 
-    // Mark the message as executed
+    // Record the executed payload for the sequence, so that adapters attesting to a different
+    // payload afterwards are flagged as equivocating
+    let sequence_record = &mut ctx.accounts.sequence_record;
+    if sequence_record.src_chain == 0 {
+        sequence_record.set_inner(SequenceRecord {
+            bump: ctx.bumps.sequence_record,
+            integrator_program_id: args.integrator_program_id,
+            src_chain: args.src_chain,
+            src_addr: args.src_addr,
+            sequence: args.sequence,
+            first_payload_hash: args.payload_hash,
+            equivocated: false,
+            executed: false,
+        });
+    }
+
+    // Mark the message and its sequence as executed
     attestation_info.executed = true;
+    sequence_record.executed = true;
 
     emit_cpi!(MessageExecuted {
         message_hash: attestation_info.message_hash,
@@ -117,5 +321,16 @@ pub fn exec_message(ctx: Context<ExecMessage>, args: ExecMessageArgs) -> Result<
         payload_hash: args.payload_hash,
     });
 
+    // With replay windows, the attestation info and sequence record are only needed while the
+    // message is in flight, as the window now guards against replays
+    if replay_windows {
+        AttestationInfo::close_delivered(
+            &ctx.accounts.attestation_info,
+            &ctx.accounts.sequence_record,
+            &ctx.accounts.payer,
+            ctx.accounts.refund_recipient.as_ref(),
+        )?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::EndpointError,
+    event::MessageExecuted,
+    state::{
+        AttestationInfo, InboundCursor, IntegratorChainConfig, ReplayFloor, ReplayWindow,
+        SequenceRecord,
+    },
+    CHAIN_ID,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExecMessageByHashArgs {
+    pub integrator_program_pda_bump: u8,
+    /// The hash of a message that has an `AttestationInfo`
+    pub message_hash: [u8; 32],
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: ExecMessageByHashArgs)]
+pub struct ExecMessageByHash<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The attestation info account
+    /// This throws when the message has not been attested to yet
+    #[account(
+        mut,
+        seeds = [AttestationInfo::SEED_PREFIX, &args.message_hash],
+        bump = attestation_info.bump,
+    )]
+    pub attestation_info: Account<'info, AttestationInfo>,
+
+    /// The sequence record of the message
+    /// The message's fields, and the integrator and source chain of the other accounts, are
+    /// taken from it
+    #[account(
+        mut,
+        constraint = sequence_record.first_message_hash(attestation_info.expiry)
+            == args.message_hash @ EndpointError::InvalidMessageHash,
+    )]
+    pub sequence_record: Account<'info, SequenceRecord>,
+
+    /// The PDA of the integrator program.
+    /// This makes sure that the one calling this is the integrator program
+    #[account(
+        seeds = [b"endpoint_integrator"],
+        bump = args.integrator_program_pda_bump,
+        seeds::program = sequence_record.integrator_program_id,
+    )]
+    pub integrator_program_pda: Signer<'info>,
+
+    /// The integrator chain config account of the source chain
+    /// This is used to check whether ordered delivery or replay windows are enabled for the
+    /// source chain. It must always be passed, and is only read if it exists: for a source chain
+    /// without a config, neither is applied
+    /// CHECK: The address is checked by the seeds, and the account by
+    /// `IntegratorChainConfig::inbound_options`
+    #[account(
+        seeds = [
+            IntegratorChainConfig::SEED_PREFIX,
+            sequence_record.integrator_program_id.as_ref(),
+            sequence_record.src_chain.to_be_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub integrator_chain_config: UncheckedAccount<'info>,
+
+    /// The inbound cursor of the source address
+    /// This is only required when ordered delivery is enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + InboundCursor::INIT_SPACE,
+        seeds = [
+            InboundCursor::SEED_PREFIX,
+            sequence_record.integrator_program_id.as_ref(),
+            sequence_record.src_chain.to_be_bytes().as_ref(),
+            sequence_record.src_addr.as_ref(),
+        ],
+        bump,
+    )]
+    pub inbound_cursor: Option<Account<'info, InboundCursor>>,
+
+    /// The replay floor of the source address
+    /// This is only required when replay windows are enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayFloor::INIT_SPACE,
+        seeds = [
+            ReplayFloor::SEED_PREFIX,
+            sequence_record.integrator_program_id.as_ref(),
+            sequence_record.src_chain.to_be_bytes().as_ref(),
+            sequence_record.src_addr.as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_floor: Option<Account<'info, ReplayFloor>>,
+
+    /// The replay window covering the sequence
+    /// This is only required when replay windows are enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayWindow::INIT_SPACE,
+        seeds = [
+            ReplayWindow::SEED_PREFIX,
+            sequence_record.integrator_program_id.as_ref(),
+            sequence_record.src_chain.to_be_bytes().as_ref(),
+            sequence_record.src_addr.as_ref(),
+            ReplayWindow::window_index(sequence_record.sequence).to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_window: Option<Box<Account<'info, ReplayWindow>>>,
+
+    /// The recipient of the attestation info's rent, if it is not the payer
+    /// This is only required when replay windows are enabled for the source chain, which closes
+    /// the attestation info and sequence record, refunding both to it
+    /// CHECK: Checked against the attestation info's `refund_recipient`
+    #[account(mut)]
+    pub refund_recipient: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Executes a message that has been attested to in the endpoint program, by its hash
+///
+/// This is a cheaper form of `exec_message` that only takes the message hash. The message's
+/// fields are read from the `SequenceRecord` of its sequence, so only a message that already
+/// has an `AttestationInfo`, and carries the first payload attested for its sequence, can be
+/// executed by hash. Other messages must go through `exec_message`.
+///
+/// This function performs the same steps as `exec_message`, except that the attestation info
+/// and sequence record must already exist.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction:
+///   - `integrator_program_pda_bump`: The bump seed for the integrator program's PDA
+///   - `message_hash`: The hash of the message
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the message is successfully executed, or an error if it fails
+///
+/// # Errors
+///
+/// This function will return the errors of `exec_message`, and also if:
+/// * The message has no `AttestationInfo` (ErrorCode::AccountNotInitialized)
+/// * The `sequence_record` does not hold the message's payload (EndpointError::InvalidMessageHash)
+///
+/// # Events
+///
+/// Emits a `MessageExecuted` event
+pub fn exec_message_by_hash(
+    ctx: Context<ExecMessageByHash>,
+    args: ExecMessageByHashArgs,
+) -> Result<()> {
+    let attestation_info = &mut ctx.accounts.attestation_info;
+    let sequence_record = &mut ctx.accounts.sequence_record;
+
+    // Check if the message, or another message with its sequence, has already been executed
+    require!(!attestation_info.executed, EndpointError::AlreadyExecuted);
+    require!(!sequence_record.executed, EndpointError::AlreadyExecuted);
+
+    let (ordered_delivery, replay_windows) =
+        IntegratorChainConfig::inbound_options(&ctx.accounts.integrator_chain_config)?;
+
+    // Only deliver the next sequence from the source address if ordered delivery is enabled
+    if ordered_delivery {
+        ctx.accounts
+            .inbound_cursor
+            .as_mut()
+            .ok_or(EndpointError::InboundCursorMissing)?
+            .init_and_deliver(
+                ctx.bumps.inbound_cursor.unwrap(),
+                sequence_record.integrator_program_id,
+                sequence_record.src_chain,
+                sequence_record.src_addr,
+                sequence_record.sequence,
+            )?;
+    }
+
+    // Record the sequence in its replay window if replay windows are enabled
+    if replay_windows {
+        ctx.accounts
+            .replay_floor
+            .as_mut()
+            .ok_or(EndpointError::ReplayWindowMissing)?
+            .init_and_check_open(
+                ctx.bumps.replay_floor.unwrap(),
+                sequence_record.integrator_program_id,
+                sequence_record.src_chain,
+                sequence_record.src_addr,
+                sequence_record.sequence,
+            )?;
+        ctx.accounts
+            .replay_window
+            .as_mut()
+            .ok_or(EndpointError::ReplayWindowMissing)?
+            .init_and_mark_executed(
+                ctx.bumps.replay_window.unwrap(),
+                sequence_record.integrator_program_id,
+                sequence_record.src_chain,
+                sequence_record.src_addr,
+                sequence_record.sequence,
+            )?;
+    }
+
+    // Mark the message and its sequence as executed
+    attestation_info.executed = true;
+    sequence_record.executed = true;
+
+    emit_cpi!(MessageExecuted {
+        message_hash: args.message_hash,
+        src_chain: sequence_record.src_chain,
+        src_addr: sequence_record.src_addr,
+        sequence: sequence_record.sequence,
+        dst_chain: CHAIN_ID,
+        dst_addr: sequence_record.integrator_program_id.to_bytes(),
+        payload_hash: sequence_record.first_payload_hash,
+    });
+
+    // With replay windows, the attestation info and sequence record are only needed while the
+    // message is in flight, as the window now guards against replays
+    if replay_windows {
+        AttestationInfo::close_delivered(
+            &ctx.accounts.attestation_info,
+            &ctx.accounts.sequence_record,
+            &ctx.accounts.payer,
+            ctx.accounts.refund_recipient.as_ref(),
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,239 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    keccak,
+    program::invoke_signed,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+use anchor_lang::Discriminator;
+
+use crate::error::EndpointError;
+use crate::event::SignedAdminExecuted;
+use crate::instruction;
+use crate::state::IntegratorConfig;
+use crate::utils::ed25519::verified_message;
+
+/// The admin instructions that can be authorised by signature
+///
+/// The signature commits to their instruction data and to all the accounts they take other than
+/// the payer, which is their first account.
+const SIGNABLE_INSTRUCTIONS: [[u8; 8]; 15] = [
+    instruction::AddAdapter::DISCRIMINATOR,
+    instruction::EnableRecvAdapter::DISCRIMINATOR,
+    instruction::EnableSendAdapter::DISCRIMINATOR,
+    instruction::DisableRecvAdapter::DISCRIMINATOR,
+    instruction::DisableSendAdapter::DISCRIMINATOR,
+    instruction::UpdateAdmin::DISCRIMINATOR,
+    instruction::TransferAdmin::DISCRIMINATOR,
+    instruction::ClaimAdmin::DISCRIMINATOR,
+    instruction::DiscardAdmin::DISCRIMINATOR,
+    instruction::SetOrderedDelivery::DISCRIMINATOR,
+    instruction::EnableReplayWindows::DISCRIMINATOR,
+    instruction::RetireAdapter::DISCRIMINATOR,
+    instruction::SkipSequence::DISCRIMINATOR,
+    instruction::Deregister::DISCRIMINATOR,
+    instruction::InitAdapterQueue::DISCRIMINATOR,
+];
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExecuteSignedAdminArgs {
+    /// The Pubkey of the integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The admin nonce of the integrator the authorisation was signed for
+    pub nonce: u64,
+
+    /// The unix timestamp after which the authorisation can no longer be used
+    pub expiry: i64,
+
+    /// The data of the admin instruction to execute
+    pub instruction_data: Vec<u8>,
+}
+
+impl ExecuteSignedAdminArgs {
+    /// Domain separator of admin authorisation messages
+    pub const DOMAIN: &'static [u8] = b"endpoint_admin_authorization_v1";
+
+    /// The message the admin signs to authorise the instruction:
+    /// `DOMAIN || endpoint_program_id || integrator_program_id || nonce || expiry || accounts_hash || instruction_data`
+    ///
+    /// All integers are big-endian.
+    pub fn message(&self, accounts_hash: &[u8; 32]) -> Vec<u8> {
+        let mut bytes = Self::DOMAIN.to_vec();
+        bytes.extend_from_slice(crate::ID.as_ref());
+        bytes.extend_from_slice(self.integrator_program_id.as_ref());
+        bytes.extend_from_slice(&self.nonce.to_be_bytes());
+        bytes.extend_from_slice(&self.expiry.to_be_bytes());
+        bytes.extend_from_slice(accounts_hash);
+        bytes.extend_from_slice(&self.instruction_data);
+        bytes
+    }
+
+    /// The hash of the admin instruction's accounts in the signed message:
+    /// `keccak256(key_0 || key_1 || ...)` over the keys of the accounts that do not sign the
+    /// transaction, in order
+    ///
+    /// This leaves out the payer, which the relayer chooses, and includes the admin authority,
+    /// which the endpoint signs for.
+    pub fn accounts_hash<'a>(keys: impl IntoIterator<Item = &'a Pubkey>) -> [u8; 32] {
+        let keys: Vec<&[u8]> = keys.into_iter().map(|key| key.as_ref()).collect();
+        keccak::hashv(&keys).to_bytes()
+    }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: ExecuteSignedAdminArgs)]
+pub struct ExecuteSignedAdmin<'info> {
+    /// The integrator config account
+    /// Its admin nonce is advanced so that the authorisation cannot be replayed
+    #[account(
+        mut,
+        seeds = [IntegratorConfig::SEED_PREFIX, args.integrator_program_id.as_ref()],
+        bump = integrator_config.bump,
+    )]
+    pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The instructions sysvar, used to read the Ed25519Program instruction
+    /// CHECK: The address constraint checks that this is the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Executes an admin instruction authorised by an ed25519 signature made off-chain
+///
+/// This lets an admin whose key cannot send transactions have any relayer submit their
+/// configuration changes. The instruction immediately before this one must be an
+/// Ed25519Program instruction verifying the signer's signature of `ExecuteSignedAdminArgs::message`.
+///
+/// The admin instruction is invoked on the endpoint with the signer's admin authority PDA,
+/// `[ADMIN_AUTHORITY_SEED_PREFIX, integrator_program_id, signer]`, signing in place of the
+/// admin. Admin checks accept the admin authority of the admin (or of the pending admin in
+/// `claim_admin`), so the admin instruction fails unless the signer is authorised.
+///
+/// `remaining_accounts` holds the accounts of the admin instruction, in order, with the admin
+/// authority not signing. The payer, their first account, must sign the transaction, and no
+/// other account may, so that every account but the payer is committed to by the accounts hash
+/// of the signed message.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, containing:
+///   * `integrator_program_id` - The Pubkey of the integrator program
+///   * `nonce` - The current admin nonce of the integrator
+///   * `expiry` - The unix timestamp after which the authorisation expires
+///   * `instruction_data` - The data of the admin instruction
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The previous instruction does not verify a signature of the message, or an account other
+///   than the payer signs (EndpointError::InvalidAdminAuthorization)
+/// * The authorisation has expired (EndpointError::AdminAuthorizationExpired)
+/// * The nonce is not the integrator's admin nonce (EndpointError::InvalidAdminNonce)
+/// * The admin instruction cannot be authorised by signature
+///   (EndpointError::AdminInstructionNotSignable)
+/// * The admin instruction fails
+///
+/// # Events
+///
+/// Emits a `SignedAdminExecuted` event
+pub fn execute_signed_admin<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteSignedAdmin<'info>>,
+    args: ExecuteSignedAdminArgs,
+) -> Result<()> {
+    // Read the signer and message from the Ed25519Program instruction before this one
+    let instructions = ctx.accounts.instructions.to_account_info();
+    let current_index = load_current_index_checked(&instructions)?;
+    require!(current_index > 0, EndpointError::InvalidAdminAuthorization);
+    let ed25519_instruction =
+        load_instruction_at_checked(current_index as usize - 1, &instructions)?;
+    let (signer, message) = verified_message(&ed25519_instruction)?;
+
+    // Only the payer may sign, so that the relayer cannot leave its own accounts out of the hash
+    require!(
+        ctx.remaining_accounts
+            .iter()
+            .skip(1)
+            .all(|account| !account.is_signer),
+        EndpointError::InvalidAdminAuthorization
+    );
+    let accounts_hash = ExecuteSignedAdminArgs::accounts_hash(
+        ctx.remaining_accounts
+            .iter()
+            .filter(|account| !account.is_signer)
+            .map(|account| account.key),
+    );
+    require!(
+        message == args.message(&accounts_hash).as_slice(),
+        EndpointError::InvalidAdminAuthorization
+    );
+
+    require!(
+        Clock::get()?.unix_timestamp <= args.expiry,
+        EndpointError::AdminAuthorizationExpired
+    );
+    require_eq!(
+        args.nonce,
+        ctx.accounts.integrator_config.admin_nonce,
+        EndpointError::InvalidAdminNonce
+    );
+    require!(
+        args.instruction_data.len() >= 8
+            && SIGNABLE_INSTRUCTIONS.contains(&args.instruction_data[..8].try_into().unwrap()),
+        EndpointError::AdminInstructionNotSignable
+    );
+
+    // Advance the nonce, and write it out before the admin instruction reads the config
+    let integrator_config = &mut ctx.accounts.integrator_config;
+    integrator_config.admin_nonce = integrator_config.admin_nonce.checked_add(1).unwrap();
+    integrator_config.exit(&crate::ID)?;
+
+    let (admin_authority, admin_authority_bump) =
+        IntegratorConfig::admin_authority(&args.integrator_program_id, &signer);
+    let instruction = Instruction {
+        program_id: crate::ID,
+        accounts: ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == admin_authority,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: args.instruction_data,
+    };
+    let mut account_infos = ctx.remaining_accounts.to_vec();
+    account_infos.push(ctx.accounts.program.to_account_info());
+
+    invoke_signed(
+        &instruction,
+        &account_infos,
+        &[&[
+            IntegratorConfig::ADMIN_AUTHORITY_SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            signer.as_ref(),
+            &[admin_authority_bump],
+        ]],
+    )?;
+
+    // Pick up the changes the admin instruction made to the config, unless `deregister` closed it
+    if !ctx
+        .accounts
+        .integrator_config
+        .to_account_info()
+        .data_is_empty()
+    {
+        ctx.accounts.integrator_config.reload()?;
+    }
+
+    emit_cpi!(SignedAdminExecuted {
+        integrator: args.integrator_program_id,
+        signer,
+        nonce: args.nonce,
+    });
+
+    Ok(())
+}
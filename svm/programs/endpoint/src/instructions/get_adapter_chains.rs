@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AdapterChainEntry, AdapterIndex};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetAdapterChainsArgs {
+    /// The Pubkey of the adapter
+    pub adapter_program_id: Pubkey,
+
+    /// The Pubkey of the integrator program
+    pub integrator_program_id: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(args: GetAdapterChainsArgs)]
+pub struct GetAdapterChains<'info> {
+    /// The reverse index entry from the adapter to the integrator
+    #[account(
+        seeds = [
+            AdapterIndex::SEED_PREFIX,
+            args.adapter_program_id.as_ref(),
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = adapter_index.bump,
+    )]
+    pub adapter_index: Account<'info, AdapterIndex>,
+}
+
+/// Returns the chains on which an adapter is enabled for an integrator
+///
+/// This is a read-only instruction meant to be called by adapter programs over CPI.
+/// The result is passed back through the program's return data.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `_args` - The arguments for the instruction, containing:
+///   * `adapter_program_id` - The Pubkey of the adapter
+///   * `integrator_program_id` - The Pubkey of the integrator program
+///
+/// # Returns
+///
+/// The `AdapterChainEntry`s for the chains on which the adapter is currently enabled, each with
+/// the chain ID and whether the adapter is send- and/or recv-enabled on it
+pub fn get_adapter_chains(
+    ctx: Context<GetAdapterChains>,
+    _args: GetAdapterChainsArgs,
+) -> Result<Vec<AdapterChainEntry>> {
+    Ok(ctx.accounts.adapter_index.chains.clone())
+}
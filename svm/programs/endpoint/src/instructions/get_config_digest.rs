@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::state::IntegratorConfig;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetConfigDigestArgs {
+    /// The Pubkey of the integrator program
+    pub integrator_program_id: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(args: GetConfigDigestArgs)]
+pub struct GetConfigDigest<'info> {
+    /// The integrator config account holding the digest
+    #[account(
+        seeds = [IntegratorConfig::SEED_PREFIX, args.integrator_program_id.as_ref()],
+        bump = integrator_config.bump,
+    )]
+    pub integrator_config: Account<'info, IntegratorConfig>,
+}
+
+/// Returns the configuration digest of an integrator
+///
+/// This is a read-only instruction, meant to be simulated or called over CPI.
+/// The result is passed back through the program's return data.
+///
+/// The digest starts from `keccak256("endpoint_config_digest_v1" || integrator_program_id)`
+/// and every change to the adapter list, the chain bitmaps or the admin is folded into it as
+/// `keccak256(digest || update)`. See `ConfigUpdate::encode` for the encoding of the updates.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `_args` - The arguments for the instruction, containing:
+///   * `integrator_program_id` - The Pubkey of the integrator program
+///
+/// # Returns
+///
+/// The current configuration digest of the integrator
+pub fn get_config_digest(
+    ctx: Context<GetConfigDigest>,
+    _args: GetConfigDigestArgs,
+) -> Result<[u8; 32]> {
+    Ok(ctx.accounts.integrator_config.config_digest)
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AdapterMetadata, AttestationInfo, AttestationMetadata, MessageExpiry};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetMessageStatusArgs {
+    /// The hash of the message, as computed by `AttestationInfo::compute_message_hash`
+    pub message_hash: [u8; 32],
+}
+
+/// The attestation status of a message, returned by `get_message_status`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MessageStatus {
+    /// Whether the message has been received or executed
+    pub executed: bool,
+
+    /// Number of adapters that have attested to the message
+    pub num_attested: u8,
+
+    /// The adapters that have attested to the message
+    pub attested_bitmap: u128,
+
+    /// The receive adapters enabled for the source chain at the first attestation
+    pub snapshot_enabled_bitmap: u128,
+
+    /// The config epoch of the source chain at the first attestation
+    pub snapshot_config_epoch: u64,
+
+    /// The deadline after which the message can no longer be received, if any
+    pub expiry: Option<MessageExpiry>,
+
+    /// The metadata attached by each adapter that attached any
+    pub metadata: Vec<AdapterMetadata>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: GetMessageStatusArgs)]
+pub struct GetMessageStatus<'info> {
+    /// The attestation info account
+    #[account(
+        seeds = [AttestationInfo::SEED_PREFIX, &args.message_hash],
+        bump = attestation_info.bump,
+    )]
+    pub attestation_info: Account<'info, AttestationInfo>,
+
+    /// The attestation metadata account
+    /// This only exists if an adapter attached metadata to its attestation
+    #[account(
+        seeds = [AttestationMetadata::SEED_PREFIX, &args.message_hash],
+        bump = attestation_metadata.bump,
+    )]
+    pub attestation_metadata: Option<Account<'info, AttestationMetadata>>,
+}
+
+/// Returns the attestation status of a message
+///
+/// This is a read-only instruction meant to be called by integrators and adapters over CPI.
+/// The result is passed back through the program's return data.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `_args` - The arguments for the instruction, containing:
+///   * `message_hash` - The hash of the message
+///
+/// # Returns
+///
+/// The `MessageStatus` of the message
+pub fn get_message_status(
+    ctx: Context<GetMessageStatus>,
+    _args: GetMessageStatusArgs,
+) -> Result<MessageStatus> {
+    let attestation_info = &ctx.accounts.attestation_info;
+
+    Ok(MessageStatus {
+        executed: attestation_info.executed,
+        num_attested: attestation_info.num_attested,
+        attested_bitmap: attestation_info.attested_adapters.as_value(),
+        snapshot_enabled_bitmap: attestation_info.enabled_adapters.as_value(),
+        snapshot_config_epoch: attestation_info.config_epoch,
+        expiry: attestation_info.expiry,
+        metadata: ctx
+            .accounts
+            .attestation_metadata
+            .as_ref()
+            .map(|attestation_metadata| attestation_metadata.entries.clone())
+            .unwrap_or_default(),
+    })
+}
@@ -0,0 +1,165 @@
+use crate::error::EndpointError;
+use crate::event::AdapterQueueInitialized;
+use crate::state::{
+    AdapterInfo, AdapterQueue, AuditLogPage, ConfigAction, IntegratorConfig, SequenceTracker,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitAdapterQueueArgs {
+    /// The Pubkey of the integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The Pubkey of the adapter program
+    pub adapter_program_id: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: InitAdapterQueueArgs)]
+pub struct InitAdapterQueue<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The admin account that has the authority to configure the integrator
+    pub admin: Signer<'info>,
+
+    /// The integrator config account
+    /// The account constraints here make sure that the one signing this transaction is the admin
+    /// of the config
+    #[account(
+        mut,
+        seeds = [IntegratorConfig::SEED_PREFIX, args.integrator_program_id.as_ref()],
+        bump = integrator_config.bump,
+    )]
+    pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The adapter info account, which holds the index of the adapter
+    /// The adapter is marked as having a queue here
+    #[account(
+        mut,
+        seeds = [
+            AdapterInfo::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_info.bump,
+        constraint = !adapter_info.retired @ EndpointError::AdapterRetired,
+    )]
+    pub adapter_info: Account<'info, AdapterInfo>,
+
+    /// The queue account to create
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AdapterQueue::INIT_SPACE,
+        seeds = [
+            AdapterQueue::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump
+    )]
+    pub adapter_queue: Box<Account<'info, AdapterQueue>>,
+
+    /// The sequence tracker of the integrator
+    /// The adapter is marked here as having a queue to push sent messages to
+    #[account(
+        mut,
+        seeds = [SequenceTracker::SEED_PREFIX, args.integrator_program_id.as_ref()],
+        bump = sequence_tracker.bump,
+    )]
+    pub sequence_tracker: Account<'info, SequenceTracker>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitAdapterQueue<'info> {
+    pub fn validate(&self) -> Result<()> {
+        self.integrator_config.check_admin(&self.admin)
+    }
+}
+
+/// Creates the queue of outbox messages an adapter has yet to pick up for an integrator
+///
+/// Once it exists, the adapter is marked in its `AdapterInfo` and in the integrator's
+/// `SequenceTracker`, and the queue
+/// must be passed to every instruction that creates or closes an outbox message the adapter is
+/// outstanding for, so that it holds exactly the messages the adapter has yet to pick up.
+/// Messages sent before the queue was created are not in it. Its creation is appended to the
+/// integrator's audit log.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, containing:
+///   * `integrator_program_id` - The program ID of the integrator
+///   * `adapter_program_id` - The program ID of the adapter
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The adapter has been retired (EndpointError::AdapterRetired)
+///
+/// # Events
+///
+/// Emits an `AdapterQueueInitialized` event
+#[access_control(InitAdapterQueue::validate(&ctx.accounts))]
+pub fn init_adapter_queue(
+    ctx: Context<InitAdapterQueue>,
+    args: InitAdapterQueueArgs,
+) -> Result<()> {
+    ctx.accounts.adapter_queue.set_inner(AdapterQueue {
+        bump: ctx.bumps.adapter_queue,
+        integrator_program_id: args.integrator_program_id,
+        adapter_program_id: args.adapter_program_id,
+        index: ctx.accounts.adapter_info.index,
+        head: 0,
+        len: 0,
+        overwritten: 0,
+        entries: [Pubkey::default(); AdapterQueue::CAPACITY],
+    });
+    ctx.accounts.adapter_info.has_queue = true;
+    ctx.accounts
+        .sequence_tracker
+        .queued_adapters
+        .set(ctx.accounts.adapter_info.index, true)?;
+
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::AdapterQueueInitialized {
+            adapter: args.adapter_program_id,
+        },
+        Clock::get()?.slot,
+    );
+
+    emit_cpi!(AdapterQueueInitialized {
+        integrator: args.integrator_program_id,
+        adapter: args.adapter_program_id,
+        queue: ctx.accounts.adapter_queue.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use crate::state::{AdapterInfo, AdapterStats};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitAdapterStatsArgs {
+    /// The Pubkey of the integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The Pubkey of the adapter program
+    pub adapter_program_id: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(args: InitAdapterStatsArgs)]
+pub struct InitAdapterStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The adapter info account, which must exist for the stats to be created
+    #[account(
+        seeds = [
+            AdapterInfo::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_info.bump,
+    )]
+    pub adapter_info: Account<'info, AdapterInfo>,
+
+    /// The account tracking the adapter's activity for this integrator
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AdapterStats::INIT_SPACE,
+        seeds = [
+            AdapterStats::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump
+    )]
+    pub adapter_stats: Account<'info, AdapterStats>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the AdapterStats of an adapter that was added before AdapterStats existed
+///
+/// `add_adapter` creates the stats of new adapters, but `pick_up_message`, `attest_message`
+/// and the other instructions that update them cannot be used by older adapters until this
+/// has been called. Anyone can call it, since the stats start at zero.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, containing:
+///   * `integrator_program_id` - The program ID of the integrator
+///   * `adapter_program_id` - The program ID of the adapter
+pub fn init_adapter_stats(
+    ctx: Context<InitAdapterStats>,
+    args: InitAdapterStatsArgs,
+) -> Result<()> {
+    ctx.accounts.adapter_stats.set_inner(AdapterStats {
+        bump: ctx.bumps.adapter_stats,
+        integrator_program_id: args.integrator_program_id,
+        adapter_program_id: args.adapter_program_id,
+        messages_picked_up: 0,
+        messages_attested: 0,
+        last_pickup_slot: 0,
+        last_attestation_slot: 0,
+        outbox_messages_closed: 0,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use crate::error::EndpointError;
+use crate::event::AttestationTreeInitialized;
+use crate::state::{tree_error, AttestationTree, AuditLogPage, ConfigAction, IntegratorConfig};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitAttestationTreeArgs {
+    /// The Pubkey of the integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The ID of the source chain
+    pub chain_id: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: InitAttestationTreeArgs)]
+pub struct InitAttestationTree<'info> {
+    /// The account that pays for a new audit log page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The admin account that has the authority to configure the integrator
+    pub admin: Signer<'info>,
+
+    /// The integrator config account
+    /// The account constraints here make sure that the one signing this transaction is the admin
+    /// of the config
+    #[account(
+        mut,
+        seeds = [IntegratorConfig::SEED_PREFIX, args.integrator_program_id.as_ref()],
+        bump = integrator_config.bump,
+    )]
+    pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The attestation tree account
+    /// This must be allocated with `AttestationTree::LEN` zeroed bytes and assigned to the
+    /// endpoint program in the same transaction
+    #[account(zero)]
+    pub attestation_tree: Account<'info, AttestationTree>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitAttestationTree<'info> {
+    pub fn validate(&self, args: &InitAttestationTreeArgs) -> Result<()> {
+        self.integrator_config.check_admin(&self.admin)?;
+
+        // Ensure chain_id is not zero
+        require!(args.chain_id != 0, EndpointError::InvalidChainId);
+
+        Ok(())
+    }
+}
+
+/// Initializes an attestation tree for messages from a chain
+///
+/// The tree is recorded in the integrator's audit log.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, containing:
+///   * `integrator_program_id` - The program ID of the integrator
+///   * `chain_id` - The source chain ID
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * `chain_id` is 0 (EndpointError::InvalidChainId)
+/// * The account is not `AttestationTree::LEN` bytes long (EndpointError::InvalidAttestationTree)
+///
+/// # Events
+///
+/// Emits an `AttestationTreeInitialized` event
+#[access_control(InitAttestationTree::validate(&ctx.accounts, &args))]
+pub fn init_attestation_tree(
+    ctx: Context<InitAttestationTree>,
+    args: InitAttestationTreeArgs,
+) -> Result<()> {
+    let attestation_tree = &mut ctx.accounts.attestation_tree;
+    attestation_tree.integrator_program_id = args.integrator_program_id;
+    attestation_tree.chain_id = args.chain_id;
+
+    AttestationTree::load_tree_mut(&mut attestation_tree.to_account_info().data.borrow_mut())?
+        .initialize()
+        .map_err(tree_error)?;
+
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        ctx.accounts.admin.key(),
+        ConfigAction::AttestationTreeInitialized {
+            chain_id: args.chain_id,
+            tree: attestation_tree.key(),
+        },
+        Clock::get()?.slot,
+    );
+
+    emit_cpi!(AttestationTreeInitialized {
+        integrator: args.integrator_program_id,
+        chain: args.chain_id,
+        tree: attestation_tree.key(),
+    });
+
+    Ok(())
+}
@@ -1,27 +1,89 @@
 pub mod add_adapter;
 pub mod attest_message;
+pub mod attest_message_by_hash;
+pub mod attest_message_compressed;
+pub mod attest_messages;
+pub mod cancel_message;
+pub mod close_deregistered_adapter;
+pub mod close_deregistered_attestation_tree;
+pub mod close_deregistered_audit_log_page;
+pub mod close_deregistered_chain;
+pub mod close_deregistered_inbound_cursor;
+pub mod close_deregistered_replay_window;
+pub mod close_deregistered_sent_message;
+pub mod close_replay_window;
+pub mod close_sent_message_record;
 pub mod common;
+pub mod deregister;
 pub mod disable_adapter;
 pub mod discard_admin;
 pub mod enable_adapter;
+pub mod enable_replay_windows;
 pub mod exec_message;
+pub mod exec_message_by_hash;
+pub mod execute_signed_admin;
+pub mod get_adapter_chains;
+pub mod get_config_digest;
+pub mod get_message_status;
+pub mod init_adapter_queue;
+pub mod init_adapter_stats;
+pub mod init_attestation_tree;
 pub mod pick_up_message;
+pub mod pick_up_messages;
 pub mod recv_message;
+pub mod recv_message_by_hash;
+pub mod recv_message_compressed;
 pub mod register;
+pub mod request_repickup;
+pub mod retire_adapter;
 pub mod send_message;
+pub mod send_message_multi;
+pub mod set_ordered_delivery;
+pub mod skip_sequence;
 pub mod transfer_admin;
 pub mod update_admin;
 
 pub use add_adapter::*;
 pub use attest_message::*;
+pub use attest_message_by_hash::*;
+pub use attest_message_compressed::*;
+pub use attest_messages::*;
+pub use cancel_message::*;
+pub use close_deregistered_adapter::*;
+pub use close_deregistered_attestation_tree::*;
+pub use close_deregistered_audit_log_page::*;
+pub use close_deregistered_chain::*;
+pub use close_deregistered_inbound_cursor::*;
+pub use close_deregistered_replay_window::*;
+pub use close_deregistered_sent_message::*;
+pub use close_replay_window::*;
+pub use close_sent_message_record::*;
 pub use common::*;
+pub use deregister::*;
 pub use disable_adapter::*;
 pub use discard_admin::*;
 pub use enable_adapter::*;
+pub use enable_replay_windows::*;
 pub use exec_message::*;
+pub use exec_message_by_hash::*;
+pub use execute_signed_admin::*;
+pub use get_adapter_chains::*;
+pub use get_config_digest::*;
+pub use get_message_status::*;
+pub use init_adapter_queue::*;
+pub use init_adapter_stats::*;
+pub use init_attestation_tree::*;
 pub use pick_up_message::*;
+pub use pick_up_messages::*;
 pub use recv_message::*;
+pub use recv_message_by_hash::*;
+pub use recv_message_compressed::*;
 pub use register::*;
+pub use request_repickup::*;
+pub use retire_adapter::*;
 pub use send_message::*;
+pub use send_message_multi::*;
+pub use set_ordered_delivery::*;
+pub use skip_sequence::*;
 pub use transfer_admin::*;
 pub use update_admin::*;
@@ -1,14 +1,17 @@
 use crate::{
     error::EndpointError,
     event::MessagePickedUp,
-    state::{AdapterInfo, OutboxMessage},
+    state::{AdapterInfo, AdapterQueue, AdapterStats, OutboxMessage},
 };
+use adapter_interface::ADAPTER_PDA_SEED;
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct PickUpMessageArgs {
     pub adapter_program_id: Pubkey,
     pub adapter_pda_bump: u8,
+    /// The consistency levels the adapter can honour, or `None` if it accepts any level
+    pub supported_consistency_levels: Option<Vec<u8>>,
 }
 
 #[event_cpi]
@@ -25,6 +28,8 @@ pub struct PickUpMessage<'info> {
 
     /// The adapter info account
     /// This account contains index of the adapter picking up the message
+    /// A retired adapter can still pick up the messages it was outstanding for, so that they are
+    /// closed
     #[account(
         seeds = [
             AdapterInfo::SEED_PREFIX,
@@ -35,10 +40,37 @@ pub struct PickUpMessage<'info> {
     )]
     pub adapter_info: Account<'info, AdapterInfo>,
 
+    /// The adapter stats account
+    /// The pick up counter and last pick up slot are updated here, and the closed outbox message
+    /// counter if the message is closed
+    #[account(
+        mut,
+        seeds = [
+            AdapterStats::SEED_PREFIX,
+            outbox_message.src_addr.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_stats.bump,
+    )]
+    pub adapter_stats: Account<'info, AdapterStats>,
+
+    /// The adapter's queue of pending messages
+    /// This is only required when the adapter has one, and the message is removed from it
+    #[account(
+        mut,
+        seeds = [
+            AdapterQueue::SEED_PREFIX,
+            outbox_message.src_addr.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_queue.bump,
+    )]
+    pub adapter_queue: Option<Box<Account<'info, AdapterQueue>>>,
+
     /// The adapter PDA account, used for signing
     /// This ensures that only the authorized adapter can pick up the message
     #[account(
-        seeds = ["adapter_pda".as_bytes()],
+        seeds = [ADAPTER_PDA_SEED],
         bump = args.adapter_pda_bump,
         seeds::program = args.adapter_program_id
     )]
@@ -56,16 +88,20 @@ pub struct PickUpMessage<'info> {
 /// Instruction for picking up a message from the outbox.
 ///
 /// This function performs the following steps:
-/// 1. Checks if the message is available for pick up by this adapter.
+/// 1. Checks if the message is available for pick up by this adapter and if the adapter
+///    supports the message's consistency level.
 /// 2. Marks the message as picked up by updating the `outstanding_adapters` bitmap.
-/// 3. Emits a MessagePickedUp event.
-/// 4. Closes the outbox message account if all adapters have picked up the message.
+/// 3. Records the pick up in the adapter's `AdapterStats`, and removes the message from its
+///    `AdapterQueue` if it has one.
+/// 4. Emits a MessagePickedUp event.
+/// 5. Closes the outbox message account if all adapters have picked up the message.
 ///
 /// # Arguments
 ///
 /// * `args` - The arguments for the instruction, including:
 ///   * `adapter_program_id`: The Pubkey of the adapter program.
 ///   * `adapter_pda_bump`: The bump seed for the adapter's PDA.
+///   * `supported_consistency_levels`: The consistency levels the adapter can honour, if restricted.
 ///
 /// # Returns
 ///
@@ -76,7 +112,9 @@ pub struct PickUpMessage<'info> {
 /// # Errors
 ///
 /// This function will return an error if:
+/// * The adapter has an `AdapterQueue` that is not passed (EndpointError::AdapterQueueMissing).
 /// * The message has already been picked up by this adapter (EndpointError::MessageAlreadyPickedUp).
+/// * The adapter does not support the message's consistency level (EndpointError::UnsupportedConsistencyLevel).
 /// * There's an issue updating the `outstanding_adapters` bitmap.
 /// * There's an issue closing the outbox message account when all adapters have picked up the message.
 ///
@@ -90,6 +128,12 @@ pub fn pick_up_message(ctx: Context<PickUpMessage>, args: PickUpMessageArgs) ->
     // Get the index of the adapter
     let adapter_index = adapter_info.index;
 
+    // Once the adapter has a queue, it must be kept up to date
+    require!(
+        ctx.accounts.adapter_queue.is_some() || !ctx.accounts.adapter_info.has_queue,
+        EndpointError::AdapterQueueMissing
+    );
+
     // Check if the message is available for pick up by this adapter
     require!(
         outbox_message
@@ -99,11 +143,25 @@ pub fn pick_up_message(ctx: Context<PickUpMessage>, args: PickUpMessageArgs) ->
         EndpointError::MessageAlreadyPickedUp
     );
 
+    // Let the adapter refuse messages it cannot deliver at the requested level
+    if let Some(supported_consistency_levels) = &args.supported_consistency_levels {
+        require!(
+            supported_consistency_levels.contains(&outbox_message.consistency_level),
+            EndpointError::UnsupportedConsistencyLevel
+        );
+    }
+
     // Mark the message as picked up by this adapter
     outbox_message
         .outstanding_adapters
         .set(adapter_index, false)?;
 
+    ctx.accounts.adapter_stats.record_pickup(Clock::get()?.slot);
+
+    if let Some(adapter_queue) = ctx.accounts.adapter_queue.as_mut() {
+        adapter_queue.remove(&outbox_message.key());
+    }
+
     emit_cpi!(MessagePickedUp {
         src_addr: outbox_message.src_addr,
         sequence: outbox_message.sequence,
@@ -112,13 +170,16 @@ pub fn pick_up_message(ctx: Context<PickUpMessage>, args: PickUpMessageArgs) ->
         payload_hash: outbox_message.payload_hash,
         adapter: args.adapter_program_id,
         remaining_adapters: outbox_message.outstanding_adapters.as_value(),
+        consistency_level: outbox_message.consistency_level,
+        expiry: outbox_message.expiry,
     });
 
     // Close `outbox_message` account if all adapters have picked up the message
     if outbox_message.outstanding_adapters.as_value() == 0 {
         ctx.accounts
             .outbox_message
-            .close(ctx.accounts.refund_recipient.to_account_info())?
+            .close(ctx.accounts.refund_recipient.to_account_info())?;
+        ctx.accounts.adapter_stats.record_outbox_closed();
     }
 
     Ok(())
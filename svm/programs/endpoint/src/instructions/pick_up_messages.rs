@@ -0,0 +1,205 @@
+use crate::{
+    error::EndpointError,
+    event::MessagePickedUp,
+    instructions::BatchMode,
+    state::{AdapterInfo, AdapterQueue, AdapterStats, OutboxMessage},
+    utils::account::is_uncreated,
+};
+use adapter_interface::ADAPTER_PDA_SEED;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PickUpMessagesArgs {
+    pub adapter_program_id: Pubkey,
+    pub adapter_pda_bump: u8,
+    /// The integrator that sent every message in the batch
+    pub integrator_program_id: Pubkey,
+    /// The consistency levels the adapter can honour, or `None` if it accepts any level
+    pub supported_consistency_levels: Option<Vec<u8>>,
+    /// What to do with messages the adapter has already picked up
+    pub mode: BatchMode,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: PickUpMessagesArgs)]
+pub struct PickUpMessages<'info> {
+    /// The adapter info account
+    /// This account contains index of the adapter picking up the messages
+    /// A retired adapter can still pick up the messages it was outstanding for, so that they are
+    /// closed
+    #[account(
+        seeds = [
+            AdapterInfo::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_info.bump,
+    )]
+    pub adapter_info: Account<'info, AdapterInfo>,
+
+    /// The adapter stats account
+    /// The pick up counter and last pick up slot are updated here, and the closed outbox message
+    /// counter as messages are closed
+    #[account(
+        mut,
+        seeds = [
+            AdapterStats::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_stats.bump,
+    )]
+    pub adapter_stats: Account<'info, AdapterStats>,
+
+    /// The adapter's queue of pending messages
+    /// This is only required when the adapter has one, and the messages are removed from it
+    #[account(
+        mut,
+        seeds = [
+            AdapterQueue::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_queue.bump,
+    )]
+    pub adapter_queue: Option<Box<Account<'info, AdapterQueue>>>,
+
+    /// The adapter PDA account, used for signing
+    /// This ensures that only the authorized adapter can pick up the messages
+    #[account(
+        seeds = [ADAPTER_PDA_SEED],
+        bump = args.adapter_pda_bump,
+        seeds::program = args.adapter_program_id
+    )]
+    pub adapter_pda: Signer<'info>,
+}
+
+/// Instruction for picking up several messages from the outbox.
+///
+/// This is the batch form of `pick_up_message`, for messages sent by one integrator. The
+/// adapter and its signer are checked once for the batch.
+///
+/// `remaining_accounts` must hold, for each message, its `OutboxMessage` followed by its
+/// refund recipient, both writable.
+///
+/// Each message is picked up as by `pick_up_message`, and its outbox message is closed once
+/// all adapters have picked it up. Picked up messages are removed from the adapter's
+/// `AdapterQueue`, which must be passed if the adapter has one. With `BatchMode::SkipDone`, messages the adapter has
+/// already picked up are left out, including those whose outbox message has been closed.
+/// With `BatchMode::AllOrNothing`, any of them fails the whole batch.
+///
+/// # Arguments
+///
+/// * `args` - The arguments for the instruction, including:
+///   * `adapter_program_id`: The Pubkey of the adapter program.
+///   * `adapter_pda_bump`: The bump seed for the adapter's PDA.
+///   * `integrator_program_id`: The integrator that sent the messages.
+///   * `supported_consistency_levels`: The consistency levels the adapter can honour, if restricted.
+///   * `mode`: What to do with messages the adapter has already picked up.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The adapter has an `AdapterQueue` that is not passed (EndpointError::AdapterQueueMissing).
+/// * The accounts in `remaining_accounts` are not pairs of an outbox message of the integrator
+///   and its refund recipient (EndpointError::InvalidBatchAccounts).
+/// * A message has already been picked up by this adapter and the mode is
+///   `BatchMode::AllOrNothing` (EndpointError::MessageAlreadyPickedUp).
+/// * The adapter does not support a message's consistency level (EndpointError::UnsupportedConsistencyLevel).
+///
+/// # Events
+///
+/// Emits a `MessagePickedUp` event for each message picked up
+pub fn pick_up_messages<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PickUpMessages<'info>>,
+    args: PickUpMessagesArgs,
+) -> Result<()> {
+    require!(
+        ctx.remaining_accounts.len() % 2 == 0,
+        EndpointError::InvalidBatchAccounts
+    );
+
+    let adapter_index = ctx.accounts.adapter_info.index;
+    let slot = Clock::get()?.slot;
+
+    // Once the adapter has a queue, it must be kept up to date
+    require!(
+        ctx.accounts.adapter_queue.is_some() || !ctx.accounts.adapter_info.has_queue,
+        EndpointError::AdapterQueueMissing
+    );
+
+    for accounts in ctx.remaining_accounts.chunks(2) {
+        let (outbox_message_account, refund_recipient) = (&accounts[0], &accounts[1]);
+
+        // A closed outbox message has been picked up by every adapter
+        if is_uncreated(outbox_message_account) {
+            require!(
+                args.mode == BatchMode::SkipDone,
+                EndpointError::MessageAlreadyPickedUp
+            );
+            continue;
+        }
+
+        let mut outbox_message = Account::<OutboxMessage>::try_from(outbox_message_account)?;
+        require!(
+            outbox_message.src_addr == args.integrator_program_id.to_bytes()
+                && outbox_message.refund_recipient == refund_recipient.key(),
+            EndpointError::InvalidBatchAccounts
+        );
+
+        // Check if the message is available for pick up by this adapter
+        if !outbox_message
+            .outstanding_adapters
+            .get(adapter_index)
+            .unwrap_or(false)
+        {
+            require!(
+                args.mode == BatchMode::SkipDone,
+                EndpointError::MessageAlreadyPickedUp
+            );
+            continue;
+        }
+
+        // Let the adapter refuse messages it cannot deliver at the requested level
+        if let Some(supported_consistency_levels) = &args.supported_consistency_levels {
+            require!(
+                supported_consistency_levels.contains(&outbox_message.consistency_level),
+                EndpointError::UnsupportedConsistencyLevel
+            );
+        }
+
+        // Mark the message as picked up by this adapter
+        outbox_message
+            .outstanding_adapters
+            .set(adapter_index, false)?;
+
+        ctx.accounts.adapter_stats.record_pickup(slot);
+
+        if let Some(adapter_queue) = ctx.accounts.adapter_queue.as_mut() {
+            adapter_queue.remove(&outbox_message.key());
+        }
+
+        emit_cpi!(MessagePickedUp {
+            src_addr: outbox_message.src_addr,
+            sequence: outbox_message.sequence,
+            dst_chain: outbox_message.dst_chain,
+            dst_addr: outbox_message.dst_addr,
+            payload_hash: outbox_message.payload_hash,
+            adapter: args.adapter_program_id,
+            remaining_adapters: outbox_message.outstanding_adapters.as_value(),
+            consistency_level: outbox_message.consistency_level,
+            expiry: outbox_message.expiry,
+        });
+
+        // Close `outbox_message` account if all adapters have picked up the message
+        if outbox_message.outstanding_adapters.as_value() == 0 {
+            outbox_message.close(refund_recipient.clone())?;
+            ctx.accounts.adapter_stats.record_outbox_closed();
+        } else {
+            outbox_message.exit(&crate::ID)?;
+        }
+    }
+
+    Ok(())
+}
@@ -3,7 +3,10 @@ use anchor_lang::prelude::*;
 use crate::{
     error::EndpointError,
     event::MessageReceived,
-    state::{AttestationInfo, IntegratorChainConfig},
+    state::{
+        AttestationInfo, InboundCursor, IntegratorChainConfig, MessageExpiry, ReplayFloor,
+        ReplayWindow, SequenceRecord,
+    },
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -15,6 +18,33 @@ pub struct RecvMessageArgs {
     pub dst_chain: u16,
     pub integrator_program_id: Pubkey,
     pub payload_hash: [u8; 32],
+    /// The deadline committed into the message by the sender, if any
+    pub expiry: Option<MessageExpiry>,
+    /// Receive the message even if conflicting attestations exist for its sequence
+    pub override_equivocation: bool,
+}
+
+/// The receive configuration of a message, returned by `recv_message`.
+///
+/// Both the configuration at the first attestation and the current configuration are
+/// returned, so integrators can decide for themselves whether attestations from adapters
+/// that were disabled or enabled since then should count.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecvMessageResult {
+    /// The receive adapters currently enabled for the source chain
+    pub enabled_bitmap: u128,
+
+    /// The current config epoch of the source chain
+    pub config_epoch: u64,
+
+    /// The receive adapters enabled for the source chain at the first attestation
+    pub snapshot_enabled_bitmap: u128,
+
+    /// The config epoch of the source chain at the first attestation
+    pub snapshot_config_epoch: u64,
+
+    /// The adapters that have attested to the message
+    pub attested_bitmap: u128,
 }
 
 #[event_cpi]
@@ -57,13 +87,88 @@ pub struct RecvMessage<'info> {
                 args.sequence,
                 args.dst_chain,
                 args.integrator_program_id.to_bytes(),
-                args.payload_hash
+                args.payload_hash,
+                args.expiry
             )
         ],
         bump = attestation_info.bump,
     )]
     pub attestation_info: Account<'info, AttestationInfo>,
 
+    /// The sequence record account
+    /// This is used to check whether conflicting attestations exist for the message's sequence
+    #[account(
+        mut,
+        seeds = [
+            SequenceRecord::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+            args.sequence.to_be_bytes().as_ref(),
+        ],
+        bump = sequence_record.bump,
+    )]
+    pub sequence_record: Account<'info, SequenceRecord>,
+
+    /// The inbound cursor of the source address
+    /// This is only required when ordered delivery is enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + InboundCursor::INIT_SPACE,
+        seeds = [
+            InboundCursor::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+        ],
+        bump,
+    )]
+    pub inbound_cursor: Option<Account<'info, InboundCursor>>,
+
+    /// The replay floor of the source address
+    /// This is only required when replay windows are enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayFloor::INIT_SPACE,
+        seeds = [
+            ReplayFloor::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_floor: Option<Account<'info, ReplayFloor>>,
+
+    /// The replay window covering the sequence
+    /// This is only required when replay windows are enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayWindow::INIT_SPACE,
+        seeds = [
+            ReplayWindow::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+            ReplayWindow::window_index(args.sequence).to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_window: Option<Box<Account<'info, ReplayWindow>>>,
+
+    /// The recipient of the attestation info's rent, if it is not the payer
+    /// This is only required when replay windows are enabled for the source chain, which closes
+    /// the attestation info and sequence record, refunding both to it
+    /// CHECK: Checked against the attestation info's `refund_recipient`
+    #[account(mut)]
+    pub refund_recipient: Option<UncheckedAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -71,8 +176,17 @@ pub struct RecvMessage<'info> {
 ///
 /// This function performs the following steps:
 /// 1. Checks if the message has already been executed.
-/// 2. Marks the message as executed.
-/// 3. Emits a MessageReceived event.
+/// 2. Checks that the message's expiry, if any, has not passed.
+/// 3. Checks that no conflicting attestations exist for the sequence, unless overridden.
+/// 4. If ordered delivery is enabled for the source chain, checks that the sequence is
+///    the next one from the source address and advances the `InboundCursor` past it.
+/// 5. If replay windows are enabled for the source chain, checks that the sequence's window is
+///    still open and marks the sequence as executed in it.
+/// 6. Marks the message and its sequence as executed.
+/// 7. Emits a MessageReceived event.
+/// 8. If replay windows are enabled, closes the `AttestationInfo`, refunding its rent to the
+///    account that paid for it, and the `SequenceRecord`, refunding its rent to the same account.
+/// 9. Returns the current and snapshotted receive configuration along with the attestations.
 ///
 /// # Arguments
 ///
@@ -85,15 +199,32 @@ pub struct RecvMessage<'info> {
 ///   * `dst_chain`: The destination chain ID.
 ///   * `integrator_program_id`: The public key of the integrator program.
 ///   * `payload_hash`: The hash of the message payload.
+///   * `expiry`: The deadline committed into the message by the sender, if any.
+///   * `override_equivocation`: Whether to receive the message despite conflicting attestations.
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` if the message is successfully received, or an error if it fails
+/// Returns a `RecvMessageResult` if the message is successfully received, or an error if it fails
 ///
 /// # Errors
 ///
 /// This function will return an error if:
 /// * The message has already been executed (EndpointError::AlreadyExecuted)
+/// * The message's expiry has passed (EndpointError::MessageExpired)
+/// * Conflicting attestations exist for the sequence and `override_equivocation` is not set
+///   (EndpointError::SequenceEquivocated)
+/// * Ordered delivery is enabled and no `inbound_cursor` is passed
+///   (EndpointError::InboundCursorMissing)
+/// * Ordered delivery is enabled and the sequence is not the next one from the source address
+///   (EndpointError::OutOfOrderSequence)
+/// * Replay windows are enabled and `replay_floor` or `replay_window` is not passed
+///   (EndpointError::ReplayWindowMissing)
+/// * Replay windows are enabled and the sequence's window has been closed
+///   (EndpointError::ReplayWindowClosed)
+/// * Replay windows are enabled and the sequence has already been executed
+///   (EndpointError::AlreadyExecuted)
+/// * Replay windows are enabled and the attestation info's refund recipient is neither the payer
+///   nor the `refund_recipient` passed (EndpointError::InvalidRefundRecipient)
 ///
 /// # Events
 ///
@@ -104,35 +235,110 @@ pub struct RecvMessage<'info> {
 /// We don't double-check for `no attestations here`. If it reaches this point,
 /// it means the `AttestationInfo` is already initialized. In other words,
 /// either `attest_message` or `exec_message` has been invoked previously.
-/// In the case of `exec_message`, `AlreadyExecuted` will be thrown.
-pub fn recv_message(ctx: Context<RecvMessage>, _args: RecvMessageArgs) -> Result<()> {
+/// Both create the `SequenceRecord`, so a message consumed by `exec_message` fails with
+/// `AlreadyExecuted`.
+pub fn recv_message(ctx: Context<RecvMessage>, args: RecvMessageArgs) -> Result<RecvMessageResult> {
     let attestation_info = &mut ctx.accounts.attestation_info;
 
     // Check if the message has already been executed
     require!(!attestation_info.executed, EndpointError::AlreadyExecuted);
 
+    // Refuse a message that arrives after the sender's deadline
+    if let Some(expiry) = attestation_info.expiry {
+        require!(
+            !expiry.has_expired(&Clock::get()?),
+            EndpointError::MessageExpired
+        );
+    }
+
+    // Refuse a sequence with conflicting attestations unless the integrator explicitly overrides
+    ctx.accounts
+        .sequence_record
+        .check_equivocation(args.override_equivocation)?;
+
+    // Only deliver the next sequence from the source address if ordered delivery is enabled
+    if ctx.accounts.integrator_chain_config.ordered_delivery {
+        ctx.accounts
+            .inbound_cursor
+            .as_mut()
+            .ok_or(EndpointError::InboundCursorMissing)?
+            .init_and_deliver(
+                ctx.bumps.inbound_cursor.unwrap(),
+                args.integrator_program_id,
+                args.src_chain,
+                args.src_addr,
+                args.sequence,
+            )?;
+    }
+
+    // Record the sequence in its replay window if replay windows are enabled
+    if ctx.accounts.integrator_chain_config.replay_windows {
+        ctx.accounts
+            .replay_floor
+            .as_mut()
+            .ok_or(EndpointError::ReplayWindowMissing)?
+            .init_and_check_open(
+                ctx.bumps.replay_floor.unwrap(),
+                args.integrator_program_id,
+                args.src_chain,
+                args.src_addr,
+                args.sequence,
+            )?;
+        ctx.accounts
+            .replay_window
+            .as_mut()
+            .ok_or(EndpointError::ReplayWindowMissing)?
+            .init_and_mark_executed(
+                ctx.bumps.replay_window.unwrap(),
+                args.integrator_program_id,
+                args.src_chain,
+                args.src_addr,
+                args.sequence,
+            )?;
+    }
+
     // There is no need to check for the src_chain and dst_chain validity since they
     // are check during the init of attestation_info in either `exec_message` or `attest_message`
 
-    // Mark the message as executed
+    // Mark the message and its sequence as executed
     attestation_info.executed = true;
+    ctx.accounts.sequence_record.executed = true;
+
+    let integrator_chain_config = &ctx.accounts.integrator_chain_config;
+    let result = RecvMessageResult {
+        enabled_bitmap: integrator_chain_config.recv_adapter_bitmap.as_value(),
+        config_epoch: integrator_chain_config.config_epoch,
+        snapshot_enabled_bitmap: attestation_info.enabled_adapters.as_value(),
+        snapshot_config_epoch: attestation_info.config_epoch,
+        attested_bitmap: attestation_info.attested_adapters.as_value(),
+    };
 
     emit_cpi!(MessageReceived {
         message_hash: attestation_info.message_hash,
-        src_chain: attestation_info.src_chain,
-        src_addr: attestation_info.src_addr,
-        sequence: attestation_info.sequence,
-        dst_chain: attestation_info.dst_chain,
-        dst_addr: attestation_info.dst_addr,
-        payload_hash: attestation_info.payload_hash,
-        enabled_bitmap: ctx
-            .accounts
-            .integrator_chain_config
-            .recv_adapter_bitmap
-            .as_value(),
-        attested_bitmap: attestation_info.attested_adapters.as_value(),
+        src_chain: args.src_chain,
+        src_addr: args.src_addr,
+        sequence: args.sequence,
+        dst_chain: args.dst_chain,
+        dst_addr: args.integrator_program_id.to_bytes(),
+        payload_hash: args.payload_hash,
+        enabled_bitmap: result.enabled_bitmap,
+        attested_bitmap: result.attested_bitmap,
+        config_epoch: result.config_epoch,
+        snapshot_enabled_bitmap: result.snapshot_enabled_bitmap,
+        snapshot_config_epoch: result.snapshot_config_epoch,
     });
 
+    // With replay windows, the attestation info and sequence record are only needed while the
+    // message is in flight, as the window now guards against replays
+    if ctx.accounts.integrator_chain_config.replay_windows {
+        AttestationInfo::close_delivered(
+            &ctx.accounts.attestation_info,
+            &ctx.accounts.sequence_record,
+            &ctx.accounts.payer,
+            ctx.accounts.refund_recipient.as_ref(),
+        )?;
+    }
+
     // Return the enabled receive Adapters for that chain along with the attestations
-    Ok(())
+    Ok(result)
 }
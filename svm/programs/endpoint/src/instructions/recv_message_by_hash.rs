@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::EndpointError,
+    event::MessageReceived,
+    instructions::RecvMessageResult,
+    state::{
+        AttestationInfo, InboundCursor, IntegratorChainConfig, ReplayFloor, ReplayWindow,
+        SequenceRecord,
+    },
+    CHAIN_ID,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RecvMessageByHashArgs {
+    pub integrator_program_pda_bump: u8,
+    /// The hash of a message that has an `AttestationInfo`
+    pub message_hash: [u8; 32],
+    /// Receive the message even if conflicting attestations exist for its sequence
+    pub override_equivocation: bool,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: RecvMessageByHashArgs)]
+pub struct RecvMessageByHash<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The attestation info account
+    /// This throws when there is no attestation as there is no account initialized yet
+    #[account(
+        mut,
+        seeds = [AttestationInfo::SEED_PREFIX, &args.message_hash],
+        bump = attestation_info.bump,
+    )]
+    pub attestation_info: Account<'info, AttestationInfo>,
+
+    /// The sequence record of the message
+    /// The message's fields, and the integrator and source chain of the other accounts, are
+    /// taken from it
+    #[account(
+        mut,
+        constraint = sequence_record.first_message_hash(attestation_info.expiry)
+            == args.message_hash @ EndpointError::InvalidMessageHash,
+    )]
+    pub sequence_record: Account<'info, SequenceRecord>,
+
+    /// The PDA of the integrator program.
+    /// This makes sure that the one calling this is the integrator program
+    #[account(
+        seeds = [b"endpoint_integrator"],
+        bump = args.integrator_program_pda_bump,
+        seeds::program = sequence_record.integrator_program_id
+    )]
+    pub integrator_program_pda: Signer<'info>,
+
+    /// The integrator chain config account
+    /// This is required to read the enabled_bitmap from
+    #[account(
+        seeds = [
+            IntegratorChainConfig::SEED_PREFIX,
+            sequence_record.integrator_program_id.as_ref(),
+            sequence_record.src_chain.to_be_bytes().as_ref()
+        ],
+        bump = integrator_chain_config.bump,
+    )]
+    pub integrator_chain_config: Account<'info, IntegratorChainConfig>,
+
+    /// The inbound cursor of the source address
+    /// This is only required when ordered delivery is enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + InboundCursor::INIT_SPACE,
+        seeds = [
+            InboundCursor::SEED_PREFIX,
+            sequence_record.integrator_program_id.as_ref(),
+            sequence_record.src_chain.to_be_bytes().as_ref(),
+            sequence_record.src_addr.as_ref(),
+        ],
+        bump,
+    )]
+    pub inbound_cursor: Option<Account<'info, InboundCursor>>,
+
+    /// The replay floor of the source address
+    /// This is only required when replay windows are enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayFloor::INIT_SPACE,
+        seeds = [
+            ReplayFloor::SEED_PREFIX,
+            sequence_record.integrator_program_id.as_ref(),
+            sequence_record.src_chain.to_be_bytes().as_ref(),
+            sequence_record.src_addr.as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_floor: Option<Account<'info, ReplayFloor>>,
+
+    /// The replay window covering the sequence
+    /// This is only required when replay windows are enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayWindow::INIT_SPACE,
+        seeds = [
+            ReplayWindow::SEED_PREFIX,
+            sequence_record.integrator_program_id.as_ref(),
+            sequence_record.src_chain.to_be_bytes().as_ref(),
+            sequence_record.src_addr.as_ref(),
+            ReplayWindow::window_index(sequence_record.sequence).to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_window: Option<Box<Account<'info, ReplayWindow>>>,
+
+    /// The recipient of the attestation info's rent, if it is not the payer
+    /// This is only required when replay windows are enabled for the source chain, which closes
+    /// the attestation info and sequence record, refunding both to it
+    /// CHECK: Checked against the attestation info's `refund_recipient`
+    #[account(mut)]
+    pub refund_recipient: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Receives a message that has been attested to in the endpoint program, by its hash
+///
+/// This is a cheaper form of `recv_message` that only takes the message hash. The message's
+/// fields are read from the `SequenceRecord` of its sequence, so only the message carrying the
+/// first payload attested for the sequence can be received by hash. A message with a
+/// conflicting payload must go through `recv_message`.
+///
+/// This function performs the same steps as `recv_message`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, including:
+///   * `integrator_program_pda_bump`: The bump seed for the integrator program PDA.
+///   * `message_hash`: The hash of the message.
+///   * `override_equivocation`: Whether to receive the message despite conflicting attestations.
+///
+/// # Returns
+///
+/// Returns a `RecvMessageResult` if the message is successfully received, or an error if it fails
+///
+/// # Errors
+///
+/// This function will return the errors of `recv_message`, and also if:
+/// * The `sequence_record` does not hold the message's payload (EndpointError::InvalidMessageHash)
+///
+/// # Events
+///
+/// Emits a `MessageReceived` event
+pub fn recv_message_by_hash(
+    ctx: Context<RecvMessageByHash>,
+    args: RecvMessageByHashArgs,
+) -> Result<RecvMessageResult> {
+    let attestation_info = &mut ctx.accounts.attestation_info;
+    let sequence_record = &mut ctx.accounts.sequence_record;
+
+    // Check if the message has already been executed
+    require!(!attestation_info.executed, EndpointError::AlreadyExecuted);
+
+    // Refuse a message that arrives after the sender's deadline
+    if let Some(expiry) = attestation_info.expiry {
+        require!(
+            !expiry.has_expired(&Clock::get()?),
+            EndpointError::MessageExpired
+        );
+    }
+
+    // Refuse a sequence with conflicting attestations unless the integrator explicitly overrides
+    sequence_record.check_equivocation(args.override_equivocation)?;
+
+    // Only deliver the next sequence from the source address if ordered delivery is enabled
+    if ctx.accounts.integrator_chain_config.ordered_delivery {
+        ctx.accounts
+            .inbound_cursor
+            .as_mut()
+            .ok_or(EndpointError::InboundCursorMissing)?
+            .init_and_deliver(
+                ctx.bumps.inbound_cursor.unwrap(),
+                sequence_record.integrator_program_id,
+                sequence_record.src_chain,
+                sequence_record.src_addr,
+                sequence_record.sequence,
+            )?;
+    }
+
+    // Record the sequence in its replay window if replay windows are enabled
+    if ctx.accounts.integrator_chain_config.replay_windows {
+        ctx.accounts
+            .replay_floor
+            .as_mut()
+            .ok_or(EndpointError::ReplayWindowMissing)?
+            .init_and_check_open(
+                ctx.bumps.replay_floor.unwrap(),
+                sequence_record.integrator_program_id,
+                sequence_record.src_chain,
+                sequence_record.src_addr,
+                sequence_record.sequence,
+            )?;
+        ctx.accounts
+            .replay_window
+            .as_mut()
+            .ok_or(EndpointError::ReplayWindowMissing)?
+            .init_and_mark_executed(
+                ctx.bumps.replay_window.unwrap(),
+                sequence_record.integrator_program_id,
+                sequence_record.src_chain,
+                sequence_record.src_addr,
+                sequence_record.sequence,
+            )?;
+    }
+
+    // Mark the message and its sequence as executed
+    attestation_info.executed = true;
+    sequence_record.executed = true;
+
+    let integrator_chain_config = &ctx.accounts.integrator_chain_config;
+    let result = RecvMessageResult {
+        enabled_bitmap: integrator_chain_config.recv_adapter_bitmap.as_value(),
+        config_epoch: integrator_chain_config.config_epoch,
+        snapshot_enabled_bitmap: attestation_info.enabled_adapters.as_value(),
+        snapshot_config_epoch: attestation_info.config_epoch,
+        attested_bitmap: attestation_info.attested_adapters.as_value(),
+    };
+
+    emit_cpi!(MessageReceived {
+        message_hash: args.message_hash,
+        src_chain: sequence_record.src_chain,
+        src_addr: sequence_record.src_addr,
+        sequence: sequence_record.sequence,
+        dst_chain: CHAIN_ID,
+        dst_addr: sequence_record.integrator_program_id.to_bytes(),
+        payload_hash: sequence_record.first_payload_hash,
+        enabled_bitmap: result.enabled_bitmap,
+        attested_bitmap: result.attested_bitmap,
+        config_epoch: result.config_epoch,
+        snapshot_enabled_bitmap: result.snapshot_enabled_bitmap,
+        snapshot_config_epoch: result.snapshot_config_epoch,
+    });
+
+    // With replay windows, the attestation info and sequence record are only needed while the
+    // message is in flight, as the window now guards against replays
+    if ctx.accounts.integrator_chain_config.replay_windows {
+        AttestationInfo::close_delivered(
+            &ctx.accounts.attestation_info,
+            &ctx.accounts.sequence_record,
+            &ctx.accounts.payer,
+            ctx.accounts.refund_recipient.as_ref(),
+        )?;
+    }
+
+    // Return the enabled receive Adapters for that chain along with the attestations
+    Ok(result)
+}
@@ -0,0 +1,363 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::EndpointError,
+    event::{AttestationLeafUpdated, MessageReceived},
+    instructions::RecvMessageResult,
+    state::{
+        tree_error, AttestationInfo, AttestationLeaf, AttestationTree, CompressedAttestation,
+        InboundCursor, IntegratorChainConfig, MessageExpiry, ReplayFloor, ReplayWindow,
+        SequenceRecord,
+    },
+    utils::account::refund_recipient,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RecvMessageCompressedArgs {
+    pub integrator_program_pda_bump: u8,
+    pub src_chain: u16,
+    pub src_addr: [u8; 32],
+    pub sequence: u64,
+    pub dst_chain: u16,
+    pub integrator_program_id: Pubkey,
+    pub payload_hash: [u8; 32],
+    /// The deadline committed into the message by the sender, if any
+    pub expiry: Option<MessageExpiry>,
+    /// The root the proof in `remaining_accounts` was built against
+    pub root: [u8; 32],
+    /// The index of the message's leaf
+    pub leaf_index: u32,
+    /// The message's current leaf
+    pub leaf: CompressedAttestation,
+    /// Receive the message even if conflicting attestations exist for its sequence
+    pub override_equivocation: bool,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: RecvMessageCompressedArgs)]
+pub struct RecvMessageCompressed<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The PDA of the integrator program.
+    /// This makes sure that the one calling this is the integrator program
+    #[account(
+        seeds = [b"endpoint_integrator"],
+        bump = args.integrator_program_pda_bump,
+        seeds::program = args.integrator_program_id
+    )]
+    pub integrator_program_pda: Signer<'info>,
+
+    /// The integrator chain config account
+    /// This is required to read the enabled_bitmap from
+    #[account(
+        seeds = [
+            IntegratorChainConfig::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref()
+        ],
+        bump = integrator_chain_config.bump,
+    )]
+    pub integrator_chain_config: Account<'info, IntegratorChainConfig>,
+
+    /// The attestation tree holding the message's leaf
+    #[account(
+        mut,
+        constraint = attestation_tree.integrator_program_id == args.integrator_program_id
+            && attestation_tree.chain_id == args.src_chain @ EndpointError::InvalidAttestationTree,
+    )]
+    pub attestation_tree: Account<'info, AttestationTree>,
+
+    /// The record of the message's leaf
+    /// This is closed once the message is received
+    #[account(
+        mut,
+        seeds = [
+            AttestationLeaf::SEED_PREFIX,
+            &AttestationInfo::compute_message_hash(
+                args.src_chain,
+                args.src_addr,
+                args.sequence,
+                args.dst_chain,
+                args.integrator_program_id.to_bytes(),
+                args.payload_hash,
+                args.expiry
+            )
+        ],
+        bump = attestation_leaf.bump,
+        constraint = attestation_leaf.tree == attestation_tree.key()
+            && attestation_leaf.leaf_index == args.leaf_index @ EndpointError::InvalidAttestationLeaf,
+    )]
+    pub attestation_leaf: Box<Account<'info, AttestationLeaf>>,
+
+    /// The sequence record account
+    /// This is used to check whether conflicting attestations exist for the message's sequence,
+    /// and is closed once the message is received
+    #[account(
+        mut,
+        seeds = [
+            SequenceRecord::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+            args.sequence.to_be_bytes().as_ref(),
+        ],
+        bump = sequence_record.bump,
+    )]
+    pub sequence_record: Box<Account<'info, SequenceRecord>>,
+
+    /// The inbound cursor of the source address
+    /// This is only required when ordered delivery is enabled for the source chain, and is
+    /// initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + InboundCursor::INIT_SPACE,
+        seeds = [
+            InboundCursor::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+        ],
+        bump,
+    )]
+    pub inbound_cursor: Option<Account<'info, InboundCursor>>,
+
+    /// The replay floor of the source address
+    /// This account is initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayFloor::INIT_SPACE,
+        seeds = [
+            ReplayFloor::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_floor: Account<'info, ReplayFloor>,
+
+    /// The replay window covering the sequence
+    /// This account is initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ReplayWindow::INIT_SPACE,
+        seeds = [
+            ReplayWindow::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+            ReplayWindow::window_index(args.sequence).to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub replay_window: Box<Account<'info, ReplayWindow>>,
+
+    /// The recipient of the attestation leaf's rent, if it is not the payer
+    /// The attestation leaf and sequence record are closed, refunding both to it
+    /// CHECK: Checked against the attestation leaf's `refund_recipient`
+    #[account(mut)]
+    pub refund_recipient: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Receives a message that has been attested to in an `AttestationTree`
+///
+/// This is the counterpart of `recv_message` for compressed attestations. The proof of the
+/// message's leaf is passed as `remaining_accounts`, as for `attest_message_compressed`.
+///
+/// A Merkle tree cannot show that a message has no other leaf, so replay protection comes
+/// from the source address's `ReplayWindow` alone, and replay windows must be enabled for the
+/// source chain.
+///
+/// This function performs the following steps:
+/// 1. Checks that `leaf` belongs to the message and has not been executed.
+/// 2. Checks that the message's expiry, if any, has not passed.
+/// 3. Checks that no conflicting attestations exist for the sequence, unless overridden.
+/// 4. If ordered delivery is enabled for the source chain, checks that the sequence is
+///    the next one from the source address and advances the `InboundCursor` past it.
+/// 5. Checks that the sequence's window is still open and marks the sequence as executed in it.
+/// 6. Replaces the leaf with one marked as executed.
+/// 7. Emits a MessageReceived event and an AttestationLeafUpdated event.
+/// 8. Closes the `AttestationLeaf` and the `SequenceRecord`, refunding their rent to the account
+///    that paid for the leaf.
+/// 9. Returns the current and snapshotted receive configuration along with the attestations.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, containing message details along with:
+///   * `root`: The root the proof was built against
+///   * `leaf_index`: The index of the message's leaf
+///   * `leaf`: The message's current leaf
+///   * `override_equivocation`: Whether to receive the message despite conflicting attestations
+///
+/// # Returns
+///
+/// Returns a `RecvMessageResult` if the message is successfully received, or an error if it fails
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * Replay windows are not enabled for the source chain (EndpointError::ReplayWindowsRequired)
+/// * `leaf` belongs to another message (EndpointError::InvalidMessageHash)
+/// * `leaf_index` or the tree is not the one recorded in the message's `AttestationLeaf`
+///   (EndpointError::InvalidAttestationLeaf)
+/// * The message has already been executed (EndpointError::AlreadyExecuted)
+/// * The message's expiry has passed (EndpointError::MessageExpired)
+/// * Conflicting attestations exist for the sequence and `override_equivocation` is not set
+///   (EndpointError::SequenceEquivocated)
+/// * Ordered delivery is enabled and no `inbound_cursor` is passed
+///   (EndpointError::InboundCursorMissing)
+/// * Ordered delivery is enabled and the sequence is not the next one from the source address
+///   (EndpointError::OutOfOrderSequence)
+/// * The sequence's window has been closed (EndpointError::ReplayWindowClosed)
+/// * The proof does not match the tree (EndpointError::InvalidAttestationProof)
+/// * The attestation leaf's refund recipient is neither the payer nor the `refund_recipient`
+///   passed (EndpointError::InvalidRefundRecipient)
+///
+/// # Events
+///
+/// Emits a `MessageReceived` and an `AttestationLeafUpdated` event
+pub fn recv_message_compressed(
+    ctx: Context<RecvMessageCompressed>,
+    args: RecvMessageCompressedArgs,
+) -> Result<RecvMessageResult> {
+    let integrator_chain_config = &ctx.accounts.integrator_chain_config;
+    require!(
+        integrator_chain_config.replay_windows,
+        EndpointError::ReplayWindowsRequired
+    );
+
+    let message_hash = AttestationInfo::compute_message_hash(
+        args.src_chain,
+        args.src_addr,
+        args.sequence,
+        args.dst_chain,
+        args.integrator_program_id.to_bytes(),
+        args.payload_hash,
+        args.expiry,
+    );
+    require!(
+        args.leaf.message_hash == message_hash,
+        EndpointError::InvalidMessageHash
+    );
+
+    // Check if the message has already been executed
+    require!(!args.leaf.executed, EndpointError::AlreadyExecuted);
+
+    // Refuse a message that arrives after the sender's deadline
+    if let Some(expiry) = args.expiry {
+        require!(
+            !expiry.has_expired(&Clock::get()?),
+            EndpointError::MessageExpired
+        );
+    }
+
+    // Refuse a sequence with conflicting attestations unless the integrator explicitly overrides
+    ctx.accounts
+        .sequence_record
+        .check_equivocation(args.override_equivocation)?;
+
+    // Only deliver the next sequence from the source address if ordered delivery is enabled
+    if integrator_chain_config.ordered_delivery {
+        ctx.accounts
+            .inbound_cursor
+            .as_mut()
+            .ok_or(EndpointError::InboundCursorMissing)?
+            .init_and_deliver(
+                ctx.bumps.inbound_cursor.unwrap(),
+                args.integrator_program_id,
+                args.src_chain,
+                args.src_addr,
+                args.sequence,
+            )?;
+    }
+
+    // Record the sequence in its replay window
+    ctx.accounts.replay_floor.init_and_check_open(
+        ctx.bumps.replay_floor,
+        args.integrator_program_id,
+        args.src_chain,
+        args.src_addr,
+        args.sequence,
+    )?;
+    ctx.accounts.replay_window.init_and_mark_executed(
+        ctx.bumps.replay_window,
+        args.integrator_program_id,
+        args.src_chain,
+        args.src_addr,
+        args.sequence,
+    )?;
+
+    // Mark the message as executed
+    let leaf = CompressedAttestation {
+        executed: true,
+        ..args.leaf
+    };
+    let proof: Vec<[u8; 32]> = ctx
+        .remaining_accounts
+        .iter()
+        .map(|node| node.key.to_bytes())
+        .collect();
+    let attestation_tree = ctx.accounts.attestation_tree.to_account_info();
+    AttestationTree::load_tree_mut(&mut attestation_tree.data.borrow_mut())?
+        .set_leaf(
+            args.root,
+            args.leaf.hash(),
+            leaf.hash(),
+            &proof,
+            args.leaf_index,
+        )
+        .map_err(tree_error)?;
+
+    let integrator_chain_config = &ctx.accounts.integrator_chain_config;
+    let result = RecvMessageResult {
+        enabled_bitmap: integrator_chain_config.recv_adapter_bitmap.as_value(),
+        config_epoch: integrator_chain_config.config_epoch,
+        snapshot_enabled_bitmap: leaf.enabled_adapters.as_value(),
+        snapshot_config_epoch: leaf.config_epoch,
+        attested_bitmap: leaf.attested_adapters.as_value(),
+    };
+
+    emit_cpi!(MessageReceived {
+        message_hash,
+        src_chain: args.src_chain,
+        src_addr: args.src_addr,
+        sequence: args.sequence,
+        dst_chain: args.dst_chain,
+        dst_addr: args.integrator_program_id.to_bytes(),
+        payload_hash: args.payload_hash,
+        enabled_bitmap: result.enabled_bitmap,
+        attested_bitmap: result.attested_bitmap,
+        config_epoch: result.config_epoch,
+        snapshot_enabled_bitmap: result.snapshot_enabled_bitmap,
+        snapshot_config_epoch: result.snapshot_config_epoch,
+    });
+
+    emit_cpi!(AttestationLeafUpdated {
+        tree: attestation_tree.key(),
+        leaf_index: args.leaf_index,
+        leaf,
+    });
+
+    // The window now guards against replays, so the leaf and sequence records are no longer
+    // needed
+    let refund_recipient = refund_recipient(
+        ctx.accounts.attestation_leaf.refund_recipient,
+        &ctx.accounts.payer,
+        ctx.accounts.refund_recipient.as_ref(),
+    )?;
+    ctx.accounts
+        .attestation_leaf
+        .close(refund_recipient.clone())?;
+    ctx.accounts.sequence_record.close(refund_recipient)?;
+
+    // Return the enabled receive Adapters for that chain along with the attestations
+    Ok(result)
+}
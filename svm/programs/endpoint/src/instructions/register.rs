@@ -1,6 +1,8 @@
 use crate::{
+    error::EndpointError,
     event::IntegratorRegistered,
-    state::{IntegratorConfig, SequenceTracker},
+    state::{ConfigDigest, IntegratorConfig, IntegratorTombstone, SequenceTracker},
+    utils::{account::is_uncreated, bitmap::Bitmap},
 };
 use anchor_lang::prelude::*;
 
@@ -50,6 +52,19 @@ pub struct Register<'info> {
     )]
     pub sequence_tracker: Account<'info, SequenceTracker>,
 
+    /// The tombstone left by `deregister`
+    /// This must not exist, so that a deregistered integrator cannot reuse its sequence numbers
+    #[account(
+        seeds = [
+            IntegratorTombstone::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+        ],
+        bump,
+        constraint = is_uncreated(&integrator_tombstone) @ EndpointError::IntegratorDeregistered,
+    )]
+    /// CHECK: Only checked to be uncreated
+    pub integrator_tombstone: UncheckedAccount<'info>,
+
     /// The integrator program's PDA
     /// This makes sure that the Signer is a Integrator Program PDA Signer
     /// TODO: Ideally there is a `AccountUncheckedOwner` that does not explicitly enforce owner
@@ -74,8 +89,9 @@ pub struct Register<'info> {
 /// 1. Initializes an IntegratorConfig account for the given integrator program.
 /// 2. Sets up the configuration with the provided admin and program ID.
 /// 3. Initializes an empty list of registered adapters.
-/// 4. Initializes a SequenceTracker account for the integrator program.
-/// 5. Emits IntegratorRegistered events.
+/// 4. Starts the configuration digest from the genesis digest and folds in the admin.
+/// 5. Initializes a SequenceTracker account for the integrator program.
+/// 6. Emits IntegratorRegistered events.
 ///
 /// # Arguments
 ///
@@ -89,6 +105,11 @@ pub struct Register<'info> {
 ///
 /// Returns `Ok(())` if the registration is successful, or an error if it fails
 ///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The integrator program has been deregistered (EndpointError::IntegratorDeregistered)
+///
 /// # Events
 ///
 /// Emits `IntegratorRegistered` event
@@ -100,13 +121,20 @@ pub fn register(ctx: Context<Register>, args: RegisterArgs) -> Result<()> {
         pending_admin: None,
         integrator_program_id: args.integrator_program_id,
         adapter_infos: Vec::new(),
+        audit_log_len: 0,
+        config_digest: ConfigDigest::genesis(&args.integrator_program_id),
+        admin_nonce: 0,
     });
+    ctx.accounts.integrator_config.update_admin_digest();
 
     // Initialize the SequenceTracker account with default values
     ctx.accounts.sequence_tracker.set_inner(SequenceTracker {
         bump: ctx.bumps.sequence_tracker,
         integrator_program_id: args.integrator_program_id,
         sequence: 0,
+        outbox_messages: 0,
+        outbound_accumulator: [0; 32],
+        queued_adapters: Bitmap::new(),
     });
 
     emit_cpi!(IntegratorRegistered {
@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::EndpointError,
+    event::MessageRepickupRequested,
+    state::{
+        AdapterQueue, AttestationInfo, IntegratorChainConfig, MessageExpiry, OutboxMessage,
+        SentMessageRecord, SequenceTracker,
+    },
+    utils::bitmap::Bitmap,
+    CHAIN_ID,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RequestRepickupArgs {
+    pub integrator_program_id: Pubkey,
+    pub integrator_program_pda_bump: u8,
+    /// The sequence the message was sent under
+    pub sequence: u64,
+    pub dst_chain: u16,
+    pub dst_addr: [u8; 32],
+    pub payload_hash: [u8; 32],
+    pub expiry: Option<MessageExpiry>,
+    /// The bitmap of the send adapters to hand the message to
+    pub adapters: u128,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: RequestRepickupArgs)]
+pub struct RequestRepickup<'info> {
+    // Payer pays for the init of `outbox_message`
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The PDA of the integrator program.
+    /// This makes sure that only the integrator program is authorized to use this ix
+    #[account(
+        seeds = [b"endpoint_integrator"],
+        bump = args.integrator_program_pda_bump,
+        seeds::program = args.integrator_program_id
+    )]
+    pub integrator_program_pda: Signer<'info>,
+
+    /// The record of the message, created when it was sent
+    /// A cancelled message cannot be handed to adapters again
+    #[account(
+        seeds = [
+            SentMessageRecord::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.sequence.to_be_bytes().as_ref(),
+        ],
+        bump = sent_message_record.bump,
+        constraint = !sent_message_record.cancelled @ EndpointError::SentMessageCancelled,
+    )]
+    pub sent_message_record: Account<'info, SentMessageRecord>,
+
+    /// The integrator chain config of the destination chain
+    /// The chosen adapters must be enabled for sending to it
+    #[account(
+        seeds = [
+            IntegratorChainConfig::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.dst_chain.to_be_bytes().as_ref()
+        ],
+        bump = integrator_chain_config.bump,
+    )]
+    pub integrator_chain_config: Account<'info, IntegratorChainConfig>,
+
+    /// The sequence tracker of the integrator
+    /// The count of its outbox messages is updated here
+    #[account(
+        mut,
+        seeds = [
+            SequenceTracker::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = sequence_tracker.bump,
+    )]
+    pub sequence_tracker: Account<'info, SequenceTracker>,
+
+    /// The new outbox message for the chosen adapters
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OutboxMessage::INIT_SPACE,
+    )]
+    pub outbox_message: Account<'info, OutboxMessage>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Hands a message that was already sent to send adapters again.
+///
+/// This recovers messages that some send adapters could not deliver, for example because one
+/// was down and a replacement has been enabled since. A new outbox message is created for the
+/// message, with the chosen adapters outstanding, and they pick it up as usual.
+///
+/// The message must match the `SentMessageRecord` created when it was sent, so that the
+/// integrator cannot send a new message under an old sequence. It keeps its sequence,
+/// consistency level and expiry, so its message hash on the destination chain is unchanged.
+/// A message with a cancelled outbox message cannot be re-picked up.
+///
+/// `remaining_accounts` must hold the `AdapterQueue`s (writable) of every chosen adapter that has
+/// a queue, in increasing adapter index, which the new outbox message is pushed onto.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts involved.
+/// * `args` - The arguments for the instruction, including:
+///   * `integrator_program_id`: The program ID of the integrator.
+///   * `integrator_program_pda_bump`: The bump seed for the integrator program PDA.
+///   * `sequence`: The sequence the message was sent under.
+///   * `dst_chain`: The destination chain ID.
+///   * `dst_addr`: The destination address as a [u8; 32].
+///   * `payload_hash`: The hash of the message payload.
+///   * `expiry`: The deadline of the message, if any.
+///   * `adapters`: The bitmap of the send adapters to hand the message to.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * An outbox message of the message has been cancelled (EndpointError::SentMessageCancelled).
+/// * The message does not match the record of the message sent under the sequence
+///   (EndpointError::SentMessageMismatch).
+/// * No adapters are chosen, or a chosen adapter is not enabled for sending to the destination
+///   chain (EndpointError::AdapterNotEnabled).
+/// * The adapter queues are not those of the chosen adapters that have one, in increasing
+///   adapter index (EndpointError::InvalidAdapterQueue).
+///
+/// # Events
+///
+/// Emits a `MessageRepickupRequested` event
+pub fn request_repickup<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RequestRepickup<'info>>,
+    args: RequestRepickupArgs,
+) -> Result<()> {
+    let message_hash = AttestationInfo::compute_message_hash(
+        CHAIN_ID,
+        args.integrator_program_id.to_bytes(),
+        args.sequence,
+        args.dst_chain,
+        args.dst_addr,
+        args.payload_hash,
+        args.expiry,
+    );
+    require!(
+        ctx.accounts.sent_message_record.message_hash == message_hash,
+        EndpointError::SentMessageMismatch
+    );
+
+    // The chosen adapters must all be enabled for sending to the destination chain
+    let send_adapter_bitmap = ctx.accounts.integrator_chain_config.send_adapter_bitmap;
+    require!(
+        args.adapters != 0 && args.adapters & !send_adapter_bitmap.as_value() == 0,
+        EndpointError::AdapterNotEnabled
+    );
+
+    let consistency_level = ctx.accounts.sent_message_record.consistency_level;
+    ctx.accounts.outbox_message.set_inner(OutboxMessage {
+        src_addr: args.integrator_program_id.to_bytes(),
+        sequence: args.sequence,
+        dst_chain: args.dst_chain,
+        dst_addr: args.dst_addr,
+        payload_hash: args.payload_hash,
+        consistency_level,
+        expiry: args.expiry,
+        initial_adapters: Bitmap::from_value(args.adapters),
+        outstanding_adapters: Bitmap::from_value(args.adapters),
+        refund_recipient: ctx.accounts.payer.key(),
+    });
+    ctx.accounts.sequence_tracker.record_outbox_created();
+
+    let queued_adapters = ctx
+        .accounts
+        .sequence_tracker
+        .queued(Bitmap::from_value(args.adapters));
+    AdapterQueue::push_all(
+        ctx.remaining_accounts,
+        &args.integrator_program_id,
+        &[(queued_adapters, ctx.accounts.outbox_message.key())],
+    )?;
+
+    emit_cpi!(MessageRepickupRequested {
+        src_addr: args.integrator_program_id.to_bytes(),
+        sequence: args.sequence,
+        dst_chain: args.dst_chain,
+        dst_addr: args.dst_addr,
+        payload_hash: args.payload_hash,
+        adapters: args.adapters,
+        consistency_level,
+        expiry: args.expiry,
+        outbox_message: ctx.accounts.outbox_message.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use crate::error::EndpointError;
+use crate::event::AdapterRetired;
+use crate::state::{
+    AdapterIndex, AdapterInfo, AuditLogPage, ConfigAction, ConfigUpdate, IntegratorChainConfig,
+    IntegratorConfig,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RetireAdapterArgs {
+    /// The Pubkey of the integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The Pubkey of the adapter to retire
+    pub adapter_program_id: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: RetireAdapterArgs)]
+pub struct RetireAdapter<'info> {
+    /// The account that pays for a new audit log page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The admin account that has the authority to retire adapters
+    pub admin: Signer<'info>,
+
+    /// The integrator config account
+    /// The account constraints here make sure that the one signing this transaction is the admin
+    /// of the config
+    #[account(
+        mut,
+        seeds = [IntegratorConfig::SEED_PREFIX, args.integrator_program_id.as_ref()],
+        bump = integrator_config.bump,
+    )]
+    pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The registered adapter account, which is marked as retired
+    #[account(
+        mut,
+        seeds = [
+            AdapterInfo::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.adapter_program_id.as_ref(),
+        ],
+        bump = adapter_info.bump,
+        constraint = !adapter_info.retired @ EndpointError::AdapterRetired,
+    )]
+    pub adapter_info: Account<'info, AdapterInfo>,
+
+    /// The reverse index entry from the adapter to the integrator
+    /// This lists every chain the adapter is enabled on, and is cleared
+    #[account(
+        mut,
+        seeds = [
+            AdapterIndex::SEED_PREFIX,
+            args.adapter_program_id.as_ref(),
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = adapter_index.bump,
+    )]
+    pub adapter_index: Account<'info, AdapterIndex>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RetireAdapter<'info> {
+    pub fn validate(&self) -> Result<()> {
+        self.integrator_config.check_admin(&self.admin)
+    }
+}
+
+/// Retires an adapter of an integrator
+///
+/// The adapter is marked as retired, after which it can no longer be enabled for any chain or
+/// attest to messages. This cannot be undone.
+///
+/// `remaining_accounts` must hold the integrator chain config of each chain in
+/// `adapter_index.chains`, writable and in the same order, so that no chain keeps the adapter
+/// enabled. Its send and receive bits are cleared in each of them, advancing the config epoch
+/// if either was set, and the chains are removed from the index.
+///
+/// Outbox messages the adapter has not picked up yet keep waiting for it. It can still pick them
+/// up, which closes them once every other adapter has too, so they do not block `deregister`.
+///
+/// The cleared chain bitmaps and the retirement are folded into the integrator's configuration
+/// digest, and the retirement is appended to its audit log.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the request
+/// * `args` - The arguments for the instruction, containing:
+///   * `integrator_program_id` - The public key of the integrator program
+///   * `adapter_program_id` - The public key of the adapter to retire
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The adapter is already retired (EndpointError::AdapterRetired)
+/// * `remaining_accounts` are not the chain configs of the indexed chains
+///   (EndpointError::InvalidIntegratorChainConfig)
+///
+/// # Events
+///
+/// Emits an `AdapterRetired` event
+#[access_control(RetireAdapter::validate(&ctx.accounts))]
+pub fn retire_adapter<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RetireAdapter<'info>>,
+    args: RetireAdapterArgs,
+) -> Result<()> {
+    let indexed_chains = std::mem::take(&mut ctx.accounts.adapter_index.chains);
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        indexed_chains.len(),
+        EndpointError::InvalidIntegratorChainConfig
+    );
+
+    let adapter_index = ctx.accounts.adapter_info.index;
+    let mut chains = Vec::with_capacity(indexed_chains.len());
+
+    for (entry, integrator_chain_config_account) in
+        indexed_chains.iter().zip(ctx.remaining_accounts)
+    {
+        let mut integrator_chain_config =
+            Account::<IntegratorChainConfig>::try_from(integrator_chain_config_account)?;
+        require!(
+            integrator_chain_config.integrator_program_id == args.integrator_program_id
+                && integrator_chain_config.chain_id == entry.chain_id,
+            EndpointError::InvalidIntegratorChainConfig
+        );
+
+        let was_enabled = integrator_chain_config
+            .send_adapter_bitmap
+            .get(adapter_index)?
+            || integrator_chain_config
+                .recv_adapter_bitmap
+                .get(adapter_index)?;
+        if !was_enabled {
+            continue;
+        }
+
+        // Disable the adapter in both bitmaps and advance the config epoch
+        integrator_chain_config
+            .send_adapter_bitmap
+            .set(adapter_index, false)?;
+        integrator_chain_config
+            .recv_adapter_bitmap
+            .set(adapter_index, false)?;
+        integrator_chain_config.advance_config_epoch();
+        integrator_chain_config.exit(&crate::ID)?;
+        ctx.accounts
+            .integrator_config
+            .update_config_digest(integrator_chain_config.digest_update());
+
+        chains.push(integrator_chain_config.chain_id);
+    }
+
+    ctx.accounts.adapter_info.retired = true;
+    ctx.accounts
+        .integrator_config
+        .update_config_digest(ConfigUpdate::AdapterRetired {
+            index: adapter_index,
+        });
+
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::AdapterRetired {
+            adapter: args.adapter_program_id,
+        },
+        Clock::get()?.slot,
+    );
+
+    emit_cpi!(AdapterRetired {
+        integrator: args.integrator_program_id,
+        adapter: args.adapter_program_id,
+        chains,
+    });
+
+    Ok(())
+}
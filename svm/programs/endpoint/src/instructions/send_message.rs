@@ -1,9 +1,14 @@
+use adapter_interface::{ReceiveOutboundArgs, OUTBOUND_AUTHORITY_SEED};
 use anchor_lang::prelude::*;
 
 use crate::{
     error::EndpointError,
-    event::MessageSent,
-    state::{IntegratorChainConfig, OutboxMessage, SequenceTracker},
+    event::{MessagePickedUp, MessageSent},
+    state::{
+        AdapterInfo, AdapterQueue, AdapterStats, AttestationInfo, IntegratorChainConfig,
+        MessageExpiry, OutboxMessage, SentMessageRecord, SequenceTracker,
+    },
+    CHAIN_ID,
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -13,6 +18,11 @@ pub struct SendMessageArgs {
     pub dst_chain: u16,
     pub dst_addr: [u8; 32],
     pub payload_hash: [u8; 32],
+    pub consistency_level: u8,
+    pub expiry: Option<MessageExpiry>,
+    /// The send adapters to push the message to, as the number of accounts to pass to each
+    /// one's `receive_outbound`. Empty for the pull model
+    pub push_account_counts: Vec<u8>,
 }
 
 #[event_cpi]
@@ -52,23 +62,69 @@ pub struct SendMessage<'info> {
     )]
     pub sequence_tracker: Account<'info, SequenceTracker>,
 
+    /// The outbox message, for the send adapters that are not pushed the message
+    /// This must not be passed when every send adapter is pushed the message
     #[account(
         init,
         payer = payer,
         space = 8 + OutboxMessage::INIT_SPACE,
     )]
-    pub outbox_message: Account<'info, OutboxMessage>,
+    pub outbox_message: Option<Account<'info, OutboxMessage>>,
+
+    /// The endpoint PDA of the integrator that signs `receive_outbound` on pushed adapters
+    /// This is only required when the message is pushed to send adapters
+    /// CHECK: This is a PDA of the endpoint with no data
+    #[account(
+        seeds = [OUTBOUND_AUTHORITY_SEED, args.integrator_program_id.as_ref()],
+        bump,
+    )]
+    pub outbound_authority: Option<UncheckedAccount<'info>>,
+
+    /// The record of the message, for `request_repickup`
+    /// Messages sent without it cannot be handed to adapters again
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SentMessageRecord::INIT_SPACE,
+        seeds = [
+            SentMessageRecord::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            sequence_tracker.sequence.to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub sent_message_record: Option<Account<'info, SentMessageRecord>>,
 
     pub system_program: Program<'info, System>,
 }
 
 /// Creates a new outbox message and initializes it with the provided information.
 ///
+/// Send adapters can instead be pushed the message in the same transaction. For each adapter in
+/// `push_account_counts`, `remaining_accounts` must hold its `AdapterInfo`, its `AdapterStats`
+/// (writable) and its program, followed by the given number of accounts for its
+/// `receive_outbound` instruction. The endpoint calls `receive_outbound` on each of them and
+/// marks them as having picked up the message. The outbox message is only created for the send
+/// adapters that were not pushed the message.
+///
+/// The accounts after those of the pushed adapters are the `AdapterQueue`s (writable) of every
+/// adapter that was not pushed the message and has a queue, in increasing adapter index. The
+/// outbox message is pushed onto each of them.
+///
+/// An `expiry` is only accepted for messages to this chain, as the EVM endpoint does not check
+/// it and does not commit to it in the message hash.
+///
+/// If `sent_message_record` is passed, the message is recorded so that `request_repickup` can
+/// later hand it to other send adapters.
+///
 /// This function performs the following steps:
 /// 1. Checks if there are any enabled send adapters for the destination chain.
-/// 2. Initializes a new `OutboxMessage` account with the provided information.
-/// 3. Increments the sequence number in the `SequenceTracker` account.
-/// 4. Emits a `MessageSent` event with details about the sent message.
+/// 2. Increments the sequence number in the `SequenceTracker` account.
+/// 3. Pushes the message to the given send adapters.
+/// 4. Initializes a new `OutboxMessage` account for the remaining send adapters, if any.
+/// 5. Records the message in the `SentMessageRecord` account, if passed.
+/// 6. Folds the message hash into the integrator's outbound accumulator.
+/// 7. Emits a `MessageSent` event with details about the sent message.
 ///
 /// # Arguments
 ///
@@ -79,47 +135,209 @@ pub struct SendMessage<'info> {
 ///   * `dst_chain`: The destination chain ID.
 ///   * `dst_addr`: The destination address as a [u8; 32].
 ///   * `payload_hash`: The hash of the message payload.
+///   * `consistency_level`: The finality level send adapters should wait for before relaying.
+///   * `expiry`: The deadline after which the message can no longer be received, if any. Only
+///     accepted for messages to this chain.
+///   * `push_account_counts`: The number of accounts for each pushed adapter's `receive_outbound`.
 ///
 /// # Errors
 ///
 /// This function will return an error if:
 /// * There are no enabled send adapters for the destination chain (EndpointError::AdapterNotEnabled).
+/// * An `expiry` is passed for a message to another chain (EndpointError::ExpiryNotSupported).
+/// * The accounts of a pushed adapter are invalid (EndpointError::InvalidPushAccounts).
+/// * A pushed adapter is not enabled for sending to the destination chain
+///   (EndpointError::AdapterNotEnabled), or is pushed twice (EndpointError::MessageAlreadyPickedUp).
+/// * The outbox message is missing while some send adapters were not pushed, or passed while all
+///   were (EndpointError::InvalidOutboxMessage).
+/// * The adapter queues are not those of the adapters that were not pushed the message and have
+///   one, in increasing adapter index (EndpointError::InvalidAdapterQueue).
+/// * A pushed adapter's `receive_outbound` fails.
 ///
 /// # Side Effects
 ///
-/// * Initializes a new `OutboxMessage` account.
-/// * Increments the sequence number in the `SequenceTracker` account.
+/// * Initializes a new `OutboxMessage` account, unless every send adapter was pushed the message.
+/// * Initializes a new `SentMessageRecord` account, if passed.
+/// * Increments the sequence number and updates the outbound accumulator in the
+///   `SequenceTracker` account.
 ///
 /// # Events
 ///
-/// Emits a `MessageSent` event
-pub fn send_message(ctx: Context<SendMessage>, args: SendMessageArgs) -> Result<()> {
+/// Emits a `MessageSent` event, and a `MessagePickedUp` event for each pushed adapter
+pub fn send_message<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SendMessage<'info>>,
+    args: SendMessageArgs,
+) -> Result<()> {
     // Check if there are any enabled send adapters for destination chain
+    let send_adapter_bitmap = ctx.accounts.integrator_chain_config.send_adapter_bitmap;
     require!(
-        !ctx.accounts
-            .integrator_chain_config
-            .send_adapter_bitmap
-            .is_empty(),
+        !send_adapter_bitmap.is_empty(),
         EndpointError::AdapterNotEnabled
     );
 
-    // Create and initialize the outbox message
-    ctx.accounts.outbox_message.set_inner(OutboxMessage {
-        src_addr: args.integrator_program_id.to_bytes(),
-        sequence: ctx.accounts.sequence_tracker.next_sequence(),
-        dst_chain: args.dst_chain,
-        dst_addr: args.dst_addr,
-        payload_hash: args.payload_hash,
-        outstanding_adapters: ctx.accounts.integrator_chain_config.send_adapter_bitmap,
-        refund_recipient: ctx.accounts.payer.key(),
-    });
+    // Only this chain's endpoint checks the expiry
+    require!(
+        args.expiry.is_none() || args.dst_chain == CHAIN_ID,
+        EndpointError::ExpiryNotSupported
+    );
+
+    let sequence = ctx.accounts.sequence_tracker.next_sequence();
+    let mut outstanding_adapters = send_adapter_bitmap;
+    let mut remaining_accounts = ctx.remaining_accounts;
+
+    if !args.push_account_counts.is_empty() {
+        let outbound_authority = ctx
+            .accounts
+            .outbound_authority
+            .as_ref()
+            .ok_or(EndpointError::InvalidPushAccounts)?
+            .to_account_info();
+        let outbound_authority_bump = ctx.bumps.outbound_authority.unwrap();
+        let slot = Clock::get()?.slot;
+
+        let receive_outbound_args = ReceiveOutboundArgs {
+            src_addr: args.integrator_program_id.to_bytes(),
+            sequence,
+            dst_chain: args.dst_chain,
+            dst_addr: args.dst_addr,
+            payload_hash: args.payload_hash,
+            consistency_level: args.consistency_level,
+            expiry: args.expiry,
+        };
+
+        for &account_count in &args.push_account_counts {
+            let account_count = account_count as usize;
+            require!(
+                remaining_accounts.len() >= 3 + account_count,
+                EndpointError::InvalidPushAccounts
+            );
+            let (accounts, rest) = remaining_accounts.split_at(3 + account_count);
+            remaining_accounts = rest;
+            let (adapter_info_account, adapter_stats_account, adapter_program) =
+                (&accounts[0], &accounts[1], &accounts[2]);
+            let adapter_accounts = &accounts[3..];
+
+            require!(
+                adapter_program.executable
+                    && adapter_info_account.key()
+                        == AdapterInfo::pda(&args.integrator_program_id, &adapter_program.key()).0
+                    && adapter_stats_account.key()
+                        == AdapterStats::pda(&args.integrator_program_id, &adapter_program.key()).0,
+                EndpointError::InvalidPushAccounts
+            );
+            let adapter_info = Account::<AdapterInfo>::try_from(adapter_info_account)?;
+            require!(!adapter_info.retired, EndpointError::AdapterRetired);
+
+            // Check if the adapter is enabled for sending and has not been pushed the message yet
+            require!(
+                send_adapter_bitmap.get(adapter_info.index)?,
+                EndpointError::AdapterNotEnabled
+            );
+            require!(
+                outstanding_adapters.get(adapter_info.index)?,
+                EndpointError::MessageAlreadyPickedUp
+            );
+
+            adapter_interface::cpi::receive_outbound(
+                adapter_program,
+                &outbound_authority,
+                adapter_accounts,
+                &receive_outbound_args,
+                &[&[
+                    OUTBOUND_AUTHORITY_SEED,
+                    args.integrator_program_id.as_ref(),
+                    &[outbound_authority_bump],
+                ]],
+            )?;
+
+            // Mark the message as picked up by this adapter
+            outstanding_adapters.set(adapter_info.index, false)?;
+
+            let mut adapter_stats = Account::<AdapterStats>::try_from(adapter_stats_account)?;
+            adapter_stats.record_pickup(slot);
+            adapter_stats.exit(&crate::ID)?;
+
+            emit_cpi!(MessagePickedUp {
+                src_addr: args.integrator_program_id.to_bytes(),
+                sequence,
+                dst_chain: args.dst_chain,
+                dst_addr: args.dst_addr,
+                payload_hash: args.payload_hash,
+                adapter: adapter_program.key(),
+                remaining_adapters: outstanding_adapters.as_value(),
+                consistency_level: args.consistency_level,
+                expiry: args.expiry,
+            });
+        }
+    }
+
+    // Create and initialize the outbox message for the adapters that were not pushed the message
+    match (
+        ctx.accounts.outbox_message.as_mut(),
+        outstanding_adapters.is_empty(),
+    ) {
+        (Some(outbox_message), false) => {
+            outbox_message.set_inner(OutboxMessage {
+                src_addr: args.integrator_program_id.to_bytes(),
+                sequence,
+                dst_chain: args.dst_chain,
+                dst_addr: args.dst_addr,
+                payload_hash: args.payload_hash,
+                consistency_level: args.consistency_level,
+                expiry: args.expiry,
+                initial_adapters: send_adapter_bitmap,
+                outstanding_adapters,
+                refund_recipient: ctx.accounts.payer.key(),
+            });
+            ctx.accounts.sequence_tracker.record_outbox_created();
+
+            // The accounts after the pushed adapters' are queues of the outstanding adapters
+            let queued_adapters = ctx.accounts.sequence_tracker.queued(outstanding_adapters);
+            AdapterQueue::push_all(
+                remaining_accounts,
+                &args.integrator_program_id,
+                &[(queued_adapters, outbox_message.key())],
+            )?;
+        }
+        (None, true) => require!(
+            remaining_accounts.is_empty(),
+            EndpointError::InvalidAdapterQueue
+        ),
+        _ => return err!(EndpointError::InvalidOutboxMessage),
+    }
+
+    let message_hash = AttestationInfo::compute_message_hash(
+        CHAIN_ID,
+        args.integrator_program_id.to_bytes(),
+        sequence,
+        args.dst_chain,
+        args.dst_addr,
+        args.payload_hash,
+        args.expiry,
+    );
+    let outbound_accumulator = ctx.accounts.sequence_tracker.record_sent(message_hash);
+
+    if let Some(sent_message_record) = ctx.accounts.sent_message_record.as_mut() {
+        sent_message_record.set_inner(SentMessageRecord {
+            bump: ctx.bumps.sent_message_record.unwrap(),
+            integrator_program_id: args.integrator_program_id,
+            sequence,
+            message_hash,
+            consistency_level: args.consistency_level,
+            payer: ctx.accounts.payer.key(),
+            cancelled: false,
+        });
+    }
 
     emit_cpi!(MessageSent {
         sender: args.integrator_program_id.to_bytes(),
-        sequence: ctx.accounts.sequence_tracker.sequence,
+        sequence,
         recipient: args.dst_addr,
         recipient_chain: args.dst_chain,
         payload_digest: args.payload_hash,
+        consistency_level: args.consistency_level,
+        expiry: args.expiry,
+        outbound_accumulator,
     });
 
     Ok(())
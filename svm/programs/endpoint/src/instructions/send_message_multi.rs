@@ -0,0 +1,263 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{
+    error::EndpointError,
+    event::MessageSent,
+    state::{
+        AdapterQueue, AttestationInfo, IntegratorChainConfig, MessageExpiry, OutboxMessage,
+        SentMessageRecord, SequenceTracker,
+    },
+    utils::account::{create_pda_account, is_uncreated},
+    CHAIN_ID,
+};
+
+/// A destination of a message sent by `send_message_multi`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct MessageDestination {
+    pub dst_chain: u16,
+    pub dst_addr: [u8; 32],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SendMessageMultiArgs {
+    pub integrator_program_id: Pubkey,
+    pub integrator_program_pda_bump: u8,
+    pub destinations: Vec<MessageDestination>,
+    pub payload_hash: [u8; 32],
+    pub consistency_level: u8,
+    pub expiry: Option<MessageExpiry>,
+    /// Records each message in its `SentMessageRecord`, so that it can be handed to adapters
+    /// again with `request_repickup`
+    pub record: bool,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: SendMessageMultiArgs)]
+pub struct SendMessageMulti<'info> {
+    // Payer pays for the init of the outbox messages
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The PDA of the integrator program.
+    /// This makes sure that only the integrator program is authorized to use this ix
+    #[account(
+        seeds = [b"endpoint_integrator"],
+        bump = args.integrator_program_pda_bump,
+        seeds::program = args.integrator_program_id
+    )]
+    pub integrator_program_pda: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SequenceTracker::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+        ],
+        bump = sequence_tracker.bump,
+    )]
+    pub sequence_tracker: Account<'info, SequenceTracker>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates one outbox message per destination for a payload sent to several chains.
+///
+/// This is the batch form of `send_message`. Each destination gets its own outbox message and
+/// the next sequence, so the messages are picked up, attested to and received independently.
+/// Their hashes are folded into the integrator's outbound accumulator in sequence order.
+///
+/// `remaining_accounts` must hold, for each destination, its `IntegratorChainConfig` followed by
+/// a new outbox message account, which must sign and be writable. If `record` is set, each
+/// destination's accounts are followed by the `SentMessageRecord` (writable) of its sequence,
+/// which is created and paid for by the payer. The accounts after those of the destinations are
+/// the `AdapterQueue`s (writable) of every adapter that has a queue and is enabled for sending to
+/// any of the destinations, in increasing adapter index. Each outbox message is pushed onto the
+/// queues of its send adapters. The number of destinations is bounded by the transaction size.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts involved.
+/// * `args` - The arguments for the instruction, including:
+///   * `integrator_program_id`: The program ID of the integrator.
+///   * `integrator_program_pda_bump`: The bump seed for the integrator program PDA.
+///   * `destinations`: The destination chains and addresses.
+///   * `payload_hash`: The hash of the message payload.
+///   * `consistency_level`: The finality level send adapters should wait for before relaying.
+///   * `expiry`: The deadline after which the messages can no longer be received, if any. Only
+///     accepted if every destination is this chain, as for `send_message`.
+///   * `record`: Whether to record each message in its `SentMessageRecord`.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The accounts in `remaining_accounts` do not match the destinations
+///   (EndpointError::InvalidBatchAccounts).
+/// * There are no enabled send adapters for a destination chain (EndpointError::AdapterNotEnabled).
+/// * An `expiry` is passed and a destination is another chain (EndpointError::ExpiryNotSupported).
+/// * The adapter queues are not those of the send adapters of the destinations that have one, in
+///   increasing adapter index (EndpointError::InvalidAdapterQueue).
+///
+/// # Events
+///
+/// Emits a `MessageSent` event for each destination
+pub fn send_message_multi<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SendMessageMulti<'info>>,
+    args: SendMessageMultiArgs,
+) -> Result<()> {
+    let accounts_per_destination = if args.record { 3 } else { 2 };
+    let destination_accounts_len = args.destinations.len() * accounts_per_destination;
+    require!(
+        !args.destinations.is_empty() && ctx.remaining_accounts.len() >= destination_accounts_len,
+        EndpointError::InvalidBatchAccounts
+    );
+
+    // Only this chain's endpoint checks the expiry
+    require!(
+        args.expiry.is_none()
+            || args
+                .destinations
+                .iter()
+                .all(|destination| destination.dst_chain == CHAIN_ID),
+        EndpointError::ExpiryNotSupported
+    );
+    let (destination_accounts, adapter_queues) =
+        ctx.remaining_accounts.split_at(destination_accounts_len);
+    let mut queued_messages = Vec::with_capacity(args.destinations.len());
+
+    let payer = ctx.accounts.payer.to_account_info();
+    let space = 8 + OutboxMessage::INIT_SPACE;
+    let lamports = Rent::get()?.minimum_balance(space);
+
+    for (destination, accounts) in args
+        .destinations
+        .iter()
+        .zip(destination_accounts.chunks(accounts_per_destination))
+    {
+        let (integrator_chain_config_account, outbox_message_account) =
+            (&accounts[0], &accounts[1]);
+
+        let (integrator_chain_config_key, _) =
+            IntegratorChainConfig::pda(&args.integrator_program_id, destination.dst_chain);
+        require_keys_eq!(
+            integrator_chain_config_account.key(),
+            integrator_chain_config_key,
+            EndpointError::InvalidBatchAccounts
+        );
+        let integrator_chain_config =
+            Account::<IntegratorChainConfig>::try_from(integrator_chain_config_account)?;
+
+        // Check if there are any enabled send adapters for destination chain
+        require!(
+            !integrator_chain_config.send_adapter_bitmap.is_empty(),
+            EndpointError::AdapterNotEnabled
+        );
+
+        // Create and initialize the outbox message
+        require!(
+            outbox_message_account.is_signer && is_uncreated(outbox_message_account),
+            EndpointError::InvalidBatchAccounts
+        );
+        system_program::create_account(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: outbox_message_account.clone(),
+                },
+            ),
+            lamports,
+            space as u64,
+            &crate::ID,
+        )?;
+
+        let outbox_message = OutboxMessage {
+            src_addr: args.integrator_program_id.to_bytes(),
+            sequence: ctx.accounts.sequence_tracker.next_sequence(),
+            dst_chain: destination.dst_chain,
+            dst_addr: destination.dst_addr,
+            payload_hash: args.payload_hash,
+            consistency_level: args.consistency_level,
+            expiry: args.expiry,
+            initial_adapters: integrator_chain_config.send_adapter_bitmap,
+            outstanding_adapters: integrator_chain_config.send_adapter_bitmap,
+            refund_recipient: payer.key(),
+        };
+        outbox_message
+            .try_serialize(&mut &mut outbox_message_account.try_borrow_mut_data()?[..])?;
+        ctx.accounts.sequence_tracker.record_outbox_created();
+        queued_messages.push((
+            ctx.accounts
+                .sequence_tracker
+                .queued(outbox_message.outstanding_adapters),
+            outbox_message_account.key(),
+        ));
+
+        let message_hash = AttestationInfo::compute_message_hash(
+            CHAIN_ID,
+            outbox_message.src_addr,
+            outbox_message.sequence,
+            outbox_message.dst_chain,
+            outbox_message.dst_addr,
+            outbox_message.payload_hash,
+            outbox_message.expiry,
+        );
+        let outbound_accumulator = ctx.accounts.sequence_tracker.record_sent(message_hash);
+
+        // Record the message if asked to
+        if args.record {
+            let sent_message_record_account = &accounts[2];
+            let sequence_bytes = outbox_message.sequence.to_be_bytes();
+            let (sent_message_record_key, bump) =
+                SentMessageRecord::pda(&args.integrator_program_id, outbox_message.sequence);
+            require!(
+                sent_message_record_account.key() == sent_message_record_key
+                    && is_uncreated(sent_message_record_account),
+                EndpointError::InvalidBatchAccounts
+            );
+            create_pda_account(
+                sent_message_record_account,
+                &payer,
+                &ctx.accounts.system_program.to_account_info(),
+                8 + SentMessageRecord::INIT_SPACE,
+                &[
+                    SentMessageRecord::SEED_PREFIX,
+                    args.integrator_program_id.as_ref(),
+                    sequence_bytes.as_ref(),
+                    &[bump],
+                ],
+            )?;
+            SentMessageRecord {
+                bump,
+                integrator_program_id: args.integrator_program_id,
+                sequence: outbox_message.sequence,
+                message_hash,
+                consistency_level: args.consistency_level,
+                payer: payer.key(),
+                cancelled: false,
+            }
+            .try_serialize(&mut &mut sent_message_record_account.try_borrow_mut_data()?[..])?;
+        }
+
+        emit_cpi!(MessageSent {
+            sender: outbox_message.src_addr,
+            sequence: outbox_message.sequence,
+            recipient: outbox_message.dst_addr,
+            recipient_chain: outbox_message.dst_chain,
+            payload_digest: outbox_message.payload_hash,
+            consistency_level: outbox_message.consistency_level,
+            expiry: outbox_message.expiry,
+            outbound_accumulator,
+        });
+    }
+
+    // The accounts after the destinations' are queues of their send adapters
+    AdapterQueue::push_all(
+        adapter_queues,
+        &args.integrator_program_id,
+        &queued_messages,
+    )?;
+
+    Ok(())
+}
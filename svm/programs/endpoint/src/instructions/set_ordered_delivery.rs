@@ -0,0 +1,123 @@
+use crate::event::OrderedDeliveryUpdated;
+use crate::state::{AuditLogPage, ConfigAction, IntegratorChainConfig, IntegratorConfig};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetOrderedDeliveryArgs {
+    /// The Pubkey of the integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// The ID of the source chain
+    pub chain_id: u16,
+
+    /// Whether messages from the chain must be delivered in sequence order
+    pub enabled: bool,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: SetOrderedDeliveryArgs)]
+pub struct SetOrderedDelivery<'info> {
+    /// The account that pays for a new audit log page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The admin account that has the authority to configure the integrator
+    pub admin: Signer<'info>,
+
+    /// The integrator config account
+    /// The account constraints here make sure that the one signing this transaction is the admin
+    /// of the config
+    #[account(
+        mut,
+        seeds = [IntegratorConfig::SEED_PREFIX, args.integrator_program_id.as_ref()],
+        bump = integrator_config.bump,
+    )]
+    pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The integrator chain config account
+    /// This is created by enabling an adapter for the chain. Else, it will throw
+    /// `AccountNotInitialized`
+    #[account(
+        mut,
+        seeds = [
+            IntegratorChainConfig::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.chain_id.to_be_bytes().as_ref(),
+        ],
+        bump = integrator_chain_config.bump,
+    )]
+    pub integrator_chain_config: Account<'info, IntegratorChainConfig>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetOrderedDelivery<'info> {
+    pub fn validate(&self) -> Result<()> {
+        self.integrator_config.check_admin(&self.admin)
+    }
+}
+
+/// Turns ordered delivery on or off for messages from a chain
+///
+/// While ordered delivery is on, `recv_message` and `exec_message` only accept the next
+/// sequence from each source address, as tracked by its `InboundCursor`.
+///
+/// The change is appended to the integrator's audit log.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, containing:
+///   * `integrator_program_id` - The program ID of the integrator
+///   * `chain_id` - The source chain ID
+///   * `enabled` - Whether messages must be delivered in sequence order
+///
+/// # Events
+///
+/// Emits an `OrderedDeliveryUpdated` event
+#[access_control(SetOrderedDelivery::validate(&ctx.accounts))]
+pub fn set_ordered_delivery(
+    ctx: Context<SetOrderedDelivery>,
+    args: SetOrderedDeliveryArgs,
+) -> Result<()> {
+    ctx.accounts.integrator_chain_config.ordered_delivery = args.enabled;
+
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::OrderedDeliverySet {
+            chain_id: args.chain_id,
+            enabled: args.enabled,
+        },
+        Clock::get()?.slot,
+    );
+
+    emit_cpi!(OrderedDeliveryUpdated {
+        integrator: args.integrator_program_id,
+        chain: args.chain_id,
+        enabled: args.enabled,
+    });
+
+    Ok(())
+}
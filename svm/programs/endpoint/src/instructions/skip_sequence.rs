@@ -0,0 +1,156 @@
+use crate::error::EndpointError;
+use crate::event::SequencesSkipped;
+use crate::state::{AuditLogPage, ConfigAction, InboundCursor, IntegratorConfig};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SkipSequenceArgs {
+    /// The Pubkey of the integrator program
+    pub integrator_program_id: Pubkey,
+
+    /// Source chain ID
+    pub src_chain: u16,
+
+    /// Source address (32 bytes)
+    pub src_addr: [u8; 32],
+
+    /// The last sequence to skip
+    pub sequence: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: SkipSequenceArgs)]
+pub struct SkipSequence<'info> {
+    /// The account that pays for the inbound cursor, if it does not exist yet, and for a new
+    /// audit log page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The admin account that has the authority to configure the integrator
+    pub admin: Signer<'info>,
+
+    /// The integrator config account
+    /// The account constraints here make sure that the one signing this transaction is the admin
+    /// of the config
+    #[account(
+        mut,
+        seeds = [IntegratorConfig::SEED_PREFIX, args.integrator_program_id.as_ref()],
+        bump = integrator_config.bump,
+    )]
+    pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The inbound cursor of the source address
+    /// This account is initialized if it doesn't exist
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + InboundCursor::INIT_SPACE,
+        seeds = [
+            InboundCursor::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            args.src_chain.to_be_bytes().as_ref(),
+            args.src_addr.as_ref(),
+        ],
+        bump,
+    )]
+    pub inbound_cursor: Account<'info, InboundCursor>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SkipSequence<'info> {
+    pub fn validate(&self, args: &SkipSequenceArgs) -> Result<()> {
+        self.integrator_config.check_admin(&self.admin)?;
+
+        // Ensure src_chain is not zero
+        require!(args.src_chain != 0, EndpointError::InvalidChainId);
+
+        Ok(())
+    }
+}
+
+/// Skips every undelivered sequence from a source address up to and including `sequence`
+///
+/// With ordered delivery, only the next sequence can be delivered, so this is the only way past
+/// a gap: sequences the sender used for other destination chains, messages that will never
+/// arrive, or the sequences before the current one when ordered delivery is turned on after
+/// messages were already delivered.
+///
+/// The skip is appended to the integrator's audit log.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction, containing the accounts
+/// * `args` - The arguments for the instruction, containing:
+///   * `integrator_program_id` - The program ID of the integrator
+///   * `src_chain` - The source chain ID
+///   * `src_addr` - The source address
+///   * `sequence` - The last sequence to skip
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * `src_chain` is 0 (EndpointError::InvalidChainId)
+/// * `sequence` has already been delivered or skipped (EndpointError::SequenceAlreadyDelivered)
+/// * `sequence` is the last possible sequence (EndpointError::SequenceOverflow)
+///
+/// # Events
+///
+/// Emits a `SequencesSkipped` event
+#[access_control(SkipSequence::validate(&ctx.accounts, &args))]
+pub fn skip_sequence(ctx: Context<SkipSequence>, args: SkipSequenceArgs) -> Result<()> {
+    let inbound_cursor = &mut ctx.accounts.inbound_cursor;
+
+    // If the inbound_cursor is newly created, initialize it
+    if inbound_cursor.src_chain == 0 {
+        inbound_cursor.set_inner(InboundCursor::new(
+            ctx.bumps.inbound_cursor,
+            args.integrator_program_id,
+            args.src_chain,
+            args.src_addr,
+        ));
+    }
+
+    inbound_cursor.skip_through(args.sequence)?;
+
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::SequencesSkipped {
+            src_chain: args.src_chain,
+            src_addr: args.src_addr,
+            sequence: args.sequence,
+        },
+        Clock::get()?.slot,
+    );
+
+    emit_cpi!(SequencesSkipped {
+        integrator: args.integrator_program_id,
+        src_chain: args.src_chain,
+        src_addr: args.src_addr,
+        next_sequence: inbound_cursor.next_sequence,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use crate::error::EndpointError;
 use crate::event::{AdminUpdateRequested, AdminUpdated};
-use crate::state::IntegratorConfig;
+use crate::state::{AuditLogPage, ConfigAction, IntegratorConfig};
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -16,6 +16,10 @@ pub struct TransferAdminArgs {
 #[derive(Accounts)]
 #[instruction(args: TransferAdminArgs)]
 pub struct TransferAdmin<'info> {
+    /// The account that pays for a new audit log page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     /// The current admin of the IntegratorConfig account
     pub admin: Signer<'info>,
 
@@ -30,6 +34,23 @@ pub struct TransferAdmin<'info> {
         bump = integrator_config.bump,
     )]
     pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> TransferAdmin<'info> {
@@ -41,6 +62,10 @@ impl<'info> TransferAdmin<'info> {
 #[event_cpi]
 #[derive(Accounts)]
 pub struct ClaimAdmin<'info> {
+    /// The account that pays for a new audit log page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     /// The signer, which must be the pending_admin
     pub new_admin: Signer<'info>,
 
@@ -50,10 +75,27 @@ pub struct ClaimAdmin<'info> {
         mut,
         constraint = integrator_config.admin.is_some() @ EndpointError::CallerNotAuthorized,
         constraint = integrator_config.pending_admin.is_some() @ EndpointError::NoAdminTransferInProgress,
-        constraint = integrator_config.pending_admin == Some(new_admin.key())
-        || integrator_config.admin == Some(new_admin.key()) @ EndpointError::CallerNotAuthorized,
+        constraint = integrator_config.acts_for(integrator_config.pending_admin, new_admin.key)
+        || integrator_config.acts_for(integrator_config.admin, new_admin.key) @ EndpointError::CallerNotAuthorized,
     )]
     pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            integrator_config.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 /// Initiates the transfer of admin rights for an IntegratorConfig account.
@@ -61,6 +103,7 @@ pub struct ClaimAdmin<'info> {
 /// This function performs the following steps:
 /// 1. Validates that the current admin is initiating the transfer.
 /// 2. Sets a pending admin for the IntegratorConfig account.
+/// 3. Appends an entry to the integrator's audit log.
 ///
 /// # Arguments
 ///
@@ -84,6 +127,21 @@ pub struct ClaimAdmin<'info> {
 #[access_control(TransferAdmin::validate(&ctx.accounts))]
 pub fn transfer_admin(ctx: Context<TransferAdmin>, args: TransferAdminArgs) -> Result<()> {
     ctx.accounts.integrator_config.pending_admin = Some(args.new_admin);
+    ctx.accounts.integrator_config.update_admin_digest();
+
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::AdminTransferRequested {
+            new_admin: args.new_admin,
+        },
+        Clock::get()?.slot,
+    );
 
     emit_cpi!(AdminUpdateRequested {
         integrator: args.integrator_program_id,
@@ -100,6 +158,7 @@ pub fn transfer_admin(ctx: Context<TransferAdmin>, args: TransferAdminArgs) -> R
 /// 1. Validates that the signer is either the pending admin or the current admin.
 /// 2. Sets the new admin as the current admin.
 /// 3. Clears the pending admin field.
+/// 4. Appends an entry to the integrator's audit log.
 ///
 /// # Arguments
 ///
@@ -119,13 +178,34 @@ pub fn transfer_admin(ctx: Context<TransferAdmin>, args: TransferAdminArgs) -> R
 pub fn claim_admin(ctx: Context<ClaimAdmin>) -> Result<()> {
     // The constraints in ClaimAdmin struct ensure that pending_admin is Some and matches the signer
     // or the admin matches the signer
-    ctx.accounts.integrator_config.admin = Some(ctx.accounts.new_admin.key());
+    let previous_admin = ctx.accounts.integrator_config.admin.unwrap();
+
+    // The signer may be the admin authority of the key it acts for
+    let integrator_config = &ctx.accounts.integrator_config;
+    let new_admin = if integrator_config
+        .acts_for(integrator_config.pending_admin, ctx.accounts.new_admin.key)
+    {
+        integrator_config.pending_admin.unwrap()
+    } else {
+        previous_admin
+    };
+
+    ctx.accounts.integrator_config.admin = Some(new_admin);
     ctx.accounts.integrator_config.pending_admin = None;
+    ctx.accounts.integrator_config.update_admin_digest();
+
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        new_admin,
+        ConfigAction::AdminClaimed { previous_admin },
+        Clock::get()?.slot,
+    );
 
     emit_cpi!(AdminUpdated {
         integrator: ctx.accounts.integrator_config.integrator_program_id,
         old_admin: ctx.accounts.integrator_config.admin.unwrap(),
-        new_admin: ctx.accounts.new_admin.key(),
+        new_admin,
     });
 
     Ok(())
@@ -1,5 +1,5 @@
 use crate::event::AdminUpdated;
-use crate::state::IntegratorConfig;
+use crate::state::{AuditLogPage, ConfigAction, IntegratorConfig};
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -15,6 +15,10 @@ pub struct UpdateAdminArgs {
 #[derive(Accounts)]
 #[instruction(args: UpdateAdminArgs)]
 pub struct UpdateAdmin<'info> {
+    /// The account that pays for a new audit log page
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     /// The current admin of the IntegratorConfig account
     pub admin: Signer<'info>,
 
@@ -29,6 +33,23 @@ pub struct UpdateAdmin<'info> {
         bump = integrator_config.bump,
     )]
     pub integrator_config: Account<'info, IntegratorConfig>,
+
+    /// The current page of the integrator's audit log, which the change is appended to
+    /// This is created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuditLogPage::INIT_SPACE,
+        seeds = [
+            AuditLogPage::SEED_PREFIX,
+            args.integrator_program_id.as_ref(),
+            integrator_config.audit_log_page().to_be_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub audit_log_page: Box<Account<'info, AuditLogPage>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> UpdateAdmin<'info> {
@@ -43,7 +64,8 @@ impl<'info> UpdateAdmin<'info> {
 /// 1. Validates that the current admin is initiating the update.
 /// 2. Checks if there's a pending admin transfer.
 /// 3. Updates the admin field in the IntegratorConfig account.
-/// 4. Emits an AdminUpdated event.
+/// 4. Appends an entry to the integrator's audit log.
+/// 5. Emits an AdminUpdated event.
 ///
 /// # Arguments
 ///
@@ -67,7 +89,24 @@ impl<'info> UpdateAdmin<'info> {
 /// Emits an `AdminUpdated` event
 #[access_control(UpdateAdmin::validate(&ctx.accounts))]
 pub fn update_admin(ctx: Context<UpdateAdmin>, args: UpdateAdminArgs) -> Result<()> {
+    // Resolved before the admin changes, as it may be the admin's admin authority
+    let actor = ctx
+        .accounts
+        .integrator_config
+        .admin_actor(ctx.accounts.admin.key);
+
     ctx.accounts.integrator_config.admin = Some(args.new_admin);
+    ctx.accounts.integrator_config.update_admin_digest();
+
+    ctx.accounts.audit_log_page.append(
+        ctx.bumps.audit_log_page,
+        &mut ctx.accounts.integrator_config,
+        actor,
+        ConfigAction::AdminUpdated {
+            new_admin: args.new_admin,
+        },
+        Clock::get()?.slot,
+    );
 
     // Emit the AdminUpdated event
     emit_cpi!(AdminUpdated {
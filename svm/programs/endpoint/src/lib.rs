@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+#[cfg(not(target_os = "solana"))]
+pub mod client;
 pub mod error;
 pub mod event;
 pub mod instructions;
@@ -35,6 +37,133 @@ pub mod endpoint {
         instructions::register::register(ctx, args)
     }
 
+    /// Deregisters an integrator, closing its configuration and leaving a tombstone that stops
+    /// it from being registered again
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `DeregisterArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    pub fn deregister(ctx: Context<Deregister>, args: DeregisterArgs) -> Result<()> {
+        instructions::deregister::deregister(ctx, args)
+    }
+
+    /// Closes an integrator chain config of a deregistered integrator
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `CloseDeregisteredChainArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the deregistered integrator
+    ///     * `chain_id` - The chain ID of the integrator chain config
+    pub fn close_deregistered_chain(
+        ctx: Context<CloseDeregisteredChain>,
+        args: CloseDeregisteredChainArgs,
+    ) -> Result<()> {
+        instructions::close_deregistered_chain::close_deregistered_chain(ctx, args)
+    }
+
+    /// Closes the adapter info and stats of a deregistered integrator, and removes it from the
+    /// adapter's index
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `CloseDeregisteredAdapterArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the deregistered integrator
+    ///     * `adapter_program_id` - The program ID of the adapter
+    pub fn close_deregistered_adapter(
+        ctx: Context<CloseDeregisteredAdapter>,
+        args: CloseDeregisteredAdapterArgs,
+    ) -> Result<()> {
+        instructions::close_deregistered_adapter::close_deregistered_adapter(ctx, args)
+    }
+
+    /// Closes an audit log page of a deregistered integrator
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `CloseDeregisteredAuditLogPageArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the deregistered integrator
+    ///     * `page_index` - The index of the audit log page
+    pub fn close_deregistered_audit_log_page(
+        ctx: Context<CloseDeregisteredAuditLogPage>,
+        args: CloseDeregisteredAuditLogPageArgs,
+    ) -> Result<()> {
+        instructions::close_deregistered_audit_log_page::close_deregistered_audit_log_page(
+            ctx, args,
+        )
+    }
+
+    /// Closes a sent message record of a deregistered integrator
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `CloseDeregisteredSentMessageArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the deregistered integrator
+    ///     * `sequence` - The sequence of the recorded message
+    pub fn close_deregistered_sent_message(
+        ctx: Context<CloseDeregisteredSentMessage>,
+        args: CloseDeregisteredSentMessageArgs,
+    ) -> Result<()> {
+        instructions::close_deregistered_sent_message::close_deregistered_sent_message(ctx, args)
+    }
+
+    /// Closes an inbound cursor of a deregistered integrator
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `CloseDeregisteredInboundCursorArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the deregistered integrator
+    ///     * `src_chain` - The source chain of the cursor
+    ///     * `src_addr` - The source address of the cursor
+    pub fn close_deregistered_inbound_cursor(
+        ctx: Context<CloseDeregisteredInboundCursor>,
+        args: CloseDeregisteredInboundCursorArgs,
+    ) -> Result<()> {
+        instructions::close_deregistered_inbound_cursor::close_deregistered_inbound_cursor(
+            ctx, args,
+        )
+    }
+
+    /// Closes a replay window of a deregistered integrator, and optionally the replay floor of
+    /// its source address
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `CloseDeregisteredReplayWindowArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the deregistered integrator
+    ///     * `src_chain` - The source chain of the window
+    ///     * `src_addr` - The source address of the window
+    ///     * `window_index` - The index of the window
+    pub fn close_deregistered_replay_window(
+        ctx: Context<CloseDeregisteredReplayWindow>,
+        args: CloseDeregisteredReplayWindowArgs,
+    ) -> Result<()> {
+        instructions::close_deregistered_replay_window::close_deregistered_replay_window(ctx, args)
+    }
+
+    /// Closes an attestation tree of a deregistered integrator
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `CloseDeregisteredAttestationTreeArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the deregistered integrator
+    pub fn close_deregistered_attestation_tree(
+        ctx: Context<CloseDeregisteredAttestationTree>,
+        args: CloseDeregisteredAttestationTreeArgs,
+    ) -> Result<()> {
+        instructions::close_deregistered_attestation_tree::close_deregistered_attestation_tree(
+            ctx, args,
+        )
+    }
+
     /// Registers a new adapter for an integrator
     ///
     /// # Arguments
@@ -99,6 +228,68 @@ pub mod endpoint {
         instructions::disable_adapter::disable_send_adapter(ctx, args)
     }
 
+    /// Retires an adapter, disabling it on the chains whose configs are passed in
+    /// `remaining_accounts` and stopping it from being used again
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `RetireAdapterArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `adapter_program_id` - The address of the adapter to retire
+    pub fn retire_adapter<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RetireAdapter<'info>>,
+        args: RetireAdapterArgs,
+    ) -> Result<()> {
+        instructions::retire_adapter::retire_adapter(ctx, args)
+    }
+
+    /// Executes an admin instruction authorised by an ed25519 signature of the admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `ExecuteSignedAdminArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `nonce` - The current admin nonce of the integrator
+    ///     * `expiry` - The unix timestamp after which the authorisation expires
+    ///     * `instruction_data` - The data of the admin instruction
+    pub fn execute_signed_admin<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteSignedAdmin<'info>>,
+        args: ExecuteSignedAdminArgs,
+    ) -> Result<()> {
+        instructions::execute_signed_admin::execute_signed_admin(ctx, args)
+    }
+
+    /// Returns the chains on which an adapter is send- or recv-enabled for an integrator
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `GetAdapterChainsArgs` struct containing:
+    ///     * `adapter_program_id` - The Pubkey of the adapter
+    ///     * `integrator_program_id` - The Pubkey of the integrator program
+    pub fn get_adapter_chains(
+        ctx: Context<GetAdapterChains>,
+        args: GetAdapterChainsArgs,
+    ) -> Result<Vec<crate::state::AdapterChainEntry>> {
+        instructions::get_adapter_chains::get_adapter_chains(ctx, args)
+    }
+
+    /// Returns the rolling digest of an integrator's configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `GetConfigDigestArgs` struct containing:
+    ///     * `integrator_program_id` - The Pubkey of the integrator program
+    pub fn get_config_digest(
+        ctx: Context<GetConfigDigest>,
+        args: GetConfigDigestArgs,
+    ) -> Result<[u8; 32]> {
+        instructions::get_config_digest::get_config_digest(ctx, args)
+    }
+
     /// Updates the admin of an IntegratorConfig account
     ///
     /// # Arguments
@@ -143,6 +334,68 @@ pub mod endpoint {
         instructions::discard_admin::discard_admin(ctx)
     }
 
+    /// Turns ordered delivery on or off for messages from a chain
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `SetOrderedDeliveryArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `chain_id` - The source chain ID
+    ///     * `enabled` - Whether messages must be delivered in sequence order
+    pub fn set_ordered_delivery(
+        ctx: Context<SetOrderedDelivery>,
+        args: SetOrderedDeliveryArgs,
+    ) -> Result<()> {
+        instructions::set_ordered_delivery::set_ordered_delivery(ctx, args)
+    }
+
+    /// Skips undelivered sequences from a source address when ordered delivery is enabled
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `SkipSequenceArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `src_chain` - The source chain ID
+    ///     * `src_addr` - The source address
+    ///     * `sequence` - The last sequence to skip
+    pub fn skip_sequence(ctx: Context<SkipSequence>, args: SkipSequenceArgs) -> Result<()> {
+        instructions::skip_sequence::skip_sequence(ctx, args)
+    }
+
+    /// Enables replay windows for messages from a chain
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `EnableReplayWindowsArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `chain_id` - The source chain ID
+    pub fn enable_replay_windows(
+        ctx: Context<EnableReplayWindows>,
+        args: EnableReplayWindowsArgs,
+    ) -> Result<()> {
+        instructions::enable_replay_windows::enable_replay_windows(ctx, args)
+    }
+
+    /// Closes a replay window of a source address, rejecting its sequences from then on
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `CloseReplayWindowArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `src_chain` - The source chain ID
+    ///     * `src_addr` - The source address
+    ///     * `window_index` - The index of the window to close
+    pub fn close_replay_window(
+        ctx: Context<CloseReplayWindow>,
+        args: CloseReplayWindowArgs,
+    ) -> Result<()> {
+        instructions::close_replay_window::close_replay_window(ctx, args)
+    }
+
     /// Sends a message through the endpoint
     ///
     /// # Arguments
@@ -154,10 +407,90 @@ pub mod endpoint {
     ///     * `dst_chain` - The destination chain ID
     ///     * `dst_addr` - The destination address
     ///     * `payload_hash` - The hash of the message payload
-    pub fn send_message(ctx: Context<SendMessage>, args: SendMessageArgs) -> Result<()> {
+    ///     * `consistency_level` - The finality level send adapters should wait for
+    ///     * `expiry` - The deadline after which the message can no longer be received, if any
+    ///     * `push_account_counts` - The number of accounts for each send adapter pushed the message
+    pub fn send_message<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SendMessage<'info>>,
+        args: SendMessageArgs,
+    ) -> Result<()> {
         instructions::send_message::send_message(ctx, args)
     }
 
+    /// Sends a message with one payload to several destinations through the endpoint
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction, with each destination's chain config, new
+    ///   outbox message and, if recorded, sent message record as remaining accounts, followed by
+    ///   the queues of the send adapters
+    /// * `args` - The `SendMessageMultiArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `integrator_program_pda_bump` - The bump for the integrator_program_pda derivation
+    ///     * `destinations` - The destination chain IDs and addresses
+    ///     * `payload_hash` - The hash of the message payload
+    ///     * `consistency_level` - The finality level send adapters should wait for
+    ///     * `expiry` - The deadline after which the messages can no longer be received, if any
+    ///     * `record` - Whether to record each message in its `SentMessageRecord`
+    pub fn send_message_multi<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SendMessageMulti<'info>>,
+        args: SendMessageMultiArgs,
+    ) -> Result<()> {
+        instructions::send_message_multi::send_message_multi(ctx, args)
+    }
+
+    /// Hands a message that was already sent to send adapters again
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `RequestRepickupArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `integrator_program_pda_bump` - The bump for the integrator_program_pda derivation
+    ///     * `sequence` - The sequence the message was sent under
+    ///     * `dst_chain` - The destination chain ID
+    ///     * `dst_addr` - The destination address
+    ///     * `payload_hash` - The hash of the message payload
+    ///     * `expiry` - The deadline of the message, if any
+    ///     * `adapters` - The bitmap of the send adapters to hand the message to
+    pub fn request_repickup<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RequestRepickup<'info>>,
+        args: RequestRepickupArgs,
+    ) -> Result<()> {
+        instructions::request_repickup::request_repickup(ctx, args)
+    }
+
+    /// Cancels an outbox message before any adapter has picked it up
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `CancelMessageArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `integrator_program_pda_bump` - The bump of the integrator program PDA
+    pub fn cancel_message<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelMessage<'info>>,
+        args: CancelMessageArgs,
+    ) -> Result<()> {
+        instructions::cancel_message::cancel_message(ctx, args)
+    }
+
+    /// Closes the record of a sent message, refunding the account that paid for it
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `CloseSentMessageRecordArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `integrator_program_pda_bump` - The bump of the integrator program PDA
+    ///     * `sequence` - The sequence of the recorded message
+    pub fn close_sent_message_record(
+        ctx: Context<CloseSentMessageRecord>,
+        args: CloseSentMessageRecordArgs,
+    ) -> Result<()> {
+        instructions::close_sent_message_record::close_sent_message_record(ctx, args)
+    }
+
     /// Picks up a message from the outbox
     ///
     /// # Arguments
@@ -169,10 +502,30 @@ pub mod endpoint {
     /// * `args` - The `PickUpMessageArgs` struct containing:
     ///     * `adapter_program_id` - The program ID of the adapter
     ///     * `adapter_pda_bump` - The bump for the adapter PDA
+    ///     * `supported_consistency_levels` - The consistency levels the adapter can honour
     pub fn pick_up_message(ctx: Context<PickUpMessage>, args: PickUpMessageArgs) -> Result<()> {
         instructions::pick_up_message::pick_up_message(ctx, args)
     }
 
+    /// Picks up several messages of one integrator from the outbox
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction, with each outbox message and its refund
+    ///   recipient as remaining accounts
+    /// * `args` - The `PickUpMessagesArgs` struct containing:
+    ///     * `adapter_program_id` - The program ID of the adapter
+    ///     * `adapter_pda_bump` - The bump for the adapter PDA
+    ///     * `integrator_program_id` - The program ID of the integrator that sent the messages
+    ///     * `supported_consistency_levels` - The consistency levels the adapter can honour
+    ///     * `mode` - What to do with messages the adapter has already picked up
+    pub fn pick_up_messages<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PickUpMessages<'info>>,
+        args: PickUpMessagesArgs,
+    ) -> Result<()> {
+        instructions::pick_up_messages::pick_up_messages(ctx, args)
+    }
+
     /// Attests to a message
     ///
     /// # Arguments
@@ -187,10 +540,125 @@ pub mod endpoint {
     ///     * `dst_chain` - The destination chain ID
     ///     * `integrator_program_id` - The program ID of the integrator, aka dst_addr
     ///     * `payload_hash` - The hash of the message payload
+    ///     * `expiry` - The deadline committed into the message by the sender, if any
+    ///     * `metadata` - Opaque metadata about the attestation
     pub fn attest_message(ctx: Context<AttestMessage>, args: AttestMessageArgs) -> Result<()> {
         instructions::attest_message::attest_message(ctx, args)
     }
 
+    /// Attests to several messages from one source chain to one integrator
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction, with each message's attestation info and
+    ///   sequence record as remaining accounts
+    /// * `args` - The `AttestMessagesArgs` struct containing:
+    ///     * `adapter_program_id` - The program ID of the adapter
+    ///     * `adapter_pda_bump` - The bump for the adapter PDA
+    ///     * `src_chain` - The source chain ID
+    ///     * `integrator_program_id` - The program ID of the integrator, aka dst_addr
+    ///     * `messages` - The source address, sequence, payload hash and expiry of each message
+    ///     * `mode` - What to do with messages the adapter has already attested to
+    pub fn attest_messages<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AttestMessages<'info>>,
+        args: AttestMessagesArgs,
+    ) -> Result<()> {
+        instructions::attest_messages::attest_messages(ctx, args)
+    }
+
+    /// Attests to a message that already has an `AttestationInfo`, identified by its hash
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `AttestMessageByHashArgs` struct containing:
+    ///     * `adapter_program_id` - The program ID of the adapter
+    ///     * `adapter_pda_bump` - The bump for the adapter PDA
+    ///     * `message_hash` - The hash of the message
+    ///     * `metadata` - Opaque metadata about the attestation
+    pub fn attest_message_by_hash(
+        ctx: Context<AttestMessageByHash>,
+        args: AttestMessageByHashArgs,
+    ) -> Result<()> {
+        instructions::attest_message_by_hash::attest_message_by_hash(ctx, args)
+    }
+
+    /// Attests to a message in an attestation tree, with the leaf's proof as remaining accounts
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `AttestMessageCompressedArgs` struct containing:
+    ///     * the message fields of `AttestMessageArgs`, without `metadata`
+    ///     * `root` - The root the proof was built against
+    ///     * `leaf_index` - The index of the message's leaf
+    ///     * `previous_leaf` - The message's current leaf, or `None` to append one
+    pub fn attest_message_compressed(
+        ctx: Context<AttestMessageCompressed>,
+        args: AttestMessageCompressedArgs,
+    ) -> Result<()> {
+        instructions::attest_message_compressed::attest_message_compressed(ctx, args)
+    }
+
+    /// Creates the queue of outbox messages an adapter has yet to pick up
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `InitAdapterQueueArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `adapter_program_id` - The program ID of the adapter
+    pub fn init_adapter_queue(
+        ctx: Context<InitAdapterQueue>,
+        args: InitAdapterQueueArgs,
+    ) -> Result<()> {
+        instructions::init_adapter_queue::init_adapter_queue(ctx, args)
+    }
+
+    /// Creates the stats account of an adapter added before adapter stats existed
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `InitAdapterStatsArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `adapter_program_id` - The program ID of the adapter
+    pub fn init_adapter_stats(
+        ctx: Context<InitAdapterStats>,
+        args: InitAdapterStatsArgs,
+    ) -> Result<()> {
+        instructions::init_adapter_stats::init_adapter_stats(ctx, args)
+    }
+
+    /// Initializes an attestation tree for messages from a chain
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `InitAttestationTreeArgs` struct containing:
+    ///     * `integrator_program_id` - The program ID of the integrator
+    ///     * `chain_id` - The source chain ID
+    pub fn init_attestation_tree(
+        ctx: Context<InitAttestationTree>,
+        args: InitAttestationTreeArgs,
+    ) -> Result<()> {
+        instructions::init_attestation_tree::init_attestation_tree(ctx, args)
+    }
+
+    /// Returns the attestation status of a message, including any adapter metadata
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `GetMessageStatusArgs` struct containing:
+    ///     * `message_hash` - The hash of the message
+    pub fn get_message_status(
+        ctx: Context<GetMessageStatus>,
+        args: GetMessageStatusArgs,
+    ) -> Result<MessageStatus> {
+        instructions::get_message_status::get_message_status(ctx, args)
+    }
+
     /// Executes a message
     ///
     /// # Arguments
@@ -204,6 +672,7 @@ pub mod endpoint {
     ///     * `dst_chain` - The destination chain ID
     ///     * `integrator_program_id` - The program ID of the integrator, aka dst_addr
     ///     * `payload_hash` - The hash of the message payload
+    ///     * `expiry` - The deadline committed into the message by the sender, if any
     pub fn exec_message(
         ctx: Context<ExecMessage>,
         args: exec_message::ExecMessageArgs,
@@ -211,6 +680,21 @@ pub mod endpoint {
         exec_message::exec_message(ctx, args)
     }
 
+    /// Executes a message that has been attested to, by its hash
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction
+    /// * `args` - The `ExecMessageByHashArgs` struct containing:
+    ///     * `integrator_program_pda_bump` - The bump for the integrator program PDA
+    ///     * `message_hash` - The hash of the message
+    pub fn exec_message_by_hash(
+        ctx: Context<ExecMessageByHash>,
+        args: ExecMessageByHashArgs,
+    ) -> Result<()> {
+        instructions::exec_message_by_hash::exec_message_by_hash(ctx, args)
+    }
+
     /// Receives a message that has been attested to.
     ///
     /// # Arguments
@@ -224,10 +708,46 @@ pub mod endpoint {
     ///     * `dst_chain` - The destination chain ID
     ///     * `integrator_program_id` - The program ID of the integrator, aka dst_addr
     ///     * `payload_hash` - The hash of the message payload
+    ///     * `expiry` - The deadline committed into the message by the sender, if any
+    ///     * `override_equivocation` - Whether to receive despite conflicting attestations
     pub fn recv_message(
         ctx: Context<RecvMessage>,
         args: recv_message::RecvMessageArgs,
-    ) -> Result<()> {
+    ) -> Result<recv_message::RecvMessageResult> {
         recv_message::recv_message(ctx, args)
     }
+
+    /// Receives a message that has been attested to, by its hash
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction, containing the accounts involved
+    /// * `args` - The `RecvMessageByHashArgs` struct containing:
+    ///     * `integrator_program_pda_bump` - The bump seed for the integrator program PDA
+    ///     * `message_hash` - The hash of the message
+    ///     * `override_equivocation` - Whether to receive despite conflicting attestations
+    pub fn recv_message_by_hash(
+        ctx: Context<RecvMessageByHash>,
+        args: RecvMessageByHashArgs,
+    ) -> Result<RecvMessageResult> {
+        instructions::recv_message_by_hash::recv_message_by_hash(ctx, args)
+    }
+
+    /// Receives a message that has been attested to in an attestation tree, with the leaf's
+    /// proof as remaining accounts
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the instruction, containing the accounts involved
+    /// * `args` - The `RecvMessageCompressedArgs` struct containing:
+    ///     * the message fields of `RecvMessageArgs`, without `override_equivocation`
+    ///     * `root` - The root the proof was built against
+    ///     * `leaf_index` - The index of the message's leaf
+    ///     * `leaf` - The message's current leaf
+    pub fn recv_message_compressed(
+        ctx: Context<RecvMessageCompressed>,
+        args: RecvMessageCompressedArgs,
+    ) -> Result<RecvMessageResult> {
+        instructions::recv_message_compressed::recv_message_compressed(ctx, args)
+    }
 }
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+
+/// The direction an adapter is enabled in for a given integrator and chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdapterDirection {
+    Send,
+    Recv,
+}
+
+/// The enabled directions of an adapter for one integrator on one chain
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AdapterChainEntry {
+    /// The chain the adapter is enabled for
+    pub chain_id: u16,
+
+    /// Whether the adapter is a send adapter for this integrator and chain
+    pub send_enabled: bool,
+
+    /// Whether the adapter is a receive adapter for this integrator and chain
+    pub recv_enabled: bool,
+}
+
+/// Reverse index from an adapter program to an integrator that uses it.
+///
+/// There is one account per adapter program and integrator, created by `add_adapter`.
+/// Adapter operators find the integrators using their adapter by filtering the endpoint's
+/// accounts on `adapter_program_id`, without scanning every `IntegratorConfig`.
+///
+/// The `enable_*`/`disable_*` instructions keep `chains` in sync with the integrator's chain
+/// bitmaps, and `retire_adapter` clears it. `get_adapter_chains` returns it as is.
+///
+/// The account grows as chains are added, so its space is computed from the current
+/// vector length rather than a fixed `INIT_SPACE`. It is not shrunk when chains are removed.
+#[account]
+#[derive(Debug)]
+pub struct AdapterIndex {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// The program ID of the adapter
+    /// This is used as a seed for PDA derivation
+    pub adapter_program_id: Pubkey,
+
+    /// The program ID of the integrator that added the adapter
+    /// This is used as a seed for PDA derivation
+    pub integrator_program_id: Pubkey,
+
+    /// Chains on which the integrator has send- or recv-enabled this adapter.
+    /// Entries are removed once both directions are disabled.
+    pub chains: Vec<AdapterChainEntry>,
+}
+
+impl AdapterIndex {
+    /// Seed prefix for deriving AdapterIndex PDAs
+    pub const SEED_PREFIX: &'static [u8] = b"adapter_index";
+
+    pub fn pda(adapter_program_id: &Pubkey, integrator_program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::SEED_PREFIX,
+                adapter_program_id.as_ref(),
+                integrator_program_id.as_ref(),
+            ],
+            &crate::ID,
+        )
+    }
+
+    /// Space required (excluding the discriminator) for the given number of chains
+    pub fn space_for(num_chains: usize) -> usize {
+        1 + 32 + 32 + 4 + num_chains * AdapterChainEntry::INIT_SPACE
+    }
+
+    /// Space required (excluding the discriminator) for the current chains
+    pub fn space(&self) -> usize {
+        Self::space_for(self.chains.len())
+    }
+
+    /// Updates the enabled direction for a chain, inserting or removing the entry as needed
+    pub fn set_direction(&mut self, chain_id: u16, direction: AdapterDirection, enabled: bool) {
+        let position = self
+            .chains
+            .iter()
+            .position(|entry| entry.chain_id == chain_id);
+
+        let index = match position {
+            Some(index) => index,
+            None if enabled => {
+                self.chains.push(AdapterChainEntry {
+                    chain_id,
+                    send_enabled: false,
+                    recv_enabled: false,
+                });
+                self.chains.len() - 1
+            }
+            // Nothing to disable
+            None => return,
+        };
+
+        let entry = &mut self.chains[index];
+        match direction {
+            AdapterDirection::Send => entry.send_enabled = enabled,
+            AdapterDirection::Recv => entry.recv_enabled = enabled,
+        }
+
+        if !entry.send_enabled && !entry.recv_enabled {
+            self.chains.remove(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_index() -> AdapterIndex {
+        AdapterIndex {
+            bump: 0,
+            adapter_program_id: Pubkey::new_unique(),
+            integrator_program_id: Pubkey::new_unique(),
+            chains: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_set_direction() {
+        let mut index = empty_index();
+
+        index.set_direction(2, AdapterDirection::Send, true);
+        index.set_direction(2, AdapterDirection::Recv, true);
+        assert_eq!(
+            index.chains,
+            vec![AdapterChainEntry {
+                chain_id: 2,
+                send_enabled: true,
+                recv_enabled: true,
+            }]
+        );
+
+        index.set_direction(2, AdapterDirection::Send, false);
+        assert!(!index.chains[0].send_enabled);
+        assert!(index.chains[0].recv_enabled);
+
+        index.set_direction(2, AdapterDirection::Recv, false);
+        assert!(index.chains.is_empty());
+    }
+
+    #[test]
+    fn test_disable_unknown_chain_is_noop() {
+        let mut index = empty_index();
+        index.set_direction(2, AdapterDirection::Recv, false);
+        assert!(index.chains.is_empty());
+    }
+
+    #[test]
+    fn test_space_matches_serialized_len() {
+        let mut index = empty_index();
+        assert_eq!(index.try_to_vec().unwrap().len(), index.space());
+
+        index.set_direction(2, AdapterDirection::Send, true);
+        index.set_direction(3, AdapterDirection::Recv, true);
+        assert_eq!(index.try_to_vec().unwrap().len(), index.space());
+    }
+}
@@ -21,6 +21,14 @@ pub struct AdapterInfo {
     /// Index of the adapter with respect to the adapter_info vector in
     /// IntegratorConfig
     pub index: u8,
+
+    /// Set by `retire_adapter`, after which the adapter can no longer be enabled or attest to
+    /// messages. It can still pick up the messages it was outstanding for. It cannot be unset
+    pub retired: bool,
+
+    /// Set by `init_adapter_queue`, after which the adapter's `AdapterQueue` must be passed
+    /// whenever it picks up a message
+    pub has_queue: bool,
 }
 
 impl AdapterInfo {
@@ -0,0 +1,251 @@
+use anchor_lang::prelude::*;
+
+use crate::error::EndpointError;
+use crate::utils::bitmap::Bitmap;
+
+/// The outbox messages an adapter has yet to pick up for an integrator.
+///
+/// This is a bounded ring buffer of outbox message addresses, oldest first, so that an
+/// adapter's relayer can read a single account to find its pending work instead of indexing
+/// `MessageSent` events. Once it exists, every instruction that creates an outbox message the
+/// adapter is outstanding for must push it, and every instruction that closes one must remove
+/// it.
+///
+/// Once the queue is full, each new message overwrites the oldest entry and `overwritten` is
+/// incremented, so that a stalled adapter does not block sending. A relayer that sees it change
+/// must fall back to events to find the messages that were overwritten.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct AdapterQueue {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// The program ID of the integrator
+    /// This is used as a seed for PDA derivation
+    pub integrator_program_id: Pubkey,
+
+    /// The program ID of the adapter
+    /// This is used as a seed for PDA derivation
+    pub adapter_program_id: Pubkey,
+
+    /// The index of the adapter in the integrator's adapter list
+    pub index: u8,
+
+    /// The position of the oldest entry in `entries`
+    pub head: u8,
+
+    /// The number of entries in the queue
+    pub len: u8,
+
+    /// The number of entries overwritten because the queue was full
+    pub overwritten: u64,
+
+    /// The outbox message addresses, as a ring buffer starting at `head`
+    pub entries: [Pubkey; 32],
+}
+
+impl AdapterQueue {
+    /// Seed prefix for deriving AdapterQueue PDAs
+    pub const SEED_PREFIX: &'static [u8] = b"adapter_queue";
+
+    /// The maximum number of entries in the queue
+    pub const CAPACITY: usize = 32;
+
+    pub fn pda(integrator_program_id: &Pubkey, adapter_program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::SEED_PREFIX,
+                integrator_program_id.as_ref(),
+                adapter_program_id.as_ref(),
+            ],
+            &crate::ID,
+        )
+    }
+
+    fn position(&self, offset: usize) -> usize {
+        (self.head as usize + offset) % Self::CAPACITY
+    }
+
+    /// Returns the outbox messages in the queue, oldest first
+    pub fn pending(&self) -> Vec<Pubkey> {
+        (0..self.len as usize)
+            .map(|offset| self.entries[self.position(offset)])
+            .collect()
+    }
+
+    /// Appends an outbox message, overwriting the oldest entry if the queue is full
+    pub fn push(&mut self, outbox_message: Pubkey) {
+        let position = self.position(self.len as usize);
+        self.entries[position] = outbox_message;
+        if self.len as usize == Self::CAPACITY {
+            self.head = self.position(1) as u8;
+            self.overwritten = self.overwritten.saturating_add(1);
+        } else {
+            self.len += 1;
+        }
+    }
+
+    /// Removes an outbox message, keeping the order of the other entries
+    /// Returns whether it was in the queue
+    pub fn remove(&mut self, outbox_message: &Pubkey) -> bool {
+        let Some(offset) = (0..self.len as usize)
+            .find(|&offset| self.entries[self.position(offset)] == *outbox_message)
+        else {
+            return false;
+        };
+
+        // Shift the newer entries down over it
+        for offset in offset..self.len as usize - 1 {
+            self.entries[self.position(offset)] = self.entries[self.position(offset + 1)];
+        }
+        let last = self.position(self.len as usize - 1);
+        self.entries[last] = Pubkey::default();
+        self.len -= 1;
+        true
+    }
+
+    /// Pushes outbox messages onto the queues of the adapters they are outstanding for
+    ///
+    /// Each message comes with the adapters whose queue it is pushed onto. `queues` must hold
+    /// exactly the writable queues of the integrator for the adapters of any of the messages, in
+    /// increasing adapter index.
+    pub fn push_all<'info>(
+        queues: &'info [AccountInfo<'info>],
+        integrator_program_id: &Pubkey,
+        messages: &[(Bitmap, Pubkey)],
+    ) -> Result<()> {
+        let adapters = messages.iter().fold(0, |adapters, (message_adapters, _)| {
+            adapters | message_adapters.as_value()
+        });
+        Self::for_each(
+            queues,
+            integrator_program_id,
+            Bitmap::from_value(adapters),
+            |queue| {
+                for (message_adapters, outbox_message) in messages {
+                    if message_adapters.get(queue.index)? {
+                        queue.push(*outbox_message);
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// Removes an outbox message from the queues of `adapters`
+    ///
+    /// `queues` must hold exactly the writable queues of the integrator for `adapters`, in
+    /// increasing adapter index.
+    pub fn remove_all<'info>(
+        queues: &'info [AccountInfo<'info>],
+        integrator_program_id: &Pubkey,
+        adapters: Bitmap,
+        outbox_message: &Pubkey,
+    ) -> Result<()> {
+        Self::for_each(queues, integrator_program_id, adapters, |queue| {
+            queue.remove(outbox_message);
+            Ok(())
+        })
+    }
+
+    /// Updates each of `queues`, which must be exactly the writable queues of the integrator for
+    /// `adapters`, in increasing adapter index
+    fn for_each<'info>(
+        queues: &'info [AccountInfo<'info>],
+        integrator_program_id: &Pubkey,
+        adapters: Bitmap,
+        mut update: impl FnMut(&mut AdapterQueue) -> Result<()>,
+    ) -> Result<()> {
+        require!(
+            queues.len() == adapters.len(),
+            EndpointError::InvalidAdapterQueue
+        );
+
+        let mut next_index = 0u16;
+        for queue_account in queues {
+            require!(
+                queue_account.is_writable,
+                EndpointError::InvalidAdapterQueue
+            );
+            let mut queue = Box::new(Account::<AdapterQueue>::try_from(queue_account)?);
+            require!(
+                queue.integrator_program_id == *integrator_program_id
+                    && queue.index as u16 >= next_index
+                    && adapters.get(queue.index)?,
+                EndpointError::InvalidAdapterQueue
+            );
+            next_index = queue.index as u16 + 1;
+
+            update(&mut queue)?;
+            queue.exit(&crate::ID)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue() -> AdapterQueue {
+        AdapterQueue {
+            bump: 0,
+            integrator_program_id: Pubkey::default(),
+            adapter_program_id: Pubkey::default(),
+            index: 0,
+            head: 0,
+            len: 0,
+            overwritten: 0,
+            entries: [Pubkey::default(); 32],
+        }
+    }
+
+    #[test]
+    fn test_push_and_remove() {
+        let mut queue = queue();
+        let messages: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        for message in &messages {
+            queue.push(*message);
+        }
+        assert_eq!(queue.pending(), messages);
+
+        // Messages can be picked up in any order
+        assert!(queue.remove(&messages[1]));
+        assert!(!queue.remove(&messages[1]));
+        assert_eq!(queue.pending(), vec![messages[0], messages[2], messages[3]]);
+
+        assert!(queue.remove(&messages[0]));
+        assert!(queue.remove(&messages[3]));
+        assert_eq!(queue.pending(), vec![messages[2]]);
+        assert_eq!(queue.overwritten, 0);
+    }
+
+    #[test]
+    fn test_push_when_full() {
+        let mut queue = queue();
+        let messages: Vec<Pubkey> = (0..AdapterQueue::CAPACITY + 2)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        for message in &messages {
+            queue.push(*message);
+        }
+
+        // The two oldest messages were overwritten
+        assert_eq!(queue.pending(), messages[2..]);
+        assert_eq!(queue.overwritten, 2);
+
+        // Removal works across the wrap-around
+        let removed = messages[AdapterQueue::CAPACITY - 1];
+        assert!(queue.remove(&removed));
+        let expected: Vec<Pubkey> = messages[2..]
+            .iter()
+            .filter(|message| **message != removed)
+            .copied()
+            .collect();
+        assert_eq!(queue.pending(), expected);
+
+        queue.push(messages[0]);
+        assert_eq!(queue.pending().last(), Some(&messages[0]));
+        assert_eq!(queue.overwritten, 2);
+    }
+}
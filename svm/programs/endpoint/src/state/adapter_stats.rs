@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::error::EndpointError;
+use crate::utils::account::is_uncreated;
+
+/// Tracks the activity of an adapter on behalf of a specific integrator.
+///
+/// This lives next to `AdapterInfo` rather than inside it so that `pick_up_message` and
+/// `attest_message` do not need to write lock `AdapterInfo`, which is read by every
+/// admin instruction for the adapter.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct AdapterStats {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// The program ID of the integrator
+    /// This is used as a seed for PDA derivation
+    pub integrator_program_id: Pubkey,
+
+    /// Public key of the adapter's address
+    /// This is used as a seed for PDA derivation
+    pub adapter_program_id: Pubkey,
+
+    /// Number of outbox messages picked up by this adapter
+    pub messages_picked_up: u64,
+
+    /// Number of messages attested to by this adapter
+    pub messages_attested: u64,
+
+    /// Slot of the most recent pick up, or 0 if the adapter has never picked up a message
+    pub last_pickup_slot: u64,
+
+    /// Slot of the most recent attestation, or 0 if the adapter has never attested
+    pub last_attestation_slot: u64,
+
+    /// Number of outbox messages closed by this adapter's pick up, as the last adapter they were
+    /// waiting on
+    /// `deregister` sums these over the integrator's adapters, so that picking up does not write
+    /// lock the integrator's `SequenceTracker`
+    pub outbox_messages_closed: u64,
+}
+
+impl AdapterStats {
+    /// Seed prefix for deriving AdapterStats PDAs
+    pub const SEED_PREFIX: &'static [u8] = b"adapter_stats";
+
+    pub fn pda(integrator_program_id: &Pubkey, adapter_program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::SEED_PREFIX,
+                integrator_program_id.as_ref(),
+                adapter_program_id.as_ref(),
+            ],
+            &crate::ID,
+        )
+    }
+
+    /// Records a pick up at `slot`
+    pub fn record_pickup(&mut self, slot: u64) {
+        self.messages_picked_up = self.messages_picked_up.saturating_add(1);
+        self.last_pickup_slot = slot;
+    }
+
+    /// Reads the number of outbox messages closed by the adapter from its stats account, which
+    /// counts as 0 if it has not been created
+    pub fn outbox_messages_closed(
+        account: &AccountInfo,
+        integrator_program_id: &Pubkey,
+        adapter_program_id: &Pubkey,
+    ) -> Result<u64> {
+        if is_uncreated(account) {
+            return Ok(0);
+        }
+
+        require_keys_eq!(
+            *account.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let stats = Self::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        require!(
+            stats.integrator_program_id == *integrator_program_id
+                && stats.adapter_program_id == *adapter_program_id,
+            EndpointError::InvalidAdapterStats
+        );
+        Ok(stats.outbox_messages_closed)
+    }
+
+    /// Records that a pick up closed an outbox message
+    pub fn record_outbox_closed(&mut self) {
+        self.outbox_messages_closed = self.outbox_messages_closed.saturating_add(1);
+    }
+
+    /// Records an attestation at `slot`
+    pub fn record_attestation(&mut self, slot: u64) {
+        self.messages_attested = self.messages_attested.saturating_add(1);
+        self.last_attestation_slot = slot;
+    }
+}
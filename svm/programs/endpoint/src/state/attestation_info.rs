@@ -2,8 +2,13 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::keccak;
 
 use crate::error::EndpointError;
-use crate::utils::bitmap::Bitmap;
+use crate::state::{MessageExpiry, SequenceRecord};
+use crate::utils::{account::refund_recipient, bitmap::Bitmap};
 
+/// The attestation status of a message
+///
+/// The message's fields are not stored, as `message_hash` commits to all of them. They are
+/// passed as instruction arguments and emitted in events instead.
 #[account]
 #[derive(InitSpace)]
 pub struct AttestationInfo {
@@ -14,23 +19,8 @@ pub struct AttestationInfo {
     /// Used as a seed for PDA derivation
     pub message_hash: [u8; 32],
 
-    /// Source chain ID
-    pub src_chain: u16,
-
-    /// Source address (32 bytes)
-    pub src_addr: [u8; 32],
-
-    /// Sequence number
-    pub sequence: u64,
-
-    /// Destination chain ID
-    pub dst_chain: u16,
-
-    /// Destination address (32 bytes)
-    pub dst_addr: [u8; 32],
-
-    /// Payload hash (32 bytes)
-    pub payload_hash: [u8; 32],
+    /// The deadline after which `recv_message` fails, if the sender set one
+    pub expiry: Option<MessageExpiry>,
 
     /// Replay protection flag
     pub executed: bool,
@@ -40,57 +30,98 @@ pub struct AttestationInfo {
 
     /// The bitmap of receive-enabled adapters for this source chain that have attested to the message
     pub attested_adapters: Bitmap,
+
+    /// The `config_epoch` of the source chain's `IntegratorChainConfig` at the first attestation
+    pub config_epoch: u64,
+
+    /// The bitmap of receive-enabled adapters for the source chain at the first attestation
+    pub enabled_adapters: Bitmap,
+
+    /// The recipient of the lamports when this account is closed, i.e. the account that paid
+    /// for it
+    pub refund_recipient: Pubkey,
 }
 
 impl AttestationInfo {
     /// Seed prefix for deriving AttestionInfo PDAs
     pub const SEED_PREFIX: &'static [u8] = b"attestation_info";
 
+    /// Version byte prefixed to the message hash preimage of messages with an expiry
+    pub const MESSAGE_HASH_VERSION_EXPIRY: u8 = 1;
+
     pub fn new(
         bump: u8,
-        src_chain: u16,
-        src_addr: [u8; 32],
-        sequence: u64,
-        dst_chain: u16,
-        dst_addr: [u8; 32],
-        payload_hash: [u8; 32],
-    ) -> Result<Self> {
-        // Check that neither chain ID is 0
-        require!(
-            src_chain != 0 && dst_chain != 0,
-            EndpointError::InvalidChainId
-        );
-
-        let mut info = Self {
+        message_hash: [u8; 32],
+        expiry: Option<MessageExpiry>,
+        refund_recipient: Pubkey,
+    ) -> Self {
+        Self {
             bump,
-            src_chain,
-            src_addr,
-            sequence,
-            dst_chain,
-            dst_addr,
-            payload_hash,
-            message_hash: [0; 32],
+            message_hash,
+            expiry,
             executed: false,
             num_attested: 0,
             attested_adapters: Bitmap::new(),
-        };
+            config_epoch: 0,
+            enabled_adapters: Bitmap::new(),
+            refund_recipient,
+        }
+    }
 
-        info.message_hash = info.compute_own_message_hash();
+    /// Whether the account has just been created by `init_if_needed`
+    /// A message hash is a keccak digest, so it is never all zeroes once set
+    pub fn is_uninitialized(&self) -> bool {
+        self.message_hash == [0; 32]
+    }
 
-        Ok(info)
+    /// Records the receive configuration the message is first attested under
+    pub fn snapshot_config(&mut self, config_epoch: u64, enabled_adapters: Bitmap) {
+        self.config_epoch = config_epoch;
+        self.enabled_adapters = enabled_adapters;
     }
 
-    pub fn compute_own_message_hash(&self) -> [u8; 32] {
-        Self::compute_message_hash(
-            self.src_chain,
-            self.src_addr,
-            self.sequence,
-            self.dst_chain,
-            self.dst_addr,
-            self.payload_hash,
-        )
+    /// Returns `true` if the adapter at `adapter_index` has attested to the message
+    pub fn has_attested(&self, adapter_index: u8) -> bool {
+        self.attested_adapters.get(adapter_index).unwrap_or(false)
     }
 
+    /// Records the attestation of the adapter at `adapter_index`, which must not have attested
+    /// to the message yet
+    pub fn attest(&mut self, adapter_index: u8) -> Result<()> {
+        require!(
+            !self.has_attested(adapter_index),
+            EndpointError::DuplicateMessageAttestation
+        );
+        self.attested_adapters.set(adapter_index, true)?;
+        // Saturates at 255
+        self.num_attested = self.num_attested.saturating_add(1);
+        Ok(())
+    }
+
+    /// Closes the attestation info and sequence record of a delivered message, once a replay
+    /// window guards against its replay, refunding both to the account that paid for the
+    /// attestation info
+    pub fn close_delivered<'info>(
+        attestation_info: &Account<'info, Self>,
+        sequence_record: &Account<'info, SequenceRecord>,
+        payer: &Signer<'info>,
+        recipient: Option<&UncheckedAccount<'info>>,
+    ) -> Result<()> {
+        let recipient = refund_recipient(attestation_info.refund_recipient, payer, recipient)?;
+        attestation_info.close(recipient.clone())?;
+        sequence_record.close(recipient)
+    }
+
+    /// Computes the message hash.
+    ///
+    /// Messages without an expiry use the original (version 0) encoding:
+    /// `keccak256(src_chain, src_addr, sequence, dst_chain, dst_addr, payload_hash)`.
+    ///
+    /// Messages with an expiry are prefixed with the version byte `1` and commit to the
+    /// expiry kind and deadline:
+    /// `keccak256(1u8, src_chain, src_addr, sequence, dst_chain, dst_addr, payload_hash, kind, deadline)`
+    ///
+    /// All integers are big-endian, matching `abi.encodePacked` on EVM.
     pub fn compute_message_hash(
         src_chain: u16,
         src_addr: [u8; 32],
@@ -98,14 +129,22 @@ impl AttestationInfo {
         dst_chain: u16,
         dst_addr: [u8; 32],
         payload_hash: [u8; 32],
+        expiry: Option<MessageExpiry>,
     ) -> [u8; 32] {
         let mut bytes = Vec::new();
+        if expiry.is_some() {
+            bytes.push(Self::MESSAGE_HASH_VERSION_EXPIRY);
+        }
         bytes.extend_from_slice(&src_chain.to_be_bytes());
         bytes.extend_from_slice(&src_addr);
         bytes.extend_from_slice(&sequence.to_be_bytes());
         bytes.extend_from_slice(&dst_chain.to_be_bytes());
         bytes.extend_from_slice(&dst_addr);
         bytes.extend_from_slice(&payload_hash);
+        if let Some(expiry) = expiry {
+            bytes.push(expiry.kind());
+            bytes.extend_from_slice(&expiry.deadline().to_be_bytes());
+        }
 
         keccak::hash(&bytes).to_bytes()
     }
@@ -148,6 +187,7 @@ mod tests {
             dst_chain,
             dst_addr,
             payload_hash,
+            None,
         );
 
         // Expected result from ethers.solidityPacked()
@@ -162,4 +202,81 @@ mod tests {
             "Computed hash does not match expected hash"
         );
     }
+
+    #[test]
+    fn test_compute_message_hash_with_expiry() {
+        let src_addr = [
+            0x12, 0x34, 0x56, 0x78, 0x90, 0x12, 0x34, 0x56, 0x78, 0x90, 0x12, 0x34, 0x56, 0x78,
+            0x90, 0x12, 0x34, 0x56, 0x78, 0x90, 0x12, 0x34, 0x56, 0x78, 0x90, 0x12, 0x34, 0x56,
+            0x78, 0x90, 0x12, 0x34,
+        ];
+        let dst_addr = [
+            0x98, 0x76, 0x54, 0x32, 0x10, 0x98, 0x76, 0x54, 0x32, 0x10, 0x98, 0x76, 0x54, 0x32,
+            0x10, 0x98, 0x76, 0x54, 0x32, 0x10, 0x98, 0x76, 0x54, 0x32, 0x10, 0x98, 0x76, 0x54,
+            0x32, 0x10, 0x98, 0x76,
+        ];
+        let payload_hash: [u8; 32] = [
+            0xaa, 0xbb, 0xcc, 0xdd, 0xaa, 0xbb, 0xcc, 0xdd, 0xaa, 0xbb, 0xcc, 0xdd, 0xaa, 0xbb,
+            0xcc, 0xdd, 0xaa, 0xbb, 0xcc, 0xdd, 0xaa, 0xbb, 0xcc, 0xdd, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xaa, 0xbb, 0xcc, 0xdd,
+        ];
+        let expiry = MessageExpiry::Timestamp(1_700_000_000);
+
+        let message_hash = AttestationInfo::compute_message_hash(
+            2,
+            src_addr,
+            42,
+            1,
+            dst_addr,
+            payload_hash,
+            Some(expiry),
+        );
+
+        // Expected result of
+        // keccak256(abi.encodePacked(uint8(1), srcChain, srcAddr, sequence, dstChain, dstAddr, payloadHash, uint8(1), uint64(1700000000)))
+        let expected_hash: [u8; 32] = [
+            0xbf, 0xa2, 0x9e, 0x51, 0x79, 0x83, 0x35, 0x0e, 0x27, 0x34, 0xce, 0x31, 0x85, 0x89,
+            0x5a, 0x69, 0xc6, 0x07, 0x93, 0xc8, 0x7d, 0xb5, 0x8a, 0x93, 0x01, 0x3e, 0x59, 0x46,
+            0xe2, 0x5c, 0x10, 0xdd,
+        ];
+        assert_eq!(message_hash, expected_hash);
+
+        // The expiry kind is committed as well as the deadline
+        assert_ne!(
+            message_hash,
+            AttestationInfo::compute_message_hash(
+                2,
+                src_addr,
+                42,
+                1,
+                dst_addr,
+                payload_hash,
+                Some(MessageExpiry::Slot(1_700_000_000)),
+            )
+        );
+    }
+
+    #[test]
+    fn test_layout() {
+        // bump, message_hash, expiry, executed, num_attested, attested_adapters, config_epoch,
+        // enabled_adapters, refund_recipient
+        assert_eq!(
+            AttestationInfo::INIT_SPACE,
+            1 + 32 + 10 + 1 + 1 + 16 + 8 + 16 + 32
+        );
+        assert!(AttestationInfo::new(0, [0; 32], None, Pubkey::default()).is_uninitialized());
+    }
+
+    #[test]
+    fn test_attest() {
+        let mut info = AttestationInfo::new(255, [1; 32], None, Pubkey::default());
+        info.attest(3).unwrap();
+        info.attest(0).unwrap();
+        assert!(info.has_attested(3));
+        assert!(!info.has_attested(1));
+        assert_eq!(info.num_attested, 2);
+
+        assert!(info.attest(3).is_err());
+        assert_eq!(info.num_attested, 2);
+    }
 }
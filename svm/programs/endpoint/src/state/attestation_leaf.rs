@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+/// Records where a message's `CompressedAttestation` leaf lives.
+///
+/// A Merkle tree cannot show that a message has no leaf yet, so without this account an
+/// adapter could append a second leaf for a message it already attested to and attest again.
+/// `attest_message_compressed` creates it when the message's first leaf is appended, and
+/// afterwards only accepts the leaf recorded here. `recv_message_compressed` closes it once the
+/// message is received, as the source address's `ReplayWindow` then refuses any further leaf.
+///
+/// The account is keyed by the message hash alone, so a message has a single leaf across all
+/// of an integrator's trees.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct AttestationLeaf {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// The attestation tree holding the leaf
+    pub tree: Pubkey,
+
+    /// The index of the leaf in the tree
+    pub leaf_index: u32,
+
+    /// The recipient of the lamports when this account is closed, i.e. the account that paid
+    /// for it
+    pub refund_recipient: Pubkey,
+}
+
+impl AttestationLeaf {
+    /// Seed prefix for deriving AttestationLeaf PDAs
+    pub const SEED_PREFIX: &'static [u8] = b"attestation_leaf";
+
+    pub fn pda(message_hash: &[u8; 32]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, message_hash], &crate::ID)
+    }
+
+    /// Whether the account was just created, before the message's leaf was recorded
+    pub fn is_uninitialized(&self) -> bool {
+        self.tree == Pubkey::default()
+    }
+}
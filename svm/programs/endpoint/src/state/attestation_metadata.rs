@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use crate::error::EndpointError;
+
+/// Opaque metadata attached by one adapter to its attestation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AdapterMetadata {
+    /// Index of the attesting adapter with respect to the adapter_info vector in
+    /// IntegratorConfig
+    pub adapter_index: u8,
+
+    /// The metadata supplied by the adapter, at most `AttestationMetadata::MAX_LEN` bytes
+    pub metadata: Vec<u8>,
+}
+
+/// Companion account to `AttestationInfo` holding the metadata adapters attached to their
+/// attestations, such as the source transaction hash, finality level or proof type.
+///
+/// It is only created once an adapter attests with non-empty metadata, so messages whose
+/// adapters attach nothing do not pay for it. The account grows with each entry.
+#[account]
+#[derive(Debug)]
+pub struct AttestationMetadata {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Message hash (32 bytes)
+    /// Used as a seed for PDA derivation
+    pub message_hash: [u8; 32],
+
+    /// The metadata of each adapter that attached any, in attestation order
+    pub entries: Vec<AdapterMetadata>,
+}
+
+impl AttestationMetadata {
+    /// Seed prefix for deriving AttestationMetadata PDAs
+    pub const SEED_PREFIX: &'static [u8] = b"attestation_metadata";
+
+    /// Maximum length of the metadata an adapter can attach to an attestation
+    pub const MAX_LEN: usize = 64;
+
+    pub fn pda(message_hash: [u8; 32]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, &message_hash], &crate::ID)
+    }
+
+    /// Space required (excluding the discriminator) for an account without entries
+    pub fn empty_space() -> usize {
+        1 + 32 + 4
+    }
+
+    /// Space required (excluding the discriminator) for the current entries
+    pub fn space(&self) -> usize {
+        Self::empty_space()
+            + self
+                .entries
+                .iter()
+                .map(|entry| 1 + 4 + entry.metadata.len())
+                .sum::<usize>()
+    }
+
+    /// Appends the metadata attached by `adapter_index`
+    /// `attest_message` only allows an adapter to attest once, so there is at most one entry
+    /// per adapter.
+    pub fn push(&mut self, adapter_index: u8, metadata: Vec<u8>) -> Result<()> {
+        require!(
+            metadata.len() <= Self::MAX_LEN,
+            EndpointError::MetadataTooLong
+        );
+        self.entries.push(AdapterMetadata {
+            adapter_index,
+            metadata,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_space_matches_serialized_len() {
+        let mut attestation_metadata = AttestationMetadata {
+            bump: 255,
+            message_hash: [1; 32],
+            entries: vec![],
+        };
+        assert_eq!(
+            attestation_metadata.try_to_vec().unwrap().len(),
+            attestation_metadata.space()
+        );
+
+        attestation_metadata.push(0, vec![2; 10]).unwrap();
+        attestation_metadata
+            .push(3, vec![4; AttestationMetadata::MAX_LEN])
+            .unwrap();
+        assert_eq!(
+            attestation_metadata.try_to_vec().unwrap().len(),
+            attestation_metadata.space()
+        );
+    }
+
+    #[test]
+    fn test_push_rejects_long_metadata() {
+        let mut attestation_metadata = AttestationMetadata {
+            bump: 255,
+            message_hash: [1; 32],
+            entries: vec![],
+        };
+        assert!(attestation_metadata
+            .push(0, vec![0; AttestationMetadata::MAX_LEN + 1])
+            .is_err());
+        assert!(attestation_metadata.entries.is_empty());
+    }
+}
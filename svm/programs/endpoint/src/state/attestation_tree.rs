@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use spl_concurrent_merkle_tree::{
+    concurrent_merkle_tree::ConcurrentMerkleTree, error::ConcurrentMerkleTreeError,
+};
+
+use crate::error::EndpointError;
+use crate::utils::bitmap::Bitmap;
+
+/// The concurrent Merkle tree stored in an `AttestationTree` account
+pub type AttestationMerkleTree =
+    ConcurrentMerkleTree<{ AttestationTree::MAX_DEPTH }, { AttestationTree::MAX_BUFFER_SIZE }>;
+
+/// An account-compression concurrent Merkle tree holding `CompressedAttestation` leaves for
+/// messages from one source chain to an integrator.
+///
+/// This lets adapters attest without creating an `AttestationInfo` account per message. The
+/// account is allocated by the client, as it is too large to be created by the program, and
+/// the tree itself follows this header at `TREE_OFFSET`.
+#[account]
+#[derive(InitSpace)]
+pub struct AttestationTree {
+    /// The program ID of the destination integrator
+    pub integrator_program_id: Pubkey,
+
+    /// Source chain ID
+    pub chain_id: u16,
+}
+
+impl AttestationTree {
+    /// Depth of the tree, allowing 2^14 leaves
+    pub const MAX_DEPTH: usize = 14;
+
+    /// Number of recent roots that proofs may still be made against
+    pub const MAX_BUFFER_SIZE: usize = 64;
+
+    /// Offset of the tree in the account data, keeping it 8-byte aligned after the header
+    pub const TREE_OFFSET: usize = 48;
+
+    /// Size of the account data
+    pub const LEN: usize = Self::TREE_OFFSET + std::mem::size_of::<AttestationMerkleTree>();
+
+    /// Returns the tree stored in the account data
+    pub fn load_tree_mut(data: &mut [u8]) -> Result<&mut AttestationMerkleTree> {
+        require_eq!(data.len(), Self::LEN, EndpointError::InvalidAttestationTree);
+        bytemuck::try_from_bytes_mut(&mut data[Self::TREE_OFFSET..])
+            .map_err(|_| EndpointError::InvalidAttestationTree.into())
+    }
+}
+
+// The header must fit before the tree
+#[allow(clippy::assertions_on_constants)]
+const _: () = assert!(8 + AttestationTree::INIT_SPACE <= AttestationTree::TREE_OFFSET);
+
+/// Converts an error from the concurrent Merkle tree into an `EndpointError`
+pub fn tree_error(error: ConcurrentMerkleTreeError) -> Error {
+    msg!("Attestation tree error: {}", error);
+    match error {
+        ConcurrentMerkleTreeError::TreeFull => EndpointError::AttestationTreeFull.into(),
+        _ => EndpointError::InvalidAttestationProof.into(),
+    }
+}
+
+/// The attestation status of a message, stored as a leaf of an `AttestationTree`.
+///
+/// Only the leaf's hash is kept on chain. The leaf itself is emitted in `AttestationLeafUpdated`
+/// events, from which clients rebuild the tree to prove it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressedAttestation {
+    /// Message hash (32 bytes)
+    pub message_hash: [u8; 32],
+
+    /// Replay protection flag
+    pub executed: bool,
+
+    /// The bitmap of receive-enabled adapters for this source chain that have attested to the message
+    pub attested_adapters: Bitmap,
+
+    /// The `config_epoch` of the source chain's `IntegratorChainConfig` at the first attestation
+    pub config_epoch: u64,
+
+    /// The bitmap of receive-enabled adapters for the source chain at the first attestation
+    pub enabled_adapters: Bitmap,
+}
+
+impl CompressedAttestation {
+    pub fn new(message_hash: [u8; 32], config_epoch: u64, enabled_adapters: Bitmap) -> Self {
+        Self {
+            message_hash,
+            executed: false,
+            attested_adapters: Bitmap::new(),
+            config_epoch,
+            enabled_adapters,
+        }
+    }
+
+    /// Records the attestation of the adapter at `adapter_index`, which must not have attested
+    /// to the message yet
+    pub fn attest(&mut self, adapter_index: u8) -> Result<()> {
+        require!(
+            !self.attested_adapters.get(adapter_index).unwrap_or(false),
+            EndpointError::DuplicateMessageAttestation
+        );
+        self.attested_adapters.set(adapter_index, true)?;
+        Ok(())
+    }
+
+    /// The leaf node stored in the tree
+    pub fn hash(&self) -> [u8; 32] {
+        keccak::hashv(&[
+            &self.message_hash,
+            &[self.executed as u8],
+            &self.attested_adapters.as_value().to_be_bytes(),
+            &self.config_epoch.to_be_bytes(),
+            &self.enabled_adapters.as_value().to_be_bytes(),
+        ])
+        .to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_layout() {
+        assert_eq!(AttestationTree::TREE_OFFSET % 8, 0);
+        assert_eq!(AttestationTree::LEN, 48 + 24 + 65 * 488);
+    }
+
+    #[test]
+    fn test_leaf_hash_commits_to_status() {
+        let leaf = CompressedAttestation::new([1; 32], 3, Bitmap::from_value(0b11));
+
+        let mut executed = leaf;
+        executed.executed = true;
+        assert_ne!(leaf.hash(), executed.hash());
+
+        let mut attested = leaf;
+        attested.attested_adapters.set(0, true).unwrap();
+        assert_ne!(leaf.hash(), attested.hash());
+    }
+}
@@ -0,0 +1,232 @@
+use anchor_lang::prelude::*;
+
+use crate::state::IntegratorConfig;
+
+/// A configuration change made to an integrator, with its parameters
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigAction {
+    AdapterAdded {
+        adapter: Pubkey,
+    },
+    SendAdapterEnabled {
+        adapter: Pubkey,
+        chain_id: u16,
+    },
+    RecvAdapterEnabled {
+        adapter: Pubkey,
+        chain_id: u16,
+    },
+    SendAdapterDisabled {
+        adapter: Pubkey,
+        chain_id: u16,
+    },
+    RecvAdapterDisabled {
+        adapter: Pubkey,
+        chain_id: u16,
+    },
+    AdapterRetired {
+        adapter: Pubkey,
+    },
+    AdminUpdated {
+        new_admin: Pubkey,
+    },
+    AdminTransferRequested {
+        new_admin: Pubkey,
+    },
+    /// The actor is the new admin, or the previous admin if the transfer was cancelled
+    AdminClaimed {
+        previous_admin: Pubkey,
+    },
+    AdminDiscarded,
+    SequencesSkipped {
+        src_chain: u16,
+        src_addr: [u8; 32],
+        sequence: u64,
+    },
+    OrderedDeliverySet {
+        chain_id: u16,
+        enabled: bool,
+    },
+    ReplayWindowsEnabled {
+        chain_id: u16,
+    },
+    ReplayWindowClosed {
+        src_chain: u16,
+        src_addr: [u8; 32],
+        window_index: u64,
+    },
+    AttestationTreeInitialized {
+        chain_id: u16,
+        tree: Pubkey,
+    },
+    AdapterQueueInitialized {
+        adapter: Pubkey,
+    },
+    /// The last entry of a deregistered integrator's log
+    Deregistered {
+        rent_recipient: Pubkey,
+    },
+}
+
+/// One entry of an integrator's audit log
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuditLogEntry {
+    /// The slot the change was made in
+    pub slot: u64,
+
+    /// The signer who made the change
+    /// For changes authorised through `execute_signed_admin`, this is the key that signed the
+    /// authorisation rather than its admin authority
+    pub actor: Pubkey,
+
+    /// The change and its parameters
+    pub action: ConfigAction,
+}
+
+/// A page of the append-only log of an integrator's configuration changes
+///
+/// Every admin-gated configuration change appends an entry to the current page, so that the
+/// history can be read from the chain without relying on event indexing. Pages are created as
+/// they are needed and hold up to `ENTRIES_PER_PAGE` entries each.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct AuditLogPage {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// The program ID of the integrator
+    /// This is used as a seed for PDA derivation
+    pub integrator_program_id: Pubkey,
+
+    /// The index of this page
+    /// This is used as a seed for PDA derivation
+    pub page_index: u64,
+
+    /// The entries of this page, oldest first
+    #[max_len(16)]
+    pub entries: Vec<AuditLogEntry>,
+}
+
+impl AuditLogPage {
+    /// Seed prefix for deriving AuditLogPage PDAs
+    pub const SEED_PREFIX: &'static [u8] = b"audit_log_page";
+
+    /// Number of entries in each page, matching the `max_len` of `entries`
+    pub const ENTRIES_PER_PAGE: u64 = 16;
+
+    pub fn pda(integrator_program_id: &Pubkey, page_index: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::SEED_PREFIX,
+                integrator_program_id.as_ref(),
+                page_index.to_be_bytes().as_ref(),
+            ],
+            &crate::ID,
+        )
+    }
+
+    /// Appends an entry for a change to `integrator_config`, initializing the page first if it
+    /// has just been created
+    ///
+    /// The page must be the current page of `integrator_config`, which the account constraints
+    /// of each instruction enforce through its seeds.
+    pub fn append(
+        &mut self,
+        bump: u8,
+        integrator_config: &mut IntegratorConfig,
+        actor: Pubkey,
+        action: ConfigAction,
+        slot: u64,
+    ) {
+        if self.entries.is_empty() {
+            self.bump = bump;
+            self.integrator_program_id = integrator_config.integrator_program_id;
+            self.page_index = integrator_config.audit_log_page();
+        }
+
+        self.entries.push(AuditLogEntry {
+            slot,
+            actor,
+            action,
+        });
+        integrator_config.audit_log_len = integrator_config.audit_log_len.checked_add(1).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn integrator_config() -> IntegratorConfig {
+        IntegratorConfig {
+            bump: 0,
+            integrator_program_id: Pubkey::new_unique(),
+            admin: None,
+            pending_admin: None,
+            adapter_infos: vec![],
+            audit_log_len: 0,
+            config_digest: [0; 32],
+            admin_nonce: 0,
+        }
+    }
+
+    fn empty_page() -> AuditLogPage {
+        AuditLogPage {
+            bump: 0,
+            integrator_program_id: Pubkey::default(),
+            page_index: 0,
+            entries: vec![],
+        }
+    }
+
+    #[test]
+    fn test_append_moves_to_next_page_when_full() {
+        let mut config = integrator_config();
+        let actor = Pubkey::new_unique();
+        let mut page = empty_page();
+
+        for slot in 0..AuditLogPage::ENTRIES_PER_PAGE {
+            assert_eq!(config.audit_log_page(), 0);
+            page.append(7, &mut config, actor, ConfigAction::AdminDiscarded, slot);
+        }
+        assert_eq!(page.bump, 7);
+        assert_eq!(page.integrator_program_id, config.integrator_program_id);
+        assert_eq!(page.page_index, 0);
+        assert_eq!(page.entries.len() as u64, AuditLogPage::ENTRIES_PER_PAGE);
+        assert_eq!(config.audit_log_page(), 1);
+
+        let mut next_page = empty_page();
+        let action = ConfigAction::AdapterAdded {
+            adapter: Pubkey::new_unique(),
+        };
+        next_page.append(3, &mut config, actor, action, 20);
+        assert_eq!(next_page.page_index, 1);
+        assert_eq!(
+            next_page.entries,
+            vec![AuditLogEntry {
+                slot: 20,
+                actor,
+                action,
+            }]
+        );
+        assert_eq!(config.audit_log_len, AuditLogPage::ENTRIES_PER_PAGE + 1);
+    }
+
+    #[test]
+    fn test_space_fits_full_page() {
+        let mut config = integrator_config();
+        let mut page = empty_page();
+        let action = ConfigAction::ReplayWindowClosed {
+            src_chain: 2,
+            src_addr: [1; 32],
+            window_index: u64::MAX,
+        };
+        for slot in 0..AuditLogPage::ENTRIES_PER_PAGE {
+            page.append(0, &mut config, Pubkey::new_unique(), action, slot);
+        }
+
+        let mut data = Vec::new();
+        page.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= 8 + AuditLogPage::INIT_SPACE);
+    }
+}
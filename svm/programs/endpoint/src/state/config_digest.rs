@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+/// A change to an integrator's configuration, as folded into its configuration digest
+///
+/// Each update commits to the resulting state rather than to the instruction that made it,
+/// so that off-chain tools can recompute the digest from the configuration history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigUpdate {
+    /// An adapter was added at `index` of the integrator's adapter list
+    AdapterAdded { index: u8, adapter: Pubkey },
+
+    /// The bitmaps of a chain changed, with their values after the change
+    ChainBitmaps {
+        chain_id: u16,
+        send_bitmap: u128,
+        recv_bitmap: u128,
+    },
+
+    /// The admin or pending admin changed, with their values after the change
+    Admin {
+        admin: Option<Pubkey>,
+        pending_admin: Option<Pubkey>,
+    },
+
+    /// The adapter at `index` of the integrator's adapter list was retired
+    AdapterRetired { index: u8 },
+}
+
+impl ConfigUpdate {
+    pub const TAG_ADAPTER_ADDED: u8 = 1;
+    pub const TAG_CHAIN_BITMAPS: u8 = 2;
+    pub const TAG_ADMIN: u8 = 3;
+    pub const TAG_ADAPTER_RETIRED: u8 = 4;
+
+    /// Encodes the update as hashed into the digest
+    ///
+    /// The encoding is a tag byte followed by the fields, with integers big-endian and
+    /// optional keys as `0` or `1 || key`:
+    /// * `AdapterAdded`: `1 || index || adapter`
+    /// * `ChainBitmaps`: `2 || chain_id || send_bitmap || recv_bitmap`
+    /// * `Admin`: `3 || admin || pending_admin`
+    /// * `AdapterRetired`: `4 || index`
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        match self {
+            Self::AdapterAdded { index, adapter } => {
+                bytes.push(Self::TAG_ADAPTER_ADDED);
+                bytes.push(*index);
+                bytes.extend_from_slice(adapter.as_ref());
+            }
+            Self::ChainBitmaps {
+                chain_id,
+                send_bitmap,
+                recv_bitmap,
+            } => {
+                bytes.push(Self::TAG_CHAIN_BITMAPS);
+                bytes.extend_from_slice(&chain_id.to_be_bytes());
+                bytes.extend_from_slice(&send_bitmap.to_be_bytes());
+                bytes.extend_from_slice(&recv_bitmap.to_be_bytes());
+            }
+            Self::Admin {
+                admin,
+                pending_admin,
+            } => {
+                bytes.push(Self::TAG_ADMIN);
+                encode_optional_key(&mut bytes, admin);
+                encode_optional_key(&mut bytes, pending_admin);
+            }
+            Self::AdapterRetired { index } => {
+                bytes.push(Self::TAG_ADAPTER_RETIRED);
+                bytes.push(*index);
+            }
+        }
+        bytes
+    }
+}
+
+fn encode_optional_key(bytes: &mut Vec<u8>, key: &Option<Pubkey>) {
+    match key {
+        Some(key) => {
+            bytes.push(1);
+            bytes.extend_from_slice(key.as_ref());
+        }
+        None => bytes.push(0),
+    }
+}
+
+/// The rolling digest of an integrator's configuration
+pub struct ConfigDigest;
+
+impl ConfigDigest {
+    /// Domain separator of the genesis digest
+    pub const DOMAIN: &'static [u8] = b"endpoint_config_digest_v1";
+
+    /// The digest an integrator starts from, before its initial admin is folded in:
+    /// `keccak256(DOMAIN || integrator_program_id)`
+    pub fn genesis(integrator_program_id: &Pubkey) -> [u8; 32] {
+        keccak::hashv(&[Self::DOMAIN, integrator_program_id.as_ref()]).to_bytes()
+    }
+
+    /// Folds an update into the digest: `keccak256(digest || update.encode())`
+    pub fn fold(digest: [u8; 32], update: &ConfigUpdate) -> [u8; 32] {
+        keccak::hashv(&[&digest, &update.encode()]).to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let adapter = Pubkey::new_from_array([7; 32]);
+
+        let mut expected = vec![1, 3];
+        expected.extend_from_slice(&[7; 32]);
+        assert_eq!(
+            ConfigUpdate::AdapterAdded { index: 3, adapter }.encode(),
+            expected
+        );
+
+        let mut expected = vec![2, 0x00, 0x02];
+        expected.extend_from_slice(&[0; 15]);
+        expected.push(0x05);
+        expected.extend_from_slice(&[0; 15]);
+        expected.push(0x01);
+        assert_eq!(
+            ConfigUpdate::ChainBitmaps {
+                chain_id: 2,
+                send_bitmap: 5,
+                recv_bitmap: 1,
+            }
+            .encode(),
+            expected
+        );
+
+        let mut expected = vec![3, 1];
+        expected.extend_from_slice(&[7; 32]);
+        expected.push(0);
+        assert_eq!(
+            ConfigUpdate::Admin {
+                admin: Some(adapter),
+                pending_admin: None,
+            }
+            .encode(),
+            expected
+        );
+
+        assert_eq!(
+            ConfigUpdate::AdapterRetired { index: 9 }.encode(),
+            vec![4, 9]
+        );
+    }
+
+    #[test]
+    fn test_fold() {
+        let integrator = Pubkey::new_from_array([1; 32]);
+        let genesis = ConfigDigest::genesis(&integrator);
+
+        let mut preimage = ConfigDigest::DOMAIN.to_vec();
+        preimage.extend_from_slice(&[1; 32]);
+        assert_eq!(genesis, keccak::hash(&preimage).to_bytes());
+
+        let update = ConfigUpdate::AdapterRetired { index: 0 };
+        let mut preimage = genesis.to_vec();
+        preimage.extend_from_slice(&[4, 0]);
+        assert_eq!(
+            ConfigDigest::fold(genesis, &update),
+            keccak::hash(&preimage).to_bytes()
+        );
+
+        // The digest depends on the order of the updates
+        let other = ConfigUpdate::AdapterRetired { index: 1 };
+        assert_ne!(
+            ConfigDigest::fold(ConfigDigest::fold(genesis, &update), &other),
+            ConfigDigest::fold(ConfigDigest::fold(genesis, &other), &update)
+        );
+    }
+}
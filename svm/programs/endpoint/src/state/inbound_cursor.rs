@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+
+use crate::error::EndpointError;
+
+/// Tracks the next sequence to be delivered from a source integrator, for integrators that
+/// opted into ordered delivery on the source chain's `IntegratorChainConfig`.
+///
+/// Each sequence can only be delivered once every lower one has been, so message N+1 can rely on
+/// message N. A sender's sequence is shared by all of its destination chains, so the sequences a
+/// destination sees from it can have gaps, which an admin moves past with `skip_sequence`.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct InboundCursor {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// The program ID of the destination integrator
+    /// This is used as a seed for PDA derivation
+    pub integrator_program_id: Pubkey,
+
+    /// Source chain ID
+    /// This is used as a seed for PDA derivation
+    pub src_chain: u16,
+
+    /// Source address (32 bytes)
+    /// This is used as a seed for PDA derivation
+    pub src_addr: [u8; 32],
+
+    /// The only sequence that can currently be received or executed
+    pub next_sequence: u64,
+}
+
+impl InboundCursor {
+    /// Seed prefix for deriving InboundCursor PDAs
+    pub const SEED_PREFIX: &'static [u8] = b"inbound_cursor";
+
+    pub fn pda(integrator_program_id: &Pubkey, src_chain: u16, src_addr: [u8; 32]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::SEED_PREFIX,
+                integrator_program_id.as_ref(),
+                src_chain.to_be_bytes().as_ref(),
+                src_addr.as_ref(),
+            ],
+            &crate::ID,
+        )
+    }
+
+    pub fn new(
+        bump: u8,
+        integrator_program_id: Pubkey,
+        src_chain: u16,
+        src_addr: [u8; 32],
+    ) -> Self {
+        Self {
+            bump,
+            integrator_program_id,
+            src_chain,
+            src_addr,
+            next_sequence: 0,
+        }
+    }
+
+    /// Delivers `sequence` through a cursor that may have just been created by `init_if_needed`,
+    /// initializing it first if so
+    pub fn init_and_deliver(
+        &mut self,
+        bump: u8,
+        integrator_program_id: Pubkey,
+        src_chain: u16,
+        src_addr: [u8; 32],
+        sequence: u64,
+    ) -> Result<()> {
+        if self.src_chain == 0 {
+            *self = Self::new(bump, integrator_program_id, src_chain, src_addr);
+        }
+        self.deliver(sequence)
+    }
+
+    /// Delivers `sequence`, which must be the next sequence
+    pub fn deliver(&mut self, sequence: u64) -> Result<()> {
+        require!(
+            sequence == self.next_sequence,
+            EndpointError::OutOfOrderSequence
+        );
+        self.advance_past(sequence)
+    }
+
+    /// Skips every undelivered sequence up to and including `sequence`
+    pub fn skip_through(&mut self, sequence: u64) -> Result<()> {
+        require!(
+            sequence >= self.next_sequence,
+            EndpointError::SequenceAlreadyDelivered
+        );
+        self.advance_past(sequence)
+    }
+
+    fn advance_past(&mut self, sequence: u64) -> Result<()> {
+        self.next_sequence = sequence
+            .checked_add(1)
+            .ok_or(EndpointError::SequenceOverflow)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor() -> InboundCursor {
+        InboundCursor::new(255, Pubkey::new_unique(), 2, [1; 32])
+    }
+
+    #[test]
+    fn test_deliver_in_order() {
+        let mut cursor = cursor();
+        cursor.deliver(0).unwrap();
+        cursor.deliver(1).unwrap();
+        assert_eq!(cursor.next_sequence, 2);
+    }
+
+    #[test]
+    fn test_deliver_jump_ahead() {
+        let mut cursor = cursor();
+        assert!(cursor.deliver(3).is_err());
+        assert_eq!(cursor.next_sequence, 0);
+        cursor.deliver(0).unwrap();
+        assert!(cursor.deliver(2).is_err());
+        assert!(cursor.deliver(0).is_err());
+        assert_eq!(cursor.next_sequence, 1);
+    }
+
+    #[test]
+    fn test_init_and_deliver() {
+        let integrator_program_id = Pubkey::new_unique();
+        let mut cursor = InboundCursor::new(0, Pubkey::default(), 0, [0; 32]);
+        cursor
+            .init_and_deliver(255, integrator_program_id, 2, [1; 32], 0)
+            .unwrap();
+        assert_eq!(cursor.bump, 255);
+        assert_eq!(cursor.integrator_program_id, integrator_program_id);
+        assert_eq!(cursor.src_chain, 2);
+        assert_eq!(cursor.next_sequence, 1);
+
+        // An existing cursor is not reset
+        cursor
+            .init_and_deliver(255, integrator_program_id, 2, [1; 32], 1)
+            .unwrap();
+        assert_eq!(cursor.next_sequence, 2);
+        assert!(cursor
+            .init_and_deliver(255, integrator_program_id, 2, [1; 32], 0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_skip_through() {
+        let mut cursor = cursor();
+        cursor.skip_through(0).unwrap();
+        assert_eq!(cursor.next_sequence, 1);
+        cursor.skip_through(5).unwrap();
+        assert_eq!(cursor.next_sequence, 6);
+        assert!(cursor.skip_through(5).is_err());
+        cursor.deliver(6).unwrap();
+    }
+
+    #[test]
+    fn test_last_sequence() {
+        let mut cursor = cursor();
+        cursor.skip_through(u64::MAX - 1).unwrap();
+        assert!(cursor.deliver(u64::MAX).is_err());
+        assert!(cursor.skip_through(u64::MAX).is_err());
+        assert_eq!(cursor.next_sequence, u64::MAX);
+    }
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
-use crate::utils::bitmap::Bitmap;
+use crate::state::ConfigUpdate;
+use crate::utils::{account::is_uncreated, bitmap::Bitmap};
 
 /// Manages the adapters for a specific integrator on a particular chain.
 ///
@@ -25,6 +26,18 @@ pub struct IntegratorChainConfig {
 
     /// Bitmap tracking the status of receive adapters
     pub recv_adapter_bitmap: Bitmap,
+
+    /// Incremented by every enable and disable of an adapter on this chain, so that
+    /// attestations can record which configuration they were made under
+    pub config_epoch: u64,
+
+    /// When set, messages from this chain can only be received or executed in sequence order
+    /// per source address, tracked by an `InboundCursor`
+    pub ordered_delivery: bool,
+
+    /// When set, executed messages from this chain are recorded in shared `ReplayWindow`
+    /// accounts instead of keeping one `AttestationInfo` per message. Once set, it cannot be unset
+    pub replay_windows: bool,
 }
 
 impl IntegratorChainConfig {
@@ -41,4 +54,35 @@ impl IntegratorChainConfig {
             &crate::ID,
         )
     }
+
+    /// Moves the config to the next epoch after an adapter has been enabled or disabled
+    pub fn advance_config_epoch(&mut self) {
+        self.config_epoch = self.config_epoch.checked_add(1).unwrap();
+    }
+
+    /// The configuration digest update recording the current bitmaps of the chain
+    pub fn digest_update(&self) -> ConfigUpdate {
+        ConfigUpdate::ChainBitmaps {
+            chain_id: self.chain_id,
+            send_bitmap: self.send_adapter_bitmap.as_value(),
+            recv_bitmap: self.recv_adapter_bitmap.as_value(),
+        }
+    }
+
+    /// Returns whether ordered delivery and replay windows are enabled in the chain config at
+    /// `account`, whose address the caller has checked. A source chain without a config has
+    /// neither enabled.
+    pub fn inbound_options(account: &AccountInfo) -> Result<(bool, bool)> {
+        if is_uncreated(account) {
+            return Ok((false, false));
+        }
+
+        require_keys_eq!(
+            *account.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let config = Self::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        Ok((config.ordered_delivery, config.replay_windows))
+    }
 }
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::error::EndpointError;
+use crate::state::{AuditLogPage, ConfigDigest, ConfigUpdate};
 
 /// Manages the configuration for a specific integrator.
 #[account]
@@ -25,6 +26,16 @@ pub struct IntegratorConfig {
     /// Vector of registered adapter addresses
     #[max_len(128)]
     pub adapter_infos: Vec<Pubkey>,
+
+    /// Number of entries in the integrator's audit log
+    pub audit_log_len: u64,
+
+    /// Rolling keccak digest of the integrator's configuration
+    /// Every change to the adapter list, the chain bitmaps or the admin is folded into it
+    pub config_digest: [u8; 32],
+
+    /// Nonce the next admin authorisation signed off-chain must carry
+    pub admin_nonce: u64,
 }
 
 impl IntegratorConfig {
@@ -34,6 +45,9 @@ impl IntegratorConfig {
     /// Maximum number of adapters allowed
     pub const MAX_ADAPTERS: usize = 128;
 
+    /// Seed prefix for deriving admin authority PDAs
+    pub const ADMIN_AUTHORITY_SEED_PREFIX: &'static [u8] = b"admin_authority";
+
     pub fn pda(integrator_program_id: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(
             &[Self::SEED_PREFIX, integrator_program_id.as_ref()],
@@ -41,9 +55,62 @@ impl IntegratorConfig {
         )
     }
 
+    /// The PDA the endpoint signs with when executing an admin instruction that `signer`
+    /// authorised off-chain for the integrator
+    pub fn admin_authority(integrator_program_id: &Pubkey, signer: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::ADMIN_AUTHORITY_SEED_PREFIX,
+                integrator_program_id.as_ref(),
+                signer.as_ref(),
+            ],
+            &crate::ID,
+        )
+    }
+
+    /// Whether `signer` acts for `key`, either as the key itself or as its admin authority
+    pub fn acts_for(&self, key: Option<Pubkey>, signer: &Pubkey) -> bool {
+        key.is_some_and(|key| {
+            *signer == key || *signer == Self::admin_authority(&self.integrator_program_id, &key).0
+        })
+    }
+
+    /// The actor recorded in the audit log for a change made by `signer` as the admin
+    ///
+    /// This is the admin itself when `signer` is its admin authority, i.e. when the change was
+    /// authorised by the admin's signature through `execute_signed_admin`.
+    pub fn admin_actor(&self, signer: &Pubkey) -> Pubkey {
+        match self.admin {
+            Some(admin)
+                if *signer == Self::admin_authority(&self.integrator_program_id, &admin).0 =>
+            {
+                admin
+            }
+            _ => *signer,
+        }
+    }
+
+    /// The index of the audit log page that the next configuration change is appended to
+    pub fn audit_log_page(&self) -> u64 {
+        self.audit_log_len / AuditLogPage::ENTRIES_PER_PAGE
+    }
+
+    /// Folds a configuration change into the configuration digest
+    pub fn update_config_digest(&mut self, update: ConfigUpdate) {
+        self.config_digest = ConfigDigest::fold(self.config_digest, &update);
+    }
+
+    /// Folds the current admin and pending admin into the configuration digest
+    pub fn update_admin_digest(&mut self) {
+        self.update_config_digest(ConfigUpdate::Admin {
+            admin: self.admin,
+            pending_admin: self.pending_admin,
+        });
+    }
+
     pub fn check_admin(&self, signer: &Signer) -> Result<()> {
         require!(
-            self.admin == Some(signer.key()),
+            self.acts_for(self.admin, signer.key),
             EndpointError::CallerNotAuthorized
         );
         require!(
@@ -61,6 +128,10 @@ impl IntegratorConfig {
             EndpointError::MaxAdaptersReached
         );
         self.adapter_infos.push(adapter);
+        self.update_config_digest(ConfigUpdate::AdapterAdded {
+            index: (self.adapter_infos.len() - 1) as u8,
+            adapter,
+        });
         Ok(())
     }
 }
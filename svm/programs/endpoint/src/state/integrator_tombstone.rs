@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+/// Left behind by `deregister` in place of an integrator's configuration
+///
+/// Its existence stops the integrator program from being registered again, which would restart
+/// its sequence numbers. It also records where the rent of the integrator's remaining accounts
+/// goes when they are closed.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct IntegratorTombstone {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// The program ID of the deregistered integrator
+    /// This is used as a seed for PDA derivation
+    pub integrator_program_id: Pubkey,
+
+    /// The recipient of the rent of the integrator's closed accounts
+    pub rent_recipient: Pubkey,
+
+    /// The next sequence the integrator would have sent
+    pub final_sequence: u64,
+}
+
+impl IntegratorTombstone {
+    /// Seed prefix for deriving IntegratorTombstone PDAs
+    pub const SEED_PREFIX: &'static [u8] = b"integrator_tombstone";
+
+    pub fn pda(integrator_program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[Self::SEED_PREFIX, integrator_program_id.as_ref()],
+            &crate::ID,
+        )
+    }
+}
@@ -1,13 +1,42 @@
+mod adapter_index;
 mod adapter_info;
+mod adapter_queue;
+mod adapter_stats;
 mod attestation_info;
+mod attestation_leaf;
+mod attestation_metadata;
+mod attestation_tree;
+mod audit_log;
+mod config_digest;
+mod inbound_cursor;
 mod integrator_chain_config;
 mod integrator_config;
+mod integrator_tombstone;
 mod outbox_message;
+mod replay_window;
+mod sent_message_record;
+mod sequence_record;
 mod sequence_tracker;
 
+pub use adapter_index::*;
 pub use adapter_info::*;
+pub use adapter_queue::*;
+pub use adapter_stats::*;
 pub use attestation_info::*;
+pub use attestation_leaf::*;
+pub use attestation_metadata::*;
+pub use attestation_tree::*;
+pub use audit_log::*;
+pub use config_digest::*;
+pub use inbound_cursor::*;
 pub use integrator_chain_config::*;
 pub use integrator_config::*;
+pub use integrator_tombstone::*;
 pub use outbox_message::*;
+pub use replay_window::*;
+pub use sent_message_record::*;
+pub use sequence_record::*;
 pub use sequence_tracker::*;
+
+// The message expiry is part of the message relayed by adapters
+pub use adapter_interface::MessageExpiry;
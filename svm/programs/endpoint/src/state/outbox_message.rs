@@ -1,3 +1,4 @@
+use crate::state::MessageExpiry;
 use crate::utils::bitmap::Bitmap;
 use anchor_lang::prelude::*;
 
@@ -19,6 +20,19 @@ pub struct OutboxMessage {
     /// The keccak256 of an arbitrary payload (32 bytes)
     pub payload_hash: [u8; 32],
 
+    /// The finality or consistency level requested by the sender
+    /// This is opaque to the endpoint and interpreted by send adapters, e.g. Wormhole's
+    /// `0` (confirmed) or `1` (finalized)
+    pub consistency_level: u8,
+
+    /// The deadline after which the message can no longer be received, if any
+    /// Adapters must relay it so the destination endpoint can recompute the message hash
+    pub expiry: Option<MessageExpiry>,
+
+    /// The bitmap of adapters the message was handed to, including any it was pushed to on send
+    /// The message can only be cancelled while this equals `outstanding_adapters`
+    pub initial_adapters: Bitmap,
+
     /// The bitmap of send-enabled adapters for this destination chain that have not picked up the message
     pub outstanding_adapters: Bitmap,
 
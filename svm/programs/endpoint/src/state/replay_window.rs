@@ -0,0 +1,359 @@
+use anchor_lang::prelude::*;
+
+use crate::error::EndpointError;
+use crate::utils::account::is_uncreated;
+
+/// Shared replay protection for a range of sequences from a source address.
+///
+/// By default every received message keeps its own `AttestationInfo` account forever, only to
+/// remember that it was executed. Integrators that enable replay windows on the source chain's
+/// `IntegratorChainConfig` instead record executed sequences as bits in these accounts, each
+/// covering `WINDOW_SIZE` consecutive sequences, and the `AttestationInfo` is closed once the
+/// message is delivered.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ReplayWindow {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// The program ID of the destination integrator
+    /// This is used as a seed for PDA derivation
+    pub integrator_program_id: Pubkey,
+
+    /// Source chain ID
+    /// This is used as a seed for PDA derivation
+    pub src_chain: u16,
+
+    /// Source address (32 bytes)
+    /// This is used as a seed for PDA derivation
+    pub src_addr: [u8; 32],
+
+    /// Index of the window, i.e. the first sequence it covers divided by `WINDOW_SIZE`
+    /// This is used as a seed for PDA derivation
+    pub window_index: u64,
+
+    /// One bit per sequence in the window, set once the sequence has been executed
+    pub executed: [u8; 1024],
+}
+
+impl ReplayWindow {
+    /// Seed prefix for deriving ReplayWindow PDAs
+    pub const SEED_PREFIX: &'static [u8] = b"replay_window";
+
+    /// Number of sequences covered by a window
+    pub const WINDOW_SIZE: u64 = 1024 * 8;
+
+    pub fn pda(
+        integrator_program_id: &Pubkey,
+        src_chain: u16,
+        src_addr: [u8; 32],
+        window_index: u64,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::SEED_PREFIX,
+                integrator_program_id.as_ref(),
+                src_chain.to_be_bytes().as_ref(),
+                src_addr.as_ref(),
+                window_index.to_be_bytes().as_ref(),
+            ],
+            &crate::ID,
+        )
+    }
+
+    /// Initializes a newly created window
+    /// The bitmap is left as created, all zeroes, rather than built on the stack for `set_inner`
+    pub fn init(
+        &mut self,
+        bump: u8,
+        integrator_program_id: Pubkey,
+        src_chain: u16,
+        src_addr: [u8; 32],
+        window_index: u64,
+    ) {
+        self.bump = bump;
+        self.integrator_program_id = integrator_program_id;
+        self.src_chain = src_chain;
+        self.src_addr = src_addr;
+        self.window_index = window_index;
+    }
+
+    /// The index of the window covering `sequence`
+    pub fn window_index(sequence: u64) -> u64 {
+        sequence / Self::WINDOW_SIZE
+    }
+
+    fn position(sequence: u64) -> (usize, u8) {
+        let offset = sequence % Self::WINDOW_SIZE;
+        ((offset / 8) as usize, 1 << (offset % 8))
+    }
+
+    pub fn is_executed(&self, sequence: u64) -> bool {
+        let (byte, mask) = Self::position(sequence);
+        self.executed[byte] & mask != 0
+    }
+
+    /// Reads whether `sequence` has been executed from the window account covering it, which
+    /// counts as not executed if it has not been created
+    pub fn is_executed_in(
+        account: &AccountInfo,
+        integrator_program_id: &Pubkey,
+        src_chain: u16,
+        src_addr: [u8; 32],
+        sequence: u64,
+    ) -> Result<bool> {
+        if is_uncreated(account) {
+            return Ok(false);
+        }
+
+        require_keys_eq!(
+            *account.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let window = Box::new(Self::try_deserialize(&mut &account.try_borrow_data()?[..])?);
+        require!(
+            window.integrator_program_id == *integrator_program_id
+                && window.src_chain == src_chain
+                && window.src_addr == src_addr
+                && window.window_index == Self::window_index(sequence),
+            ErrorCode::ConstraintSeeds
+        );
+        Ok(window.is_executed(sequence))
+    }
+
+    /// Marks `sequence` as executed
+    /// `sequence` must be covered by this window
+    pub fn mark_executed(&mut self, sequence: u64) -> Result<()> {
+        debug_assert_eq!(Self::window_index(sequence), self.window_index);
+        require!(!self.is_executed(sequence), EndpointError::AlreadyExecuted);
+
+        let (byte, mask) = Self::position(sequence);
+        self.executed[byte] |= mask;
+        Ok(())
+    }
+
+    /// Marks `sequence` as executed in a window that may have just been created by
+    /// `init_if_needed`, initializing it first if so
+    pub fn init_and_mark_executed(
+        &mut self,
+        bump: u8,
+        integrator_program_id: Pubkey,
+        src_chain: u16,
+        src_addr: [u8; 32],
+        sequence: u64,
+    ) -> Result<()> {
+        if self.src_chain == 0 {
+            self.init(
+                bump,
+                integrator_program_id,
+                src_chain,
+                src_addr,
+                Self::window_index(sequence),
+            );
+        }
+        self.mark_executed(sequence)
+    }
+}
+
+/// Records which replay windows of a source address have been closed.
+///
+/// Closed windows no longer exist, so without this a closed window would be recreated empty
+/// and its sequences could be executed again.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ReplayFloor {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// The program ID of the destination integrator
+    /// This is used as a seed for PDA derivation
+    pub integrator_program_id: Pubkey,
+
+    /// Source chain ID
+    /// This is used as a seed for PDA derivation
+    pub src_chain: u16,
+
+    /// Source address (32 bytes)
+    /// This is used as a seed for PDA derivation
+    pub src_addr: [u8; 32],
+
+    /// Every window below this index is treated as closed, and all of its sequences as executed
+    pub first_open_window: u64,
+}
+
+impl ReplayFloor {
+    /// Seed prefix for deriving ReplayFloor PDAs
+    pub const SEED_PREFIX: &'static [u8] = b"replay_floor";
+
+    pub fn pda(integrator_program_id: &Pubkey, src_chain: u16, src_addr: [u8; 32]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::SEED_PREFIX,
+                integrator_program_id.as_ref(),
+                src_chain.to_be_bytes().as_ref(),
+                src_addr.as_ref(),
+            ],
+            &crate::ID,
+        )
+    }
+
+    pub fn new(
+        bump: u8,
+        integrator_program_id: Pubkey,
+        src_chain: u16,
+        src_addr: [u8; 32],
+    ) -> Self {
+        Self {
+            bump,
+            integrator_program_id,
+            src_chain,
+            src_addr,
+            first_open_window: 0,
+        }
+    }
+
+    /// Returns `true` if the window covering `sequence` has been closed
+    pub fn is_closed(&self, sequence: u64) -> bool {
+        ReplayWindow::window_index(sequence) < self.first_open_window
+    }
+
+    /// Checks that the window covering `sequence` is still open, initializing a floor that has
+    /// just been created by `init_if_needed` first
+    pub fn init_and_check_open(
+        &mut self,
+        bump: u8,
+        integrator_program_id: Pubkey,
+        src_chain: u16,
+        src_addr: [u8; 32],
+        sequence: u64,
+    ) -> Result<()> {
+        if self.src_chain == 0 {
+            *self = Self::new(bump, integrator_program_id, src_chain, src_addr);
+        }
+        require!(!self.is_closed(sequence), EndpointError::ReplayWindowClosed);
+        Ok(())
+    }
+
+    /// Treats every window up to and including `window_index` as closed
+    pub fn close_through(&mut self, window_index: u64) {
+        self.first_open_window = self
+            .first_open_window
+            .max(window_index.checked_add(1).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(window_index: u64) -> ReplayWindow {
+        ReplayWindow {
+            bump: 255,
+            integrator_program_id: Pubkey::new_unique(),
+            src_chain: 2,
+            src_addr: [1; 32],
+            window_index,
+            executed: [0; 1024],
+        }
+    }
+
+    #[test]
+    fn test_window_index() {
+        assert_eq!(ReplayWindow::window_index(0), 0);
+        assert_eq!(ReplayWindow::window_index(ReplayWindow::WINDOW_SIZE - 1), 0);
+        assert_eq!(ReplayWindow::window_index(ReplayWindow::WINDOW_SIZE), 1);
+    }
+
+    #[test]
+    fn test_mark_executed() {
+        let sequence = ReplayWindow::WINDOW_SIZE + 9;
+        let mut window = window(1);
+
+        assert!(!window.is_executed(sequence));
+        window.mark_executed(sequence).unwrap();
+        assert!(window.is_executed(sequence));
+        assert!(!window.is_executed(sequence - 1));
+        assert!(!window.is_executed(sequence + 1));
+
+        assert!(window.mark_executed(sequence).is_err());
+    }
+
+    #[test]
+    fn test_mark_executed_window_edges() {
+        let mut window = window(0);
+        window.mark_executed(0).unwrap();
+        window.mark_executed(ReplayWindow::WINDOW_SIZE - 1).unwrap();
+        assert_eq!(window.executed[0], 1);
+        assert_eq!(window.executed[1023], 0x80);
+    }
+
+    #[test]
+    fn test_init_and_mark_executed() {
+        let integrator_program_id = Pubkey::new_unique();
+        let sequence = ReplayWindow::WINDOW_SIZE + 9;
+        let mut window = ReplayWindow {
+            bump: 0,
+            integrator_program_id: Pubkey::default(),
+            src_chain: 0,
+            src_addr: [0; 32],
+            window_index: 0,
+            executed: [0; 1024],
+        };
+
+        window
+            .init_and_mark_executed(255, integrator_program_id, 2, [1; 32], sequence)
+            .unwrap();
+        assert_eq!(window.integrator_program_id, integrator_program_id);
+        assert_eq!(window.window_index, 1);
+        assert!(window.is_executed(sequence));
+
+        window
+            .init_and_mark_executed(255, integrator_program_id, 2, [1; 32], sequence + 1)
+            .unwrap();
+        assert!(window.is_executed(sequence));
+        assert!(window
+            .init_and_mark_executed(255, integrator_program_id, 2, [1; 32], sequence)
+            .is_err());
+    }
+
+    #[test]
+    fn test_replay_floor() {
+        let mut floor = ReplayFloor::new(255, Pubkey::new_unique(), 2, [1; 32]);
+        assert!(!floor.is_closed(0));
+
+        floor.close_through(1);
+        assert!(floor.is_closed(0));
+        assert!(floor.is_closed(2 * ReplayWindow::WINDOW_SIZE - 1));
+        assert!(!floor.is_closed(2 * ReplayWindow::WINDOW_SIZE));
+
+        // Closing an earlier window does not reopen later ones
+        floor.close_through(0);
+        assert_eq!(floor.first_open_window, 2);
+    }
+
+    #[test]
+    fn test_init_and_check_open() {
+        let integrator_program_id = Pubkey::new_unique();
+        let mut floor = ReplayFloor::new(0, Pubkey::default(), 0, [0; 32]);
+        floor
+            .init_and_check_open(255, integrator_program_id, 2, [1; 32], 0)
+            .unwrap();
+        assert_eq!(floor.integrator_program_id, integrator_program_id);
+        assert_eq!(floor.src_chain, 2);
+
+        floor.close_through(0);
+        assert!(floor
+            .init_and_check_open(255, integrator_program_id, 2, [1; 32], 0)
+            .is_err());
+        floor
+            .init_and_check_open(
+                255,
+                integrator_program_id,
+                2,
+                [1; 32],
+                ReplayWindow::WINDOW_SIZE,
+            )
+            .unwrap();
+    }
+}
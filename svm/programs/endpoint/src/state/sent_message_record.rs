@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+/// Records a message sent by an integrator, after its outbox message has closed.
+///
+/// `send_message` and `send_message_multi` create this account when the integrator asks for it.
+/// It lets `request_repickup` check that a message being handed to adapters again was actually
+/// sent under its sequence, so that an integrator cannot send a new message under an old
+/// sequence. The integrator closes it with `close_sent_message_record` once the message no longer
+/// needs to be re-picked up, refunding its payer. `cancel_message` marks it as cancelled, after
+/// which the message can no longer be re-picked up.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct SentMessageRecord {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// The program ID of the sending integrator
+    /// This is used as a seed for PDA derivation
+    pub integrator_program_id: Pubkey,
+
+    /// Sequence number of the message
+    /// This is used as a seed for PDA derivation
+    pub sequence: u64,
+
+    /// The hash of the message, as computed by `AttestationInfo::compute_message_hash`
+    /// This commits to the destination, the payload hash and the expiry
+    pub message_hash: [u8; 32],
+
+    /// The consistency level the message was sent with
+    pub consistency_level: u8,
+
+    /// The account that paid for this record, which its rent is refunded to
+    pub payer: Pubkey,
+
+    /// Whether an outbox message of the message has been cancelled
+    pub cancelled: bool,
+}
+
+impl SentMessageRecord {
+    /// Seed prefix for deriving SentMessageRecord PDAs
+    pub const SEED_PREFIX: &'static [u8] = b"sent_message_record";
+
+    pub fn pda(integrator_program_id: &Pubkey, sequence: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::SEED_PREFIX,
+                integrator_program_id.as_ref(),
+                sequence.to_be_bytes().as_ref(),
+            ],
+            &crate::ID,
+        )
+    }
+}
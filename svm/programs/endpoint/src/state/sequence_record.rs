@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+
+use crate::error::EndpointError;
+use crate::event::EquivocationDetected;
+use crate::state::{AttestationInfo, MessageExpiry};
+use crate::CHAIN_ID;
+
+/// Records the first payload hash attested for a source sequence.
+///
+/// `AttestationInfo` is keyed by the full message hash, so two attestations that disagree on
+/// the payload for the same `(src_chain, src_addr, sequence)` would otherwise land in two
+/// unrelated accounts. This account is keyed by the sequence alone, which lets
+/// `attest_message` notice the disagreement and `recv_message` refuse to deliver either
+/// message until the integrator explicitly overrides.
+///
+/// The account is kept after the sequence has been received, so that it keeps detecting
+/// conflicting attestations, unless replay windows are enabled for the source chain. The
+/// `ReplayWindow` then records the delivery, and the account is closed along with the
+/// message's `AttestationInfo`.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct SequenceRecord {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// The program ID of the destination integrator
+    /// This is used as a seed for PDA derivation
+    pub integrator_program_id: Pubkey,
+
+    /// Source chain ID
+    /// This is used as a seed for PDA derivation
+    pub src_chain: u16,
+
+    /// Source address (32 bytes)
+    /// This is used as a seed for PDA derivation
+    pub src_addr: [u8; 32],
+
+    /// Sequence number
+    /// This is used as a seed for PDA derivation
+    pub sequence: u64,
+
+    /// The payload hash of the first attestation seen for this sequence
+    pub first_payload_hash: [u8; 32],
+
+    /// Set once an attestation with a different payload hash has been seen
+    pub equivocated: bool,
+
+    /// Set once a message with this sequence has been received or executed
+    pub executed: bool,
+}
+
+impl SequenceRecord {
+    /// Seed prefix for deriving SequenceRecord PDAs
+    pub const SEED_PREFIX: &'static [u8] = b"sequence_record";
+
+    pub fn pda(
+        integrator_program_id: &Pubkey,
+        src_chain: u16,
+        src_addr: [u8; 32],
+        sequence: u64,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::SEED_PREFIX,
+                integrator_program_id.as_ref(),
+                src_chain.to_be_bytes().as_ref(),
+                src_addr.as_ref(),
+                sequence.to_be_bytes().as_ref(),
+            ],
+            &crate::ID,
+        )
+    }
+
+    pub fn new(
+        bump: u8,
+        integrator_program_id: Pubkey,
+        src_chain: u16,
+        src_addr: [u8; 32],
+        sequence: u64,
+        first_payload_hash: [u8; 32],
+    ) -> Self {
+        Self {
+            bump,
+            integrator_program_id,
+            src_chain,
+            src_addr,
+            sequence,
+            first_payload_hash,
+            equivocated: false,
+            executed: false,
+        }
+    }
+
+    /// Computes the hash of the message to this chain carrying the first payload attested for
+    /// this sequence.
+    ///
+    /// The instructions taking only a message hash use this to check that the record belongs
+    /// to the message, and read the message's fields from it.
+    pub fn first_message_hash(&self, expiry: Option<MessageExpiry>) -> [u8; 32] {
+        AttestationInfo::compute_message_hash(
+            self.src_chain,
+            self.src_addr,
+            self.sequence,
+            CHAIN_ID,
+            self.integrator_program_id.to_bytes(),
+            self.first_payload_hash,
+            expiry,
+        )
+    }
+
+    /// Records a payload hash attested by `attesting_adapter`.
+    ///
+    /// Returns the event to emit if `payload_hash` conflicts with the first payload hash seen
+    /// for this sequence.
+    pub fn record(
+        &mut self,
+        payload_hash: [u8; 32],
+        attesting_adapter: Pubkey,
+    ) -> Option<EquivocationDetected> {
+        if payload_hash == self.first_payload_hash {
+            return None;
+        }
+        self.equivocated = true;
+        Some(EquivocationDetected {
+            integrator: self.integrator_program_id,
+            src_chain: self.src_chain,
+            src_addr: self.src_addr,
+            sequence: self.sequence,
+            first_payload_hash: self.first_payload_hash,
+            conflicting_payload_hash: payload_hash,
+            attesting_adapter: attesting_adapter.to_bytes(),
+        })
+    }
+
+    /// Checks that the sequence can be delivered, i.e. that it has no conflicting attestations
+    /// or that the integrator explicitly overrides them
+    pub fn check_equivocation(&self, override_equivocation: bool) -> Result<()> {
+        require!(
+            !self.equivocated || override_equivocation,
+            EndpointError::SequenceEquivocated
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let integrator_program_id = Pubkey::new_unique();
+        let adapter = Pubkey::new_unique();
+        let mut record = SequenceRecord::new(255, integrator_program_id, 2, [1; 32], 7, [3; 32]);
+
+        assert!(record.record([3; 32], adapter).is_none());
+        assert!(!record.equivocated);
+        record.check_equivocation(false).unwrap();
+
+        let event = record.record([4; 32], adapter).unwrap();
+        assert!(record.equivocated);
+        assert_eq!(event.integrator, integrator_program_id);
+        assert_eq!(event.sequence, 7);
+        assert_eq!(event.first_payload_hash, [3; 32]);
+        assert_eq!(event.conflicting_payload_hash, [4; 32]);
+        assert_eq!(event.attesting_adapter, adapter.to_bytes());
+
+        assert!(record.check_equivocation(false).is_err());
+        record.check_equivocation(true).unwrap();
+    }
+}
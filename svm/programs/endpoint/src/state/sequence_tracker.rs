@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::utils::bitmap::Bitmap;
 
 /// Tracks the sequence number for an integrator program
 /// We could have put this in the `IntegratorConfig` account,
@@ -18,6 +21,20 @@ pub struct SequenceTracker {
 
     /// The current sequence number for this integrator
     pub sequence: u64,
+
+    /// The number of outbox messages of this integrator that have been created and not cancelled
+    /// Those closed by pick up are counted in the `AdapterStats` of the adapter that closed them,
+    /// and the integrator cannot be deregistered until the two match
+    pub outbox_messages: u64,
+
+    /// The hash chain of the hashes of every message sent by this integrator, in sequence order
+    /// It starts at zero and is updated as `keccak256(outbound_accumulator || message_hash)`
+    pub outbound_accumulator: [u8; 32],
+
+    /// Bitmap of the adapters that have an `AdapterQueue`
+    /// Their queue must be passed whenever an outbox message they are outstanding for is created
+    /// or closed
+    pub queued_adapters: Bitmap,
 }
 
 impl SequenceTracker {
@@ -37,4 +54,35 @@ impl SequenceTracker {
         self.sequence = self.sequence.checked_add(1).unwrap();
         sequence
     }
+
+    /// Folds a message hash into an outbound accumulator:
+    /// `keccak256(outbound_accumulator || message_hash)`
+    pub fn fold_outbound_accumulator(
+        outbound_accumulator: [u8; 32],
+        message_hash: [u8; 32],
+    ) -> [u8; 32] {
+        keccak::hashv(&[&outbound_accumulator, &message_hash]).to_bytes()
+    }
+
+    /// Records a sent message in the outbound accumulator and returns its new value
+    pub fn record_sent(&mut self, message_hash: [u8; 32]) -> [u8; 32] {
+        self.outbound_accumulator =
+            Self::fold_outbound_accumulator(self.outbound_accumulator, message_hash);
+        self.outbound_accumulator
+    }
+
+    /// Records that an outbox message has been created
+    pub fn record_outbox_created(&mut self) {
+        self.outbox_messages = self.outbox_messages.checked_add(1).unwrap();
+    }
+
+    /// Returns the adapters among `adapters` that have an `AdapterQueue`
+    pub fn queued(&self, adapters: Bitmap) -> Bitmap {
+        Bitmap::from_value(adapters.as_value() & self.queued_adapters.as_value())
+    }
+
+    /// Records that an outbox message has been cancelled
+    pub fn record_outbox_cancelled(&mut self) {
+        self.outbox_messages = self.outbox_messages.saturating_sub(1);
+    }
 }
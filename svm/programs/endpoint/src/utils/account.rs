@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::error::EndpointError;
+
+/// Grows `account` to `new_len` bytes if it is currently smaller, topping up its lamports
+/// from `payer` so that it stays rent exempt.
+///
+/// This is used for endpoint accounts that hold variable length vectors and are resized
+/// inside the instruction handler rather than through the `realloc` account constraint,
+/// since the required size is only known once the handler has inspected the account.
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let missing_lamports = required_lamports.saturating_sub(account.lamports());
+    if missing_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            missing_lamports,
+        )?;
+    }
+
+    account.realloc(new_len, false)?;
+
+    Ok(())
+}
+
+/// Creates `account` as a PDA of the endpoint program with `space` bytes, paying the rent from
+/// `payer`.
+///
+/// This does what the `init` account constraint does for accounts that are passed in
+/// `remaining_accounts`, including taking over an address that has already been funded.
+pub fn create_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let required_lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+
+    if current_lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                &[signer_seeds],
+            ),
+            required_lamports,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let missing_lamports = required_lamports.saturating_sub(current_lamports);
+    if missing_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            missing_lamports,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+/// Whether `account` has not been created yet
+pub fn is_uncreated(account: &AccountInfo) -> bool {
+    account.owner == &system_program::ID && account.data_is_empty()
+}
+
+/// Returns the account to send the lamports of a closed account to, given the
+/// `expected` recipient recorded in it.
+///
+/// This is the `payer` if it is the expected recipient, so callers only need to pass
+/// `recipient` when someone else paid for the account.
+pub fn refund_recipient<'info>(
+    expected: Pubkey,
+    payer: &Signer<'info>,
+    recipient: Option<&UncheckedAccount<'info>>,
+) -> Result<AccountInfo<'info>> {
+    if payer.key() == expected {
+        return Ok(payer.to_account_info());
+    }
+
+    let recipient = recipient.ok_or(EndpointError::InvalidRefundRecipient)?;
+    require_keys_eq!(
+        recipient.key(),
+        expected,
+        EndpointError::InvalidRefundRecipient
+    );
+    Ok(recipient.to_account_info())
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction};
+
+use crate::error::EndpointError;
+
+/// Size of the header of an Ed25519Program instruction: the signature count and a padding byte
+const HEADER_LEN: usize = 2;
+
+/// Size of the offsets of one signature in an Ed25519Program instruction
+const OFFSETS_LEN: usize = 14;
+
+const PUBKEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+
+/// Offsets that point into the Ed25519Program instruction itself
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Returns the public key and message of an Ed25519Program instruction verifying one signature
+///
+/// The runtime only executes the transaction if the signature is valid, so the caller only has
+/// to check that the key and message are the ones it expects. The signature, key and message
+/// must all be held by the instruction itself, so that they cannot be swapped for data in
+/// another instruction of the transaction.
+pub fn verified_message(instruction: &Instruction) -> Result<(Pubkey, &[u8])> {
+    require_keys_eq!(
+        instruction.program_id,
+        ed25519_program::ID,
+        EndpointError::InvalidAdminAuthorization
+    );
+
+    let data = &instruction.data;
+    require!(
+        data.len() >= HEADER_LEN + OFFSETS_LEN && data[0] == 1,
+        EndpointError::InvalidAdminAuthorization
+    );
+
+    let offsets = &data[HEADER_LEN..HEADER_LEN + OFFSETS_LEN];
+    let read_u16 = |index: usize| u16::from_le_bytes([offsets[index], offsets[index + 1]]);
+    let signature_offset = read_u16(0) as usize;
+    let signature_instruction_index = read_u16(2);
+    let public_key_offset = read_u16(4) as usize;
+    let public_key_instruction_index = read_u16(6);
+    let message_data_offset = read_u16(8) as usize;
+    let message_data_size = read_u16(10) as usize;
+    let message_instruction_index = read_u16(12);
+
+    require!(
+        signature_instruction_index == CURRENT_INSTRUCTION
+            && public_key_instruction_index == CURRENT_INSTRUCTION
+            && message_instruction_index == CURRENT_INSTRUCTION
+            && signature_offset + SIGNATURE_LEN <= data.len(),
+        EndpointError::InvalidAdminAuthorization
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + PUBKEY_LEN)
+        .ok_or(EndpointError::InvalidAdminAuthorization)?;
+    let message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(EndpointError::InvalidAdminAuthorization)?;
+
+    Ok((Pubkey::try_from(public_key).unwrap(), message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds an Ed25519Program instruction as `new_ed25519_instruction` in the Solana SDK does
+    fn ed25519_instruction(public_key: &Pubkey, message: &[u8], index: u16) -> Instruction {
+        let public_key_offset = HEADER_LEN + OFFSETS_LEN;
+        let signature_offset = public_key_offset + PUBKEY_LEN;
+        let message_data_offset = signature_offset + SIGNATURE_LEN;
+
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset as u16,
+            index,
+            public_key_offset as u16,
+            index,
+            message_data_offset as u16,
+            message.len() as u16,
+            index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(public_key.as_ref());
+        data.extend_from_slice(&[0; SIGNATURE_LEN]);
+        data.extend_from_slice(message);
+
+        Instruction {
+            program_id: ed25519_program::ID,
+            accounts: vec![],
+            data,
+        }
+    }
+
+    #[test]
+    fn test_verified_message() {
+        let public_key = Pubkey::new_unique();
+        let instruction = ed25519_instruction(&public_key, b"hello", CURRENT_INSTRUCTION);
+
+        let (signer, message) = verified_message(&instruction).unwrap();
+        assert_eq!(signer, public_key);
+        assert_eq!(message, b"hello");
+    }
+
+    #[test]
+    fn test_verified_message_rejects_other_instructions() {
+        let public_key = Pubkey::new_unique();
+
+        // Data held by another instruction of the transaction
+        let instruction = ed25519_instruction(&public_key, b"hello", 0);
+        assert!(verified_message(&instruction).is_err());
+
+        // Another program
+        let mut instruction = ed25519_instruction(&public_key, b"hello", CURRENT_INSTRUCTION);
+        instruction.program_id = Pubkey::new_unique();
+        assert!(verified_message(&instruction).is_err());
+
+        // Truncated message
+        let mut instruction = ed25519_instruction(&public_key, b"hello", CURRENT_INSTRUCTION);
+        instruction.data.truncate(instruction.data.len() - 1);
+        assert!(verified_message(&instruction).is_err());
+    }
+}
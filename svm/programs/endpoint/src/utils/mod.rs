@@ -1 +1,3 @@
+pub mod account;
 pub mod bitmap;
+pub mod ed25519;
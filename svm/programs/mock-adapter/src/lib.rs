@@ -1,17 +1,84 @@
+use adapter_interface::{
+    AdapterDescription, DeliveryQuote, GetPeerArgs, QuoteDeliveryArgs, ReceiveOutboundArgs,
+    ADAPTER_PDA_SEED, INTERFACE_VERSION, OUTBOUND_AUTHORITY_SEED,
+};
 use anchor_lang::prelude::*;
-use endpoint::cpi::accounts::{AttestMessage, PickUpMessage};
-use endpoint::instructions::{AttestMessageArgs, PickUpMessageArgs};
+use endpoint::cpi::accounts::{
+    AttestMessage, AttestMessageByHash, AttestMessageCompressed, AttestMessages, PickUpMessage,
+    PickUpMessages,
+};
+use endpoint::instructions::{
+    AttestMessageArgs, AttestMessageByHashArgs, AttestMessageCompressedArgs, AttestMessagesArgs,
+    BatchMode, BatchedMessage, PickUpMessageArgs, PickUpMessagesArgs,
+};
 use endpoint::program::Endpoint;
+use endpoint::state::{CompressedAttestation, MessageExpiry};
 use endpoint::{self};
 
 // Declare the program ID for the mock adapter
 declare_id!("5k8XySmYJ6nQTF8ZFZtRoevjCx9Y9PS5MT9oJDLNA162");
 
+/// The consistency levels the mock adapter can honour (confirmed and finalized)
+pub const SUPPORTED_CONSISTENCY_LEVELS: [u8; 2] = [0, 1];
+
+/// The fee in lamports quoted by the mock adapter for delivering a message
+pub const DELIVERY_FEE: u64 = 5_000;
+
+#[error_code]
+pub enum MockAdapterError {
+    #[msg("Consistency level is not supported by the adapter")]
+    UnsupportedConsistencyLevel,
+}
+
 #[program]
 pub mod mock_adapter {
 
     use super::*;
 
+    /// Receives a message pushed by the endpoint program during `send_message`
+    ///
+    /// This implements `receive_outbound` of the adapter interface, so that messages can be
+    /// pushed to the adapter instead of being picked up from the outbox. A real adapter would
+    /// relay the message here.
+    pub fn receive_outbound(
+        _ctx: Context<ReceiveOutbound>,
+        args: ReceiveOutboundArgs,
+    ) -> Result<()> {
+        msg!(
+            "Received message {} to chain {}",
+            args.sequence,
+            args.dst_chain
+        );
+        Ok(())
+    }
+
+    /// Implements `quote_delivery` of the adapter interface, with a flat fee for every chain
+    pub fn quote_delivery(
+        _ctx: Context<AdapterView>,
+        args: QuoteDeliveryArgs,
+    ) -> Result<DeliveryQuote> {
+        require!(
+            SUPPORTED_CONSISTENCY_LEVELS.contains(&args.consistency_level),
+            MockAdapterError::UnsupportedConsistencyLevel
+        );
+        Ok(DeliveryQuote { fee: DELIVERY_FEE })
+    }
+
+    /// Implements `describe` of the adapter interface
+    pub fn describe(_ctx: Context<AdapterView>) -> Result<AdapterDescription> {
+        Ok(AdapterDescription {
+            interface_version: INTERFACE_VERSION,
+            supported_consistency_levels: Some(SUPPORTED_CONSISTENCY_LEVELS.to_vec()),
+            supports_push: true,
+        })
+    }
+
+    /// Implements `get_peer` of the adapter interface
+    /// The mock adapter is deployed at the same address on every chain
+    pub fn get_peer(_ctx: Context<AdapterView>, _args: GetPeerArgs) -> Result<Option<[u8; 32]>> {
+        Ok(Some(crate::ID.to_bytes()))
+    }
+
     /// Invokes the pick_up_message instruction on the endpoint program via CPI
     ///
     /// This function demonstrates how an adapter program would interact
@@ -19,7 +86,7 @@ pub mod mock_adapter {
     pub fn invoke_pick_up_message(ctx: Context<InvokePickUpMessage>) -> Result<()> {
         // Prepare the seeds for PDA signing
         let bump_seed = &[ctx.bumps.adapter_pda][..];
-        let signer_seeds: &[&[&[u8]]] = &[&[b"adapter_pda", bump_seed]];
+        let signer_seeds: &[&[&[u8]]] = &[&[ADAPTER_PDA_SEED, bump_seed]];
 
         // Perform the CPI call to the endpoint program's pick_up_message instruction
         endpoint::cpi::pick_up_message(
@@ -29,6 +96,35 @@ pub mod mock_adapter {
             PickUpMessageArgs {
                 adapter_program_id: crate::id(),
                 adapter_pda_bump: ctx.bumps.adapter_pda,
+                supported_consistency_levels: Some(SUPPORTED_CONSISTENCY_LEVELS.to_vec()),
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Invokes the pick_up_messages instruction on the endpoint program via CPI,
+    /// forwarding the outbox messages and refund recipients from the remaining accounts
+    pub fn invoke_pick_up_messages<'info>(
+        ctx: Context<'_, '_, '_, 'info, InvokePickUpMessages<'info>>,
+        args: InvokePickUpMessagesArgs,
+    ) -> Result<()> {
+        // Prepare the seeds for PDA signing
+        let bump_seed = &[ctx.bumps.adapter_pda][..];
+        let signer_seeds: &[&[&[u8]]] = &[&[ADAPTER_PDA_SEED, bump_seed]];
+
+        // Perform the CPI call to the endpoint program's pick_up_messages instruction
+        endpoint::cpi::pick_up_messages(
+            ctx.accounts
+                .invoke_pick_up_messages()
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+                .with_signer(signer_seeds),
+            PickUpMessagesArgs {
+                adapter_program_id: crate::id(),
+                adapter_pda_bump: ctx.bumps.adapter_pda,
+                integrator_program_id: args.integrator_program_id,
+                supported_consistency_levels: Some(SUPPORTED_CONSISTENCY_LEVELS.to_vec()),
+                mode: args.mode,
             },
         )?;
 
@@ -42,7 +138,7 @@ pub mod mock_adapter {
     ) -> Result<()> {
         // Prepare the seeds for PDA signing
         let bump_seed = &[ctx.bumps.adapter_pda][..];
-        let signer_seeds: &[&[&[u8]]] = &[&[b"adapter_pda", bump_seed]];
+        let signer_seeds: &[&[&[u8]]] = &[&[ADAPTER_PDA_SEED, bump_seed]];
 
         // Perform the CPI call to the endpoint program's attest_message instruction
         endpoint::cpi::attest_message(
@@ -58,6 +154,97 @@ pub mod mock_adapter {
                 dst_chain: args.dst_chain,
                 integrator_program_id: args.integrator_program_id,
                 payload_hash: args.payload_hash,
+                expiry: args.expiry,
+                metadata: args.metadata,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Invokes the attest_messages instruction on the endpoint program via CPI,
+    /// forwarding the attestation infos and sequence records from the remaining accounts
+    pub fn invoke_attest_messages<'info>(
+        ctx: Context<'_, '_, '_, 'info, InvokeAttestMessages<'info>>,
+        args: InvokeAttestMessagesArgs,
+    ) -> Result<()> {
+        // Prepare the seeds for PDA signing
+        let bump_seed = &[ctx.bumps.adapter_pda][..];
+        let signer_seeds: &[&[&[u8]]] = &[&[ADAPTER_PDA_SEED, bump_seed]];
+
+        // Perform the CPI call to the endpoint program's attest_messages instruction
+        endpoint::cpi::attest_messages(
+            ctx.accounts
+                .invoke_attest_messages()
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+                .with_signer(signer_seeds),
+            AttestMessagesArgs {
+                adapter_program_id: crate::id(),
+                adapter_pda_bump: ctx.bumps.adapter_pda,
+                src_chain: args.src_chain,
+                integrator_program_id: args.integrator_program_id,
+                messages: args.messages,
+                mode: args.mode,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Invokes the attest_message_by_hash instruction on the endpoint program via CPI
+    pub fn invoke_attest_message_by_hash(
+        ctx: Context<InvokeAttestMessageByHash>,
+        args: InvokeAttestMessageByHashArgs,
+    ) -> Result<()> {
+        // Prepare the seeds for PDA signing
+        let bump_seed = &[ctx.bumps.adapter_pda][..];
+        let signer_seeds: &[&[&[u8]]] = &[&[ADAPTER_PDA_SEED, bump_seed]];
+
+        // Perform the CPI call to the endpoint program's attest_message_by_hash instruction
+        endpoint::cpi::attest_message_by_hash(
+            ctx.accounts
+                .invoke_attest_message_by_hash()
+                .with_signer(signer_seeds),
+            AttestMessageByHashArgs {
+                adapter_program_id: crate::id(),
+                adapter_pda_bump: ctx.bumps.adapter_pda,
+                message_hash: args.message_hash,
+                metadata: args.metadata,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Invokes the attest_message_compressed instruction on the endpoint program via CPI,
+    /// forwarding the leaf's proof from the remaining accounts
+    pub fn invoke_attest_message_compressed<'info>(
+        ctx: Context<'_, '_, '_, 'info, InvokeAttestMessageCompressed<'info>>,
+        args: InvokeAttestMessageCompressedArgs,
+    ) -> Result<()> {
+        // Prepare the seeds for PDA signing
+        let bump_seed = &[ctx.bumps.adapter_pda][..];
+        let signer_seeds: &[&[&[u8]]] = &[&[ADAPTER_PDA_SEED, bump_seed]];
+
+        // Perform the CPI call to the endpoint program's attest_message_compressed instruction
+        endpoint::cpi::attest_message_compressed(
+            ctx.accounts
+                .invoke_attest_message_compressed()
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+                .with_signer(signer_seeds),
+            AttestMessageCompressedArgs {
+                adapter_program_id: crate::id(),
+                adapter_pda_bump: ctx.bumps.adapter_pda,
+                src_chain: args.src_chain,
+                src_addr: args.src_addr,
+                sequence: args.sequence,
+                dst_chain: args.dst_chain,
+                integrator_program_id: args.integrator_program_id,
+                payload_hash: args.payload_hash,
+                expiry: args.expiry,
+                root: args.root,
+                leaf_index: args.leaf_index,
+                previous_leaf: args.previous_leaf,
             },
         )?;
 
@@ -65,6 +252,25 @@ pub mod mock_adapter {
     }
 }
 
+/// Accounts struct for the receive_outbound instruction
+#[derive(Accounts)]
+#[instruction(args: ReceiveOutboundArgs)]
+pub struct ReceiveOutbound<'info> {
+    /// The endpoint PDA that pushes messages for the sender, which makes sure that only the
+    /// endpoint program can call this instruction, on behalf of the integrator in `args.src_addr`
+    #[account(
+        seeds = [OUTBOUND_AUTHORITY_SEED, args.src_addr.as_ref()],
+        bump,
+        seeds::program = endpoint::id(),
+    )]
+    pub outbound_authority: Signer<'info>,
+}
+
+/// Accounts struct for the read-only instructions of the adapter interface
+/// The mock adapter keeps no state, so they take no accounts
+#[derive(Accounts)]
+pub struct AdapterView {}
+
 /// Accounts struct for the invoke_pick_up_message instruction
 #[derive(Accounts)]
 pub struct InvokePickUpMessage<'info> {
@@ -77,9 +283,19 @@ pub struct InvokePickUpMessage<'info> {
     /// CHECK: This account is checked by the endpoint program
     pub adapter_info: UncheckedAccount<'info>,
 
+    /// The adapter stats account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub adapter_stats: UncheckedAccount<'info>,
+
+    /// The adapter's queue of pending messages, if it has one
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub adapter_queue: Option<UncheckedAccount<'info>>,
+
     /// The adapter PDA account, used for signing
     #[account(
-        seeds = [b"adapter_pda"],
+        seeds = [ADAPTER_PDA_SEED],
         bump,
     )]
     pub adapter_pda: SystemAccount<'info>,
@@ -113,6 +329,11 @@ impl<'info> InvokePickUpMessage<'info> {
         let cpi_accounts = PickUpMessage {
             outbox_message: self.outbox_message.to_account_info(),
             adapter_info: self.adapter_info.to_account_info(),
+            adapter_stats: self.adapter_stats.to_account_info(),
+            adapter_queue: self
+                .adapter_queue
+                .as_ref()
+                .map(|adapter_queue| adapter_queue.to_account_info()),
             adapter_pda: self.adapter_pda.to_account_info(),
             event_authority: self.event_authority.to_account_info(),
             program: self.program.to_account_info(),
@@ -132,6 +353,8 @@ pub struct InvokeAttestMessageArgs {
     pub integrator_program_id: Pubkey,
     pub payload_hash: [u8; 32],
     pub message_hash: [u8; 32],
+    pub expiry: Option<MessageExpiry>,
+    pub metadata: Vec<u8>,
 }
 
 /// Accounts struct for the invoke_attest_message instruction
@@ -144,9 +367,14 @@ pub struct InvokeAttestMessage<'info> {
     /// CHECK: This account is checked by the endpoint program
     pub adapter_info: UncheckedAccount<'info>,
 
+    /// The adapter stats account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub adapter_stats: UncheckedAccount<'info>,
+
     /// The adapter PDA account, used for signing
     #[account(
-        seeds = [b"adapter_pda"],
+        seeds = [ADAPTER_PDA_SEED],
         bump,
     )]
     pub adapter_pda: SystemAccount<'info>,
@@ -160,6 +388,20 @@ pub struct InvokeAttestMessage<'info> {
     #[account(mut)]
     pub attestation_info: UncheckedAccount<'info>,
 
+    /// The sequence record account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub sequence_record: UncheckedAccount<'info>,
+
+    /// The replay window covering the sequence
+    /// CHECK: This account is checked by the endpoint program
+    pub replay_window: UncheckedAccount<'info>,
+
+    /// The attestation metadata account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub attestation_metadata: Option<UncheckedAccount<'info>>,
+
     /// The event authority PDA
     /// CHECK: This should be seeded with `__event_authority`
     #[account(
@@ -186,9 +428,345 @@ impl<'info> InvokeAttestMessage<'info> {
         let cpi_accounts = AttestMessage {
             payer: self.payer.to_account_info(),
             adapter_info: self.adapter_info.to_account_info(),
+            adapter_stats: self.adapter_stats.to_account_info(),
+            adapter_pda: self.adapter_pda.to_account_info(),
+            integrator_chain_config: self.integrator_chain_config.to_account_info(),
+            attestation_info: self.attestation_info.to_account_info(),
+            sequence_record: self.sequence_record.to_account_info(),
+            replay_window: self.replay_window.to_account_info(),
+            attestation_metadata: self
+                .attestation_metadata
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            event_authority: self.event_authority.to_account_info(),
+            program: self.program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        };
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InvokePickUpMessagesArgs {
+    pub integrator_program_id: Pubkey,
+    pub mode: BatchMode,
+}
+
+/// Accounts struct for the invoke_pick_up_messages instruction
+#[derive(Accounts)]
+pub struct InvokePickUpMessages<'info> {
+    /// The adapter info account
+    /// CHECK: This account is checked by the endpoint program
+    pub adapter_info: UncheckedAccount<'info>,
+
+    /// The adapter stats account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub adapter_stats: UncheckedAccount<'info>,
+
+    /// The adapter's queue of pending messages, if it has one
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub adapter_queue: Option<UncheckedAccount<'info>>,
+
+    /// The adapter PDA account, used for signing
+    #[account(
+        seeds = [ADAPTER_PDA_SEED],
+        bump,
+    )]
+    pub adapter_pda: SystemAccount<'info>,
+
+    /// The event authority PDA
+    /// CHECK: This should be seeded with `__event_authority`
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = endpoint::id(),
+    )]
+    pub event_authority: AccountInfo<'info>,
+
+    /// CHECK: Self-CPI will fail if the program is n
+    pub program: AccountInfo<'info>,
+
+    /// The endpoint program
+    pub endpoint_program: Program<'info, Endpoint>,
+}
+
+impl<'info> InvokePickUpMessages<'info> {
+    /// Helper function to create the CpiContext for the pick_up_messages instruction
+    pub fn invoke_pick_up_messages(&self) -> CpiContext<'_, '_, '_, 'info, PickUpMessages<'info>> {
+        let cpi_program = self.endpoint_program.to_account_info();
+        let cpi_accounts = PickUpMessages {
+            adapter_info: self.adapter_info.to_account_info(),
+            adapter_stats: self.adapter_stats.to_account_info(),
+            adapter_queue: self
+                .adapter_queue
+                .as_ref()
+                .map(|adapter_queue| adapter_queue.to_account_info()),
+            adapter_pda: self.adapter_pda.to_account_info(),
+            event_authority: self.event_authority.to_account_info(),
+            program: self.program.to_account_info(),
+        };
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InvokeAttestMessagesArgs {
+    pub src_chain: u16,
+    pub integrator_program_id: Pubkey,
+    pub messages: Vec<BatchedMessage>,
+    pub mode: BatchMode,
+}
+
+/// Accounts struct for the invoke_attest_messages instruction
+#[derive(Accounts)]
+pub struct InvokeAttestMessages<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The adapter info account
+    /// CHECK: This account is checked by the endpoint program
+    pub adapter_info: UncheckedAccount<'info>,
+
+    /// The adapter stats account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub adapter_stats: UncheckedAccount<'info>,
+
+    /// The adapter PDA account, used for signing
+    #[account(
+        seeds = [ADAPTER_PDA_SEED],
+        bump,
+    )]
+    pub adapter_pda: SystemAccount<'info>,
+
+    /// The integrator chain config account
+    /// CHECK: This account is checked by the endpoint program
+    pub integrator_chain_config: UncheckedAccount<'info>,
+
+    /// The event authority PDA
+    /// CHECK: This should be seeded with `__event_authority`
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = endpoint::id(),
+    )]
+    pub event_authority: AccountInfo<'info>,
+
+    /// CHECK: Self-CPI will fail if the program is n
+    pub program: AccountInfo<'info>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+
+    /// The endpoint program
+    pub endpoint_program: Program<'info, Endpoint>,
+}
+
+impl<'info> InvokeAttestMessages<'info> {
+    /// Helper function to create the CpiContext for the attest_messages instruction
+    pub fn invoke_attest_messages(&self) -> CpiContext<'_, '_, '_, 'info, AttestMessages<'info>> {
+        let cpi_program = self.endpoint_program.to_account_info();
+        let cpi_accounts = AttestMessages {
+            payer: self.payer.to_account_info(),
+            adapter_info: self.adapter_info.to_account_info(),
+            adapter_stats: self.adapter_stats.to_account_info(),
             adapter_pda: self.adapter_pda.to_account_info(),
             integrator_chain_config: self.integrator_chain_config.to_account_info(),
+            event_authority: self.event_authority.to_account_info(),
+            program: self.program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        };
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InvokeAttestMessageByHashArgs {
+    pub message_hash: [u8; 32],
+    pub metadata: Vec<u8>,
+}
+
+/// Accounts struct for the invoke_attest_message_by_hash instruction
+#[derive(Accounts)]
+pub struct InvokeAttestMessageByHash<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The attestation info account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub attestation_info: UncheckedAccount<'info>,
+
+    /// The sequence record account
+    /// CHECK: This account is checked by the endpoint program
+    pub sequence_record: UncheckedAccount<'info>,
+
+    /// The adapter info account
+    /// CHECK: This account is checked by the endpoint program
+    pub adapter_info: UncheckedAccount<'info>,
+
+    /// The adapter stats account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub adapter_stats: UncheckedAccount<'info>,
+
+    /// The adapter PDA account, used for signing
+    #[account(
+        seeds = [ADAPTER_PDA_SEED],
+        bump,
+    )]
+    pub adapter_pda: SystemAccount<'info>,
+
+    /// The integrator chain config account
+    /// CHECK: This account is checked by the endpoint program
+    pub integrator_chain_config: UncheckedAccount<'info>,
+
+    /// The attestation metadata account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub attestation_metadata: Option<UncheckedAccount<'info>>,
+
+    /// The event authority PDA
+    /// CHECK: This should be seeded with `__event_authority`
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = endpoint::id(),
+    )]
+    pub event_authority: AccountInfo<'info>,
+
+    /// CHECK: Self-CPI will fail if the program is n
+    pub program: AccountInfo<'info>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+
+    /// The endpoint program
+    pub endpoint_program: Program<'info, Endpoint>,
+}
+
+impl<'info> InvokeAttestMessageByHash<'info> {
+    /// Helper function to create the CpiContext for the attest_message_by_hash instruction
+    pub fn invoke_attest_message_by_hash(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, AttestMessageByHash<'info>> {
+        let cpi_program = self.endpoint_program.to_account_info();
+        let cpi_accounts = AttestMessageByHash {
+            payer: self.payer.to_account_info(),
             attestation_info: self.attestation_info.to_account_info(),
+            sequence_record: self.sequence_record.to_account_info(),
+            adapter_info: self.adapter_info.to_account_info(),
+            adapter_stats: self.adapter_stats.to_account_info(),
+            adapter_pda: self.adapter_pda.to_account_info(),
+            integrator_chain_config: self.integrator_chain_config.to_account_info(),
+            attestation_metadata: self
+                .attestation_metadata
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            event_authority: self.event_authority.to_account_info(),
+            program: self.program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        };
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InvokeAttestMessageCompressedArgs {
+    pub src_chain: u16,
+    pub src_addr: [u8; 32],
+    pub sequence: u64,
+    pub dst_chain: u16,
+    pub integrator_program_id: Pubkey,
+    pub payload_hash: [u8; 32],
+    pub expiry: Option<MessageExpiry>,
+    pub root: [u8; 32],
+    pub leaf_index: u32,
+    pub previous_leaf: Option<CompressedAttestation>,
+}
+
+/// Accounts struct for the invoke_attest_message_compressed instruction
+#[derive(Accounts)]
+pub struct InvokeAttestMessageCompressed<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The adapter info account
+    /// CHECK: This account is checked by the endpoint program
+    pub adapter_info: UncheckedAccount<'info>,
+
+    /// The adapter stats account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub adapter_stats: UncheckedAccount<'info>,
+
+    /// The adapter PDA account, used for signing
+    #[account(
+        seeds = [ADAPTER_PDA_SEED],
+        bump,
+    )]
+    pub adapter_pda: SystemAccount<'info>,
+
+    /// The integrator chain config account
+    /// CHECK: This account is checked by the endpoint program
+    pub integrator_chain_config: UncheckedAccount<'info>,
+
+    /// The attestation tree account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub attestation_tree: UncheckedAccount<'info>,
+
+    /// The attestation leaf account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub attestation_leaf: UncheckedAccount<'info>,
+
+    /// The sequence record account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub sequence_record: UncheckedAccount<'info>,
+
+    /// The replay window covering the sequence
+    /// CHECK: This account is checked by the endpoint program
+    pub replay_window: UncheckedAccount<'info>,
+
+    /// The event authority PDA
+    /// CHECK: This should be seeded with `__event_authority`
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = endpoint::id(),
+    )]
+    pub event_authority: AccountInfo<'info>,
+
+    /// CHECK: Self-CPI will fail if the program is n
+    pub program: AccountInfo<'info>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+
+    /// The endpoint program
+    pub endpoint_program: Program<'info, Endpoint>,
+}
+
+impl<'info> InvokeAttestMessageCompressed<'info> {
+    /// Helper function to create the CpiContext for the attest_message_compressed instruction
+    pub fn invoke_attest_message_compressed(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, AttestMessageCompressed<'info>> {
+        let cpi_program = self.endpoint_program.to_account_info();
+        let cpi_accounts = AttestMessageCompressed {
+            payer: self.payer.to_account_info(),
+            adapter_info: self.adapter_info.to_account_info(),
+            adapter_stats: self.adapter_stats.to_account_info(),
+            adapter_pda: self.adapter_pda.to_account_info(),
+            integrator_chain_config: self.integrator_chain_config.to_account_info(),
+            attestation_tree: self.attestation_tree.to_account_info(),
+            attestation_leaf: self.attestation_leaf.to_account_info(),
+            sequence_record: self.sequence_record.to_account_info(),
+            replay_window: self.replay_window.to_account_info(),
             event_authority: self.event_authority.to_account_info(),
             program: self.program.to_account_info(),
             system_program: self.system_program.to_account_info(),
@@ -2,8 +2,15 @@ use anchor_lang::prelude::*;
 use endpoint::program::Endpoint;
 use endpoint::{self};
 use endpoint::{
-    cpi::accounts::{RecvMessage, Register, SendMessage},
-    instructions::{RegisterArgs, SendMessageArgs},
+    cpi::accounts::{
+        CancelMessage, CloseSentMessageRecord, RecvMessage, RecvMessageCompressed, Register,
+        RequestRepickup, SendMessage, SendMessageMulti,
+    },
+    instructions::{
+        CancelMessageArgs, CloseSentMessageRecordArgs, MessageDestination, RegisterArgs,
+        RequestRepickupArgs, SendMessageArgs, SendMessageMultiArgs,
+    },
+    state::MessageExpiry,
 };
 
 declare_id!("661Ly6gSCDiGWzC4tKJhS8tqXNWJU6yfbhxNKC4gPF5t");
@@ -34,21 +41,127 @@ pub mod mock_integrator {
         Ok(())
     }
 
-    pub fn invoke_send_message(
-        ctx: Context<InvokeSendMessage>,
+    /// Invokes the send_message instruction on the endpoint program via CPI,
+    /// forwarding the accounts of the pushed send adapters and any adapter queues from the
+    /// remaining accounts
+    pub fn invoke_send_message<'info>(
+        ctx: Context<'_, '_, '_, 'info, InvokeSendMessage<'info>>,
         args: InvokeSendMessageArgs,
     ) -> Result<()> {
         let bump_seed = &[ctx.bumps.integrator_program_pda][..];
         let signer_seeds: &[&[&[u8]]] = &[&[b"endpoint_integrator", bump_seed]];
 
         endpoint::cpi::send_message(
-            ctx.accounts.invoke_send_message().with_signer(signer_seeds),
+            ctx.accounts
+                .invoke_send_message()
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+                .with_signer(signer_seeds),
             SendMessageArgs {
                 integrator_program_id: crate::ID,
                 integrator_program_pda_bump: ctx.bumps.integrator_program_pda,
                 dst_chain: args.dst_chain,
                 dst_addr: args.dst_addr,
                 payload_hash: args.payload_hash,
+                consistency_level: args.consistency_level,
+                expiry: args.expiry,
+                push_account_counts: args.push_account_counts,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Invokes the send_message_multi instruction on the endpoint program via CPI,
+    /// forwarding the chain configs and outbox messages from the remaining accounts
+    pub fn invoke_send_message_multi<'info>(
+        ctx: Context<'_, '_, '_, 'info, InvokeSendMessageMulti<'info>>,
+        args: InvokeSendMessageMultiArgs,
+    ) -> Result<()> {
+        let bump_seed = &[ctx.bumps.integrator_program_pda][..];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"endpoint_integrator", bump_seed]];
+
+        endpoint::cpi::send_message_multi(
+            ctx.accounts
+                .invoke_send_message_multi()
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+                .with_signer(signer_seeds),
+            SendMessageMultiArgs {
+                integrator_program_id: crate::ID,
+                integrator_program_pda_bump: ctx.bumps.integrator_program_pda,
+                destinations: args.destinations,
+                payload_hash: args.payload_hash,
+                consistency_level: args.consistency_level,
+                expiry: args.expiry,
+                record: args.record,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Invokes the request_repickup instruction on the endpoint program via CPI,
+    /// forwarding any adapter queues from the remaining accounts
+    pub fn invoke_request_repickup<'info>(
+        ctx: Context<'_, '_, '_, 'info, InvokeRequestRepickup<'info>>,
+        args: InvokeRequestRepickupArgs,
+    ) -> Result<()> {
+        let bump_seed = &[ctx.bumps.integrator_program_pda][..];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"endpoint_integrator", bump_seed]];
+
+        endpoint::cpi::request_repickup(
+            ctx.accounts
+                .invoke_request_repickup()
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+                .with_signer(signer_seeds),
+            RequestRepickupArgs {
+                integrator_program_id: crate::ID,
+                integrator_program_pda_bump: ctx.bumps.integrator_program_pda,
+                sequence: args.sequence,
+                dst_chain: args.dst_chain,
+                dst_addr: args.dst_addr,
+                payload_hash: args.payload_hash,
+                expiry: args.expiry,
+                adapters: args.adapters,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Invokes the cancel_message instruction on the endpoint program via CPI,
+    /// forwarding any adapter queues from the remaining accounts
+    pub fn invoke_cancel_message<'info>(
+        ctx: Context<'_, '_, '_, 'info, InvokeCancelMessage<'info>>,
+    ) -> Result<()> {
+        let bump_seed = &[ctx.bumps.integrator_program_pda][..];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"endpoint_integrator", bump_seed]];
+
+        endpoint::cpi::cancel_message(
+            ctx.accounts
+                .invoke_cancel_message()
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+                .with_signer(signer_seeds),
+            CancelMessageArgs {
+                integrator_program_id: crate::ID,
+                integrator_program_pda_bump: ctx.bumps.integrator_program_pda,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Invokes the close_sent_message_record instruction on the endpoint program via CPI
+    pub fn invoke_close_sent_message_record(
+        ctx: Context<InvokeCloseSentMessageRecord>,
+        args: InvokeCloseSentMessageRecordArgs,
+    ) -> Result<()> {
+        let bump_seed = &[ctx.bumps.integrator_program_pda][..];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"endpoint_integrator", bump_seed]];
+
+        endpoint::cpi::close_sent_message_record(
+            ctx.accounts
+                .invoke_close_sent_message_record()
+                .with_signer(signer_seeds),
+            CloseSentMessageRecordArgs {
+                integrator_program_id: crate::ID,
+                integrator_program_pda_bump: ctx.bumps.integrator_program_pda,
+                sequence: args.sequence,
             },
         )?;
         Ok(())
@@ -72,6 +185,48 @@ pub mod mock_integrator {
         Ok(())
     }
 
+    /// Invokes the recv_message_by_hash instruction on the endpoint program via CPI
+    pub fn invoke_recv_message_by_hash(
+        ctx: Context<InvokeRecvMessage>,
+        args: endpoint::instructions::RecvMessageByHashArgs,
+    ) -> Result<()> {
+        // Prepare the seeds for PDA signing
+        let bump_seed = &[ctx.bumps.integrator_program_pda][..];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"endpoint_integrator", bump_seed]];
+
+        // Perform the CPI call to the endpoint program's recv_message_by_hash instruction
+        endpoint::cpi::recv_message_by_hash(
+            ctx.accounts
+                .invoke_recv_message_by_hash()
+                .with_signer(signer_seeds),
+            args,
+        )?;
+
+        Ok(())
+    }
+
+    /// Invokes the recv_message_compressed instruction on the endpoint program via CPI,
+    /// forwarding the leaf's proof from the remaining accounts
+    pub fn invoke_recv_message_compressed<'info>(
+        ctx: Context<'_, '_, '_, 'info, InvokeRecvMessageCompressed<'info>>,
+        args: endpoint::instructions::RecvMessageCompressedArgs,
+    ) -> Result<()> {
+        // Prepare the seeds for PDA signing
+        let bump_seed = &[ctx.bumps.integrator_program_pda][..];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"endpoint_integrator", bump_seed]];
+
+        // Perform the CPI call to the endpoint program's recv_message_compressed instruction
+        endpoint::cpi::recv_message_compressed(
+            ctx.accounts
+                .invoke_recv_message_compressed()
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec())
+                .with_signer(signer_seeds),
+            args,
+        )?;
+
+        Ok(())
+    }
+
     /// Invokes the exec_message instruction on the endpoint program via CPI
     pub fn invoke_exec_message(
         ctx: Context<InvokeExecMessage>,
@@ -89,6 +244,26 @@ pub mod mock_integrator {
 
         Ok(())
     }
+
+    /// Invokes the exec_message_by_hash instruction on the endpoint program via CPI
+    pub fn invoke_exec_message_by_hash(
+        ctx: Context<InvokeExecMessage>,
+        args: endpoint::instructions::ExecMessageByHashArgs,
+    ) -> Result<()> {
+        // Prepare the seeds for PDA signing
+        let bump_seed = &[ctx.bumps.integrator_program_pda][..];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"endpoint_integrator", bump_seed]];
+
+        // Perform the CPI call to the endpoint program's exec_message_by_hash instruction
+        endpoint::cpi::exec_message_by_hash(
+            ctx.accounts
+                .invoke_exec_message_by_hash()
+                .with_signer(signer_seeds),
+            args,
+        )?;
+
+        Ok(())
+    }
 }
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct InvokeRegisterArgs {
@@ -109,6 +284,9 @@ pub struct InvokeRegister<'info> {
     /// CHECK: This account is to be checked and initialized by the endpoint program
     pub sequence_tracker: UncheckedAccount<'info>,
 
+    /// CHECK: This account is checked by the endpoint program
+    pub integrator_tombstone: UncheckedAccount<'info>,
+
     /// The integrator program's PDA
     #[account(
         seeds = [b"endpoint_integrator"],
@@ -142,6 +320,7 @@ impl<'info> InvokeRegister<'info> {
             integrator_config: self.integrator_config.to_account_info(),
             integrator_program_pda: self.integrator_program_pda.to_account_info(),
             sequence_tracker: self.sequence_tracker.to_account_info(),
+            integrator_tombstone: self.integrator_tombstone.to_account_info(),
             system_program: self.system_program.to_account_info(),
             event_authority: self.event_authority.to_account_info(),
             program: self.program.to_account_info(),
@@ -155,6 +334,9 @@ pub struct InvokeSendMessageArgs {
     pub dst_chain: u16,
     pub dst_addr: [u8; 32],
     pub payload_hash: [u8; 32],
+    pub consistency_level: u8,
+    pub expiry: Option<MessageExpiry>,
+    pub push_account_counts: Vec<u8>,
 }
 
 #[derive(Accounts)]
@@ -174,7 +356,14 @@ pub struct InvokeSendMessage<'info> {
 
     #[account(mut)]
     /// CHECK: This account is initialized by the endpoint program
-    pub outbox_message: Signer<'info>,
+    pub outbox_message: Option<Signer<'info>>,
+
+    /// CHECK: This account is checked by the endpoint program
+    pub outbound_authority: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: This account is initialized by the endpoint program
+    pub sent_message_record: Option<UncheckedAccount<'info>>,
 
     #[account(mut)]
     /// CHECK: This account is checked by the endpoint program
@@ -204,7 +393,18 @@ impl<'info> InvokeSendMessage<'info> {
             integrator_program_pda: self.integrator_program_pda.to_account_info(),
             payer: self.payer.to_account_info(),
             integrator_chain_config: self.integrator_chain_config.to_account_info(),
-            outbox_message: self.outbox_message.to_account_info(),
+            outbox_message: self
+                .outbox_message
+                .as_ref()
+                .map(|outbox_message| outbox_message.to_account_info()),
+            outbound_authority: self
+                .outbound_authority
+                .as_ref()
+                .map(|outbound_authority| outbound_authority.to_account_info()),
+            sent_message_record: self
+                .sent_message_record
+                .as_ref()
+                .map(|sent_message_record| sent_message_record.to_account_info()),
             sequence_tracker: self.sequence_tracker.to_account_info(),
             system_program: self.system_program.to_account_info(),
             event_authority: self.event_authority.to_account_info(),
@@ -214,6 +414,227 @@ impl<'info> InvokeSendMessage<'info> {
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InvokeSendMessageMultiArgs {
+    pub destinations: Vec<MessageDestination>,
+    pub payload_hash: [u8; 32],
+    pub consistency_level: u8,
+    pub expiry: Option<MessageExpiry>,
+    pub record: bool,
+}
+
+#[derive(Accounts)]
+pub struct InvokeSendMessageMulti<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"endpoint_integrator"],
+        bump,
+    )]
+    pub integrator_program_pda: SystemAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: This account is checked by the endpoint program
+    pub sequence_tracker: UncheckedAccount<'info>,
+
+    /// The event authority PDA
+    /// CHECK: This should be seeded with `__event_authority`
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = endpoint::id(),
+    )]
+    pub event_authority: AccountInfo<'info>,
+
+    /// CHECK: Self-CPI will fail if the program is n
+    pub program: AccountInfo<'info>,
+
+    pub endpoint_program: Program<'info, Endpoint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InvokeSendMessageMulti<'info> {
+    pub fn invoke_send_message_multi(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, SendMessageMulti<'info>> {
+        let cpi_program = self.endpoint_program.to_account_info();
+        let cpi_accounts = SendMessageMulti {
+            integrator_program_pda: self.integrator_program_pda.to_account_info(),
+            payer: self.payer.to_account_info(),
+            sequence_tracker: self.sequence_tracker.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            event_authority: self.event_authority.to_account_info(),
+            program: self.program.to_account_info(),
+        };
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InvokeRequestRepickupArgs {
+    pub sequence: u64,
+    pub dst_chain: u16,
+    pub dst_addr: [u8; 32],
+    pub payload_hash: [u8; 32],
+    pub expiry: Option<MessageExpiry>,
+    pub adapters: u128,
+}
+
+#[derive(Accounts)]
+pub struct InvokeRequestRepickup<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"endpoint_integrator"],
+        bump,
+    )]
+    pub integrator_program_pda: SystemAccount<'info>,
+
+    /// CHECK: This account is checked by the endpoint program
+    pub sent_message_record: UncheckedAccount<'info>,
+
+    /// CHECK: This account is checked by the endpoint program
+    pub integrator_chain_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: This account is checked by the endpoint program
+    pub sequence_tracker: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: This account is initialized by the endpoint program
+    pub outbox_message: Signer<'info>,
+
+    /// The event authority PDA
+    /// CHECK: This should be seeded with `__event_authority`
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = endpoint::id(),
+    )]
+    pub event_authority: AccountInfo<'info>,
+
+    /// CHECK: Self-CPI will fail if the program is n
+    pub program: AccountInfo<'info>,
+
+    pub endpoint_program: Program<'info, Endpoint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InvokeRequestRepickup<'info> {
+    pub fn invoke_request_repickup(&self) -> CpiContext<'_, '_, '_, 'info, RequestRepickup<'info>> {
+        let cpi_program = self.endpoint_program.to_account_info();
+        let cpi_accounts = RequestRepickup {
+            payer: self.payer.to_account_info(),
+            integrator_program_pda: self.integrator_program_pda.to_account_info(),
+            sent_message_record: self.sent_message_record.to_account_info(),
+            integrator_chain_config: self.integrator_chain_config.to_account_info(),
+            sequence_tracker: self.sequence_tracker.to_account_info(),
+            outbox_message: self.outbox_message.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            event_authority: self.event_authority.to_account_info(),
+            program: self.program.to_account_info(),
+        };
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+#[derive(Accounts)]
+pub struct InvokeCancelMessage<'info> {
+    #[account(
+        seeds = [b"endpoint_integrator"],
+        bump,
+    )]
+    pub integrator_program_pda: SystemAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: This account is checked by the endpoint program
+    pub outbox_message: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: This account is checked by the endpoint program
+    pub sequence_tracker: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: This account is checked by the endpoint program
+    pub sent_message_record: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: This account is checked by the endpoint program
+    pub refund_recipient: UncheckedAccount<'info>,
+
+    /// The event authority PDA
+    /// CHECK: This should be seeded with `__event_authority`
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = endpoint::id(),
+    )]
+    pub event_authority: AccountInfo<'info>,
+
+    /// CHECK: Self-CPI will fail if the program is n
+    pub program: AccountInfo<'info>,
+
+    pub endpoint_program: Program<'info, Endpoint>,
+}
+
+impl<'info> InvokeCancelMessage<'info> {
+    pub fn invoke_cancel_message(&self) -> CpiContext<'_, '_, '_, 'info, CancelMessage<'info>> {
+        let cpi_program = self.endpoint_program.to_account_info();
+        let cpi_accounts = CancelMessage {
+            integrator_program_pda: self.integrator_program_pda.to_account_info(),
+            outbox_message: self.outbox_message.to_account_info(),
+            sequence_tracker: self.sequence_tracker.to_account_info(),
+            sent_message_record: self.sent_message_record.to_account_info(),
+            refund_recipient: self.refund_recipient.to_account_info(),
+            event_authority: self.event_authority.to_account_info(),
+            program: self.program.to_account_info(),
+        };
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InvokeCloseSentMessageRecordArgs {
+    pub sequence: u64,
+}
+
+#[derive(Accounts)]
+pub struct InvokeCloseSentMessageRecord<'info> {
+    #[account(
+        seeds = [b"endpoint_integrator"],
+        bump,
+    )]
+    pub integrator_program_pda: SystemAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: This account is checked by the endpoint program
+    pub sent_message_record: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: This account is checked by the endpoint program
+    pub payer: UncheckedAccount<'info>,
+
+    pub endpoint_program: Program<'info, Endpoint>,
+}
+
+impl<'info> InvokeCloseSentMessageRecord<'info> {
+    pub fn invoke_close_sent_message_record(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, CloseSentMessageRecord<'info>> {
+        let cpi_program = self.endpoint_program.to_account_info();
+        let cpi_accounts = CloseSentMessageRecord {
+            integrator_program_pda: self.integrator_program_pda.to_account_info(),
+            sent_message_record: self.sent_message_record.to_account_info(),
+            payer: self.payer.to_account_info(),
+        };
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
 /// Accounts struct for the invoke_recv_message instruction
 #[derive(Accounts)]
 pub struct InvokeRecvMessage<'info> {
@@ -235,6 +656,31 @@ pub struct InvokeRecvMessage<'info> {
     /// CHECK: This account is checked by the endpoint program
     pub integrator_chain_config: UncheckedAccount<'info>,
 
+    /// The sequence record account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub sequence_record: UncheckedAccount<'info>,
+
+    /// The inbound cursor account, required when ordered delivery is enabled
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub inbound_cursor: Option<UncheckedAccount<'info>>,
+
+    /// The replay floor account, required when replay windows are enabled
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub replay_floor: Option<UncheckedAccount<'info>>,
+
+    /// The replay window account, required when replay windows are enabled
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub replay_window: Option<UncheckedAccount<'info>>,
+
+    /// The recipient of the attestation info's rent, if it is not the payer
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub refund_recipient: Option<UncheckedAccount<'info>>,
+
     /// The event authority PDA
     /// CHECK: This should be seeded with `__event_authority`
     #[account(
@@ -263,6 +709,158 @@ impl<'info> InvokeRecvMessage<'info> {
             payer: self.payer.to_account_info(),
             attestation_info: self.attestation_info.to_account_info(),
             integrator_chain_config: self.integrator_chain_config.to_account_info(),
+            sequence_record: self.sequence_record.to_account_info(),
+            inbound_cursor: self
+                .inbound_cursor
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            replay_floor: self
+                .replay_floor
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            replay_window: self
+                .replay_window
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            refund_recipient: self
+                .refund_recipient
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            system_program: self.system_program.to_account_info(),
+            event_authority: self.event_authority.to_account_info(),
+            program: self.program.to_account_info(),
+        };
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Helper function to create the CpiContext for the recv_message_by_hash instruction
+    pub fn invoke_recv_message_by_hash(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, endpoint::cpi::accounts::RecvMessageByHash<'info>> {
+        let cpi_program = self.endpoint_program.to_account_info();
+        let cpi_accounts = endpoint::cpi::accounts::RecvMessageByHash {
+            payer: self.payer.to_account_info(),
+            attestation_info: self.attestation_info.to_account_info(),
+            sequence_record: self.sequence_record.to_account_info(),
+            integrator_program_pda: self.integrator_program_pda.to_account_info(),
+            integrator_chain_config: self.integrator_chain_config.to_account_info(),
+            inbound_cursor: self
+                .inbound_cursor
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            replay_floor: self
+                .replay_floor
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            replay_window: self
+                .replay_window
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            refund_recipient: self
+                .refund_recipient
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            system_program: self.system_program.to_account_info(),
+            event_authority: self.event_authority.to_account_info(),
+            program: self.program.to_account_info(),
+        };
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Accounts struct for the invoke_recv_message_compressed instruction
+#[derive(Accounts)]
+pub struct InvokeRecvMessageCompressed<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"endpoint_integrator"],
+        bump,
+    )]
+    pub integrator_program_pda: SystemAccount<'info>,
+
+    /// The integrator chain config account
+    /// CHECK: This account is checked by the endpoint program
+    pub integrator_chain_config: UncheckedAccount<'info>,
+
+    /// The attestation tree account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub attestation_tree: UncheckedAccount<'info>,
+
+    /// The attestation leaf account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub attestation_leaf: UncheckedAccount<'info>,
+
+    /// The sequence record account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub sequence_record: UncheckedAccount<'info>,
+
+    /// The inbound cursor account, required when ordered delivery is enabled
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub inbound_cursor: Option<UncheckedAccount<'info>>,
+
+    /// The replay floor account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub replay_floor: UncheckedAccount<'info>,
+
+    /// The replay window account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub replay_window: UncheckedAccount<'info>,
+
+    /// The recipient of the attestation leaf's rent, if it is not the payer
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub refund_recipient: Option<UncheckedAccount<'info>>,
+
+    /// The event authority PDA
+    /// CHECK: This should be seeded with `__event_authority`
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = endpoint::id(),
+    )]
+    pub event_authority: AccountInfo<'info>,
+
+    /// CHECK: Self-CPI will fail if the program is n
+    pub program: AccountInfo<'info>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+
+    /// The endpoint program
+    pub endpoint_program: Program<'info, Endpoint>,
+}
+
+impl<'info> InvokeRecvMessageCompressed<'info> {
+    /// Helper function to create the CpiContext for the recv_message_compressed instruction
+    pub fn invoke_recv_message_compressed(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, RecvMessageCompressed<'info>> {
+        let cpi_program = self.endpoint_program.to_account_info();
+        let cpi_accounts = RecvMessageCompressed {
+            payer: self.payer.to_account_info(),
+            integrator_program_pda: self.integrator_program_pda.to_account_info(),
+            integrator_chain_config: self.integrator_chain_config.to_account_info(),
+            attestation_tree: self.attestation_tree.to_account_info(),
+            attestation_leaf: self.attestation_leaf.to_account_info(),
+            sequence_record: self.sequence_record.to_account_info(),
+            inbound_cursor: self
+                .inbound_cursor
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            replay_floor: self.replay_floor.to_account_info(),
+            replay_window: self.replay_window.to_account_info(),
+            refund_recipient: self
+                .refund_recipient
+                .as_ref()
+                .map(|account| account.to_account_info()),
             system_program: self.system_program.to_account_info(),
             event_authority: self.event_authority.to_account_info(),
             program: self.program.to_account_info(),
@@ -287,6 +885,35 @@ pub struct InvokeExecMessage<'info> {
     #[account(mut)]
     pub attestation_info: UncheckedAccount<'info>,
 
+    /// The integrator chain config account of the source chain, which may not exist
+    /// CHECK: This account is checked by the endpoint program
+    pub integrator_chain_config: UncheckedAccount<'info>,
+
+    /// The sequence record account
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub sequence_record: UncheckedAccount<'info>,
+
+    /// The inbound cursor account, required when ordered delivery is enabled
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub inbound_cursor: Option<UncheckedAccount<'info>>,
+
+    /// The replay floor account, required when replay windows are enabled
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub replay_floor: Option<UncheckedAccount<'info>>,
+
+    /// The replay window account, required when replay windows are enabled
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub replay_window: Option<UncheckedAccount<'info>>,
+
+    /// The recipient of the attestation info's rent, if it is not the payer
+    /// CHECK: This account is checked by the endpoint program
+    #[account(mut)]
+    pub refund_recipient: Option<UncheckedAccount<'info>>,
+
     /// The event authority PDA
     /// CHECK: This should be seeded with `__event_authority`
     #[account(
@@ -316,6 +943,58 @@ impl<'info> InvokeExecMessage<'info> {
             payer: self.payer.to_account_info(),
             integrator_program_pda: self.integrator_program_pda.to_account_info(),
             attestation_info: self.attestation_info.to_account_info(),
+            integrator_chain_config: self.integrator_chain_config.to_account_info(),
+            sequence_record: self.sequence_record.to_account_info(),
+            inbound_cursor: self
+                .inbound_cursor
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            replay_floor: self
+                .replay_floor
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            replay_window: self
+                .replay_window
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            refund_recipient: self
+                .refund_recipient
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            system_program: self.system_program.to_account_info(),
+            event_authority: self.event_authority.to_account_info(),
+            program: self.program.to_account_info(),
+        };
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Helper function to create the CpiContext for the exec_message_by_hash instruction
+    pub fn invoke_exec_message_by_hash(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, endpoint::cpi::accounts::ExecMessageByHash<'info>> {
+        let cpi_program = self.endpoint_program.to_account_info();
+        let cpi_accounts = endpoint::cpi::accounts::ExecMessageByHash {
+            payer: self.payer.to_account_info(),
+            attestation_info: self.attestation_info.to_account_info(),
+            sequence_record: self.sequence_record.to_account_info(),
+            integrator_program_pda: self.integrator_program_pda.to_account_info(),
+            integrator_chain_config: self.integrator_chain_config.to_account_info(),
+            inbound_cursor: self
+                .inbound_cursor
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            replay_floor: self
+                .replay_floor
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            replay_window: self
+                .replay_window
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            refund_recipient: self
+                .refund_recipient
+                .as_ref()
+                .map(|account| account.to_account_info()),
             system_program: self.system_program.to_account_info(),
             event_authority: self.event_authority.to_account_info(),
             program: self.program.to_account_info(),
@@ -0,0 +1,403 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::cancel_message::cancel_message;
+use crate::instructions::enable_adapter::enable_send_adapter;
+use crate::instructions::init_adapter_queue::init_adapter_queue;
+use crate::instructions::pick_up_message::pick_up_message;
+use crate::instructions::register::register;
+use crate::instructions::send_message::{send_message_with_options, SendMessageOptions};
+use crate::instructions::send_message_multi::send_message_multi;
+
+use anchor_lang::prelude::*;
+use common::error::custom_error;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::instructions::MessageDestination;
+use endpoint::state::{
+    AdapterInfo, AdapterQueue, IntegratorChainConfig, IntegratorConfig, SequenceTracker,
+};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const DST_CHAIN: u16 = 2;
+const DST_ADDR: [u8; 32] = [1u8; 32];
+const PAYLOAD_HASH: [u8; 32] = [2u8; 32];
+
+/// Registers the mock integrator with the mock adapter and `other_adapter` enabled for sending
+/// to `DST_CHAIN`, each with a queue
+async fn setup_test_environment(other_adapter: Pubkey) -> (ProgramTestContext, Keypair, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    for adapter_program_id in [mock_adapter::id(), other_adapter] {
+        let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+        add_adapter(
+            &mut context,
+            &admin,
+            &payer,
+            integrator_config_pda,
+            adapter_info_pda,
+            integrator_program_id,
+            adapter_program_id,
+        )
+        .await
+        .unwrap();
+
+        enable_send_adapter(
+            &mut context,
+            &admin,
+            &payer,
+            integrator_config_pda,
+            IntegratorChainConfig::pda(&integrator_program_id, DST_CHAIN).0,
+            adapter_info_pda,
+            DST_CHAIN,
+            adapter_program_id,
+            integrator_program_id,
+        )
+        .await
+        .unwrap();
+
+        init_adapter_queue(
+            &mut context,
+            &admin,
+            &payer,
+            integrator_program_id,
+            adapter_program_id,
+        )
+        .await
+        .unwrap();
+    }
+
+    (context, payer, admin)
+}
+
+async fn send(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    outbox_message: &Keypair,
+    adapter_queues: &[Pubkey],
+) -> std::result::Result<(), BanksClientError> {
+    send_message_with_options(
+        context,
+        payer,
+        Some(outbox_message),
+        DST_CHAIN,
+        DST_ADDR,
+        PAYLOAD_HASH,
+        SendMessageOptions {
+            adapter_queues,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// The queues of the mock adapter and `other_adapter`, in adapter index order
+fn adapter_queues(other_adapter: Pubkey) -> [Pubkey; 2] {
+    [
+        AdapterQueue::pda(&mock_integrator::id(), &mock_adapter::id()).0,
+        AdapterQueue::pda(&mock_integrator::id(), &other_adapter).0,
+    ]
+}
+
+async fn pending(context: &mut ProgramTestContext, adapter_program_id: Pubkey) -> Vec<Pubkey> {
+    let (adapter_queue_pda, _) = AdapterQueue::pda(&mock_integrator::id(), &adapter_program_id);
+    let adapter_queue: AdapterQueue =
+        get_account(&mut context.banks_client, adapter_queue_pda).await;
+    adapter_queue.pending()
+}
+
+#[tokio::test]
+async fn test_adapter_queue_send_and_pick_up() {
+    let other_adapter = Keypair::new().pubkey();
+    let (mut context, payer, _) = setup_test_environment(other_adapter).await;
+    let integrator_program_id = mock_integrator::id();
+
+    let (adapter_queue_pda, _) = AdapterQueue::pda(&integrator_program_id, &mock_adapter::id());
+    let adapter_queue: AdapterQueue =
+        get_account(&mut context.banks_client, adapter_queue_pda).await;
+    assert_eq!(adapter_queue.index, 0);
+    assert_eq!(adapter_queue.adapter_program_id, mock_adapter::id());
+
+    // Both adapters are marked as having a queue
+    let (sequence_tracker_pda, _) = SequenceTracker::pda(&integrator_program_id);
+    let sequence_tracker: SequenceTracker =
+        get_account(&mut context.banks_client, sequence_tracker_pda).await;
+    assert_eq!(sequence_tracker.queued_adapters.as_value(), 0b11);
+
+    let outbox_message = Keypair::new();
+    send(
+        &mut context,
+        &payer,
+        &outbox_message,
+        &adapter_queues(other_adapter),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        pending(&mut context, mock_adapter::id()).await,
+        vec![outbox_message.pubkey()]
+    );
+    assert_eq!(
+        pending(&mut context, other_adapter).await,
+        vec![outbox_message.pubkey()]
+    );
+
+    // Picking the message up only removes it from the mock adapter's queue
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &mock_adapter::id());
+    let (adapter_pda, _) = Pubkey::find_program_address(&[b"adapter_pda"], &mock_adapter::id());
+    pick_up_message(
+        &mut context,
+        &payer,
+        outbox_message.pubkey(),
+        adapter_info_pda,
+        adapter_pda,
+        payer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    assert!(pending(&mut context, mock_adapter::id()).await.is_empty());
+    assert_eq!(
+        pending(&mut context, other_adapter).await,
+        vec![outbox_message.pubkey()]
+    );
+}
+
+#[tokio::test]
+async fn test_adapter_queue_cancel_message() {
+    let other_adapter = Keypair::new().pubkey();
+    let (mut context, payer, _) = setup_test_environment(other_adapter).await;
+    let outbox_message = Keypair::new();
+
+    send(
+        &mut context,
+        &payer,
+        &outbox_message,
+        &adapter_queues(other_adapter),
+    )
+    .await
+    .unwrap();
+
+    // The message must be removed from the queue of every outstanding adapter
+    let result = cancel_message(
+        &mut context,
+        &payer,
+        outbox_message.pubkey(),
+        payer.pubkey(),
+        &adapter_queues(other_adapter)[..1],
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        custom_error(0, EndpointError::InvalidAdapterQueue)
+    );
+
+    cancel_message(
+        &mut context,
+        &payer,
+        outbox_message.pubkey(),
+        payer.pubkey(),
+        &adapter_queues(other_adapter),
+    )
+    .await
+    .unwrap();
+
+    assert!(pending(&mut context, mock_adapter::id()).await.is_empty());
+    assert!(pending(&mut context, other_adapter).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_adapter_queue_missing() {
+    let other_adapter = Keypair::new().pubkey();
+    let (mut context, payer, _) = setup_test_environment(other_adapter).await;
+
+    // Every outstanding adapter with a queue must be pushed the message
+    for adapter_queues in [&adapter_queues(other_adapter)[..1], &[]] {
+        let result = send(&mut context, &payer, &Keypair::new(), adapter_queues).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            custom_error(0, EndpointError::InvalidAdapterQueue)
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_adapter_queue_full() {
+    let other_adapter = Keypair::new().pubkey();
+    let (mut context, payer, _) = setup_test_environment(other_adapter).await;
+
+    let outbox_messages: Vec<_> = (0..AdapterQueue::CAPACITY + 1)
+        .map(|_| Keypair::new())
+        .collect();
+    for outbox_message in &outbox_messages {
+        send(
+            &mut context,
+            &payer,
+            outbox_message,
+            &adapter_queues(other_adapter),
+        )
+        .await
+        .unwrap();
+    }
+
+    // Sending to a full queue overwrites the oldest entry
+    let expected: Vec<Pubkey> = outbox_messages[1..]
+        .iter()
+        .map(|outbox_message| outbox_message.pubkey())
+        .collect();
+    assert_eq!(pending(&mut context, mock_adapter::id()).await, expected);
+    let (adapter_queue_pda, _) = AdapterQueue::pda(&mock_integrator::id(), &mock_adapter::id());
+    let adapter_queue: AdapterQueue =
+        get_account(&mut context.banks_client, adapter_queue_pda).await;
+    assert_eq!(adapter_queue.overwritten, 1);
+
+    // The overwritten message can still be cancelled
+    cancel_message(
+        &mut context,
+        &payer,
+        outbox_messages[0].pubkey(),
+        payer.pubkey(),
+        &adapter_queues(other_adapter),
+    )
+    .await
+    .unwrap();
+    assert_eq!(pending(&mut context, mock_adapter::id()).await, expected);
+}
+
+#[tokio::test]
+async fn test_adapter_queue_send_message_multi() {
+    let other_adapter = Keypair::new().pubkey();
+    let (mut context, payer, _) = setup_test_environment(other_adapter).await;
+    let destinations = vec![
+        MessageDestination {
+            dst_chain: DST_CHAIN,
+            dst_addr: DST_ADDR,
+        },
+        MessageDestination {
+            dst_chain: DST_CHAIN,
+            dst_addr: [3u8; 32],
+        },
+    ];
+    let outbox_messages = [Keypair::new(), Keypair::new()];
+
+    // The queues are required as for `send_message`
+    let result = send_message_multi(
+        &mut context,
+        &payer,
+        destinations.clone(),
+        &outbox_messages,
+        PAYLOAD_HASH,
+        false,
+        &[],
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        custom_error(0, EndpointError::InvalidAdapterQueue)
+    );
+
+    send_message_multi(
+        &mut context,
+        &payer,
+        destinations,
+        &outbox_messages,
+        PAYLOAD_HASH,
+        false,
+        &adapter_queues(other_adapter),
+    )
+    .await
+    .unwrap();
+
+    // Each queue holds every message, in sequence order
+    let expected = vec![outbox_messages[0].pubkey(), outbox_messages[1].pubkey()];
+    assert_eq!(pending(&mut context, mock_adapter::id()).await, expected);
+    assert_eq!(pending(&mut context, other_adapter).await, expected);
+}
+
+#[tokio::test]
+async fn test_adapter_queue_out_of_order() {
+    let other_adapter = Keypair::new().pubkey();
+    let (mut context, payer, _) = setup_test_environment(other_adapter).await;
+    let integrator_program_id = mock_integrator::id();
+
+    let result = send(
+        &mut context,
+        &payer,
+        &Keypair::new(),
+        &[
+            AdapterQueue::pda(&integrator_program_id, &other_adapter).0,
+            AdapterQueue::pda(&integrator_program_id, &mock_adapter::id()).0,
+        ],
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        custom_error(0, EndpointError::InvalidAdapterQueue)
+    );
+}
+
+#[tokio::test]
+async fn test_init_adapter_queue_not_admin() {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        AdapterInfo::pda(&integrator_program_id, &mock_adapter::id()).0,
+        integrator_program_id,
+        mock_adapter::id(),
+    )
+    .await
+    .unwrap();
+
+    let result = init_adapter_queue(
+        &mut context,
+        &Keypair::new(),
+        &payer,
+        integrator_program_id,
+        mock_adapter::id(),
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        custom_error(0, EndpointError::CallerNotAuthorized)
+    );
+}
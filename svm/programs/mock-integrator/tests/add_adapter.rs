@@ -10,7 +10,7 @@ use crate::instructions::transfer_admin::transfer_admin;
 use anchor_lang::prelude::*;
 use common::setup::{get_account, setup};
 use endpoint::error::EndpointError;
-use endpoint::state::{AdapterInfo, IntegratorConfig};
+use endpoint::state::{AdapterIndex, AdapterInfo, IntegratorConfig};
 use solana_program_test::*;
 use solana_sdk::{
     instruction::InstructionError, signature::Keypair, signer::Signer,
@@ -94,6 +94,14 @@ async fn test_add_adapter_success() {
         get_account(&mut context.banks_client, integrator_config_pda).await;
     assert_eq!(integrator_config.adapter_infos.len(), 1);
     assert_eq!(integrator_config.adapter_infos[0], adapter_program_id);
+
+    // Verify that the adapter's reverse index entry for the integrator has been created
+    let (adapter_index_pda, _) = AdapterIndex::pda(&adapter_program_id, &integrator_program_id);
+    let adapter_index: AdapterIndex =
+        get_account(&mut context.banks_client, adapter_index_pda).await;
+    assert_eq!(adapter_index.adapter_program_id, adapter_program_id);
+    assert_eq!(adapter_index.integrator_program_id, integrator_program_id);
+    assert!(adapter_index.chains.is_empty());
 }
 
 #[tokio::test]
@@ -10,12 +10,21 @@ use crate::instructions::exec_message::exec_message;
 use crate::instructions::register::register;
 
 use anchor_lang::prelude::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::execute_transaction::execute_transaction;
 use common::setup::{get_account, setup};
 use endpoint::error::EndpointError;
-use endpoint::state::{AdapterInfo, AttestationInfo, IntegratorChainConfig, IntegratorConfig};
+use endpoint::state::{
+    AdapterInfo, AdapterMetadata, AdapterStats, AttestationInfo, AttestationMetadata,
+    IntegratorChainConfig, IntegratorConfig, ReplayWindow, SequenceRecord,
+};
+use mock_adapter::{accounts::InvokeAttestMessage, InvokeAttestMessageArgs};
 use solana_program_test::*;
 use solana_sdk::{
-    instruction::InstructionError, signature::Keypair, transaction::TransactionError,
+    instruction::{Instruction, InstructionError},
+    signature::Keypair,
+    signer::Signer,
+    transaction::TransactionError,
 };
 
 async fn setup_test_environment(
@@ -126,6 +135,8 @@ async fn test_attest_message_success() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
+        vec![],
     )
     .await;
 
@@ -139,23 +150,42 @@ async fn test_attest_message_success() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
     ));
     let attestation_info: AttestationInfo =
         get_account(&mut context.banks_client, attestation_info_pda).await;
-    assert_eq!(attestation_info.src_chain, src_chain);
-    assert_eq!(attestation_info.src_addr, src_addr);
-    assert_eq!(attestation_info.sequence, sequence);
-    assert_eq!(attestation_info.dst_chain, dst_chain);
-    assert_eq!(attestation_info.dst_addr, dst_addr);
-    assert_eq!(attestation_info.payload_hash, payload_hash);
+    assert_eq!(
+        AttestationInfo::pda(attestation_info.message_hash).0,
+        attestation_info_pda
+    );
     assert_eq!(attestation_info.num_attested, 1);
 
+    // Verify the receive configuration was snapshotted at the first attestation
+    let integrator_chain_config: IntegratorChainConfig =
+        get_account(&mut context.banks_client, integrator_chain_config_pda).await;
+    assert_eq!(
+        attestation_info.config_epoch,
+        integrator_chain_config.config_epoch
+    );
+    assert_eq!(
+        attestation_info.enabled_adapters,
+        integrator_chain_config.recv_adapter_bitmap
+    );
+
     // Verify that the adapter's bit is set in the attested_adapters bitmap
     let adapter_info: AdapterInfo = get_account(&mut context.banks_client, adapter_info_pda).await;
     assert!(attestation_info
         .attested_adapters
         .get(adapter_info.index)
         .unwrap());
+
+    // Verify that the attestation was recorded in the adapter's stats
+    let (adapter_stats_pda, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let adapter_stats: AdapterStats =
+        get_account(&mut context.banks_client, adapter_stats_pda).await;
+    assert_eq!(adapter_stats.messages_attested, 1);
+    assert_eq!(adapter_stats.messages_picked_up, 0);
+    assert!(adapter_stats.last_attestation_slot > 0);
 }
 
 #[tokio::test]
@@ -188,6 +218,7 @@ async fn test_attest_message_after_exec() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
     )
     .await
     .unwrap();
@@ -204,6 +235,8 @@ async fn test_attest_message_after_exec() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
+        vec![],
     )
     .await;
 
@@ -217,15 +250,14 @@ async fn test_attest_message_after_exec() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
     ));
     let attestation_info: AttestationInfo =
         get_account(&mut context.banks_client, attestation_info_pda).await;
-    assert_eq!(attestation_info.src_chain, src_chain);
-    assert_eq!(attestation_info.src_addr, src_addr);
-    assert_eq!(attestation_info.sequence, sequence);
-    assert_eq!(attestation_info.dst_chain, dst_chain);
-    assert_eq!(attestation_info.dst_addr, dst_addr);
-    assert_eq!(attestation_info.payload_hash, payload_hash);
+    assert_eq!(
+        AttestationInfo::pda(attestation_info.message_hash).0,
+        attestation_info_pda
+    );
 
     // Verify that the adapter's bit is set in the attested_adapters bitmap
     let adapter_info: AdapterInfo = get_account(&mut context.banks_client, adapter_info_pda).await;
@@ -269,6 +301,8 @@ async fn test_attest_message_duplicate_attestation() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
+        vec![],
     )
     .await
     .unwrap();
@@ -286,6 +320,8 @@ async fn test_attest_message_duplicate_attestation() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
+        vec![],
     )
     .await;
 
@@ -331,6 +367,8 @@ async fn test_attest_message_invalid_destination_chain() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
+        vec![],
     )
     .await;
 
@@ -344,4 +382,179 @@ async fn test_attest_message_invalid_destination_chain() {
     );
 }
 
+#[tokio::test]
+async fn test_attest_message_with_metadata() {
+    let (
+        mut context,
+        payer,
+        _,
+        _,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        adapter_pda,
+        chain_id,
+    ) = setup_test_environment(2).await;
+
+    let src_chain: u16 = chain_id;
+    let src_addr = [1u8; 32];
+    let sequence: u64 = 1;
+    let dst_chain = 1;
+    let dst_addr = mock_integrator::id().to_bytes();
+    let payload_hash = [3u8; 32];
+    let metadata = vec![7u8; 40];
+
+    let result = attest_message(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        integrator_chain_config_pda,
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        dst_addr,
+        payload_hash,
+        None,
+        metadata.clone(),
+    )
+    .await;
+
+    assert!(result.is_ok(), "attest_message failed: {:?}", result.err());
+
+    // Verify the metadata was stored against the adapter's index
+    let message_hash = AttestationInfo::compute_message_hash(
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        dst_addr,
+        payload_hash,
+        None,
+    );
+    let (attestation_metadata_pda, _) = AttestationMetadata::pda(message_hash);
+    let attestation_metadata: AttestationMetadata =
+        get_account(&mut context.banks_client, attestation_metadata_pda).await;
+    let adapter_info: AdapterInfo = get_account(&mut context.banks_client, adapter_info_pda).await;
+    assert_eq!(attestation_metadata.message_hash, message_hash);
+    assert_eq!(
+        attestation_metadata.entries,
+        vec![AdapterMetadata {
+            adapter_index: adapter_info.index,
+            metadata,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn test_attest_message_empty_metadata_with_account() {
+    let (
+        mut context,
+        payer,
+        _,
+        _,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        adapter_pda,
+        chain_id,
+    ) = setup_test_environment(2).await;
+
+    let src_addr = [1u8; 32];
+    let message_hash = AttestationInfo::compute_message_hash(
+        chain_id,
+        src_addr,
+        1,
+        1,
+        mock_integrator::id().to_bytes(),
+        [3u8; 32],
+        None,
+    );
+    let (attestation_metadata_pda, attestation_metadata_bump) =
+        AttestationMetadata::pda(message_hash);
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    // Pass the metadata account without attaching any metadata
+    let accounts = InvokeAttestMessage {
+        payer: payer.pubkey(),
+        adapter_info: adapter_info_pda,
+        adapter_stats: AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id()).0,
+        adapter_pda,
+        integrator_chain_config: integrator_chain_config_pda,
+        attestation_info: AttestationInfo::pda(message_hash).0,
+        sequence_record: SequenceRecord::pda(&mock_integrator::id(), chain_id, src_addr, 1).0,
+        replay_window: ReplayWindow::pda(&mock_integrator::id(), chain_id, src_addr, 0).0,
+        attestation_metadata: Some(attestation_metadata_pda),
+        system_program: solana_sdk::system_program::id(),
+        endpoint_program: endpoint::id(),
+        program: endpoint::id(),
+        event_authority,
+    };
+    let args = InvokeAttestMessageArgs {
+        src_chain: chain_id,
+        src_addr,
+        sequence: 1,
+        dst_chain: 1,
+        integrator_program_id: mock_integrator::id(),
+        payload_hash: [3u8; 32],
+        message_hash,
+        expiry: None,
+        metadata: vec![],
+    };
+    let ix = Instruction {
+        program_id: mock_adapter::id(),
+        accounts: accounts.to_account_metas(None),
+        data: mock_adapter::instruction::InvokeAttestMessage { args }.data(),
+    };
+    execute_transaction(&mut context, ix, &[&payer], &payer)
+        .await
+        .unwrap();
+
+    // The created account is still initialized, so that it can be read back
+    let attestation_metadata: AttestationMetadata =
+        get_account(&mut context.banks_client, attestation_metadata_pda).await;
+    assert_eq!(attestation_metadata.bump, attestation_metadata_bump);
+    assert_eq!(attestation_metadata.message_hash, message_hash);
+    assert!(attestation_metadata.entries.is_empty());
+}
+
+#[tokio::test]
+async fn test_attest_message_metadata_too_long() {
+    let (
+        mut context,
+        payer,
+        _,
+        _,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        adapter_pda,
+        chain_id,
+    ) = setup_test_environment(2).await;
+
+    let result = attest_message(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        integrator_chain_config_pda,
+        chain_id,
+        [1u8; 32],
+        1,
+        1,
+        mock_integrator::id().to_bytes(),
+        [3u8; 32],
+        None,
+        vec![7u8; AttestationMetadata::MAX_LEN + 1],
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::MetadataTooLong.into())
+        )
+    );
+}
+
 // TODO: test using disabled_adapter. Need to find out how to make two adapters without having to duplicate the program
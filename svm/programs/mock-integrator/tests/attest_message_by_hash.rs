@@ -0,0 +1,294 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::attest_message::attest_message;
+use crate::instructions::attest_message_by_hash::{attest_message_by_hash, MessageSource};
+use crate::instructions::enable_adapter::{enable_recv_adapter, enable_send_adapter};
+use crate::instructions::exec_message::exec_message;
+use crate::instructions::register::register;
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::state::{
+    AdapterInfo, AttestationInfo, AttestationMetadata, IntegratorChainConfig, IntegratorConfig,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 2;
+const OTHER_CHAIN_ID: u16 = 3;
+const SRC_ADDR: [u8; 32] = [1u8; 32];
+const PAYLOAD_HASH: [u8; 32] = [3u8; 32];
+
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Pubkey, Pubkey) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_program_id = mock_adapter::id();
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    let (adapter_pda, _) = Pubkey::find_program_address(&[b"adapter_pda"], &adapter_program_id);
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    // The adapter receives from CHAIN_ID, and only sends to OTHER_CHAIN_ID
+    enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID).0,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_send_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        IntegratorChainConfig::pda(&integrator_program_id, OTHER_CHAIN_ID).0,
+        adapter_info_pda,
+        OTHER_CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    (context, payer, adapter_info_pda, adapter_pda)
+}
+
+fn message_hash(src_chain: u16) -> [u8; 32] {
+    AttestationInfo::compute_message_hash(
+        src_chain,
+        SRC_ADDR,
+        1,
+        1,
+        mock_integrator::id().to_bytes(),
+        PAYLOAD_HASH,
+        None,
+    )
+}
+
+/// Creates the message's `AttestationInfo` without attesting to it
+async fn exec(context: &mut ProgramTestContext, payer: &Keypair, src_chain: u16) {
+    exec_message(
+        context,
+        payer,
+        src_chain,
+        SRC_ADDR,
+        1,
+        1,
+        mock_integrator::id().to_bytes(),
+        PAYLOAD_HASH,
+        None,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_attest_message_by_hash_success() {
+    let (mut context, payer, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+    exec(&mut context, &payer, CHAIN_ID).await;
+
+    attest_message_by_hash(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        MessageSource {
+            src_chain: CHAIN_ID,
+            src_addr: SRC_ADDR,
+            sequence: 1,
+        },
+        message_hash(CHAIN_ID),
+        vec![7, 8, 9],
+    )
+    .await
+    .unwrap();
+
+    let (attestation_info_pda, _) = AttestationInfo::pda(message_hash(CHAIN_ID));
+    let attestation_info: AttestationInfo =
+        get_account(&mut context.banks_client, attestation_info_pda).await;
+    let adapter_info: AdapterInfo = get_account(&mut context.banks_client, adapter_info_pda).await;
+    assert_eq!(attestation_info.num_attested, 1);
+    assert!(attestation_info
+        .attested_adapters
+        .get(adapter_info.index)
+        .unwrap());
+
+    let (attestation_metadata_pda, _) = AttestationMetadata::pda(message_hash(CHAIN_ID));
+    let attestation_metadata: AttestationMetadata =
+        get_account(&mut context.banks_client, attestation_metadata_pda).await;
+    assert_eq!(attestation_metadata.entries.len(), 1);
+    assert_eq!(attestation_metadata.entries[0].metadata, vec![7, 8, 9]);
+}
+
+#[tokio::test]
+async fn test_attest_message_by_hash_duplicate_attestation() {
+    let (mut context, payer, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+
+    attest_message(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        IntegratorChainConfig::pda(&mock_integrator::id(), CHAIN_ID).0,
+        CHAIN_ID,
+        SRC_ADDR,
+        1,
+        1,
+        mock_integrator::id().to_bytes(),
+        PAYLOAD_HASH,
+        None,
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let result = attest_message_by_hash(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        MessageSource {
+            src_chain: CHAIN_ID,
+            src_addr: SRC_ADDR,
+            sequence: 1,
+        },
+        message_hash(CHAIN_ID),
+        vec![],
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::DuplicateMessageAttestation.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_attest_message_by_hash_without_attestation_info() {
+    let (mut context, payer, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+
+    // The first attestation must go through attest_message
+    let result = attest_message_by_hash(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        MessageSource {
+            src_chain: CHAIN_ID,
+            src_addr: SRC_ADDR,
+            sequence: 1,
+        },
+        message_hash(CHAIN_ID),
+        vec![],
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(3012)) // AccountNotInitialized
+    );
+}
+
+#[tokio::test]
+async fn test_attest_message_by_hash_adapter_not_enabled() {
+    let (mut context, payer, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+    exec(&mut context, &payer, OTHER_CHAIN_ID).await;
+
+    // The adapter is not a receive adapter for the message's source chain
+    let result = attest_message_by_hash(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        MessageSource {
+            src_chain: OTHER_CHAIN_ID,
+            src_addr: SRC_ADDR,
+            sequence: 1,
+        },
+        message_hash(OTHER_CHAIN_ID),
+        vec![],
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::AdapterNotEnabled.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_attest_message_by_hash_wrong_sequence_record() {
+    let (mut context, payer, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+    exec(&mut context, &payer, CHAIN_ID).await;
+    exec(&mut context, &payer, OTHER_CHAIN_ID).await;
+
+    // The sequence record, from which the chain config is derived, must be the one of the
+    // message, even if the adapter is enabled on the chain it belongs to
+    let result = attest_message_by_hash(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        MessageSource {
+            src_chain: CHAIN_ID,
+            src_addr: SRC_ADDR,
+            sequence: 1,
+        },
+        message_hash(OTHER_CHAIN_ID),
+        vec![],
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::InvalidMessageHash.into())
+        )
+    );
+}
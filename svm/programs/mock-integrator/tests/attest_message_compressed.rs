@@ -0,0 +1,363 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::attest_message_compressed::attest_message_compressed;
+use crate::instructions::enable_adapter::enable_recv_adapter;
+use crate::instructions::init_attestation_tree::init_attestation_tree;
+use crate::instructions::register::register;
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::client::attestation_tree::{AttestationTreeIndex, AttestationTreeProof};
+use endpoint::error::EndpointError;
+use endpoint::instructions::BatchedMessage;
+use endpoint::state::{
+    AdapterInfo, AdapterStats, AttestationInfo, AttestationLeaf, AttestationTree,
+    CompressedAttestation, IntegratorChainConfig, IntegratorConfig,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 2;
+const SRC_ADDR: [u8; 32] = [1u8; 32];
+const PAYLOAD_HASH: [u8; 32] = [3u8; 32];
+
+struct TestEnvironment {
+    context: ProgramTestContext,
+    payer: Keypair,
+    admin: Keypair,
+    attestation_tree: Pubkey,
+}
+
+async fn setup_test_environment() -> TestEnvironment {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_program_id = mock_adapter::id();
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let attestation_tree = Keypair::new();
+    init_attestation_tree(
+        &mut context,
+        &admin,
+        &payer,
+        &attestation_tree,
+        integrator_program_id,
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+
+    TestEnvironment {
+        context,
+        payer,
+        admin,
+        attestation_tree: attestation_tree.pubkey(),
+    }
+}
+
+async fn attest(
+    env: &mut TestEnvironment,
+    attestation_tree: Pubkey,
+    sequence: u64,
+    proof: &AttestationTreeProof,
+    previous_leaf: Option<CompressedAttestation>,
+) -> std::result::Result<(), BanksClientError> {
+    attest_message_compressed(
+        &mut env.context,
+        &env.payer,
+        attestation_tree,
+        CHAIN_ID,
+        &BatchedMessage {
+            src_addr: SRC_ADDR,
+            sequence,
+            payload_hash: PAYLOAD_HASH,
+            expiry: None,
+        },
+        proof,
+        previous_leaf,
+    )
+    .await
+}
+
+/// The leaf expected after the first attestation of the message
+async fn attested_leaf(env: &mut TestEnvironment, sequence: u64) -> CompressedAttestation {
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&mock_integrator::id(), CHAIN_ID);
+    let integrator_chain_config: IntegratorChainConfig =
+        get_account(&mut env.context.banks_client, integrator_chain_config_pda).await;
+
+    let mut leaf = CompressedAttestation::new(
+        AttestationInfo::compute_message_hash(
+            CHAIN_ID,
+            SRC_ADDR,
+            sequence,
+            1,
+            mock_integrator::id().to_bytes(),
+            PAYLOAD_HASH,
+            None,
+        ),
+        integrator_chain_config.config_epoch,
+        integrator_chain_config.recv_adapter_bitmap,
+    );
+    leaf.attested_adapters.set(0, true).unwrap();
+    leaf
+}
+
+async fn tree_root(env: &mut TestEnvironment) -> [u8; 32] {
+    let mut data = env
+        .context
+        .banks_client
+        .get_account(env.attestation_tree)
+        .await
+        .unwrap()
+        .expect("account not found")
+        .data;
+    AttestationTree::load_tree_mut(&mut data)
+        .unwrap()
+        .get_root()
+}
+
+fn append_proof(index: &AttestationTreeIndex) -> AttestationTreeProof {
+    AttestationTreeProof {
+        root: index.root(),
+        leaf_index: 0,
+        proof: vec![],
+    }
+}
+
+#[tokio::test]
+async fn test_attest_message_compressed_appends_leaves() {
+    let mut env = setup_test_environment().await;
+    let mut index = AttestationTreeIndex::new();
+
+    for sequence in 1..=3 {
+        let attestation_tree = env.attestation_tree;
+        attest(
+            &mut env,
+            attestation_tree,
+            sequence,
+            &append_proof(&index),
+            None,
+        )
+        .await
+        .unwrap();
+
+        index.set_leaf(sequence as u32 - 1, attested_leaf(&mut env, sequence).await);
+        assert_eq!(tree_root(&mut env).await, index.root());
+    }
+
+    // The attestations are recorded in the adapter's stats
+    let (adapter_stats_pda, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let adapter_stats: AdapterStats =
+        get_account(&mut env.context.banks_client, adapter_stats_pda).await;
+    assert_eq!(adapter_stats.messages_attested, 3);
+}
+
+#[tokio::test]
+async fn test_attest_message_compressed_duplicate_attestation() {
+    let mut env = setup_test_environment().await;
+    let mut index = AttestationTreeIndex::new();
+    let attestation_tree = env.attestation_tree;
+
+    attest(&mut env, attestation_tree, 1, &append_proof(&index), None)
+        .await
+        .unwrap();
+    index.set_leaf(0, attested_leaf(&mut env, 1).await);
+
+    let proof = index.proof(0).unwrap();
+    let result = attest(
+        &mut env,
+        attestation_tree,
+        1,
+        &proof,
+        Some(*index.leaf(0).unwrap()),
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::DuplicateMessageAttestation.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_attest_message_compressed_invalid_proof() {
+    let mut env = setup_test_environment().await;
+    let mut index = AttestationTreeIndex::new();
+    let attestation_tree = env.attestation_tree;
+
+    attest(&mut env, attestation_tree, 1, &append_proof(&index), None)
+        .await
+        .unwrap();
+    index.set_leaf(0, attested_leaf(&mut env, 1).await);
+
+    // A leaf that does not match the tree cannot be replaced
+    let mut previous_leaf = *index.leaf(0).unwrap();
+    previous_leaf.attested_adapters.set(0, false).unwrap();
+    let result = attest(
+        &mut env,
+        attestation_tree,
+        1,
+        &index.proof(0).unwrap(),
+        Some(previous_leaf),
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::InvalidAttestationProof.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_attest_message_compressed_wrong_leaf() {
+    let mut env = setup_test_environment().await;
+    let mut index = AttestationTreeIndex::new();
+    let attestation_tree = env.attestation_tree;
+
+    attest(&mut env, attestation_tree, 1, &append_proof(&index), None)
+        .await
+        .unwrap();
+    index.set_leaf(0, attested_leaf(&mut env, 1).await);
+
+    // The leaf of sequence 1 cannot be used for sequence 2
+    let result = attest(
+        &mut env,
+        attestation_tree,
+        2,
+        &index.proof(0).unwrap(),
+        Some(*index.leaf(0).unwrap()),
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::InvalidMessageHash.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_attest_message_compressed_wrong_tree() {
+    let mut env = setup_test_environment().await;
+
+    // A tree for another source chain
+    let other_tree = Keypair::new();
+    init_attestation_tree(
+        &mut env.context,
+        &env.admin,
+        &env.payer,
+        &other_tree,
+        mock_integrator::id(),
+        CHAIN_ID + 1,
+    )
+    .await
+    .unwrap();
+
+    let result = attest(
+        &mut env,
+        other_tree.pubkey(),
+        1,
+        &append_proof(&AttestationTreeIndex::new()),
+        None,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::InvalidAttestationTree.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_attest_message_compressed_second_leaf() {
+    let mut env = setup_test_environment().await;
+    let mut index = AttestationTreeIndex::new();
+    let attestation_tree = env.attestation_tree;
+
+    attest(&mut env, attestation_tree, 1, &append_proof(&index), None)
+        .await
+        .unwrap();
+    index.set_leaf(0, attested_leaf(&mut env, 1).await);
+
+    // The message's leaf is recorded
+    let attestation_leaf: AttestationLeaf = get_account(
+        &mut env.context.banks_client,
+        AttestationLeaf::pda(&index.leaf(0).unwrap().message_hash).0,
+    )
+    .await;
+    assert_eq!(attestation_leaf.tree, attestation_tree);
+    assert_eq!(attestation_leaf.leaf_index, 0);
+    assert_eq!(attestation_leaf.refund_recipient, env.payer.pubkey());
+
+    // A second leaf cannot be appended for the same message to attest to it again
+    let result = attest(&mut env, attestation_tree, 1, &append_proof(&index), None).await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::InvalidAttestationLeaf.into())
+        )
+    );
+}
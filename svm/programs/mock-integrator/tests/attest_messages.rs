@@ -0,0 +1,230 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::attest_message::attest_message;
+use crate::instructions::attest_messages::attest_messages;
+use crate::instructions::enable_adapter::enable_recv_adapter;
+use crate::instructions::register::register;
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::instructions::{BatchMode, BatchedMessage};
+use endpoint::state::{
+    AdapterInfo, AdapterStats, AttestationInfo, IntegratorChainConfig, IntegratorConfig,
+    SequenceRecord,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 2;
+const SRC_ADDR: [u8; 32] = [1u8; 32];
+
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Pubkey, Pubkey) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_program_id = mock_adapter::id();
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    let (adapter_pda, _) = Pubkey::find_program_address(&[b"adapter_pda"], &adapter_program_id);
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID).0,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    (context, payer, adapter_info_pda, adapter_pda)
+}
+
+fn batched_message(sequence: u64) -> BatchedMessage {
+    BatchedMessage {
+        src_addr: SRC_ADDR,
+        sequence,
+        payload_hash: [sequence as u8; 32],
+        expiry: None,
+    }
+}
+
+fn message_hash(sequence: u64) -> [u8; 32] {
+    AttestationInfo::compute_message_hash(
+        CHAIN_ID,
+        SRC_ADDR,
+        sequence,
+        endpoint::CHAIN_ID,
+        mock_integrator::id().to_bytes(),
+        [sequence as u8; 32],
+        None,
+    )
+}
+
+/// Attests to the message with `sequence` on its own
+async fn attest_one(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    adapter_info_pda: Pubkey,
+    adapter_pda: Pubkey,
+    sequence: u64,
+) {
+    attest_message(
+        context,
+        payer,
+        adapter_info_pda,
+        adapter_pda,
+        IntegratorChainConfig::pda(&mock_integrator::id(), CHAIN_ID).0,
+        CHAIN_ID,
+        SRC_ADDR,
+        sequence,
+        endpoint::CHAIN_ID,
+        mock_integrator::id().to_bytes(),
+        [sequence as u8; 32],
+        None,
+        vec![],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_attest_messages_success() {
+    let (mut context, payer, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+
+    attest_messages(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        CHAIN_ID,
+        (1..=3).map(batched_message).collect(),
+        BatchMode::AllOrNothing,
+    )
+    .await
+    .unwrap();
+
+    let adapter_info: AdapterInfo = get_account(&mut context.banks_client, adapter_info_pda).await;
+    for sequence in 1..=3 {
+        let (attestation_info_pda, _) = AttestationInfo::pda(message_hash(sequence));
+        let attestation_info: AttestationInfo =
+            get_account(&mut context.banks_client, attestation_info_pda).await;
+        assert_eq!(attestation_info.message_hash, message_hash(sequence));
+        assert_eq!(attestation_info.num_attested, 1);
+        assert!(attestation_info
+            .attested_adapters
+            .get(adapter_info.index)
+            .unwrap());
+
+        let (sequence_record_pda, _) =
+            SequenceRecord::pda(&mock_integrator::id(), CHAIN_ID, SRC_ADDR, sequence);
+        let sequence_record: SequenceRecord =
+            get_account(&mut context.banks_client, sequence_record_pda).await;
+        assert_eq!(sequence_record.first_payload_hash, [sequence as u8; 32]);
+        assert!(!sequence_record.equivocated);
+    }
+
+    let (adapter_stats_pda, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let adapter_stats: AdapterStats =
+        get_account(&mut context.banks_client, adapter_stats_pda).await;
+    assert_eq!(adapter_stats.messages_attested, 3);
+}
+
+#[tokio::test]
+async fn test_attest_messages_skip_done() {
+    let (mut context, payer, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+    attest_one(&mut context, &payer, adapter_info_pda, adapter_pda, 2).await;
+
+    attest_messages(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        CHAIN_ID,
+        (1..=3).map(batched_message).collect(),
+        BatchMode::SkipDone,
+    )
+    .await
+    .unwrap();
+
+    // The message attested to on its own is not counted twice
+    let (attestation_info_pda, _) = AttestationInfo::pda(message_hash(2));
+    let attestation_info: AttestationInfo =
+        get_account(&mut context.banks_client, attestation_info_pda).await;
+    assert_eq!(attestation_info.num_attested, 1);
+
+    let (adapter_stats_pda, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let adapter_stats: AdapterStats =
+        get_account(&mut context.banks_client, adapter_stats_pda).await;
+    assert_eq!(adapter_stats.messages_attested, 3);
+}
+
+#[tokio::test]
+async fn test_attest_messages_all_or_nothing_duplicate() {
+    let (mut context, payer, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+    attest_one(&mut context, &payer, adapter_info_pda, adapter_pda, 2).await;
+
+    let result = attest_messages(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        CHAIN_ID,
+        (1..=3).map(batched_message).collect(),
+        BatchMode::AllOrNothing,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::DuplicateMessageAttestation.into())
+        )
+    );
+
+    // Nothing in the batch was attested to
+    let (attestation_info_pda, _) = AttestationInfo::pda(message_hash(1));
+    let account = context
+        .banks_client
+        .get_account(attestation_info_pda)
+        .await
+        .unwrap();
+    assert!(account.is_none());
+}
@@ -0,0 +1,381 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::deregister::deregister;
+use crate::instructions::disable_adapter::disable_send_adapter;
+use crate::instructions::discard_admin::discard_admin;
+use crate::instructions::enable_adapter::{enable_recv_adapter, enable_send_adapter};
+use crate::instructions::enable_replay_windows::enable_replay_windows;
+use crate::instructions::init_adapter_queue::init_adapter_queue;
+use crate::instructions::init_attestation_tree::init_attestation_tree;
+use crate::instructions::register::register;
+use crate::instructions::set_ordered_delivery::set_ordered_delivery;
+use crate::instructions::skip_sequence::skip_sequence;
+use crate::instructions::transfer_admin::{claim_admin, transfer_admin};
+use crate::instructions::update_admin::update_admin;
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::state::{
+    AdapterInfo, AuditLogPage, ConfigAction, IntegratorChainConfig, IntegratorConfig,
+};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const CHAIN_ID: u16 = 2;
+
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    (context, payer, admin)
+}
+
+async fn get_page(context: &mut ProgramTestContext, page_index: u64) -> AuditLogPage {
+    let (audit_log_page_pda, _) = AuditLogPage::pda(&mock_integrator::id(), page_index);
+    get_account(&mut context.banks_client, audit_log_page_pda).await
+}
+
+#[tokio::test]
+async fn test_audit_log_records_config_changes() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+    let new_admin = Keypair::new();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    enable_send_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    disable_send_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    transfer_admin(
+        &mut context,
+        &admin,
+        &new_admin.pubkey(),
+        &payer,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    claim_admin(&mut context, &new_admin, &payer, integrator_config_pda)
+        .await
+        .unwrap();
+
+    update_admin(
+        &mut context,
+        &new_admin,
+        &admin.pubkey(),
+        &payer,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    discard_admin(&mut context, &admin, &payer, integrator_config_pda)
+        .await
+        .unwrap();
+
+    let page = get_page(&mut context, 0).await;
+    assert_eq!(page.integrator_program_id, integrator_program_id);
+    assert_eq!(page.page_index, 0);
+
+    let entries: Vec<(Pubkey, ConfigAction)> = page
+        .entries
+        .iter()
+        .map(|entry| (entry.actor, entry.action))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (
+                admin.pubkey(),
+                ConfigAction::AdapterAdded {
+                    adapter: adapter_program_id
+                }
+            ),
+            (
+                admin.pubkey(),
+                ConfigAction::SendAdapterEnabled {
+                    adapter: adapter_program_id,
+                    chain_id: CHAIN_ID
+                }
+            ),
+            (
+                admin.pubkey(),
+                ConfigAction::SendAdapterDisabled {
+                    adapter: adapter_program_id,
+                    chain_id: CHAIN_ID
+                }
+            ),
+            (
+                admin.pubkey(),
+                ConfigAction::AdminTransferRequested {
+                    new_admin: new_admin.pubkey()
+                }
+            ),
+            (
+                new_admin.pubkey(),
+                ConfigAction::AdminClaimed {
+                    previous_admin: admin.pubkey()
+                }
+            ),
+            (
+                new_admin.pubkey(),
+                ConfigAction::AdminUpdated {
+                    new_admin: admin.pubkey()
+                }
+            ),
+            (admin.pubkey(), ConfigAction::AdminDiscarded),
+        ]
+    );
+
+    // Entries are appended in slot order
+    assert!(page
+        .entries
+        .windows(2)
+        .all(|pair| pair[0].slot <= pair[1].slot));
+
+    let integrator_config: IntegratorConfig =
+        get_account(&mut context.banks_client, integrator_config_pda).await;
+    assert_eq!(integrator_config.audit_log_len, 7);
+}
+
+#[tokio::test]
+async fn test_audit_log_records_delivery_changes() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+    let attestation_tree = Keypair::new();
+    let rent_recipient = Pubkey::new_unique();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    set_ordered_delivery(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        CHAIN_ID,
+        true,
+    )
+    .await
+    .unwrap();
+
+    skip_sequence(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        CHAIN_ID,
+        [1u8; 32],
+        5,
+    )
+    .await
+    .unwrap();
+
+    enable_replay_windows(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+
+    init_attestation_tree(
+        &mut context,
+        &admin,
+        &payer,
+        &attestation_tree,
+        integrator_program_id,
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+
+    init_adapter_queue(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    deregister(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        rent_recipient,
+    )
+    .await
+    .unwrap();
+
+    // The page outlives the deregistered integrator
+    let page = get_page(&mut context, 0).await;
+    let actions: Vec<ConfigAction> = page.entries[2..]
+        .iter()
+        .map(|entry| {
+            assert_eq!(entry.actor, admin.pubkey());
+            entry.action
+        })
+        .collect();
+    assert_eq!(
+        actions,
+        vec![
+            ConfigAction::OrderedDeliverySet {
+                chain_id: CHAIN_ID,
+                enabled: true
+            },
+            ConfigAction::SequencesSkipped {
+                src_chain: CHAIN_ID,
+                src_addr: [1u8; 32],
+                sequence: 5
+            },
+            ConfigAction::ReplayWindowsEnabled { chain_id: CHAIN_ID },
+            ConfigAction::AttestationTreeInitialized {
+                chain_id: CHAIN_ID,
+                tree: attestation_tree.pubkey()
+            },
+            ConfigAction::AdapterQueueInitialized {
+                adapter: adapter_program_id
+            },
+            ConfigAction::Deregistered { rent_recipient },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_audit_log_pages() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    // Fill the first page, which already holds the `add_adapter` entry, and start the second
+    for chain_id in 1..=AuditLogPage::ENTRIES_PER_PAGE as u16 {
+        let (integrator_chain_config_pda, _) =
+            IntegratorChainConfig::pda(&integrator_program_id, chain_id);
+        enable_recv_adapter(
+            &mut context,
+            &admin,
+            &payer,
+            integrator_config_pda,
+            integrator_chain_config_pda,
+            adapter_info_pda,
+            chain_id,
+            adapter_program_id,
+            integrator_program_id,
+        )
+        .await
+        .unwrap();
+    }
+
+    let first_page = get_page(&mut context, 0).await;
+    assert_eq!(
+        first_page.entries.len() as u64,
+        AuditLogPage::ENTRIES_PER_PAGE
+    );
+
+    let second_page = get_page(&mut context, 1).await;
+    assert_eq!(second_page.page_index, 1);
+    assert_eq!(second_page.entries.len(), 1);
+    assert_eq!(
+        second_page.entries[0].action,
+        ConfigAction::RecvAdapterEnabled {
+            adapter: adapter_program_id,
+            chain_id: AuditLogPage::ENTRIES_PER_PAGE as u16,
+        }
+    );
+
+    let integrator_config: IntegratorConfig =
+        get_account(&mut context.banks_client, integrator_config_pda).await;
+    assert_eq!(
+        integrator_config.audit_log_len,
+        AuditLogPage::ENTRIES_PER_PAGE + 1
+    );
+}
@@ -0,0 +1,202 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::cancel_message::cancel_message;
+use crate::instructions::enable_adapter::enable_send_adapter;
+use crate::instructions::pick_up_message::pick_up_message;
+use crate::instructions::register::register;
+use crate::instructions::send_message::{
+    send_message, send_message_with_options, SendMessageOptions,
+};
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::state::{AdapterInfo, IntegratorChainConfig, IntegratorConfig, SequenceTracker};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+
+const DST_CHAIN: u16 = 2;
+const DST_ADDR: [u8; 32] = [1u8; 32];
+const PAYLOAD_HASH: [u8; 32] = [2u8; 32];
+
+/// Registers the mock integrator with the mock adapter and another adapter enabled for sending
+/// to `DST_CHAIN`
+async fn setup_test_environment() -> (ProgramTestContext, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    for adapter_program_id in [mock_adapter::id(), Keypair::new().pubkey()] {
+        let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+        add_adapter(
+            &mut context,
+            &admin,
+            &payer,
+            integrator_config_pda,
+            adapter_info_pda,
+            integrator_program_id,
+            adapter_program_id,
+        )
+        .await
+        .unwrap();
+
+        enable_send_adapter(
+            &mut context,
+            &admin,
+            &payer,
+            integrator_config_pda,
+            IntegratorChainConfig::pda(&integrator_program_id, DST_CHAIN).0,
+            adapter_info_pda,
+            DST_CHAIN,
+            adapter_program_id,
+            integrator_program_id,
+        )
+        .await
+        .unwrap();
+    }
+
+    (context, payer)
+}
+
+async fn send(context: &mut ProgramTestContext, payer: &Keypair, outbox_message: &Keypair) {
+    let integrator_program_id = mock_integrator::id();
+    let (integrator_program_pda, _) =
+        Pubkey::find_program_address(&[b"endpoint_integrator"], &integrator_program_id);
+
+    send_message(
+        context,
+        payer,
+        integrator_program_pda,
+        IntegratorChainConfig::pda(&integrator_program_id, DST_CHAIN).0,
+        outbox_message,
+        SequenceTracker::pda(&integrator_program_id).0,
+        DST_CHAIN,
+        DST_ADDR,
+        PAYLOAD_HASH,
+        0,
+        None,
+    )
+    .await
+    .unwrap();
+}
+
+fn already_picked_up() -> TransactionError {
+    TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(EndpointError::MessageAlreadyPickedUp.into()),
+    )
+}
+
+#[tokio::test]
+async fn test_cancel_message_success() {
+    let (mut context, payer) = setup_test_environment().await;
+    let outbox_message = Keypair::new();
+    send(&mut context, &payer, &outbox_message).await;
+
+    cancel_message(
+        &mut context,
+        &payer,
+        outbox_message.pubkey(),
+        payer.pubkey(),
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // The outbox message is closed and no longer counted as outstanding
+    let account = context
+        .banks_client
+        .get_account(outbox_message.pubkey())
+        .await
+        .unwrap();
+    assert!(account.is_none());
+
+    let (sequence_tracker_pda, _) = SequenceTracker::pda(&mock_integrator::id());
+    let sequence_tracker: SequenceTracker =
+        get_account(&mut context.banks_client, sequence_tracker_pda).await;
+    assert_eq!(sequence_tracker.sequence, 1);
+    assert_eq!(sequence_tracker.outbox_messages, 0);
+}
+
+#[tokio::test]
+async fn test_cancel_message_after_pick_up() {
+    let (mut context, payer) = setup_test_environment().await;
+    let outbox_message = Keypair::new();
+    send(&mut context, &payer, &outbox_message).await;
+
+    let (adapter_info_pda, _) = AdapterInfo::pda(&mock_integrator::id(), &mock_adapter::id());
+    let (adapter_pda, _) = Pubkey::find_program_address(&[b"adapter_pda"], &mock_adapter::id());
+    pick_up_message(
+        &mut context,
+        &payer,
+        outbox_message.pubkey(),
+        adapter_info_pda,
+        adapter_pda,
+        payer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let result = cancel_message(
+        &mut context,
+        &payer,
+        outbox_message.pubkey(),
+        payer.pubkey(),
+        &[],
+    )
+    .await;
+    assert_eq!(result.unwrap_err().unwrap(), already_picked_up());
+}
+
+#[tokio::test]
+async fn test_cancel_message_after_push() {
+    let (mut context, payer) = setup_test_environment().await;
+    let outbox_message = Keypair::new();
+
+    send_message_with_options(
+        &mut context,
+        &payer,
+        Some(&outbox_message),
+        DST_CHAIN,
+        DST_ADDR,
+        PAYLOAD_HASH,
+        SendMessageOptions {
+            consistency_level: 1,
+            pushed_adapters: &[mock_adapter::id()],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // The message was handed to the pushed adapter, so it can no longer be cancelled
+    let result = cancel_message(
+        &mut context,
+        &payer,
+        outbox_message.pubkey(),
+        payer.pubkey(),
+        &[],
+    )
+    .await;
+    assert_eq!(result.unwrap_err().unwrap(), already_picked_up());
+}
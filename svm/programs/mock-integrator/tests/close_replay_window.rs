@@ -0,0 +1,200 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::close_replay_window::close_replay_window;
+use crate::instructions::enable_adapter::enable_recv_adapter;
+use crate::instructions::enable_replay_windows::enable_replay_windows;
+use crate::instructions::exec_message::exec_message;
+use crate::instructions::register::register;
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::instructions::CloseReplayWindowArgs;
+use endpoint::state::{
+    AdapterInfo, IntegratorChainConfig, IntegratorConfig, ReplayFloor, ReplayWindow,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 2;
+const SRC_ADDR: [u8; 32] = [1u8; 32];
+
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_program_id = mock_adapter::id();
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_replay_windows(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+
+    (context, payer, admin)
+}
+
+async fn exec(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    sequence: u64,
+) -> std::result::Result<(), BanksClientError> {
+    exec_message(
+        context,
+        payer,
+        CHAIN_ID,
+        SRC_ADDR,
+        sequence,
+        1,
+        mock_integrator::id().to_bytes(),
+        [3u8; 32],
+        None,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_close_replay_window_success() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let recipient = Pubkey::new_unique();
+
+    exec(&mut context, &payer, 0).await.unwrap();
+
+    let (replay_window_pda, _) = ReplayWindow::pda(&mock_integrator::id(), CHAIN_ID, SRC_ADDR, 0);
+    let window_lamports = context
+        .banks_client
+        .get_balance(replay_window_pda)
+        .await
+        .unwrap();
+
+    close_replay_window(
+        &mut context,
+        &admin,
+        &payer,
+        CloseReplayWindowArgs {
+            integrator_program_id: mock_integrator::id(),
+            src_chain: CHAIN_ID,
+            src_addr: SRC_ADDR,
+            window_index: 0,
+        },
+        recipient,
+    )
+    .await
+    .unwrap();
+
+    // The window is closed and its rent refunded
+    let account = context
+        .banks_client
+        .get_account(replay_window_pda)
+        .await
+        .expect("Failed to get account info");
+    assert!(account.is_none());
+    assert_eq!(
+        context.banks_client.get_balance(recipient).await.unwrap(),
+        window_lamports
+    );
+
+    let (replay_floor_pda, _) = ReplayFloor::pda(&mock_integrator::id(), CHAIN_ID, SRC_ADDR);
+    let replay_floor: ReplayFloor = get_account(&mut context.banks_client, replay_floor_pda).await;
+    assert_eq!(replay_floor.first_open_window, 1);
+
+    // Sequences in the closed window stay rejected, executed or not
+    for sequence in [0, 1] {
+        let result = exec(&mut context, &payer, sequence).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EndpointError::ReplayWindowClosed.into())
+            )
+        );
+    }
+
+    // The next window is unaffected
+    exec(&mut context, &payer, ReplayWindow::WINDOW_SIZE)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_close_replay_window_non_admin() {
+    let (mut context, payer, _) = setup_test_environment().await;
+    let non_admin = Keypair::new();
+
+    exec(&mut context, &payer, 0).await.unwrap();
+
+    let result = close_replay_window(
+        &mut context,
+        &non_admin,
+        &payer,
+        CloseReplayWindowArgs {
+            integrator_program_id: mock_integrator::id(),
+            src_chain: CHAIN_ID,
+            src_addr: SRC_ADDR,
+            window_index: 0,
+        },
+        Pubkey::new_unique(),
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::CallerNotAuthorized.into())
+        )
+    );
+}
@@ -0,0 +1,7 @@
+use endpoint::error::EndpointError;
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+/// The error of a transaction whose instruction at `instruction_index` fails with `error`
+pub fn custom_error(instruction_index: u8, error: EndpointError) -> TransactionError {
+    TransactionError::InstructionError(instruction_index, InstructionError::Custom(error.into()))
+}
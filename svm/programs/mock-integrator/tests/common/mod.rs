@@ -1,2 +1,3 @@
+pub mod error;
 pub mod execute_transaction;
 pub mod setup;
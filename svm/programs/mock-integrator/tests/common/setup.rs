@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use endpoint::id as endpoint_id;
+use endpoint::state::{AuditLogPage, IntegratorConfig};
 use solana_program_test::{ProgramTest, ProgramTestContext};
 
 pub async fn setup() -> ProgramTestContext {
@@ -23,3 +24,22 @@ pub async fn get_account<T: AccountDeserialize>(
 
     T::try_deserialize(&mut account.data.as_ref()).unwrap()
 }
+
+/// Returns the audit log page that the next configuration change of the integrator is appended to
+///
+/// This is derived from the integrator config at `integrator_config`, if it exists.
+pub async fn current_audit_log_page(
+    banks_client: &mut solana_program_test::BanksClient,
+    integrator_config: Pubkey,
+) -> Pubkey {
+    let config = banks_client
+        .get_account(integrator_config)
+        .await
+        .unwrap()
+        .and_then(|account| IntegratorConfig::try_deserialize(&mut account.data.as_ref()).ok());
+
+    match config {
+        Some(config) => AuditLogPage::pda(&config.integrator_program_id, config.audit_log_page()).0,
+        None => Pubkey::default(),
+    }
+}
@@ -0,0 +1,255 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::disable_adapter::disable_send_adapter;
+use crate::instructions::enable_adapter::{enable_recv_adapter, enable_send_adapter};
+use crate::instructions::get_config_digest::get_config_digest;
+use crate::instructions::register::register;
+use crate::instructions::transfer_admin::{claim_admin, transfer_admin};
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::state::{
+    AdapterInfo, ConfigDigest, ConfigUpdate, IntegratorChainConfig, IntegratorConfig,
+};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const CHAIN_ID: u16 = 2;
+
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    (context, payer, admin)
+}
+
+/// Recomputes a digest off-chain from the genesis digest and a list of updates
+fn recompute(integrator_program_id: &Pubkey, updates: &[ConfigUpdate]) -> [u8; 32] {
+    updates.iter().fold(
+        ConfigDigest::genesis(integrator_program_id),
+        ConfigDigest::fold,
+    )
+}
+
+#[tokio::test]
+async fn test_config_digest_matches_recomputation() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+    let new_admin = Keypair::new();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    let mut updates = vec![
+        ConfigUpdate::Admin {
+            admin: Some(admin.pubkey()),
+            pending_admin: None,
+        },
+        ConfigUpdate::AdapterAdded {
+            index: 0,
+            adapter: adapter_program_id,
+        },
+    ];
+    assert_eq!(
+        get_config_digest(
+            &mut context,
+            &payer,
+            integrator_config_pda,
+            integrator_program_id
+        )
+        .await
+        .unwrap(),
+        recompute(&integrator_program_id, &updates)
+    );
+
+    enable_send_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    transfer_admin(
+        &mut context,
+        &admin,
+        &new_admin.pubkey(),
+        &payer,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    claim_admin(&mut context, &new_admin, &payer, integrator_config_pda)
+        .await
+        .unwrap();
+
+    updates.extend([
+        ConfigUpdate::ChainBitmaps {
+            chain_id: CHAIN_ID,
+            send_bitmap: 1,
+            recv_bitmap: 0,
+        },
+        ConfigUpdate::ChainBitmaps {
+            chain_id: CHAIN_ID,
+            send_bitmap: 1,
+            recv_bitmap: 1,
+        },
+        ConfigUpdate::Admin {
+            admin: Some(admin.pubkey()),
+            pending_admin: Some(new_admin.pubkey()),
+        },
+        ConfigUpdate::Admin {
+            admin: Some(new_admin.pubkey()),
+            pending_admin: None,
+        },
+    ]);
+    let expected = recompute(&integrator_program_id, &updates);
+
+    let digest = get_config_digest(
+        &mut context,
+        &payer,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+    assert_eq!(digest, expected);
+
+    let integrator_config: IntegratorConfig =
+        get_account(&mut context.banks_client, integrator_config_pda).await;
+    assert_eq!(integrator_config.config_digest, expected);
+}
+
+#[tokio::test]
+async fn test_config_digest_commits_to_history() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    let initial_digest = get_config_digest(
+        &mut context,
+        &payer,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_send_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    disable_send_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    // The bitmaps are back to their initial state, but the digest still records the changes
+    let digest = get_config_digest(
+        &mut context,
+        &payer,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+    assert_ne!(digest, initial_digest);
+    assert_eq!(
+        digest,
+        [
+            ConfigUpdate::ChainBitmaps {
+                chain_id: CHAIN_ID,
+                send_bitmap: 1,
+                recv_bitmap: 0,
+            },
+            ConfigUpdate::ChainBitmaps {
+                chain_id: CHAIN_ID,
+                send_bitmap: 0,
+                recv_bitmap: 0,
+            },
+        ]
+        .iter()
+        .fold(initial_digest, ConfigDigest::fold)
+    );
+}
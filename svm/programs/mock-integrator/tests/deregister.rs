@@ -0,0 +1,458 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::deregister::{
+    close_deregistered_adapter, close_deregistered_audit_log_page, close_deregistered_chain,
+    close_deregistered_sent_message, deregister,
+};
+use crate::instructions::enable_adapter::enable_send_adapter;
+use crate::instructions::pick_up_message::pick_up_message;
+use crate::instructions::register::register;
+use crate::instructions::send_message::{
+    send_message, send_message_with_options, SendMessageOptions,
+};
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::state::{
+    AdapterIndex, AdapterInfo, AdapterStats, AuditLogPage, IntegratorChainConfig, IntegratorConfig,
+    IntegratorTombstone, SentMessageRecord, SequenceTracker,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 1;
+
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_send_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    (context, payer, admin)
+}
+
+async fn send(context: &mut ProgramTestContext, payer: &Keypair) -> Keypair {
+    let integrator_program_id = mock_integrator::id();
+    let outbox_message = Keypair::new();
+    let (integrator_program_pda, _) =
+        Pubkey::find_program_address(&[b"endpoint_integrator"], &integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+    let (sequence_tracker_pda, _) = SequenceTracker::pda(&integrator_program_id);
+
+    send_message(
+        context,
+        payer,
+        integrator_program_pda,
+        integrator_chain_config_pda,
+        &outbox_message,
+        sequence_tracker_pda,
+        CHAIN_ID,
+        [1u8; 32],
+        [2u8; 32],
+        1,
+        None,
+    )
+    .await
+    .unwrap();
+
+    outbox_message
+}
+
+async fn pick_up(context: &mut ProgramTestContext, payer: &Keypair, outbox_message: &Keypair) {
+    let (adapter_info_pda, _) = AdapterInfo::pda(&mock_integrator::id(), &mock_adapter::id());
+    let (adapter_pda, _) = Pubkey::find_program_address(&[b"adapter_pda"], &mock_adapter::id());
+
+    pick_up_message(
+        context,
+        payer,
+        outbox_message.pubkey(),
+        adapter_info_pda,
+        adapter_pda,
+        payer.pubkey(),
+    )
+    .await
+    .unwrap();
+}
+
+async fn lamports(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    context.banks_client.get_balance(address).await.unwrap()
+}
+
+async fn is_closed(context: &mut ProgramTestContext, address: Pubkey) -> bool {
+    context
+        .banks_client
+        .get_account(address)
+        .await
+        .expect("Failed to get account info")
+        .is_none()
+}
+
+#[tokio::test]
+async fn test_deregister_success() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let rent_recipient = Pubkey::new_unique();
+
+    let outbox_message = send(&mut context, &payer).await;
+    pick_up(&mut context, &payer, &outbox_message).await;
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (sequence_tracker_pda, _) = SequenceTracker::pda(&integrator_program_id);
+    let config_lamports = lamports(&mut context, integrator_config_pda).await
+        + lamports(&mut context, sequence_tracker_pda).await;
+
+    deregister(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        rent_recipient,
+    )
+    .await
+    .unwrap();
+
+    assert!(is_closed(&mut context, integrator_config_pda).await);
+    assert!(is_closed(&mut context, sequence_tracker_pda).await);
+    assert_eq!(
+        lamports(&mut context, rent_recipient).await,
+        config_lamports
+    );
+
+    let (integrator_tombstone_pda, bump) = IntegratorTombstone::pda(&integrator_program_id);
+    let tombstone: IntegratorTombstone =
+        get_account(&mut context.banks_client, integrator_tombstone_pda).await;
+    assert_eq!(tombstone.bump, bump);
+    assert_eq!(tombstone.integrator_program_id, integrator_program_id);
+    assert_eq!(tombstone.rent_recipient, rent_recipient);
+    assert_eq!(tombstone.final_sequence, 1);
+}
+
+#[tokio::test]
+async fn test_deregister_with_outstanding_message() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+
+    let outbox_message = send(&mut context, &payer).await;
+
+    let result = deregister(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        Pubkey::new_unique(),
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::OutboxMessagesOutstanding.into())
+        )
+    );
+
+    // Once the message is picked up, the integrator can be deregistered
+    pick_up(&mut context, &payer, &outbox_message).await;
+
+    // The close is counted in the stats of the adapter that picked it up, not in the tracker
+    let (sequence_tracker_pda, _) = SequenceTracker::pda(&integrator_program_id);
+    let sequence_tracker: SequenceTracker =
+        get_account(&mut context.banks_client, sequence_tracker_pda).await;
+    assert_eq!(sequence_tracker.outbox_messages, 1);
+    let (adapter_stats_pda, _) = AdapterStats::pda(&integrator_program_id, &mock_adapter::id());
+    let adapter_stats: AdapterStats =
+        get_account(&mut context.banks_client, adapter_stats_pda).await;
+    assert_eq!(adapter_stats.outbox_messages_closed, 1);
+
+    deregister(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        Pubkey::new_unique(),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_deregister_non_admin() {
+    let (mut context, payer, _) = setup_test_environment().await;
+    let non_admin = Keypair::new();
+
+    let result = deregister(
+        &mut context,
+        &non_admin,
+        &payer,
+        mock_integrator::id(),
+        Pubkey::new_unique(),
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::CallerNotAuthorized.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_register_after_deregister() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    deregister(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        Pubkey::new_unique(),
+    )
+    .await
+    .unwrap();
+
+    let result = register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::IntegratorDeregistered.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_close_deregistered_accounts() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+    let rent_recipient = Pubkey::new_unique();
+
+    // The accounts cannot be closed while the integrator is registered
+    let result = close_deregistered_chain(
+        &mut context,
+        &payer,
+        integrator_program_id,
+        CHAIN_ID,
+        rent_recipient,
+    )
+    .await;
+    assert!(result.is_err());
+
+    deregister(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        rent_recipient,
+    )
+    .await
+    .unwrap();
+
+    // The rent can only go to the recipient chosen by the admin
+    let result = close_deregistered_chain(
+        &mut context,
+        &payer,
+        integrator_program_id,
+        CHAIN_ID,
+        payer.pubkey(),
+    )
+    .await;
+    assert!(result.is_err());
+
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    let (adapter_stats_pda, _) = AdapterStats::pda(&integrator_program_id, &adapter_program_id);
+    let before = lamports(&mut context, rent_recipient).await;
+    let closed_lamports = lamports(&mut context, integrator_chain_config_pda).await
+        + lamports(&mut context, adapter_info_pda).await
+        + lamports(&mut context, adapter_stats_pda).await;
+
+    close_deregistered_chain(
+        &mut context,
+        &payer,
+        integrator_program_id,
+        CHAIN_ID,
+        rent_recipient,
+    )
+    .await
+    .unwrap();
+
+    close_deregistered_adapter(
+        &mut context,
+        &payer,
+        integrator_program_id,
+        adapter_program_id,
+        rent_recipient,
+    )
+    .await
+    .unwrap();
+
+    assert!(is_closed(&mut context, integrator_chain_config_pda).await);
+    assert!(is_closed(&mut context, adapter_info_pda).await);
+    assert!(is_closed(&mut context, adapter_stats_pda).await);
+    assert_eq!(
+        lamports(&mut context, rent_recipient).await,
+        before + closed_lamports
+    );
+
+    // The integrator is no longer listed in the adapter's index
+    let (adapter_index_pda, _) = AdapterIndex::pda(&adapter_program_id, &integrator_program_id);
+    assert!(is_closed(&mut context, adapter_index_pda).await);
+}
+
+#[tokio::test]
+async fn test_close_deregistered_records() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let rent_recipient = Pubkey::new_unique();
+
+    let outbox_message = Keypair::new();
+    send_message_with_options(
+        &mut context,
+        &payer,
+        Some(&outbox_message),
+        CHAIN_ID,
+        [1u8; 32],
+        [2u8; 32],
+        SendMessageOptions {
+            consistency_level: 1,
+            record: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    pick_up(&mut context, &payer, &outbox_message).await;
+
+    // The records cannot be closed while the integrator is registered
+    let result = close_deregistered_sent_message(
+        &mut context,
+        &payer,
+        integrator_program_id,
+        0,
+        payer.pubkey(),
+    )
+    .await;
+    assert!(result.is_err());
+
+    deregister(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        rent_recipient,
+    )
+    .await
+    .unwrap();
+
+    let (audit_log_page_pda, _) = AuditLogPage::pda(&integrator_program_id, 0);
+    let (sent_message_record_pda, _) = SentMessageRecord::pda(&integrator_program_id, 0);
+    let before = lamports(&mut context, rent_recipient).await;
+    let closed_lamports = lamports(&mut context, audit_log_page_pda).await;
+
+    close_deregistered_audit_log_page(
+        &mut context,
+        &payer,
+        integrator_program_id,
+        0,
+        rent_recipient,
+    )
+    .await
+    .unwrap();
+
+    assert!(is_closed(&mut context, audit_log_page_pda).await);
+    assert_eq!(
+        lamports(&mut context, rent_recipient).await,
+        before + closed_lamports
+    );
+
+    // The record's rent goes back to the account that paid for it
+    let result = close_deregistered_sent_message(
+        &mut context,
+        &payer,
+        integrator_program_id,
+        0,
+        rent_recipient,
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ErrorCode::ConstraintHasOne.into())
+        )
+    );
+
+    close_deregistered_sent_message(
+        &mut context,
+        &payer,
+        integrator_program_id,
+        0,
+        payer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    assert!(is_closed(&mut context, sent_message_record_pda).await);
+}
@@ -13,7 +13,9 @@ use anchor_lang::prelude::*;
 use common::setup::{get_account, setup};
 use endpoint::error::EndpointError;
 use endpoint::{
-    state::{AdapterInfo, IntegratorChainConfig, IntegratorConfig},
+    state::{
+        AdapterChainEntry, AdapterIndex, AdapterInfo, IntegratorChainConfig, IntegratorConfig,
+    },
     utils::bitmap::Bitmap,
 };
 use solana_program_test::*;
@@ -140,6 +142,17 @@ async fn test_disable_recv_adapter_success() {
 
     // Verify that the adapter is disabled
     verify_adapter_state(&mut context, integrator_chain_config_pda, 0, 0).await;
+
+    // Verify that both the enable and the disable advanced the config epoch
+    let integrator_chain_config: IntegratorChainConfig =
+        get_account(&mut context.banks_client, integrator_chain_config_pda).await;
+    assert_eq!(integrator_chain_config.config_epoch, 2);
+
+    // Verify that the chain is removed from the adapter's reverse index
+    let (adapter_index_pda, _) = AdapterIndex::pda(&adapter, &integrator_program_id);
+    let adapter_index: AdapterIndex =
+        get_account(&mut context.banks_client, adapter_index_pda).await;
+    assert!(adapter_index.chains.is_empty());
 }
 
 #[tokio::test]
@@ -195,6 +208,107 @@ async fn test_disable_send_adapter_success() {
     verify_adapter_state(&mut context, integrator_chain_config_pda, 0, 0).await;
 }
 
+#[tokio::test]
+async fn test_disable_adapter_updates_adapter_index() {
+    let mut context = setup().await;
+    let (
+        authority,
+        integrator_program_id,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        adapter,
+        chain_id,
+    ) = initialize_test_environment(&mut context).await;
+
+    let payer = context.payer.insecure_clone();
+    let (adapter_index_pda, _) = AdapterIndex::pda(&adapter, &integrator_program_id);
+
+    enable_send_adapter(
+        &mut context,
+        &authority,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        chain_id,
+        adapter,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+    enable_recv_adapter(
+        &mut context,
+        &authority,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        chain_id,
+        adapter,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    // Both directions are recorded on the same entry
+    let adapter_index: AdapterIndex =
+        get_account(&mut context.banks_client, adapter_index_pda).await;
+    assert_eq!(
+        adapter_index.chains,
+        vec![AdapterChainEntry {
+            chain_id,
+            send_enabled: true,
+            recv_enabled: true,
+        }]
+    );
+
+    // Disabling one direction keeps the entry for the other
+    disable_send_adapter(
+        &mut context,
+        &authority,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        chain_id,
+        adapter,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_index: AdapterIndex =
+        get_account(&mut context.banks_client, adapter_index_pda).await;
+    assert_eq!(
+        adapter_index.chains,
+        vec![AdapterChainEntry {
+            chain_id,
+            send_enabled: false,
+            recv_enabled: true,
+        }]
+    );
+
+    // Disabling the other direction removes the entry
+    disable_recv_adapter(
+        &mut context,
+        &authority,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        chain_id,
+        adapter,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_index: AdapterIndex =
+        get_account(&mut context.banks_client, adapter_index_pda).await;
+    assert!(adapter_index.chains.is_empty());
+}
+
 #[tokio::test]
 async fn test_disable_adapter_invalid_authority() {
     let mut context = setup().await;
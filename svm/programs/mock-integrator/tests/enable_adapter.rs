@@ -13,7 +13,9 @@ use anchor_lang::prelude::*;
 use common::setup::{get_account, setup};
 use endpoint::error::EndpointError;
 use endpoint::{
-    state::{AdapterInfo, IntegratorChainConfig, IntegratorConfig},
+    state::{
+        AdapterChainEntry, AdapterIndex, AdapterInfo, IntegratorChainConfig, IntegratorConfig,
+    },
     utils::bitmap::Bitmap,
 };
 use solana_program_test::*;
@@ -137,6 +139,19 @@ async fn test_enable_in_adapters_success() {
         integrator_program_id,
     )
     .await;
+
+    // Verify that the adapter's reverse index records the enabled direction
+    let (adapter_index_pda, _) = AdapterIndex::pda(&adapter, &integrator_program_id);
+    let adapter_index: AdapterIndex =
+        get_account(&mut context.banks_client, adapter_index_pda).await;
+    assert_eq!(
+        adapter_index.chains,
+        vec![AdapterChainEntry {
+            chain_id,
+            send_enabled: false,
+            recv_enabled: true,
+        }]
+    );
 }
 
 #[tokio::test]
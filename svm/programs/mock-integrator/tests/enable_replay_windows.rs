@@ -0,0 +1,403 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::attest_message::attest_message;
+use crate::instructions::enable_adapter::enable_recv_adapter;
+use crate::instructions::enable_replay_windows::enable_replay_windows;
+use crate::instructions::exec_message::exec_message;
+use crate::instructions::recv_message::recv_message;
+use crate::instructions::register::register;
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::state::{
+    AdapterInfo, AttestationInfo, IntegratorChainConfig, IntegratorConfig, ReplayWindow,
+    SequenceRecord,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::AccountSharedData, instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 2;
+const SRC_ADDR: [u8; 32] = [1u8; 32];
+const PAYLOAD_HASH: [u8; 32] = [3u8; 32];
+
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Keypair, Pubkey, Pubkey) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_program_id = mock_adapter::id();
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    let (adapter_pda, _) = Pubkey::find_program_address(&[b"adapter_pda"], &adapter_program_id);
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    (context, payer, admin, adapter_info_pda, adapter_pda)
+}
+
+async fn attest(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    adapter_info_pda: Pubkey,
+    adapter_pda: Pubkey,
+    sequence: u64,
+) {
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&mock_integrator::id(), CHAIN_ID);
+
+    attest_message(
+        context,
+        payer,
+        adapter_info_pda,
+        adapter_pda,
+        integrator_chain_config_pda,
+        CHAIN_ID,
+        SRC_ADDR,
+        sequence,
+        1,
+        mock_integrator::id().to_bytes(),
+        PAYLOAD_HASH,
+        None,
+        vec![],
+    )
+    .await
+    .unwrap();
+}
+
+async fn recv(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    sequence: u64,
+) -> std::result::Result<(), BanksClientError> {
+    recv_message(
+        context,
+        payer,
+        attestation_info_pda(sequence),
+        CHAIN_ID,
+        SRC_ADDR,
+        sequence,
+        1,
+        PAYLOAD_HASH,
+        None,
+        false,
+    )
+    .await
+}
+
+async fn exec(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    sequence: u64,
+) -> std::result::Result<(), BanksClientError> {
+    exec_message(
+        context,
+        payer,
+        CHAIN_ID,
+        SRC_ADDR,
+        sequence,
+        1,
+        mock_integrator::id().to_bytes(),
+        PAYLOAD_HASH,
+        None,
+    )
+    .await
+}
+
+fn attestation_info_pda(sequence: u64) -> Pubkey {
+    AttestationInfo::pda(AttestationInfo::compute_message_hash(
+        CHAIN_ID,
+        SRC_ADDR,
+        sequence,
+        1,
+        mock_integrator::id().to_bytes(),
+        PAYLOAD_HASH,
+        None,
+    ))
+    .0
+}
+
+async fn account_exists(context: &mut ProgramTestContext, address: Pubkey) -> bool {
+    context
+        .banks_client
+        .get_account(address)
+        .await
+        .expect("Failed to get account info")
+        .is_some()
+}
+
+async fn lamports(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    context
+        .banks_client
+        .get_balance(address)
+        .await
+        .expect("Failed to get balance")
+}
+
+async fn replay_window(context: &mut ProgramTestContext, sequence: u64) -> ReplayWindow {
+    let (replay_window_pda, _) = ReplayWindow::pda(
+        &mock_integrator::id(),
+        CHAIN_ID,
+        SRC_ADDR,
+        ReplayWindow::window_index(sequence),
+    );
+    get_account(&mut context.banks_client, replay_window_pda).await
+}
+
+#[tokio::test]
+async fn test_enable_replay_windows_success() {
+    let (mut context, payer, admin, _, _) = setup_test_environment().await;
+
+    enable_replay_windows(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&mock_integrator::id(), CHAIN_ID);
+    let integrator_chain_config: IntegratorChainConfig =
+        get_account(&mut context.banks_client, integrator_chain_config_pda).await;
+    assert!(integrator_chain_config.replay_windows);
+}
+
+#[tokio::test]
+async fn test_enable_replay_windows_non_admin() {
+    let (mut context, payer, _, _, _) = setup_test_environment().await;
+    let non_admin = Keypair::new();
+
+    let result = enable_replay_windows(
+        &mut context,
+        &non_admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::CallerNotAuthorized.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_recv_message_with_replay_windows() {
+    let (mut context, payer, admin, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+    let sequence = 5;
+
+    enable_replay_windows(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+
+    // The attestation is relayed by someone other than the receiver
+    let relayer = Keypair::new();
+    context.set_account(
+        &relayer.pubkey(),
+        &AccountSharedData::new(1_000_000_000, 0, &solana_sdk::system_program::id()),
+    );
+    attest(
+        &mut context,
+        &relayer,
+        adapter_info_pda,
+        adapter_pda,
+        sequence,
+    )
+    .await;
+    let (sequence_record_pda, _) =
+        SequenceRecord::pda(&mock_integrator::id(), CHAIN_ID, SRC_ADDR, sequence);
+    let relayer_lamports = lamports(&mut context, relayer.pubkey()).await;
+    let record_lamports = lamports(&mut context, attestation_info_pda(sequence)).await
+        + lamports(&mut context, sequence_record_pda).await;
+
+    recv(&mut context, &payer, sequence).await.unwrap();
+
+    // The attestation info and sequence record are closed once the message is delivered,
+    // refunding whoever paid for them, as the window now records the delivery
+    assert!(!account_exists(&mut context, attestation_info_pda(sequence)).await);
+    assert!(!account_exists(&mut context, sequence_record_pda).await);
+    assert_eq!(
+        lamports(&mut context, relayer.pubkey()).await,
+        relayer_lamports + record_lamports
+    );
+
+    let window = replay_window(&mut context, sequence).await;
+    assert!(window.is_executed(sequence));
+    assert!(!window.is_executed(sequence + 1));
+
+    // A late attestation does not leave an attestation info or sequence record behind, and the
+    // window still rejects a replay
+    attest(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        sequence,
+    )
+    .await;
+    assert!(!account_exists(&mut context, attestation_info_pda(sequence)).await);
+    assert!(!account_exists(&mut context, sequence_record_pda).await);
+    let result = recv(&mut context, &payer, sequence).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(3012)) // AccountNotInitialized
+    );
+}
+
+#[tokio::test]
+async fn test_exec_message_with_replay_windows() {
+    let (mut context, payer, admin, _, _) = setup_test_environment().await;
+    let sequence = 5;
+
+    enable_replay_windows(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+
+    exec(&mut context, &payer, sequence).await.unwrap();
+    assert!(!account_exists(&mut context, attestation_info_pda(sequence)).await);
+    let (sequence_record_pda, _) =
+        SequenceRecord::pda(&mock_integrator::id(), CHAIN_ID, SRC_ADDR, sequence);
+    assert!(!account_exists(&mut context, sequence_record_pda).await);
+    assert!(replay_window(&mut context, sequence)
+        .await
+        .is_executed(sequence));
+
+    let result = exec(&mut context, &payer, sequence).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::AlreadyExecuted.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_exec_message_after_recv_with_replay_windows() {
+    let (mut context, payer, admin, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+    let sequence = 5;
+
+    enable_replay_windows(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+
+    attest(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        sequence,
+    )
+    .await;
+    recv(&mut context, &payer, sequence).await.unwrap();
+    assert!(!account_exists(&mut context, attestation_info_pda(sequence)).await);
+
+    // Executing the delivered message recreates its attestation info and sequence record, but
+    // the window already records the sequence as executed
+    let result = exec(&mut context, &payer, sequence).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::AlreadyExecuted.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_replay_windows_keep_existing_executions() {
+    let (mut context, payer, admin, _, _) = setup_test_environment().await;
+    let sequence = 5;
+
+    // Executed before replay windows are enabled, so only its attestation info records it
+    exec(&mut context, &payer, sequence).await.unwrap();
+
+    enable_replay_windows(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+
+    let result = exec(&mut context, &payer, sequence).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::AlreadyExecuted.into())
+        )
+    );
+}
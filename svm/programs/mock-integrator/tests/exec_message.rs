@@ -7,6 +7,7 @@ use crate::instructions::add_adapter::add_adapter;
 use crate::instructions::attest_message::attest_message;
 use crate::instructions::enable_adapter::enable_recv_adapter;
 use crate::instructions::exec_message::exec_message;
+use crate::instructions::recv_message::recv_message;
 use crate::instructions::register::register;
 
 use anchor_lang::prelude::*;
@@ -96,6 +97,7 @@ async fn test_exec_message_success() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
     )
     .await;
 
@@ -109,15 +111,14 @@ async fn test_exec_message_success() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
     ));
     let attestation_info: AttestationInfo =
         get_account(&mut context.banks_client, attestation_info_pda).await;
-    assert_eq!(attestation_info.src_chain, src_chain);
-    assert_eq!(attestation_info.src_addr, src_addr);
-    assert_eq!(attestation_info.sequence, sequence);
-    assert_eq!(attestation_info.dst_chain, dst_chain);
-    assert_eq!(attestation_info.dst_addr, dst_addr);
-    assert_eq!(attestation_info.payload_hash, payload_hash);
+    assert_eq!(
+        AttestationInfo::pda(attestation_info.message_hash).0,
+        attestation_info_pda
+    );
     assert!(attestation_info.executed);
 }
 
@@ -142,6 +143,7 @@ async fn test_exec_message_duplicate_execution() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
     )
     .await
     .unwrap();
@@ -156,6 +158,7 @@ async fn test_exec_message_duplicate_execution() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
     )
     .await;
 
@@ -169,6 +172,94 @@ async fn test_exec_message_duplicate_execution() {
     );
 }
 
+#[tokio::test]
+async fn test_recv_message_after_exec() {
+    let (mut context, payer, _, _, chain_id) = setup_test_environment().await;
+
+    let src_chain: u16 = chain_id;
+    let src_addr = [1u8; 32];
+    let sequence: u64 = 1;
+    let dst_chain = 1;
+    let dst_addr = mock_integrator::id().to_bytes();
+    let payload_hash = [3u8; 32];
+
+    exec_message(
+        &mut context,
+        &payer,
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        dst_addr,
+        payload_hash,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // The sequence record created by exec lets recv fail as already executed
+    let (attestation_info_pda, _) = AttestationInfo::pda(AttestationInfo::compute_message_hash(
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        dst_addr,
+        payload_hash,
+        None,
+    ));
+    let result = recv_message(
+        &mut context,
+        &payer,
+        attestation_info_pda,
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        payload_hash,
+        None,
+        false,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::AlreadyExecuted.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_exec_message_without_chain_config() {
+    let (mut context, payer, _, _, _) = setup_test_environment().await;
+
+    // No adapter was ever enabled for chain 3, so it has no integrator chain config
+    let src_chain: u16 = 3;
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&mock_integrator::id(), src_chain);
+    assert!(context
+        .banks_client
+        .get_account(integrator_chain_config_pda)
+        .await
+        .unwrap()
+        .is_none());
+
+    exec_message(
+        &mut context,
+        &payer,
+        src_chain,
+        [1u8; 32],
+        1,
+        1,
+        mock_integrator::id().to_bytes(),
+        [3u8; 32],
+        None,
+    )
+    .await
+    .unwrap();
+}
+
 #[tokio::test]
 async fn test_exec_message_zero_chain_id() {
     let (mut context, payer, _, _, chain_id) = setup_test_environment().await;
@@ -190,6 +281,7 @@ async fn test_exec_message_zero_chain_id() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
     )
     .await;
 
@@ -0,0 +1,178 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::attest_message::attest_message;
+use crate::instructions::enable_adapter::enable_recv_adapter;
+use crate::instructions::exec_message_by_hash::exec_message_by_hash;
+use crate::instructions::recv_message::recv_message;
+use crate::instructions::register::register;
+
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::state::{
+    AdapterInfo, AttestationInfo, IntegratorChainConfig, IntegratorConfig, SequenceRecord,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 2;
+const SRC_ADDR: [u8; 32] = [1u8; 32];
+const SEQUENCE: u64 = 1;
+const PAYLOAD_HASH: [u8; 32] = [3u8; 32];
+
+async fn setup_test_environment() -> (ProgramTestContext, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_program_id = mock_adapter::id();
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID).0,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    (context, payer)
+}
+
+fn message_hash() -> [u8; 32] {
+    AttestationInfo::compute_message_hash(
+        CHAIN_ID,
+        SRC_ADDR,
+        SEQUENCE,
+        1,
+        mock_integrator::id().to_bytes(),
+        PAYLOAD_HASH,
+        None,
+    )
+}
+
+#[tokio::test]
+async fn test_exec_message_by_hash_success() {
+    let (mut context, payer) = setup_test_environment().await;
+
+    attest_message(
+        &mut context,
+        &payer,
+        AdapterInfo::pda(&mock_integrator::id(), &mock_adapter::id()).0,
+        adapter_interface::adapter_pda(&mock_adapter::id()).0,
+        IntegratorChainConfig::pda(&mock_integrator::id(), CHAIN_ID).0,
+        CHAIN_ID,
+        SRC_ADDR,
+        SEQUENCE,
+        1,
+        mock_integrator::id().to_bytes(),
+        PAYLOAD_HASH,
+        None,
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    exec_message_by_hash(
+        &mut context,
+        &payer,
+        CHAIN_ID,
+        SRC_ADDR,
+        SEQUENCE,
+        message_hash(),
+    )
+    .await
+    .unwrap();
+
+    // The message and its sequence are marked as executed
+    let (attestation_info_pda, _) = AttestationInfo::pda(message_hash());
+    let attestation_info: AttestationInfo =
+        get_account(&mut context.banks_client, attestation_info_pda).await;
+    assert!(attestation_info.executed);
+
+    let (sequence_record_pda, _) =
+        SequenceRecord::pda(&mock_integrator::id(), CHAIN_ID, SRC_ADDR, SEQUENCE);
+    let sequence_record: SequenceRecord =
+        get_account(&mut context.banks_client, sequence_record_pda).await;
+    assert!(sequence_record.executed);
+
+    // The message can no longer be received
+    let result = recv_message(
+        &mut context,
+        &payer,
+        attestation_info_pda,
+        CHAIN_ID,
+        SRC_ADDR,
+        SEQUENCE,
+        1,
+        PAYLOAD_HASH,
+        None,
+        false,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::AlreadyExecuted.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_exec_message_by_hash_without_attestation_info() {
+    let (mut context, payer) = setup_test_environment().await;
+
+    // A message that has not been attested to must be executed with its full fields
+    let result = exec_message_by_hash(
+        &mut context,
+        &payer,
+        CHAIN_ID,
+        SRC_ADDR,
+        SEQUENCE,
+        message_hash(),
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(3012)) // AccountNotInitialized
+    );
+}
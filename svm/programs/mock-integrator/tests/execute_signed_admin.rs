@@ -0,0 +1,505 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::execute_signed_admin::{
+    accounts_hash, admin_authority, execute_signed_admin, execute_signed_admin_with_message,
+};
+use crate::instructions::register::register;
+
+use anchor_lang::{prelude::*, InstructionData};
+use common::error::custom_error;
+use common::setup::{current_audit_log_page, get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::instructions::{
+    AddAdapterArgs, CloseReplayWindowArgs, DeregisterArgs, ExecuteSignedAdminArgs,
+    RetireAdapterArgs,
+};
+use endpoint::state::{
+    AdapterIndex, AdapterInfo, AdapterStats, AuditLogPage, IntegratorConfig, IntegratorTombstone,
+    SequenceTracker,
+};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+/// The admin instruction is the second in the transaction, after the Ed25519Program instruction
+fn event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id()).0
+}
+
+/// Registers the mock integrator with `admin`, a key that never signs a transaction
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    (context, payer, admin)
+}
+
+/// Builds a `deregister` instruction with `admin` as the admin
+async fn deregister_instruction(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    admin: Pubkey,
+    rent_recipient: Pubkey,
+) -> Instruction {
+    let integrator_program_id = mock_integrator::id();
+    let (integrator_config, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    let accounts = endpoint::accounts::Deregister {
+        payer: payer.pubkey(),
+        admin,
+        integrator_config,
+        sequence_tracker: SequenceTracker::pda(&integrator_program_id).0,
+        integrator_tombstone: IntegratorTombstone::pda(&integrator_program_id).0,
+        rent_recipient,
+        audit_log_page: current_audit_log_page(&mut context.banks_client, integrator_config).await,
+        system_program: solana_sdk::system_program::id(),
+        event_authority: event_authority(),
+        program: endpoint::id(),
+    };
+
+    let args = DeregisterArgs {
+        integrator_program_id,
+    };
+
+    let integrator_config: IntegratorConfig =
+        get_account(&mut context.banks_client, integrator_config).await;
+    let mut accounts = accounts.to_account_metas(None);
+    accounts.extend(
+        integrator_config
+            .adapter_infos
+            .iter()
+            .map(|adapter_program_id| {
+                AccountMeta::new_readonly(
+                    AdapterStats::pda(&integrator_program_id, adapter_program_id).0,
+                    false,
+                )
+            }),
+    );
+
+    Instruction {
+        program_id: endpoint::id(),
+        accounts,
+        data: endpoint::instruction::Deregister { args }.data(),
+    }
+}
+
+/// Builds an `add_adapter` instruction for the mock adapter with `admin` as the admin
+async fn add_adapter_instruction(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    admin: Pubkey,
+) -> Instruction {
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+    let (integrator_config, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    let accounts = endpoint::accounts::AddAdapter {
+        payer: payer.pubkey(),
+        admin,
+        integrator_config,
+        audit_log_page: current_audit_log_page(&mut context.banks_client, integrator_config).await,
+        adapter_info: AdapterInfo::pda(&integrator_program_id, &adapter_program_id).0,
+        adapter_stats: AdapterStats::pda(&integrator_program_id, &adapter_program_id).0,
+        adapter_index: AdapterIndex::pda(&adapter_program_id, &integrator_program_id).0,
+        system_program: solana_sdk::system_program::id(),
+        event_authority: event_authority(),
+        program: endpoint::id(),
+    };
+
+    let args = AddAdapterArgs {
+        integrator_program_id,
+        adapter_program_id,
+    };
+
+    Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::AddAdapter { args }.data(),
+    }
+}
+
+#[tokio::test]
+async fn test_execute_signed_admin_add_adapter() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let authority = admin_authority(&integrator_program_id, &admin.pubkey());
+
+    let ix = add_adapter_instruction(&mut context, &payer, authority).await;
+    execute_signed_admin(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_program_id,
+        0,
+        i64::MAX,
+        ix,
+    )
+    .await
+    .unwrap();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let integrator_config: IntegratorConfig =
+        get_account(&mut context.banks_client, integrator_config_pda).await;
+    assert_eq!(integrator_config.adapter_infos, vec![mock_adapter::id()]);
+    assert_eq!(integrator_config.admin_nonce, 1);
+    assert_eq!(integrator_config.audit_log_len, 1);
+
+    // The change is logged as made by the signer, not by its admin authority
+    let (audit_log_page_pda, _) = AuditLogPage::pda(&integrator_program_id, 0);
+    let audit_log_page: AuditLogPage =
+        get_account(&mut context.banks_client, audit_log_page_pda).await;
+    assert_eq!(audit_log_page.entries[0].actor, admin.pubkey());
+}
+
+#[tokio::test]
+async fn test_execute_signed_admin_rejects_replay() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let authority = admin_authority(&integrator_program_id, &admin.pubkey());
+
+    let ix = add_adapter_instruction(&mut context, &payer, authority).await;
+    execute_signed_admin(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_program_id,
+        0,
+        i64::MAX,
+        ix.clone(),
+    )
+    .await
+    .unwrap();
+
+    let err = execute_signed_admin(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_program_id,
+        0,
+        i64::MAX,
+        ix,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        custom_error(1, EndpointError::InvalidAdminNonce)
+    );
+}
+
+#[tokio::test]
+async fn test_execute_signed_admin_rejects_expired_authorization() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let authority = admin_authority(&integrator_program_id, &admin.pubkey());
+
+    let ix = add_adapter_instruction(&mut context, &payer, authority).await;
+    let err = execute_signed_admin(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_program_id,
+        0,
+        0,
+        ix,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        custom_error(1, EndpointError::AdminAuthorizationExpired)
+    );
+}
+
+#[tokio::test]
+async fn test_execute_signed_admin_rejects_other_message() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let authority = admin_authority(&integrator_program_id, &admin.pubkey());
+
+    let ix = add_adapter_instruction(&mut context, &payer, authority).await;
+    let args = ExecuteSignedAdminArgs {
+        integrator_program_id,
+        nonce: 0,
+        expiry: i64::MAX,
+        instruction_data: ix.data.clone(),
+    };
+
+    // The admin signed for a later expiry than the one submitted
+    let message = ExecuteSignedAdminArgs {
+        integrator_program_id,
+        nonce: 0,
+        expiry: i64::MAX - 1,
+        instruction_data: ix.data.clone(),
+    }
+    .message(&accounts_hash(&integrator_program_id, &admin.pubkey(), &ix));
+
+    let err = execute_signed_admin_with_message(&mut context, &payer, &admin, args, &message, ix)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        custom_error(1, EndpointError::InvalidAdminAuthorization)
+    );
+}
+
+#[tokio::test]
+async fn test_execute_signed_admin_rejects_other_accounts() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let authority = admin_authority(&integrator_program_id, &admin.pubkey());
+    let rent_recipient = Pubkey::new_unique();
+
+    let ix = deregister_instruction(&mut context, &payer, authority, rent_recipient).await;
+    let args = ExecuteSignedAdminArgs {
+        integrator_program_id,
+        nonce: 0,
+        expiry: i64::MAX,
+        instruction_data: ix.data.clone(),
+    };
+    let message = args.message(&accounts_hash(&integrator_program_id, &admin.pubkey(), &ix));
+
+    // The relayer submits the signed deregistration with its own rent recipient
+    let other_ix = deregister_instruction(&mut context, &payer, authority, payer.pubkey()).await;
+    let err =
+        execute_signed_admin_with_message(&mut context, &payer, &admin, args, &message, other_ix)
+            .await
+            .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        custom_error(1, EndpointError::InvalidAdminAuthorization)
+    );
+
+    execute_signed_admin(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_program_id,
+        0,
+        i64::MAX,
+        ix,
+    )
+    .await
+    .unwrap();
+
+    let (integrator_tombstone_pda, _) = IntegratorTombstone::pda(&integrator_program_id);
+    let integrator_tombstone: IntegratorTombstone =
+        get_account(&mut context.banks_client, integrator_tombstone_pda).await;
+    assert_eq!(integrator_tombstone.rent_recipient, rent_recipient);
+}
+
+#[tokio::test]
+async fn test_execute_signed_admin_retire_adapter() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+    let authority = admin_authority(&integrator_program_id, &admin.pubkey());
+
+    let ix = add_adapter_instruction(&mut context, &payer, authority).await;
+    execute_signed_admin(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_program_id,
+        0,
+        i64::MAX,
+        ix,
+    )
+    .await
+    .unwrap();
+
+    let (integrator_config, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    let accounts = endpoint::accounts::RetireAdapter {
+        payer: payer.pubkey(),
+        admin: authority,
+        integrator_config,
+        adapter_info: adapter_info_pda,
+        adapter_index: AdapterIndex::pda(&adapter_program_id, &integrator_program_id).0,
+        audit_log_page: current_audit_log_page(&mut context.banks_client, integrator_config).await,
+        system_program: solana_sdk::system_program::id(),
+        event_authority: event_authority(),
+        program: endpoint::id(),
+    };
+    let args = RetireAdapterArgs {
+        integrator_program_id,
+        adapter_program_id,
+    };
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::RetireAdapter { args }.data(),
+    };
+    execute_signed_admin(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_program_id,
+        1,
+        i64::MAX,
+        ix,
+    )
+    .await
+    .unwrap();
+
+    let adapter_info: AdapterInfo = get_account(&mut context.banks_client, adapter_info_pda).await;
+    assert!(adapter_info.retired);
+}
+
+#[tokio::test]
+async fn test_execute_signed_admin_rejects_non_admin() {
+    let (mut context, payer, _admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let other = Keypair::new();
+    let authority = admin_authority(&integrator_program_id, &other.pubkey());
+
+    let ix = add_adapter_instruction(&mut context, &payer, authority).await;
+    let err = execute_signed_admin(
+        &mut context,
+        &payer,
+        &other,
+        integrator_program_id,
+        0,
+        i64::MAX,
+        ix,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        custom_error(1, EndpointError::CallerNotAuthorized)
+    );
+}
+
+#[tokio::test]
+async fn test_execute_signed_admin_rejects_unsignable_instruction() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+
+    let args = CloseReplayWindowArgs {
+        integrator_program_id,
+        src_chain: 1,
+        src_addr: [1u8; 32],
+        window_index: 0,
+    };
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: vec![],
+        data: endpoint::instruction::CloseReplayWindow { args }.data(),
+    };
+
+    let err = execute_signed_admin(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_program_id,
+        0,
+        i64::MAX,
+        ix,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        custom_error(1, EndpointError::AdminInstructionNotSignable)
+    );
+}
+
+#[tokio::test]
+async fn test_execute_signed_admin_transfer_and_claim() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let new_admin = Keypair::new();
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    let accounts = endpoint::accounts::TransferAdmin {
+        payer: payer.pubkey(),
+        admin: admin_authority(&integrator_program_id, &admin.pubkey()),
+        integrator_config: integrator_config_pda,
+        audit_log_page: current_audit_log_page(&mut context.banks_client, integrator_config_pda)
+            .await,
+        system_program: solana_sdk::system_program::id(),
+        event_authority: event_authority(),
+        program: endpoint::id(),
+    };
+    let args = endpoint::instructions::TransferAdminArgs {
+        integrator_program_id,
+        new_admin: new_admin.pubkey(),
+    };
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::TransferAdmin { args }.data(),
+    };
+    execute_signed_admin(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_program_id,
+        0,
+        i64::MAX,
+        ix,
+    )
+    .await
+    .unwrap();
+
+    // The new admin claims by signature as well
+    let accounts = endpoint::accounts::ClaimAdmin {
+        payer: payer.pubkey(),
+        new_admin: admin_authority(&integrator_program_id, &new_admin.pubkey()),
+        integrator_config: integrator_config_pda,
+        audit_log_page: current_audit_log_page(&mut context.banks_client, integrator_config_pda)
+            .await,
+        system_program: solana_sdk::system_program::id(),
+        event_authority: event_authority(),
+        program: endpoint::id(),
+    };
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::ClaimAdmin {}.data(),
+    };
+    execute_signed_admin(
+        &mut context,
+        &payer,
+        &new_admin,
+        integrator_program_id,
+        1,
+        i64::MAX,
+        ix,
+    )
+    .await
+    .unwrap();
+
+    let integrator_config: IntegratorConfig =
+        get_account(&mut context.banks_client, integrator_config_pda).await;
+    assert_eq!(integrator_config.admin, Some(new_admin.pubkey()));
+    assert_eq!(integrator_config.pending_admin, None);
+    assert_eq!(integrator_config.admin_nonce, 2);
+
+    let (audit_log_page_pda, _) = AuditLogPage::pda(&integrator_program_id, 0);
+    let audit_log_page: AuditLogPage =
+        get_account(&mut context.banks_client, audit_log_page_pda).await;
+    let actors: Vec<Pubkey> = audit_log_page
+        .entries
+        .iter()
+        .map(|entry| entry.actor)
+        .collect();
+    assert_eq!(actors, vec![admin.pubkey(), new_admin.pubkey()]);
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::init_attestation_tree::init_attestation_tree;
+use crate::instructions::register::register;
+
+use common::setup::{get_account, setup};
+use endpoint::client::attestation_tree::AttestationTreeIndex;
+use endpoint::error::EndpointError;
+use endpoint::state::{AttestationTree, IntegratorConfig};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 2;
+
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    (context, payer, admin)
+}
+
+#[tokio::test]
+async fn test_init_attestation_tree_success() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let attestation_tree = Keypair::new();
+
+    init_attestation_tree(
+        &mut context,
+        &admin,
+        &payer,
+        &attestation_tree,
+        mock_integrator::id(),
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+
+    let tree: AttestationTree =
+        get_account(&mut context.banks_client, attestation_tree.pubkey()).await;
+    assert_eq!(tree.integrator_program_id, mock_integrator::id());
+    assert_eq!(tree.chain_id, CHAIN_ID);
+
+    // The tree starts out empty
+    let mut data = context
+        .banks_client
+        .get_account(attestation_tree.pubkey())
+        .await
+        .unwrap()
+        .expect("account not found")
+        .data;
+    let root = AttestationTree::load_tree_mut(&mut data)
+        .unwrap()
+        .get_root();
+    assert_eq!(root, AttestationTreeIndex::new().root());
+}
+
+#[tokio::test]
+async fn test_init_attestation_tree_non_admin() {
+    let (mut context, payer, _) = setup_test_environment().await;
+    let non_admin = Keypair::new();
+
+    let result = init_attestation_tree(
+        &mut context,
+        &non_admin,
+        &payer,
+        &Keypair::new(),
+        mock_integrator::id(),
+        CHAIN_ID,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(EndpointError::CallerNotAuthorized.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_init_attestation_tree_invalid_chain_id() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+
+    let result = init_attestation_tree(
+        &mut context,
+        &admin,
+        &payer,
+        &Keypair::new(),
+        mock_integrator::id(),
+        0,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(EndpointError::InvalidChainId.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_init_attestation_tree_already_initialized() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let attestation_tree = Keypair::new();
+
+    init_attestation_tree(
+        &mut context,
+        &admin,
+        &payer,
+        &attestation_tree,
+        mock_integrator::id(),
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+
+    // The account already exists, so it cannot be allocated again
+    let result = init_attestation_tree(
+        &mut context,
+        &admin,
+        &payer,
+        &attestation_tree,
+        mock_integrator::id(),
+        CHAIN_ID,
+    )
+    .await;
+    assert!(result.is_err());
+}
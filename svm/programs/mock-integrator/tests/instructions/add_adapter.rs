@@ -1,6 +1,7 @@
 use anchor_lang::{InstructionData, ToAccountMetas};
 use endpoint::accounts::AddAdapter;
 use endpoint::instructions::AddAdapterArgs;
+use endpoint::state::{AdapterIndex, AdapterStats};
 use solana_program_test::*;
 use solana_sdk::{
     instruction::Instruction,
@@ -9,6 +10,7 @@ use solana_sdk::{
 };
 
 use crate::common::execute_transaction::execute_transaction;
+use crate::common::setup::current_audit_log_page;
 
 pub async fn add_adapter(
     context: &mut ProgramTestContext,
@@ -19,14 +21,20 @@ pub async fn add_adapter(
     integrator_program_id: Pubkey,
     adapter_program_id: Pubkey,
 ) -> Result<(), BanksClientError> {
+    let (adapter_stats, _) = AdapterStats::pda(&integrator_program_id, &adapter_program_id);
+    let (adapter_index, _) = AdapterIndex::pda(&adapter_program_id, &integrator_program_id);
     let (event_authority, _) =
         Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+    let audit_log_page = current_audit_log_page(&mut context.banks_client, integrator_config).await;
 
     let accounts = AddAdapter {
         payer: payer.pubkey(),
         admin: admin.pubkey(),
         integrator_config,
+        audit_log_page,
         adapter_info,
+        adapter_stats,
+        adapter_index,
         system_program: solana_sdk::system_program::id(),
         program: endpoint::id(),
         event_authority,
@@ -1,5 +1,7 @@
 use anchor_lang::{InstructionData, ToAccountMetas};
-use endpoint::state::AttestationInfo;
+use endpoint::state::{
+    AdapterStats, AttestationInfo, AttestationMetadata, MessageExpiry, ReplayWindow, SequenceRecord,
+};
 use mock_adapter::{accounts::InvokeAttestMessage, InvokeAttestMessageArgs};
 use solana_program_test::*;
 use solana_sdk::{
@@ -22,6 +24,8 @@ pub async fn attest_message(
     dst_chain: u16,
     dst_addr: [u8; 32],
     payload_hash: [u8; 32],
+    expiry: Option<MessageExpiry>,
+    metadata: Vec<u8>,
 ) -> Result<(), BanksClientError> {
     let message_hash = AttestationInfo::compute_message_hash(
         src_chain,
@@ -30,17 +34,34 @@ pub async fn attest_message(
         dst_chain,
         dst_addr,
         payload_hash,
+        expiry,
     );
     let (attestation_info, _) = AttestationInfo::pda(message_hash);
+    let (adapter_stats, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let (sequence_record, _) =
+        SequenceRecord::pda(&mock_integrator::id(), src_chain, src_addr, sequence);
+    let (replay_window, _) = ReplayWindow::pda(
+        &mock_integrator::id(),
+        src_chain,
+        src_addr,
+        ReplayWindow::window_index(sequence),
+    );
+    // The metadata account is only needed when metadata is attached
+    let attestation_metadata =
+        (!metadata.is_empty()).then(|| AttestationMetadata::pda(message_hash).0);
     let (event_authority, _) =
         Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
 
     let accounts = InvokeAttestMessage {
         payer: payer.pubkey(),
         adapter_info,
+        adapter_stats,
         adapter_pda,
         integrator_chain_config,
         attestation_info,
+        sequence_record,
+        replay_window,
+        attestation_metadata,
         system_program: solana_sdk::system_program::id(),
         endpoint_program: endpoint::id(),
         program: endpoint::id(),
@@ -55,6 +76,8 @@ pub async fn attest_message(
         integrator_program_id: mock_integrator::id(),
         payload_hash,
         message_hash,
+        expiry,
+        metadata,
     };
 
     let ix = Instruction {
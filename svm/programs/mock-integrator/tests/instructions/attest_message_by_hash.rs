@@ -0,0 +1,76 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::state::{
+    AdapterStats, AttestationInfo, AttestationMetadata, IntegratorChainConfig, SequenceRecord,
+};
+use mock_adapter::{accounts::InvokeAttestMessageByHash, InvokeAttestMessageByHashArgs};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+
+/// The source of the message attested to by its hash, which is only used to derive the accounts
+pub struct MessageSource {
+    pub src_chain: u16,
+    pub src_addr: [u8; 32],
+    pub sequence: u64,
+}
+
+/// Attests to a message by its hash, as the mock adapter
+pub async fn attest_message_by_hash(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    adapter_info: Pubkey,
+    adapter_pda: Pubkey,
+    source: MessageSource,
+    message_hash: [u8; 32],
+    metadata: Vec<u8>,
+) -> Result<(), BanksClientError> {
+    let MessageSource {
+        src_chain,
+        src_addr,
+        sequence,
+    } = source;
+    let (attestation_info, _) = AttestationInfo::pda(message_hash);
+    let (sequence_record, _) =
+        SequenceRecord::pda(&mock_integrator::id(), src_chain, src_addr, sequence);
+    let (adapter_stats, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let (integrator_chain_config, _) =
+        IntegratorChainConfig::pda(&mock_integrator::id(), src_chain);
+    // The metadata account is only needed when metadata is attached
+    let attestation_metadata =
+        (!metadata.is_empty()).then(|| AttestationMetadata::pda(message_hash).0);
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = InvokeAttestMessageByHash {
+        payer: payer.pubkey(),
+        attestation_info,
+        sequence_record,
+        adapter_info,
+        adapter_stats,
+        adapter_pda,
+        integrator_chain_config,
+        attestation_metadata,
+        system_program: solana_sdk::system_program::id(),
+        endpoint_program: endpoint::id(),
+        program: endpoint::id(),
+        event_authority,
+    };
+
+    let args = InvokeAttestMessageByHashArgs {
+        message_hash,
+        metadata,
+    };
+
+    let ix = Instruction {
+        program_id: mock_adapter::id(),
+        accounts: accounts.to_account_metas(None),
+        data: mock_adapter::instruction::InvokeAttestMessageByHash { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
@@ -0,0 +1,100 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::client::attestation_tree::AttestationTreeProof;
+use endpoint::instructions::BatchedMessage;
+use endpoint::state::{
+    AdapterInfo, AdapterStats, AttestationInfo, AttestationLeaf, CompressedAttestation,
+    IntegratorChainConfig, ReplayWindow, SequenceRecord,
+};
+use mock_adapter::{accounts::InvokeAttestMessageCompressed, InvokeAttestMessageCompressedArgs};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+
+/// Attests to a message sent to the mock integrator on this chain in `attestation_tree`, as the
+/// mock adapter
+/// Without a `previous_leaf` a new leaf is appended, and `proof` is only used for its root
+pub async fn attest_message_compressed(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    attestation_tree: Pubkey,
+    src_chain: u16,
+    message: &BatchedMessage,
+    proof: &AttestationTreeProof,
+    previous_leaf: Option<CompressedAttestation>,
+) -> Result<(), BanksClientError> {
+    let (adapter_info, _) = AdapterInfo::pda(&mock_integrator::id(), &mock_adapter::id());
+    let (adapter_stats, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let (adapter_pda, _) = adapter_interface::adapter_pda(&mock_adapter::id());
+    let (integrator_chain_config, _) =
+        IntegratorChainConfig::pda(&mock_integrator::id(), src_chain);
+    let (attestation_leaf, _) = AttestationLeaf::pda(&AttestationInfo::compute_message_hash(
+        src_chain,
+        message.src_addr,
+        message.sequence,
+        endpoint::CHAIN_ID,
+        mock_integrator::id().to_bytes(),
+        message.payload_hash,
+        message.expiry,
+    ));
+    let (sequence_record, _) = SequenceRecord::pda(
+        &mock_integrator::id(),
+        src_chain,
+        message.src_addr,
+        message.sequence,
+    );
+    let (replay_window, _) = ReplayWindow::pda(
+        &mock_integrator::id(),
+        src_chain,
+        message.src_addr,
+        ReplayWindow::window_index(message.sequence),
+    );
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = InvokeAttestMessageCompressed {
+        payer: payer.pubkey(),
+        adapter_info,
+        adapter_stats,
+        adapter_pda,
+        integrator_chain_config,
+        attestation_tree,
+        attestation_leaf,
+        sequence_record,
+        replay_window,
+        system_program: solana_sdk::system_program::id(),
+        endpoint_program: endpoint::id(),
+        program: endpoint::id(),
+        event_authority,
+    };
+
+    let args = InvokeAttestMessageCompressedArgs {
+        src_chain,
+        src_addr: message.src_addr,
+        sequence: message.sequence,
+        dst_chain: endpoint::CHAIN_ID,
+        integrator_program_id: mock_integrator::id(),
+        payload_hash: message.payload_hash,
+        expiry: message.expiry,
+        root: proof.root,
+        leaf_index: proof.leaf_index,
+        previous_leaf,
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    if previous_leaf.is_some() {
+        account_metas.extend(proof.remaining_accounts());
+    }
+
+    let ix = Instruction {
+        program_id: mock_adapter::id(),
+        accounts: account_metas,
+        data: mock_adapter::instruction::InvokeAttestMessageCompressed { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
@@ -0,0 +1,88 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::instructions::{BatchMode, BatchedMessage};
+use endpoint::state::{
+    AdapterStats, AttestationInfo, IntegratorChainConfig, ReplayWindow, SequenceRecord,
+};
+use mock_adapter::{accounts::InvokeAttestMessages, InvokeAttestMessagesArgs};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+
+/// Attests to `messages` from `src_chain`, passing each message's attestation info, sequence
+/// record and replay window as remaining accounts
+pub async fn attest_messages(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    adapter_info: Pubkey,
+    adapter_pda: Pubkey,
+    src_chain: u16,
+    messages: Vec<BatchedMessage>,
+    mode: BatchMode,
+) -> Result<(), BanksClientError> {
+    let (adapter_stats, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let (integrator_chain_config, _) =
+        IntegratorChainConfig::pda(&mock_integrator::id(), src_chain);
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = InvokeAttestMessages {
+        payer: payer.pubkey(),
+        adapter_info,
+        adapter_stats,
+        adapter_pda,
+        integrator_chain_config,
+        system_program: solana_sdk::system_program::id(),
+        endpoint_program: endpoint::id(),
+        program: endpoint::id(),
+        event_authority,
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    for message in &messages {
+        let message_hash = AttestationInfo::compute_message_hash(
+            src_chain,
+            message.src_addr,
+            message.sequence,
+            endpoint::CHAIN_ID,
+            mock_integrator::id().to_bytes(),
+            message.payload_hash,
+            message.expiry,
+        );
+        let (attestation_info, _) = AttestationInfo::pda(message_hash);
+        let (sequence_record, _) = SequenceRecord::pda(
+            &mock_integrator::id(),
+            src_chain,
+            message.src_addr,
+            message.sequence,
+        );
+        account_metas.push(AccountMeta::new(attestation_info, false));
+        let (replay_window, _) = ReplayWindow::pda(
+            &mock_integrator::id(),
+            src_chain,
+            message.src_addr,
+            ReplayWindow::window_index(message.sequence),
+        );
+        account_metas.push(AccountMeta::new(sequence_record, false));
+        account_metas.push(AccountMeta::new_readonly(replay_window, false));
+    }
+
+    let args = InvokeAttestMessagesArgs {
+        src_chain,
+        integrator_program_id: mock_integrator::id(),
+        messages,
+        mode,
+    };
+
+    let ix = Instruction {
+        program_id: mock_adapter::id(),
+        accounts: account_metas,
+        data: mock_adapter::instruction::InvokeAttestMessages { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
@@ -0,0 +1,62 @@
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use endpoint::state::{OutboxMessage, SentMessageRecord, SequenceTracker};
+use mock_integrator::accounts::InvokeCancelMessage;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::keypair::Keypair,
+};
+
+use crate::common::execute_transaction::execute_transaction;
+
+/// Cancels `outbox_message`, removing it from `adapter_queues`
+pub async fn cancel_message(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    outbox_message: Pubkey,
+    refund_recipient: Pubkey,
+    adapter_queues: &[Pubkey],
+) -> Result<(), BanksClientError> {
+    let integrator_program_id = mock_integrator::id();
+    let (integrator_program_pda, _) =
+        Pubkey::find_program_address(&[b"endpoint_integrator"], &integrator_program_id);
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    // The record of the message's sequence is marked as cancelled, if the message has one
+    let sequence = match context.banks_client.get_account(outbox_message).await? {
+        Some(account) => {
+            OutboxMessage::try_deserialize(&mut account.data.as_slice())
+                .expect("invalid outbox message")
+                .sequence
+        }
+        None => 0,
+    };
+
+    let accounts = InvokeCancelMessage {
+        integrator_program_pda,
+        outbox_message,
+        sequence_tracker: SequenceTracker::pda(&integrator_program_id).0,
+        sent_message_record: SentMessageRecord::pda(&integrator_program_id, sequence).0,
+        refund_recipient,
+        event_authority,
+        program: endpoint::id(),
+        endpoint_program: endpoint::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(
+        adapter_queues
+            .iter()
+            .map(|adapter_queue| AccountMeta::new(*adapter_queue, false)),
+    );
+
+    let ix = Instruction {
+        program_id: mock_integrator::id(),
+        accounts: account_metas,
+        data: mock_integrator::instruction::InvokeCancelMessage {}.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
@@ -0,0 +1,55 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::accounts::CloseReplayWindow;
+use endpoint::instructions::CloseReplayWindowArgs;
+use endpoint::state::{IntegratorConfig, ReplayFloor, ReplayWindow};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+use crate::common::setup::current_audit_log_page;
+
+pub async fn close_replay_window(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    payer: &Keypair,
+    args: CloseReplayWindowArgs,
+    recipient: Pubkey,
+) -> Result<(), BanksClientError> {
+    let (integrator_config, _) = IntegratorConfig::pda(&args.integrator_program_id);
+    let audit_log_page = current_audit_log_page(&mut context.banks_client, integrator_config).await;
+    let (replay_floor, _) =
+        ReplayFloor::pda(&args.integrator_program_id, args.src_chain, args.src_addr);
+    let (replay_window, _) = ReplayWindow::pda(
+        &args.integrator_program_id,
+        args.src_chain,
+        args.src_addr,
+        args.window_index,
+    );
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = CloseReplayWindow {
+        payer: payer.pubkey(),
+        admin: admin.pubkey(),
+        integrator_config,
+        replay_floor,
+        replay_window,
+        recipient,
+        audit_log_page,
+        system_program: solana_sdk::system_program::id(),
+        event_authority,
+        program: endpoint::id(),
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::CloseReplayWindow { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[admin, payer], payer).await
+}
@@ -0,0 +1,35 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::state::SentMessageRecord;
+use mock_integrator::{accounts::InvokeCloseSentMessageRecord, InvokeCloseSentMessageRecordArgs};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::keypair::Keypair};
+
+use crate::common::execute_transaction::execute_transaction;
+
+pub async fn close_sent_message_record(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    sequence: u64,
+    record_payer: Pubkey,
+) -> Result<(), BanksClientError> {
+    let integrator_program_id = mock_integrator::id();
+    let (integrator_program_pda, _) =
+        Pubkey::find_program_address(&[b"endpoint_integrator"], &integrator_program_id);
+
+    let accounts = InvokeCloseSentMessageRecord {
+        integrator_program_pda,
+        sent_message_record: SentMessageRecord::pda(&integrator_program_id, sequence).0,
+        payer: record_payer,
+        endpoint_program: endpoint::id(),
+    };
+
+    let args = InvokeCloseSentMessageRecordArgs { sequence };
+
+    let ix = Instruction {
+        program_id: mock_integrator::id(),
+        accounts: accounts.to_account_metas(None),
+        data: mock_integrator::instruction::InvokeCloseSentMessageRecord { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
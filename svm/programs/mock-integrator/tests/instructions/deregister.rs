@@ -0,0 +1,324 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::accounts::{
+    CloseDeregisteredAdapter, CloseDeregisteredAttestationTree, CloseDeregisteredAuditLogPage,
+    CloseDeregisteredChain, CloseDeregisteredInboundCursor, CloseDeregisteredReplayWindow,
+    CloseDeregisteredSentMessage, Deregister,
+};
+use endpoint::instructions::{
+    CloseDeregisteredAdapterArgs, CloseDeregisteredAttestationTreeArgs,
+    CloseDeregisteredAuditLogPageArgs, CloseDeregisteredChainArgs,
+    CloseDeregisteredInboundCursorArgs, CloseDeregisteredReplayWindowArgs,
+    CloseDeregisteredSentMessageArgs, DeregisterArgs,
+};
+use endpoint::state::{
+    AdapterIndex, AdapterInfo, AdapterQueue, AdapterStats, AuditLogPage, InboundCursor,
+    IntegratorChainConfig, IntegratorConfig, IntegratorTombstone, ReplayFloor, ReplayWindow,
+    SentMessageRecord, SequenceTracker,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+use crate::common::setup::{current_audit_log_page, get_account};
+
+pub async fn deregister(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    rent_recipient: Pubkey,
+) -> Result<(), BanksClientError> {
+    let (integrator_config, _) = IntegratorConfig::pda(&integrator_program_id);
+    let audit_log_page = current_audit_log_page(&mut context.banks_client, integrator_config).await;
+    let (sequence_tracker, _) = SequenceTracker::pda(&integrator_program_id);
+    let (integrator_tombstone, _) = IntegratorTombstone::pda(&integrator_program_id);
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = Deregister {
+        payer: payer.pubkey(),
+        admin: admin.pubkey(),
+        integrator_config,
+        sequence_tracker,
+        integrator_tombstone,
+        rent_recipient,
+        audit_log_page,
+        system_program: solana_sdk::system_program::id(),
+        event_authority,
+        program: endpoint::id(),
+    };
+
+    let args = DeregisterArgs {
+        integrator_program_id,
+    };
+
+    // The adapter stats of each of the integrator's adapters, in order
+    let integrator_config: IntegratorConfig =
+        get_account(&mut context.banks_client, integrator_config).await;
+    let mut accounts = accounts.to_account_metas(None);
+    accounts.extend(
+        integrator_config
+            .adapter_infos
+            .iter()
+            .map(|adapter_program_id| {
+                AccountMeta::new_readonly(
+                    AdapterStats::pda(&integrator_program_id, adapter_program_id).0,
+                    false,
+                )
+            }),
+    );
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts,
+        data: endpoint::instruction::Deregister { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[admin, payer], payer).await
+}
+
+pub async fn close_deregistered_chain(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    chain_id: u16,
+    rent_recipient: Pubkey,
+) -> Result<(), BanksClientError> {
+    let (integrator_tombstone, _) = IntegratorTombstone::pda(&integrator_program_id);
+    let (integrator_chain_config, _) = IntegratorChainConfig::pda(&integrator_program_id, chain_id);
+
+    let accounts = CloseDeregisteredChain {
+        integrator_tombstone,
+        integrator_chain_config,
+        rent_recipient,
+    };
+
+    let args = CloseDeregisteredChainArgs {
+        integrator_program_id,
+        chain_id,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::CloseDeregisteredChain { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
+
+pub async fn close_deregistered_adapter(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    adapter_program_id: Pubkey,
+    rent_recipient: Pubkey,
+) -> Result<(), BanksClientError> {
+    let (integrator_tombstone, _) = IntegratorTombstone::pda(&integrator_program_id);
+    let (adapter_info, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    let (adapter_stats, _) = AdapterStats::pda(&integrator_program_id, &adapter_program_id);
+    let (adapter_index, _) = AdapterIndex::pda(&adapter_program_id, &integrator_program_id);
+    let (adapter_queue, _) = AdapterQueue::pda(&integrator_program_id, &adapter_program_id);
+
+    // The queue is only passed if the adapter has one
+    let adapter_queue = context
+        .banks_client
+        .get_account(adapter_queue)
+        .await?
+        .map(|_| adapter_queue);
+
+    // Adapters added before the index existed may not have one
+    let adapter_index = context
+        .banks_client
+        .get_account(adapter_index)
+        .await?
+        .map(|_| adapter_index);
+
+    let accounts = CloseDeregisteredAdapter {
+        integrator_tombstone,
+        adapter_info,
+        adapter_stats,
+        adapter_queue,
+        adapter_index,
+        rent_recipient,
+    };
+
+    let args = CloseDeregisteredAdapterArgs {
+        integrator_program_id,
+        adapter_program_id,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::CloseDeregisteredAdapter { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
+
+pub async fn close_deregistered_audit_log_page(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    page_index: u64,
+    rent_recipient: Pubkey,
+) -> Result<(), BanksClientError> {
+    let (integrator_tombstone, _) = IntegratorTombstone::pda(&integrator_program_id);
+    let (audit_log_page, _) = AuditLogPage::pda(&integrator_program_id, page_index);
+
+    let accounts = CloseDeregisteredAuditLogPage {
+        integrator_tombstone,
+        audit_log_page,
+        rent_recipient,
+    };
+
+    let args = CloseDeregisteredAuditLogPageArgs {
+        integrator_program_id,
+        page_index,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::CloseDeregisteredAuditLogPage { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
+
+pub async fn close_deregistered_sent_message(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    sequence: u64,
+    record_payer: Pubkey,
+) -> Result<(), BanksClientError> {
+    let (integrator_tombstone, _) = IntegratorTombstone::pda(&integrator_program_id);
+    let (sent_message_record, _) = SentMessageRecord::pda(&integrator_program_id, sequence);
+
+    let accounts = CloseDeregisteredSentMessage {
+        integrator_tombstone,
+        sent_message_record,
+        payer: record_payer,
+    };
+
+    let args = CloseDeregisteredSentMessageArgs {
+        integrator_program_id,
+        sequence,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::CloseDeregisteredSentMessage { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
+
+pub async fn close_deregistered_inbound_cursor(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    src_chain: u16,
+    src_addr: [u8; 32],
+    rent_recipient: Pubkey,
+) -> Result<(), BanksClientError> {
+    let (integrator_tombstone, _) = IntegratorTombstone::pda(&integrator_program_id);
+    let (inbound_cursor, _) = InboundCursor::pda(&integrator_program_id, src_chain, src_addr);
+
+    let accounts = CloseDeregisteredInboundCursor {
+        integrator_tombstone,
+        inbound_cursor,
+        rent_recipient,
+    };
+
+    let args = CloseDeregisteredInboundCursorArgs {
+        integrator_program_id,
+        src_chain,
+        src_addr,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::CloseDeregisteredInboundCursor { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
+
+pub async fn close_deregistered_replay_window(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    src_chain: u16,
+    src_addr: [u8; 32],
+    window_index: u64,
+    rent_recipient: Pubkey,
+) -> Result<(), BanksClientError> {
+    let (integrator_tombstone, _) = IntegratorTombstone::pda(&integrator_program_id);
+    let (replay_window, _) =
+        ReplayWindow::pda(&integrator_program_id, src_chain, src_addr, window_index);
+    let (replay_floor, _) = ReplayFloor::pda(&integrator_program_id, src_chain, src_addr);
+    let replay_floor = context
+        .banks_client
+        .get_account(replay_floor)
+        .await?
+        .map(|_| replay_floor);
+
+    let accounts = CloseDeregisteredReplayWindow {
+        integrator_tombstone,
+        replay_window,
+        replay_floor,
+        rent_recipient,
+    };
+
+    let args = CloseDeregisteredReplayWindowArgs {
+        integrator_program_id,
+        src_chain,
+        src_addr,
+        window_index,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::CloseDeregisteredReplayWindow { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
+
+pub async fn close_deregistered_attestation_tree(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    attestation_tree: Pubkey,
+    rent_recipient: Pubkey,
+) -> Result<(), BanksClientError> {
+    let (integrator_tombstone, _) = IntegratorTombstone::pda(&integrator_program_id);
+
+    let accounts = CloseDeregisteredAttestationTree {
+        integrator_tombstone,
+        attestation_tree,
+        rent_recipient,
+    };
+
+    let args = CloseDeregisteredAttestationTreeArgs {
+        integrator_program_id,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::CloseDeregisteredAttestationTree { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
@@ -1,6 +1,7 @@
 use anchor_lang::{InstructionData, ToAccountMetas};
 use endpoint::accounts::DisableAdapter;
 use endpoint::instructions::AdapterInfoArgs;
+use endpoint::state::AdapterIndex;
 use solana_program_test::*;
 use solana_sdk::{
     instruction::Instruction,
@@ -9,24 +10,35 @@ use solana_sdk::{
 };
 
 use crate::common::execute_transaction::execute_transaction;
+use crate::common::setup::current_audit_log_page;
+use crate::instructions::enable_adapter::AdapterAccounts;
 
 async fn execute_disable_adapter(
     context: &mut ProgramTestContext,
     admin: &Keypair,
     payer: &Keypair,
-    integrator_config: Pubkey,
-    integrator_chain_config: Pubkey,
-    adapter_info: Pubkey,
+    adapter_accounts: AdapterAccounts,
     instruction_data: Vec<u8>,
 ) -> Result<(), BanksClientError> {
+    let AdapterAccounts {
+        integrator_config,
+        integrator_chain_config,
+        adapter_info,
+        adapter_index,
+    } = adapter_accounts;
     let (event_authority, _) =
         Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+    let audit_log_page = current_audit_log_page(&mut context.banks_client, integrator_config).await;
 
     let accounts = DisableAdapter {
+        payer: payer.pubkey(),
         admin: admin.pubkey(),
         integrator_config,
+        audit_log_page,
         integrator_chain_config,
         adapter_info,
+        adapter_index,
+        system_program: solana_sdk::system_program::id(),
         event_authority,
         program: endpoint::id(),
     };
@@ -55,14 +67,18 @@ pub async fn disable_recv_adapter(
         adapter_program_id,
         integrator_program_id,
     };
+    let (adapter_index, _) = AdapterIndex::pda(&adapter_program_id, &integrator_program_id);
     let instruction_data = endpoint::instruction::DisableRecvAdapter { args }.data();
     execute_disable_adapter(
         context,
         admin,
         payer,
-        integrator_config,
-        integrator_chain_config,
-        adapter_info,
+        AdapterAccounts {
+            integrator_config,
+            integrator_chain_config,
+            adapter_info,
+            adapter_index,
+        },
         instruction_data,
     )
     .await
@@ -84,14 +100,18 @@ pub async fn disable_send_adapter(
         adapter_program_id,
         integrator_program_id,
     };
+    let (adapter_index, _) = AdapterIndex::pda(&adapter_program_id, &integrator_program_id);
     let instruction_data = endpoint::instruction::DisableSendAdapter { args }.data();
     execute_disable_adapter(
         context,
         admin,
         payer,
-        integrator_config,
-        integrator_chain_config,
-        adapter_info,
+        AdapterAccounts {
+            integrator_config,
+            integrator_chain_config,
+            adapter_info,
+            adapter_index,
+        },
         instruction_data,
     )
     .await
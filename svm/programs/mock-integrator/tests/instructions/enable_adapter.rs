@@ -1,6 +1,7 @@
 use anchor_lang::{InstructionData, ToAccountMetas};
 use endpoint::accounts::EnableAdapter;
 use endpoint::instructions::AdapterInfoArgs;
+use endpoint::state::AdapterIndex;
 use solana_program_test::*;
 use solana_sdk::{
     instruction::Instruction,
@@ -9,25 +10,41 @@ use solana_sdk::{
 };
 
 use crate::common::execute_transaction::execute_transaction;
+use crate::common::setup::current_audit_log_page;
+
+/// The accounts of the adapter being enabled or disabled, and of its integrator
+pub struct AdapterAccounts {
+    pub integrator_config: Pubkey,
+    pub integrator_chain_config: Pubkey,
+    pub adapter_info: Pubkey,
+    pub adapter_index: Pubkey,
+}
 
 pub async fn execute_enable_adapter(
     context: &mut ProgramTestContext,
     admin: &Keypair,
     payer: &Keypair,
-    integrator_config: Pubkey,
-    integrator_chain_config: Pubkey,
-    adapter_info: Pubkey,
+    adapter_accounts: AdapterAccounts,
     instruction_data: Vec<u8>,
 ) -> Result<(), BanksClientError> {
+    let AdapterAccounts {
+        integrator_config,
+        integrator_chain_config,
+        adapter_info,
+        adapter_index,
+    } = adapter_accounts;
     let (event_authority, _) =
         Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+    let audit_log_page = current_audit_log_page(&mut context.banks_client, integrator_config).await;
 
     let accounts = EnableAdapter {
         payer: payer.pubkey(),
         admin: admin.pubkey(),
         integrator_config,
+        audit_log_page,
         integrator_chain_config,
         adapter_info,
+        adapter_index,
         system_program: solana_sdk::system_program::id(),
         event_authority,
         program: endpoint::id(),
@@ -57,14 +74,18 @@ pub async fn enable_recv_adapter(
         adapter_program_id,
         integrator_program_id,
     };
+    let (adapter_index, _) = AdapterIndex::pda(&adapter_program_id, &integrator_program_id);
     let instruction_data = endpoint::instruction::EnableRecvAdapter { args }.data();
     execute_enable_adapter(
         context,
         admin,
         payer,
-        integrator_config,
-        integrator_chain_config,
-        adapter_info,
+        AdapterAccounts {
+            integrator_config,
+            integrator_chain_config,
+            adapter_info,
+            adapter_index,
+        },
         instruction_data,
     )
     .await
@@ -86,14 +107,18 @@ pub async fn enable_send_adapter(
         adapter_program_id,
         integrator_program_id,
     };
+    let (adapter_index, _) = AdapterIndex::pda(&adapter_program_id, &integrator_program_id);
     let instruction_data = endpoint::instruction::EnableSendAdapter { args }.data();
     execute_enable_adapter(
         context,
         admin,
         payer,
-        integrator_config,
-        integrator_chain_config,
-        adapter_info,
+        AdapterAccounts {
+            integrator_config,
+            integrator_chain_config,
+            adapter_info,
+            adapter_index,
+        },
         instruction_data,
     )
     .await
@@ -0,0 +1,51 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::accounts::EnableReplayWindows;
+use endpoint::instructions::EnableReplayWindowsArgs;
+use endpoint::state::{IntegratorChainConfig, IntegratorConfig};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+use crate::common::setup::current_audit_log_page;
+
+pub async fn enable_replay_windows(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    chain_id: u16,
+) -> Result<(), BanksClientError> {
+    let (integrator_config, _) = IntegratorConfig::pda(&integrator_program_id);
+    let audit_log_page = current_audit_log_page(&mut context.banks_client, integrator_config).await;
+    let (integrator_chain_config, _) = IntegratorChainConfig::pda(&integrator_program_id, chain_id);
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = EnableReplayWindows {
+        payer: payer.pubkey(),
+        admin: admin.pubkey(),
+        integrator_config,
+        integrator_chain_config,
+        audit_log_page,
+        system_program: solana_sdk::system_program::id(),
+        event_authority,
+        program: endpoint::id(),
+    };
+
+    let args = EnableReplayWindowsArgs {
+        integrator_program_id,
+        chain_id,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::EnableReplayWindows { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[admin, payer], payer).await
+}
@@ -1,5 +1,11 @@
 use anchor_lang::{InstructionData, ToAccountMetas};
-use endpoint::{instructions::ExecMessageArgs, state::AttestationInfo};
+use endpoint::{
+    instructions::ExecMessageArgs,
+    state::{
+        AttestationInfo, InboundCursor, IntegratorChainConfig, MessageExpiry, ReplayFloor,
+        ReplayWindow, SequenceRecord,
+    },
+};
 use mock_integrator::accounts::InvokeExecMessage;
 use solana_program_test::*;
 use solana_sdk::{
@@ -9,6 +15,7 @@ use solana_sdk::{
 };
 
 use crate::common::execute_transaction::execute_transaction;
+use crate::instructions::recv_message::attestation_refund_recipient;
 
 pub async fn exec_message(
     context: &mut ProgramTestContext,
@@ -19,6 +26,7 @@ pub async fn exec_message(
     dst_chain: u16,
     dst_addr: [u8; 32],
     payload_hash: [u8; 32],
+    expiry: Option<MessageExpiry>,
 ) -> Result<(), BanksClientError> {
     let (integrator_program_pda, integrator_program_pda_bump) =
         Pubkey::find_program_address(&[b"endpoint_integrator"], &mock_integrator::id());
@@ -32,13 +40,32 @@ pub async fn exec_message(
         dst_chain,
         dst_addr,
         payload_hash,
+        expiry,
     );
     let (attestation_info, _) = AttestationInfo::pda(message_hash);
+    let (sequence_record, _) =
+        SequenceRecord::pda(&mock_integrator::id(), src_chain, src_addr, sequence);
+    let (integrator_chain_config, _) =
+        IntegratorChainConfig::pda(&mock_integrator::id(), src_chain);
+    let (inbound_cursor, _) = InboundCursor::pda(&mock_integrator::id(), src_chain, src_addr);
+    let (replay_floor, _) = ReplayFloor::pda(&mock_integrator::id(), src_chain, src_addr);
+    let (replay_window, _) = ReplayWindow::pda(
+        &mock_integrator::id(),
+        src_chain,
+        src_addr,
+        ReplayWindow::window_index(sequence),
+    );
 
     let accounts = InvokeExecMessage {
         payer: payer.pubkey(),
         integrator_program_pda,
         attestation_info,
+        integrator_chain_config,
+        sequence_record,
+        inbound_cursor: Some(inbound_cursor),
+        replay_floor: Some(replay_floor),
+        replay_window: Some(replay_window),
+        refund_recipient: attestation_refund_recipient(context, attestation_info, payer).await?,
         system_program: solana_sdk::system_program::id(),
         endpoint_program: endpoint::id(),
         program: endpoint::id(),
@@ -53,6 +80,7 @@ pub async fn exec_message(
         integrator_program_id: mock_integrator::id(),
         dst_chain,
         payload_hash,
+        expiry,
     };
 
     let ix = Instruction {
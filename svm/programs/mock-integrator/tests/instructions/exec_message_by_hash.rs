@@ -0,0 +1,77 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::{
+    instructions::ExecMessageByHashArgs,
+    state::{
+        AttestationInfo, InboundCursor, IntegratorChainConfig, ReplayFloor, ReplayWindow,
+        SequenceRecord,
+    },
+};
+use mock_integrator::accounts::InvokeExecMessage;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+use crate::instructions::recv_message::attestation_refund_recipient;
+
+/// Executes a message by its hash
+/// The source chain, address and sequence are only used to derive the accounts
+pub async fn exec_message_by_hash(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    src_chain: u16,
+    src_addr: [u8; 32],
+    sequence: u64,
+    message_hash: [u8; 32],
+) -> Result<(), BanksClientError> {
+    let (integrator_program_pda, integrator_program_pda_bump) =
+        Pubkey::find_program_address(&[b"endpoint_integrator"], &mock_integrator::id());
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let (attestation_info, _) = AttestationInfo::pda(message_hash);
+    let (sequence_record, _) =
+        SequenceRecord::pda(&mock_integrator::id(), src_chain, src_addr, sequence);
+    let (integrator_chain_config, _) =
+        IntegratorChainConfig::pda(&mock_integrator::id(), src_chain);
+    let (inbound_cursor, _) = InboundCursor::pda(&mock_integrator::id(), src_chain, src_addr);
+    let (replay_floor, _) = ReplayFloor::pda(&mock_integrator::id(), src_chain, src_addr);
+    let (replay_window, _) = ReplayWindow::pda(
+        &mock_integrator::id(),
+        src_chain,
+        src_addr,
+        ReplayWindow::window_index(sequence),
+    );
+
+    let accounts = InvokeExecMessage {
+        payer: payer.pubkey(),
+        integrator_program_pda,
+        attestation_info,
+        integrator_chain_config,
+        sequence_record,
+        inbound_cursor: Some(inbound_cursor),
+        replay_floor: Some(replay_floor),
+        replay_window: Some(replay_window),
+        refund_recipient: attestation_refund_recipient(context, attestation_info, payer).await?,
+        system_program: solana_sdk::system_program::id(),
+        endpoint_program: endpoint::id(),
+        program: endpoint::id(),
+        event_authority,
+    };
+
+    let args = ExecMessageByHashArgs {
+        integrator_program_pda_bump,
+        message_hash,
+    };
+
+    let ix = Instruction {
+        program_id: mock_integrator::id(),
+        accounts: accounts.to_account_metas(None),
+        data: mock_integrator::instruction::InvokeExecMessageByHash { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
@@ -0,0 +1,141 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::accounts::ExecuteSignedAdmin;
+use endpoint::instructions::ExecuteSignedAdminArgs;
+use endpoint::state::IntegratorConfig;
+use solana_program_test::*;
+use solana_sdk::{
+    ed25519_program,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    sysvar,
+    transaction::Transaction,
+};
+
+/// Builds an Ed25519Program instruction verifying `signer`'s signature of `message`, with the
+/// signature, key and message all held by the instruction
+pub fn ed25519_instruction(signer: &Keypair, message: &[u8]) -> Instruction {
+    const HEADER_LEN: u16 = 2;
+    const OFFSETS_LEN: u16 = 14;
+    let public_key_offset = HEADER_LEN + OFFSETS_LEN;
+    let signature_offset = public_key_offset + 32;
+    let message_data_offset = signature_offset + 64;
+
+    let mut data = vec![1, 0];
+    for value in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_data_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(signer.sign_message(message).as_ref());
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::id(),
+        accounts: vec![],
+        data,
+    }
+}
+
+/// The PDA that signs in place of `signer` when executing admin instructions it authorised
+pub fn admin_authority(integrator_program_id: &Pubkey, signer: &Pubkey) -> Pubkey {
+    IntegratorConfig::admin_authority(integrator_program_id, signer).0
+}
+
+/// The accounts hash `signer` signs for `instruction`, over the keys of its accounts other than
+/// the payer, with `signer`'s admin authority included
+pub fn accounts_hash(
+    integrator_program_id: &Pubkey,
+    signer: &Pubkey,
+    instruction: &Instruction,
+) -> [u8; 32] {
+    let authority = admin_authority(integrator_program_id, signer);
+    ExecuteSignedAdminArgs::accounts_hash(
+        instruction
+            .accounts
+            .iter()
+            .filter(|meta| !meta.is_signer || meta.pubkey == authority)
+            .map(|meta| &meta.pubkey),
+    )
+}
+
+/// Submits `instruction`, an admin instruction whose admin is `signer`'s admin authority, as
+/// authorised by `signer` with the given nonce and expiry
+pub async fn execute_signed_admin(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    signer: &Keypair,
+    integrator_program_id: Pubkey,
+    nonce: u64,
+    expiry: i64,
+    instruction: Instruction,
+) -> Result<(), BanksClientError> {
+    let args = ExecuteSignedAdminArgs {
+        integrator_program_id,
+        nonce,
+        expiry,
+        instruction_data: instruction.data.clone(),
+    };
+    let message = args.message(&accounts_hash(
+        &integrator_program_id,
+        &signer.pubkey(),
+        &instruction,
+    ));
+
+    execute_signed_admin_with_message(context, payer, signer, args, &message, instruction).await
+}
+
+/// Submits `instruction` as `execute_signed_admin` does, with `signer` signing `message`
+/// instead of the message of `args`
+pub async fn execute_signed_admin_with_message(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    signer: &Keypair,
+    args: ExecuteSignedAdminArgs,
+    message: &[u8],
+    instruction: Instruction,
+) -> Result<(), BanksClientError> {
+    let (integrator_config, _) = IntegratorConfig::pda(&args.integrator_program_id);
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = ExecuteSignedAdmin {
+        integrator_config,
+        instructions: sysvar::instructions::id(),
+        event_authority,
+        program: endpoint::id(),
+    };
+
+    // The admin authority is signed for by the endpoint
+    let authority = admin_authority(&args.integrator_program_id, &signer.pubkey());
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(instruction.accounts.into_iter().map(|mut meta| {
+        if meta.pubkey == authority {
+            meta.is_signer = false;
+        }
+        meta
+    }));
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: account_metas,
+        data: endpoint::instruction::ExecuteSignedAdmin { args }.data(),
+    };
+
+    let recent_blockhash = context.get_new_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[ed25519_instruction(signer, message), ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+
+    context.banks_client.process_transaction(transaction).await
+}
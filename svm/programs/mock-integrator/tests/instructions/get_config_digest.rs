@@ -0,0 +1,51 @@
+use anchor_lang::{AnchorDeserialize, InstructionData, ToAccountMetas};
+use endpoint::accounts::GetConfigDigest;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::Transaction,
+};
+
+/// Simulates `get_config_digest` and decodes the digest from its return data
+pub async fn get_config_digest(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    integrator_config: Pubkey,
+    integrator_program_id: Pubkey,
+) -> Result<[u8; 32], BanksClientError> {
+    let accounts = GetConfigDigest { integrator_config };
+
+    let args = endpoint::instructions::GetConfigDigestArgs {
+        integrator_program_id,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::GetConfigDigest { args }.data(),
+    };
+
+    let recent_blockhash = context.get_new_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+
+    let simulation = context
+        .banks_client
+        .simulate_transaction(transaction)
+        .await?;
+    if let Some(Err(err)) = simulation.result {
+        return Err(BanksClientError::TransactionError(err));
+    }
+
+    let return_data = simulation
+        .simulation_details
+        .and_then(|details| details.return_data)
+        .expect("get_config_digest sets return data");
+    Ok(<[u8; 32]>::deserialize(&mut return_data.data.as_slice()).unwrap())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::accounts::InitAdapterQueue;
+use endpoint::instructions::InitAdapterQueueArgs;
+use endpoint::state::{AdapterInfo, AdapterQueue, IntegratorConfig, SequenceTracker};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+use crate::common::setup::current_audit_log_page;
+
+pub async fn init_adapter_queue(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    adapter_program_id: Pubkey,
+) -> Result<(), BanksClientError> {
+    let (integrator_config, _) = IntegratorConfig::pda(&integrator_program_id);
+    let audit_log_page = current_audit_log_page(&mut context.banks_client, integrator_config).await;
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = InitAdapterQueue {
+        payer: payer.pubkey(),
+        admin: admin.pubkey(),
+        integrator_config,
+        adapter_info: AdapterInfo::pda(&integrator_program_id, &adapter_program_id).0,
+        adapter_queue: AdapterQueue::pda(&integrator_program_id, &adapter_program_id).0,
+        sequence_tracker: SequenceTracker::pda(&integrator_program_id).0,
+        audit_log_page,
+        system_program: solana_sdk::system_program::id(),
+        event_authority,
+        program: endpoint::id(),
+    };
+
+    let args = InitAdapterQueueArgs {
+        integrator_program_id,
+        adapter_program_id,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::InitAdapterQueue { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[admin, payer], payer).await
+}
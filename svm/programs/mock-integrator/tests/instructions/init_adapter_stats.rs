@@ -0,0 +1,39 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::accounts::InitAdapterStats;
+use endpoint::instructions::InitAdapterStatsArgs;
+use endpoint::state::{AdapterInfo, AdapterStats};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+
+pub async fn init_adapter_stats(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    adapter_program_id: Pubkey,
+) -> Result<(), BanksClientError> {
+    let accounts = InitAdapterStats {
+        payer: payer.pubkey(),
+        adapter_info: AdapterInfo::pda(&integrator_program_id, &adapter_program_id).0,
+        adapter_stats: AdapterStats::pda(&integrator_program_id, &adapter_program_id).0,
+        system_program: solana_sdk::system_program::id(),
+    };
+
+    let args = InitAdapterStatsArgs {
+        integrator_program_id,
+        adapter_program_id,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::InitAdapterStats { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
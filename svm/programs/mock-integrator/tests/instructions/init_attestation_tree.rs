@@ -0,0 +1,70 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::accounts::InitAttestationTree;
+use endpoint::instructions::InitAttestationTreeArgs;
+use endpoint::state::{AttestationTree, IntegratorConfig};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+
+use crate::common::setup::current_audit_log_page;
+
+/// Allocates the tree account and initializes it in the same transaction
+pub async fn init_attestation_tree(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    payer: &Keypair,
+    attestation_tree: &Keypair,
+    integrator_program_id: Pubkey,
+    chain_id: u16,
+) -> Result<(), BanksClientError> {
+    let (integrator_config, _) = IntegratorConfig::pda(&integrator_program_id);
+    let audit_log_page = current_audit_log_page(&mut context.banks_client, integrator_config).await;
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let rent = context.banks_client.get_rent().await?;
+    let create_ix = system_instruction::create_account(
+        &payer.pubkey(),
+        &attestation_tree.pubkey(),
+        rent.minimum_balance(AttestationTree::LEN),
+        AttestationTree::LEN as u64,
+        &endpoint::id(),
+    );
+
+    let accounts = InitAttestationTree {
+        payer: payer.pubkey(),
+        admin: admin.pubkey(),
+        integrator_config,
+        attestation_tree: attestation_tree.pubkey(),
+        audit_log_page,
+        system_program: solana_sdk::system_program::id(),
+        event_authority,
+        program: endpoint::id(),
+    };
+
+    let args = InitAttestationTreeArgs {
+        integrator_program_id,
+        chain_id,
+    };
+
+    let init_ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::InitAttestationTree { args }.data(),
+    };
+
+    let recent_blockhash = context.get_new_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[create_ix, init_ix],
+        Some(&payer.pubkey()),
+        &[payer, admin, attestation_tree],
+        recent_blockhash,
+    );
+
+    context.banks_client.process_transaction(transaction).await
+}
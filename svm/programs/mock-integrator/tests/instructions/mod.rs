@@ -1,12 +1,34 @@
 pub mod add_adapter;
 pub mod attest_message;
+pub mod attest_message_by_hash;
+pub mod attest_message_compressed;
+pub mod attest_messages;
+pub mod cancel_message;
+pub mod close_replay_window;
+pub mod close_sent_message_record;
+pub mod deregister;
 pub mod disable_adapter;
 pub mod discard_admin;
 pub mod enable_adapter;
+pub mod enable_replay_windows;
 pub mod exec_message;
+pub mod exec_message_by_hash;
+pub mod execute_signed_admin;
+pub mod get_config_digest;
+pub mod init_adapter_queue;
+pub mod init_adapter_stats;
+pub mod init_attestation_tree;
 pub mod pick_up_message;
+pub mod pick_up_messages;
 pub mod recv_message;
+pub mod recv_message_by_hash;
+pub mod recv_message_compressed;
 pub mod register;
+pub mod request_repickup;
+pub mod retire_adapter;
 pub mod send_message;
+pub mod send_message_multi;
+pub mod set_ordered_delivery;
+pub mod skip_sequence;
 pub mod transfer_admin;
 pub mod update_admin;
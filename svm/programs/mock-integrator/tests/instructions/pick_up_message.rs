@@ -1,4 +1,5 @@
 use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::state::{AdapterQueue, AdapterStats};
 use mock_adapter::accounts::InvokePickUpMessage;
 use solana_program_test::*;
 use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::keypair::Keypair};
@@ -13,12 +14,16 @@ pub async fn pick_up_message(
     adapter_pda: Pubkey,
     refund_recipient: Pubkey,
 ) -> Result<(), BanksClientError> {
+    let (adapter_stats, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
     let (event_authority, _) =
         Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+    let adapter_queue = existing_adapter_queue(context).await;
 
     let accounts = InvokePickUpMessage {
         outbox_message,
         adapter_info,
+        adapter_stats,
+        adapter_queue,
         adapter_pda,
         system_program: solana_sdk::system_program::id(),
         endpoint_program: endpoint::id(),
@@ -35,3 +40,14 @@ pub async fn pick_up_message(
 
     execute_transaction(context, ix, &[payer], payer).await
 }
+
+/// The mock adapter's queue for the mock integrator, if it has been created
+pub async fn existing_adapter_queue(context: &mut ProgramTestContext) -> Option<Pubkey> {
+    let (adapter_queue, _) = AdapterQueue::pda(&mock_integrator::id(), &mock_adapter::id());
+    context
+        .banks_client
+        .get_account(adapter_queue)
+        .await
+        .unwrap()
+        .map(|_| adapter_queue)
+}
@@ -0,0 +1,57 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::instructions::BatchMode;
+use endpoint::state::AdapterStats;
+use mock_adapter::{accounts::InvokePickUpMessages, InvokePickUpMessagesArgs};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::keypair::Keypair,
+};
+
+use crate::common::execute_transaction::execute_transaction;
+use crate::instructions::pick_up_message::existing_adapter_queue;
+
+/// Picks up each outbox message in `outbox_messages`, refunding its rent to the paired
+/// recipient once it is closed
+pub async fn pick_up_messages(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    adapter_info: Pubkey,
+    adapter_pda: Pubkey,
+    outbox_messages: &[(Pubkey, Pubkey)],
+    mode: BatchMode,
+) -> Result<(), BanksClientError> {
+    let (adapter_stats, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = InvokePickUpMessages {
+        adapter_info,
+        adapter_stats,
+        adapter_queue: existing_adapter_queue(context).await,
+        adapter_pda,
+        endpoint_program: endpoint::id(),
+        program: endpoint::id(),
+        event_authority,
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    for (outbox_message, refund_recipient) in outbox_messages {
+        account_metas.push(AccountMeta::new(*outbox_message, false));
+        account_metas.push(AccountMeta::new(*refund_recipient, false));
+    }
+
+    let args = InvokePickUpMessagesArgs {
+        integrator_program_id: mock_integrator::id(),
+        mode,
+    };
+
+    let ix = Instruction {
+        program_id: mock_adapter::id(),
+        accounts: account_metas,
+        data: mock_adapter::instruction::InvokePickUpMessages { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
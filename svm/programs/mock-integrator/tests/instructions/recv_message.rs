@@ -1,5 +1,11 @@
-use anchor_lang::{InstructionData, ToAccountMetas};
-use endpoint::{instructions::recv_message::RecvMessageArgs, state::IntegratorChainConfig};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use endpoint::{
+    instructions::recv_message::RecvMessageArgs,
+    state::{
+        AttestationInfo, InboundCursor, IntegratorChainConfig, MessageExpiry, ReplayFloor,
+        ReplayWindow, SequenceRecord,
+    },
+};
 use mock_integrator::accounts::InvokeRecvMessage;
 use solana_program_test::*;
 use solana_sdk::{
@@ -10,6 +16,22 @@ use solana_sdk::{
 
 use crate::common::execute_transaction::execute_transaction;
 
+/// Returns the recipient of the attestation info's rent if it exists and was paid for by
+/// someone other than `payer`
+pub async fn attestation_refund_recipient(
+    context: &mut ProgramTestContext,
+    attestation_info: Pubkey,
+    payer: &Keypair,
+) -> Result<Option<Pubkey>, BanksClientError> {
+    let Some(account) = context.banks_client.get_account(attestation_info).await? else {
+        return Ok(None);
+    };
+    let attestation_info = AttestationInfo::try_deserialize(&mut account.data.as_slice())
+        .expect("invalid attestation info");
+    Ok((attestation_info.refund_recipient != payer.pubkey())
+        .then_some(attestation_info.refund_recipient))
+}
+
 pub async fn recv_message(
     context: &mut ProgramTestContext,
     payer: &Keypair,
@@ -19,6 +41,8 @@ pub async fn recv_message(
     sequence: u64,
     dst_chain: u16,
     payload_hash: [u8; 32],
+    expiry: Option<MessageExpiry>,
+    override_equivocation: bool,
 ) -> Result<(), BanksClientError> {
     let (integrator_program_pda, integrator_program_pda_bump) =
         Pubkey::find_program_address(&[b"endpoint_integrator"], &mock_integrator::id());
@@ -27,6 +51,16 @@ pub async fn recv_message(
     let integrator_program_id = mock_integrator::id();
     let (integrator_chain_config, _) =
         IntegratorChainConfig::pda(&integrator_program_id, src_chain);
+    let (sequence_record, _) =
+        SequenceRecord::pda(&integrator_program_id, src_chain, src_addr, sequence);
+    let (inbound_cursor, _) = InboundCursor::pda(&integrator_program_id, src_chain, src_addr);
+    let (replay_floor, _) = ReplayFloor::pda(&integrator_program_id, src_chain, src_addr);
+    let (replay_window, _) = ReplayWindow::pda(
+        &integrator_program_id,
+        src_chain,
+        src_addr,
+        ReplayWindow::window_index(sequence),
+    );
 
     let accounts = InvokeRecvMessage {
         payer: payer.pubkey(),
@@ -34,6 +68,11 @@ pub async fn recv_message(
         attestation_info,
         system_program: solana_sdk::system_program::id(),
         integrator_chain_config,
+        sequence_record,
+        inbound_cursor: Some(inbound_cursor),
+        replay_floor: Some(replay_floor),
+        replay_window: Some(replay_window),
+        refund_recipient: attestation_refund_recipient(context, attestation_info, payer).await?,
         endpoint_program: endpoint::id(),
         program: endpoint::id(),
         event_authority,
@@ -47,6 +86,8 @@ pub async fn recv_message(
         dst_chain,
         integrator_program_id: mock_integrator::id(),
         payload_hash,
+        expiry,
+        override_equivocation,
     };
 
     let ix = Instruction {
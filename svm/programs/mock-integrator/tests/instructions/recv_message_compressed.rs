@@ -0,0 +1,115 @@
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use endpoint::{
+    client::attestation_tree::AttestationTreeProof,
+    instructions::{BatchedMessage, RecvMessageCompressedArgs},
+    state::{
+        AttestationInfo, AttestationLeaf, CompressedAttestation, InboundCursor,
+        IntegratorChainConfig, ReplayFloor, ReplayWindow, SequenceRecord,
+    },
+};
+use mock_integrator::accounts::InvokeRecvMessageCompressed;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+
+pub async fn recv_message_compressed(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    attestation_tree: Pubkey,
+    src_chain: u16,
+    message: &BatchedMessage,
+    proof: &AttestationTreeProof,
+    leaf: CompressedAttestation,
+) -> Result<(), BanksClientError> {
+    let BatchedMessage {
+        src_addr,
+        sequence,
+        payload_hash,
+        expiry,
+    } = message.clone();
+    let (integrator_program_pda, integrator_program_pda_bump) =
+        Pubkey::find_program_address(&[b"endpoint_integrator"], &mock_integrator::id());
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+    let integrator_program_id = mock_integrator::id();
+    let (integrator_chain_config, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, src_chain);
+    let (inbound_cursor, _) = InboundCursor::pda(&integrator_program_id, src_chain, src_addr);
+    let (replay_floor, _) = ReplayFloor::pda(&integrator_program_id, src_chain, src_addr);
+    let (replay_window, _) = ReplayWindow::pda(
+        &integrator_program_id,
+        src_chain,
+        src_addr,
+        ReplayWindow::window_index(sequence),
+    );
+    let (attestation_leaf, _) = AttestationLeaf::pda(&AttestationInfo::compute_message_hash(
+        src_chain,
+        src_addr,
+        sequence,
+        endpoint::CHAIN_ID,
+        integrator_program_id.to_bytes(),
+        payload_hash,
+        expiry,
+    ));
+    let (sequence_record, _) =
+        SequenceRecord::pda(&integrator_program_id, src_chain, src_addr, sequence);
+
+    // The leaf's rent goes back to whoever paid for it, which is passed if it is not the payer
+    let refund_recipient = match context.banks_client.get_account(attestation_leaf).await? {
+        Some(account) => {
+            let attestation_leaf = AttestationLeaf::try_deserialize(&mut account.data.as_slice())
+                .expect("invalid attestation leaf");
+            (attestation_leaf.refund_recipient != payer.pubkey())
+                .then_some(attestation_leaf.refund_recipient)
+        }
+        None => None,
+    };
+
+    let accounts = InvokeRecvMessageCompressed {
+        payer: payer.pubkey(),
+        integrator_program_pda,
+        integrator_chain_config,
+        attestation_tree,
+        attestation_leaf,
+        sequence_record,
+        inbound_cursor: Some(inbound_cursor),
+        replay_floor,
+        replay_window,
+        refund_recipient,
+        system_program: solana_sdk::system_program::id(),
+        endpoint_program: endpoint::id(),
+        program: endpoint::id(),
+        event_authority,
+    };
+
+    let args = RecvMessageCompressedArgs {
+        integrator_program_pda_bump,
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain: endpoint::CHAIN_ID,
+        integrator_program_id,
+        payload_hash,
+        expiry,
+        root: proof.root,
+        leaf_index: proof.leaf_index,
+        leaf,
+        override_equivocation: false,
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(proof.remaining_accounts());
+
+    let ix = Instruction {
+        program_id: mock_integrator::id(),
+        accounts: account_metas,
+        data: mock_integrator::instruction::InvokeRecvMessageCompressed { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer], payer).await
+}
@@ -1,5 +1,5 @@
 use anchor_lang::{InstructionData, ToAccountMetas};
-use endpoint::state::SequenceTracker;
+use endpoint::state::{IntegratorTombstone, SequenceTracker};
 use mock_integrator::{accounts::InvokeRegister, InvokeRegisterArgs};
 use solana_program_test::*;
 use solana_sdk::{
@@ -22,11 +22,13 @@ pub async fn register(
     let (event_authority, _) =
         Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
     let (sequence_tracker, _) = SequenceTracker::pda(&integrator_program_id);
+    let (integrator_tombstone, _) = IntegratorTombstone::pda(&integrator_program_id);
 
     let accounts = InvokeRegister {
         payer: payer.pubkey(),
         integrator_config,
         sequence_tracker,
+        integrator_tombstone,
         integrator_program_pda,
         event_authority,
         program: endpoint::id(),
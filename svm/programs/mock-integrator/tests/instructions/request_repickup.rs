@@ -0,0 +1,46 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::state::{IntegratorChainConfig, SentMessageRecord, SequenceTracker};
+use mock_integrator::{accounts::InvokeRequestRepickup, InvokeRequestRepickupArgs};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+
+pub async fn request_repickup(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    outbox_message: &Keypair,
+    args: InvokeRequestRepickupArgs,
+) -> Result<(), BanksClientError> {
+    let integrator_program_id = mock_integrator::id();
+    let (integrator_program_pda, _) =
+        Pubkey::find_program_address(&[b"endpoint_integrator"], &integrator_program_id);
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = InvokeRequestRepickup {
+        payer: payer.pubkey(),
+        integrator_program_pda,
+        sent_message_record: SentMessageRecord::pda(&integrator_program_id, args.sequence).0,
+        integrator_chain_config: IntegratorChainConfig::pda(&integrator_program_id, args.dst_chain)
+            .0,
+        sequence_tracker: SequenceTracker::pda(&integrator_program_id).0,
+        outbox_message: outbox_message.pubkey(),
+        event_authority,
+        program: endpoint::id(),
+        endpoint_program: endpoint::id(),
+        system_program: solana_sdk::system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: mock_integrator::id(),
+        accounts: accounts.to_account_metas(None),
+        data: mock_integrator::instruction::InvokeRequestRepickup { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[payer, outbox_message], payer).await
+}
@@ -0,0 +1,63 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::accounts::RetireAdapter;
+use endpoint::instructions::RetireAdapterArgs;
+use endpoint::state::{AdapterIndex, AdapterInfo, IntegratorConfig};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+use crate::common::setup::current_audit_log_page;
+
+/// Retires an adapter, disabling it on each of `integrator_chain_configs`, which must be the
+/// chain configs of its indexed chains
+pub async fn retire_adapter(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    adapter_program_id: Pubkey,
+    integrator_chain_configs: &[Pubkey],
+) -> Result<(), BanksClientError> {
+    let (integrator_config, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (adapter_info, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    let (adapter_index, _) = AdapterIndex::pda(&adapter_program_id, &integrator_program_id);
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+    let audit_log_page = current_audit_log_page(&mut context.banks_client, integrator_config).await;
+
+    let accounts = RetireAdapter {
+        payer: payer.pubkey(),
+        admin: admin.pubkey(),
+        integrator_config,
+        audit_log_page,
+        adapter_info,
+        adapter_index,
+        system_program: solana_sdk::system_program::id(),
+        event_authority,
+        program: endpoint::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(
+        integrator_chain_configs
+            .iter()
+            .map(|integrator_chain_config| AccountMeta::new(*integrator_chain_config, false)),
+    );
+
+    let args = RetireAdapterArgs {
+        integrator_program_id,
+        adapter_program_id,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: account_metas,
+        data: endpoint::instruction::RetireAdapter { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[admin, payer], payer).await
+}
@@ -1,22 +1,68 @@
 use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::state::{
+    AdapterInfo, AdapterStats, IntegratorChainConfig, MessageExpiry, SentMessageRecord,
+    SequenceTracker,
+};
 use mock_integrator::{accounts::InvokeSendMessage, InvokeSendMessageArgs};
 use solana_program_test::*;
 use solana_sdk::{
-    instruction::Instruction,
+    instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signer::{keypair::Keypair, Signer},
 };
 
-use crate::common::execute_transaction::execute_transaction;
+use crate::common::{execute_transaction::execute_transaction, setup::get_account};
+
+/// How `send_message_with_options` sends a message, besides its destination and payload
+#[derive(Default)]
+pub struct SendMessageOptions<'a> {
+    pub consistency_level: u8,
+    pub expiry: Option<MessageExpiry>,
+    /// Records the message in its `SentMessageRecord`, so that it can be handed to adapters again
+    /// with `request_repickup`
+    pub record: bool,
+    /// The send adapters to push the message to
+    pub pushed_adapters: &'a [Pubkey],
+    /// Passed in place of the integrator's outbound authority when pushing
+    pub outbound_authority: Option<Pubkey>,
+    /// The adapter queues to push the outbox message onto
+    pub adapter_queues: &'a [Pubkey],
+}
 
 async fn execute_send_message(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    accounts: InvokeSendMessage,
+    remaining_accounts: Vec<AccountMeta>,
+    outbox_message: Option<&Keypair>,
+    args: InvokeSendMessageArgs,
+) -> Result<(), BanksClientError> {
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(remaining_accounts);
+
+    let ix = Instruction {
+        program_id: mock_integrator::id(),
+        accounts: account_metas,
+        data: mock_integrator::instruction::InvokeSendMessage { args }.data(),
+    };
+
+    let mut signers = vec![payer];
+    signers.extend(outbox_message);
+    execute_transaction(context, ix, &signers, payer).await
+}
+
+pub async fn send_message(
     context: &mut ProgramTestContext,
     payer: &Keypair,
     integrator_program_pda: Pubkey,
     integrator_chain_config: Pubkey,
     outbox_message: &Keypair,
     sequence_tracker: Pubkey,
-    instruction_data: Vec<u8>,
+    dst_chain: u16,
+    dst_addr: [u8; 32],
+    payload_hash: [u8; 32],
+    consistency_level: u8,
+    expiry: Option<MessageExpiry>,
 ) -> Result<(), BanksClientError> {
     let (event_authority, _) =
         Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
@@ -25,7 +71,9 @@ async fn execute_send_message(
         payer: payer.pubkey(),
         integrator_program_pda,
         integrator_chain_config,
-        outbox_message: outbox_message.pubkey(),
+        outbox_message: Some(outbox_message.pubkey()),
+        outbound_authority: None,
+        sent_message_record: None,
         sequence_tracker,
         system_program: solana_sdk::system_program::id(),
         endpoint_program: endpoint::id(),
@@ -33,42 +81,94 @@ async fn execute_send_message(
         program: endpoint::id(),
     };
 
-    let ix = Instruction {
-        program_id: mock_integrator::id(),
-        accounts: accounts.to_account_metas(None),
-        data: instruction_data,
+    let args = InvokeSendMessageArgs {
+        dst_chain,
+        dst_addr,
+        payload_hash,
+        consistency_level,
+        expiry,
+        push_account_counts: vec![],
     };
 
-    execute_transaction(context, ix, &[payer, outbox_message], payer).await
+    execute_send_message(context, payer, accounts, vec![], Some(outbox_message), args).await
 }
 
-pub async fn send_message(
+/// Sends a message from the mock integrator as `send_message` does, as set out in `options`
+/// The outbox message is only passed when some send adapters are not pushed the message
+pub async fn send_message_with_options(
     context: &mut ProgramTestContext,
     payer: &Keypair,
-    integrator_program_pda: Pubkey,
-    integrator_chain_config: Pubkey,
-    outbox_message: &Keypair,
-    sequence_tracker: Pubkey,
+    outbox_message: Option<&Keypair>,
     dst_chain: u16,
     dst_addr: [u8; 32],
     payload_hash: [u8; 32],
+    options: SendMessageOptions<'_>,
 ) -> Result<(), BanksClientError> {
+    let integrator_program_id = mock_integrator::id();
+    let (integrator_program_pda, _) =
+        Pubkey::find_program_address(&[b"endpoint_integrator"], &integrator_program_id);
+    let (sequence_tracker, _) = SequenceTracker::pda(&integrator_program_id);
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let sent_message_record = if options.record {
+        let tracker: SequenceTracker =
+            get_account(&mut context.banks_client, sequence_tracker).await;
+        Some(SentMessageRecord::pda(&integrator_program_id, tracker.sequence).0)
+    } else {
+        None
+    };
+
+    let accounts = InvokeSendMessage {
+        payer: payer.pubkey(),
+        integrator_program_pda,
+        integrator_chain_config: IntegratorChainConfig::pda(&integrator_program_id, dst_chain).0,
+        outbox_message: outbox_message.map(|outbox_message| outbox_message.pubkey()),
+        outbound_authority: (!options.pushed_adapters.is_empty()).then(|| {
+            options.outbound_authority.unwrap_or_else(|| {
+                adapter_interface::outbound_authority(&endpoint::id(), &integrator_program_id).0
+            })
+        }),
+        sent_message_record,
+        sequence_tracker,
+        system_program: solana_sdk::system_program::id(),
+        endpoint_program: endpoint::id(),
+        event_authority,
+        program: endpoint::id(),
+    };
+
+    // The mock adapter's `receive_outbound` takes no accounts besides the outbound authority
+    let mut remaining_accounts = vec![];
+    for adapter_program_id in options.pushed_adapters {
+        let (adapter_info, _) = AdapterInfo::pda(&integrator_program_id, adapter_program_id);
+        let (adapter_stats, _) = AdapterStats::pda(&integrator_program_id, adapter_program_id);
+        remaining_accounts.push(AccountMeta::new_readonly(adapter_info, false));
+        remaining_accounts.push(AccountMeta::new(adapter_stats, false));
+        remaining_accounts.push(AccountMeta::new_readonly(*adapter_program_id, false));
+    }
+    remaining_accounts.extend(
+        options
+            .adapter_queues
+            .iter()
+            .map(|adapter_queue| AccountMeta::new(*adapter_queue, false)),
+    );
+
     let args = InvokeSendMessageArgs {
         dst_chain,
         dst_addr,
         payload_hash,
+        consistency_level: options.consistency_level,
+        expiry: options.expiry,
+        push_account_counts: vec![0; options.pushed_adapters.len()],
     };
 
-    let instruction_data = mock_integrator::instruction::InvokeSendMessage { args }.data();
-
     execute_send_message(
         context,
         payer,
-        integrator_program_pda,
-        integrator_chain_config,
+        accounts,
+        remaining_accounts,
         outbox_message,
-        sequence_tracker,
-        instruction_data,
+        args,
     )
     .await
 }
@@ -0,0 +1,85 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::instructions::MessageDestination;
+use endpoint::state::{IntegratorChainConfig, SentMessageRecord, SequenceTracker};
+use mock_integrator::{accounts::InvokeSendMessageMulti, InvokeSendMessageMultiArgs};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+use crate::common::setup::get_account;
+
+/// Sends `payload_hash` to each destination, creating its outbox message at the keypair
+/// with the same index in `outbox_messages`, recording each message if `record` is set and
+/// pushing it onto `adapter_queues`
+pub async fn send_message_multi(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    destinations: Vec<MessageDestination>,
+    outbox_messages: &[Keypair],
+    payload_hash: [u8; 32],
+    record: bool,
+    adapter_queues: &[Pubkey],
+) -> Result<(), BanksClientError> {
+    let (integrator_program_pda, _) =
+        Pubkey::find_program_address(&[b"endpoint_integrator"], &mock_integrator::id());
+    let (sequence_tracker, _) = SequenceTracker::pda(&mock_integrator::id());
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = InvokeSendMessageMulti {
+        payer: payer.pubkey(),
+        integrator_program_pda,
+        sequence_tracker,
+        system_program: solana_sdk::system_program::id(),
+        endpoint_program: endpoint::id(),
+        event_authority,
+        program: endpoint::id(),
+    };
+
+    // The destinations take consecutive sequences from the tracker's next one
+    let next_sequence = get_account::<SequenceTracker>(&mut context.banks_client, sequence_tracker)
+        .await
+        .sequence;
+
+    let mut account_metas = accounts.to_account_metas(None);
+    for (index, (destination, outbox_message)) in
+        destinations.iter().zip(outbox_messages).enumerate()
+    {
+        let (integrator_chain_config, _) =
+            IntegratorChainConfig::pda(&mock_integrator::id(), destination.dst_chain);
+        account_metas.push(AccountMeta::new_readonly(integrator_chain_config, false));
+        account_metas.push(AccountMeta::new(outbox_message.pubkey(), true));
+        if record {
+            let (sent_message_record, _) =
+                SentMessageRecord::pda(&mock_integrator::id(), next_sequence + index as u64);
+            account_metas.push(AccountMeta::new(sent_message_record, false));
+        }
+    }
+    account_metas.extend(
+        adapter_queues
+            .iter()
+            .map(|adapter_queue| AccountMeta::new(*adapter_queue, false)),
+    );
+
+    let args = InvokeSendMessageMultiArgs {
+        destinations,
+        payload_hash,
+        consistency_level: 1,
+        expiry: None,
+        record,
+    };
+
+    let ix = Instruction {
+        program_id: mock_integrator::id(),
+        accounts: account_metas,
+        data: mock_integrator::instruction::InvokeSendMessageMulti { args }.data(),
+    };
+
+    let mut signers = vec![payer];
+    signers.extend(outbox_messages);
+    execute_transaction(context, ix, &signers, payer).await
+}
@@ -0,0 +1,53 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::accounts::SetOrderedDelivery;
+use endpoint::instructions::SetOrderedDeliveryArgs;
+use endpoint::state::{IntegratorChainConfig, IntegratorConfig};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+use crate::common::setup::current_audit_log_page;
+
+pub async fn set_ordered_delivery(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    chain_id: u16,
+    enabled: bool,
+) -> Result<(), BanksClientError> {
+    let (integrator_config, _) = IntegratorConfig::pda(&integrator_program_id);
+    let audit_log_page = current_audit_log_page(&mut context.banks_client, integrator_config).await;
+    let (integrator_chain_config, _) = IntegratorChainConfig::pda(&integrator_program_id, chain_id);
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = SetOrderedDelivery {
+        payer: payer.pubkey(),
+        admin: admin.pubkey(),
+        integrator_config,
+        integrator_chain_config,
+        audit_log_page,
+        system_program: solana_sdk::system_program::id(),
+        event_authority,
+        program: endpoint::id(),
+    };
+
+    let args = SetOrderedDeliveryArgs {
+        integrator_program_id,
+        chain_id,
+        enabled,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::SetOrderedDelivery { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[admin, payer], payer).await
+}
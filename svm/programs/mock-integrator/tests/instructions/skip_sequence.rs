@@ -0,0 +1,55 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use endpoint::accounts::SkipSequence;
+use endpoint::instructions::SkipSequenceArgs;
+use endpoint::state::{InboundCursor, IntegratorConfig};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+
+use crate::common::execute_transaction::execute_transaction;
+use crate::common::setup::current_audit_log_page;
+
+pub async fn skip_sequence(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    payer: &Keypair,
+    integrator_program_id: Pubkey,
+    src_chain: u16,
+    src_addr: [u8; 32],
+    sequence: u64,
+) -> Result<(), BanksClientError> {
+    let (integrator_config, _) = IntegratorConfig::pda(&integrator_program_id);
+    let audit_log_page = current_audit_log_page(&mut context.banks_client, integrator_config).await;
+    let (inbound_cursor, _) = InboundCursor::pda(&integrator_program_id, src_chain, src_addr);
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+
+    let accounts = SkipSequence {
+        payer: payer.pubkey(),
+        admin: admin.pubkey(),
+        integrator_config,
+        inbound_cursor,
+        audit_log_page,
+        system_program: solana_sdk::system_program::id(),
+        event_authority,
+        program: endpoint::id(),
+    };
+
+    let args = SkipSequenceArgs {
+        integrator_program_id,
+        src_chain,
+        src_addr,
+        sequence,
+    };
+
+    let ix = Instruction {
+        program_id: endpoint::id(),
+        accounts: accounts.to_account_metas(None),
+        data: endpoint::instruction::SkipSequence { args }.data(),
+    };
+
+    execute_transaction(context, ix, &[admin, payer], payer).await
+}
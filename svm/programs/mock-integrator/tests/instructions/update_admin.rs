@@ -8,6 +8,7 @@ use solana_sdk::{
 };
 
 use crate::common::execute_transaction::execute_transaction;
+use crate::common::setup::current_audit_log_page;
 
 pub async fn update_admin(
     context: &mut ProgramTestContext,
@@ -19,10 +20,14 @@ pub async fn update_admin(
 ) -> Result<(), BanksClientError> {
     let (event_authority, _) =
         Pubkey::find_program_address(&[b"__event_authority"], &endpoint::id());
+    let audit_log_page = current_audit_log_page(&mut context.banks_client, integrator_config).await;
 
     let accounts = UpdateAdmin {
+        payer: payer.pubkey(),
         admin: admin.pubkey(),
         integrator_config,
+        audit_log_page,
+        system_program: solana_sdk::system_program::id(),
         event_authority,
         program: endpoint::id(),
     };
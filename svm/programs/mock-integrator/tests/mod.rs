@@ -1,2 +1,4 @@
+#![cfg(feature = "test-sbf")]
+
 mod common;
 mod instructions;
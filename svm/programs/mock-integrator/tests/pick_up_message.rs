@@ -6,6 +6,7 @@ mod instructions;
 use crate::instructions::add_adapter::add_adapter;
 use crate::instructions::disable_adapter::disable_send_adapter;
 use crate::instructions::enable_adapter::enable_send_adapter;
+use crate::instructions::init_adapter_stats::init_adapter_stats;
 use crate::instructions::pick_up_message::pick_up_message;
 use crate::instructions::register::register;
 use crate::instructions::send_message::send_message;
@@ -14,11 +15,12 @@ use anchor_lang::prelude::*;
 use common::setup::{get_account, setup};
 use endpoint::error::EndpointError;
 use endpoint::state::{
-    AdapterInfo, IntegratorChainConfig, IntegratorConfig, OutboxMessage, SequenceTracker,
+    AdapterInfo, AdapterStats, IntegratorChainConfig, IntegratorConfig, OutboxMessage,
+    SequenceTracker,
 };
 use solana_program_test::*;
 use solana_sdk::{
-    instruction::InstructionError, signature::Keypair, signer::Signer,
+    account::AccountSharedData, instruction::InstructionError, signature::Keypair, signer::Signer,
     transaction::TransactionError,
 };
 
@@ -125,6 +127,8 @@ async fn create_and_send_message(
         chain_id,
         dst_addr,
         payload_hash,
+        1,
+        None,
     )
     .await
     .unwrap();
@@ -185,6 +189,101 @@ async fn test_pick_up_message_success() {
     if account.is_none() {
         println!("OutboxMessage account closed as expected.");
     }
+
+    // Verify that the pick up was recorded in the adapter's stats
+    let (adapter_stats_pda, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let adapter_stats: AdapterStats =
+        get_account(&mut context.banks_client, adapter_stats_pda).await;
+    assert_eq!(adapter_stats.messages_picked_up, 1);
+    assert_eq!(adapter_stats.messages_attested, 0);
+    assert!(adapter_stats.last_pickup_slot > 0);
+}
+
+#[tokio::test]
+async fn test_pick_up_message_adapter_without_stats() {
+    let (
+        mut context,
+        payer,
+        _,
+        integrator_program_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        adapter_pda,
+        bump,
+        chain_id,
+    ) = setup_test_environment().await;
+
+    let outbox_message = create_and_send_message(
+        &mut context,
+        &payer,
+        integrator_program_pda,
+        integrator_chain_config_pda,
+        mock_integrator::id(),
+        bump,
+        chain_id,
+    )
+    .await;
+
+    // Remove the stats, as for an adapter added before they existed
+    let (adapter_stats_pda, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    context.set_account(&adapter_stats_pda, &AccountSharedData::default());
+
+    let result = pick_up_message(
+        &mut context,
+        &payer,
+        outbox_message.pubkey(),
+        adapter_info_pda,
+        adapter_pda,
+        payer.pubkey(),
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(3012)) // AccountNotInitialized
+    );
+
+    // Anyone can create the missing stats, after which the adapter can pick up again
+    let relayer = Keypair::new();
+    context.set_account(
+        &relayer.pubkey(),
+        &AccountSharedData::new(1_000_000_000, 0, &solana_sdk::system_program::id()),
+    );
+    init_adapter_stats(
+        &mut context,
+        &relayer,
+        mock_integrator::id(),
+        mock_adapter::id(),
+    )
+    .await
+    .unwrap();
+
+    pick_up_message(
+        &mut context,
+        &payer,
+        outbox_message.pubkey(),
+        adapter_info_pda,
+        adapter_pda,
+        payer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let adapter_stats: AdapterStats =
+        get_account(&mut context.banks_client, adapter_stats_pda).await;
+    assert_eq!(adapter_stats.messages_picked_up, 1);
+
+    // The stats can only be created once
+    let result = init_adapter_stats(
+        &mut context,
+        &relayer,
+        mock_integrator::id(),
+        mock_adapter::id(),
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(0)) // Already in use
+    );
 }
 
 /// This test checks `disabled_adapter` attempting to pick up as well, since the `outstanding_tranceivers`
@@ -243,3 +342,64 @@ async fn test_pick_up_message_all_already_picked_up() {
         TransactionError::InstructionError(0, InstructionError::Custom(3012)) // AccountNotInitialized
     );
 }
+
+#[tokio::test]
+async fn test_pick_up_message_unsupported_consistency_level() {
+    let (
+        mut context,
+        payer,
+        _,
+        integrator_program_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        adapter_pda,
+        _,
+        chain_id,
+    ) = setup_test_environment().await;
+
+    // Send a message at a level the mock adapter does not support
+    let outbox_message = Keypair::new();
+    let (sequence_tracker_pda, _) = SequenceTracker::pda(&mock_integrator::id());
+    let consistency_level = 200;
+    assert!(!mock_adapter::SUPPORTED_CONSISTENCY_LEVELS.contains(&consistency_level));
+
+    send_message(
+        &mut context,
+        &payer,
+        integrator_program_pda,
+        integrator_chain_config_pda,
+        &outbox_message,
+        sequence_tracker_pda,
+        chain_id,
+        [1u8; 32],
+        [2u8; 32],
+        consistency_level,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let result = pick_up_message(
+        &mut context,
+        &payer,
+        outbox_message.pubkey(),
+        adapter_info_pda,
+        adapter_pda,
+        payer.pubkey(),
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::UnsupportedConsistencyLevel.into())
+        )
+    );
+
+    // The message stays outstanding for the adapter
+    let outbox_message: OutboxMessage =
+        get_account(&mut context.banks_client, outbox_message.pubkey()).await;
+    assert_eq!(outbox_message.consistency_level, consistency_level);
+    assert!(outbox_message.outstanding_adapters.get(0).unwrap());
+}
@@ -0,0 +1,255 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::enable_adapter::enable_send_adapter;
+use crate::instructions::pick_up_message::pick_up_message;
+use crate::instructions::pick_up_messages::pick_up_messages;
+use crate::instructions::register::register;
+use crate::instructions::send_message::send_message;
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::instructions::BatchMode;
+use endpoint::state::{
+    AdapterInfo, AdapterStats, IntegratorChainConfig, IntegratorConfig, SequenceTracker,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 2;
+
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Pubkey, Pubkey) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_program_id = mock_adapter::id();
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    let (adapter_pda, _) = Pubkey::find_program_address(&[b"adapter_pda"], &adapter_program_id);
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_send_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID).0,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    (context, payer, adapter_info_pda, adapter_pda)
+}
+
+/// Sends `count` messages, returning their outbox message accounts
+async fn send_messages(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    count: usize,
+) -> Vec<Keypair> {
+    let integrator_program_id = mock_integrator::id();
+    let (integrator_program_pda, _) =
+        Pubkey::find_program_address(&[b"endpoint_integrator"], &integrator_program_id);
+    let (sequence_tracker_pda, _) = SequenceTracker::pda(&integrator_program_id);
+
+    let mut outbox_messages = Vec::with_capacity(count);
+    for _ in 0..count {
+        let outbox_message = Keypair::new();
+        send_message(
+            context,
+            payer,
+            integrator_program_pda,
+            IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID).0,
+            &outbox_message,
+            sequence_tracker_pda,
+            CHAIN_ID,
+            [1u8; 32],
+            [2u8; 32],
+            1,
+            None,
+        )
+        .await
+        .unwrap();
+        outbox_messages.push(outbox_message);
+    }
+    outbox_messages
+}
+
+#[tokio::test]
+async fn test_pick_up_messages_success() {
+    let (mut context, payer, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+    let outbox_messages = send_messages(&mut context, &payer, 3).await;
+
+    let batch: Vec<_> = outbox_messages
+        .iter()
+        .map(|outbox_message| (outbox_message.pubkey(), payer.pubkey()))
+        .collect();
+    pick_up_messages(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        &batch,
+        BatchMode::AllOrNothing,
+    )
+    .await
+    .unwrap();
+
+    // Every outbox message is closed, since the adapter is the only one enabled
+    for outbox_message in &outbox_messages {
+        let account = context
+            .banks_client
+            .get_account(outbox_message.pubkey())
+            .await
+            .unwrap();
+        assert!(account.is_none());
+    }
+
+    let (adapter_stats_pda, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let adapter_stats: AdapterStats =
+        get_account(&mut context.banks_client, adapter_stats_pda).await;
+    assert_eq!(adapter_stats.messages_picked_up, 3);
+}
+
+#[tokio::test]
+async fn test_pick_up_messages_skip_done() {
+    let (mut context, payer, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+    let outbox_messages = send_messages(&mut context, &payer, 2).await;
+
+    pick_up_message(
+        &mut context,
+        &payer,
+        outbox_messages[0].pubkey(),
+        adapter_info_pda,
+        adapter_pda,
+        payer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let batch: Vec<_> = outbox_messages
+        .iter()
+        .map(|outbox_message| (outbox_message.pubkey(), payer.pubkey()))
+        .collect();
+    pick_up_messages(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        &batch,
+        BatchMode::SkipDone,
+    )
+    .await
+    .unwrap();
+
+    let (adapter_stats_pda, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let adapter_stats: AdapterStats =
+        get_account(&mut context.banks_client, adapter_stats_pda).await;
+    assert_eq!(adapter_stats.messages_picked_up, 2);
+}
+
+#[tokio::test]
+async fn test_pick_up_messages_all_or_nothing_already_picked_up() {
+    let (mut context, payer, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+    let outbox_messages = send_messages(&mut context, &payer, 2).await;
+
+    pick_up_message(
+        &mut context,
+        &payer,
+        outbox_messages[0].pubkey(),
+        adapter_info_pda,
+        adapter_pda,
+        payer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let batch: Vec<_> = outbox_messages
+        .iter()
+        .map(|outbox_message| (outbox_message.pubkey(), payer.pubkey()))
+        .collect();
+    let result = pick_up_messages(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        &batch,
+        BatchMode::AllOrNothing,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::MessageAlreadyPickedUp.into())
+        )
+    );
+
+    // The second message is still outstanding
+    let account = context
+        .banks_client
+        .get_account(outbox_messages[1].pubkey())
+        .await
+        .unwrap();
+    assert!(account.is_some());
+}
+
+#[tokio::test]
+async fn test_pick_up_messages_wrong_refund_recipient() {
+    let (mut context, payer, adapter_info_pda, adapter_pda) = setup_test_environment().await;
+    let outbox_messages = send_messages(&mut context, &payer, 1).await;
+
+    let result = pick_up_messages(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        &[(outbox_messages[0].pubkey(), Keypair::new().pubkey())],
+        BatchMode::AllOrNothing,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::InvalidBatchAccounts.into())
+        )
+    );
+}
@@ -6,13 +6,17 @@ mod instructions;
 use crate::instructions::add_adapter::add_adapter;
 use crate::instructions::attest_message::attest_message;
 use crate::instructions::enable_adapter::enable_recv_adapter;
+use crate::instructions::exec_message::exec_message;
 use crate::instructions::recv_message::recv_message;
 use crate::instructions::register::register;
 
 use anchor_lang::prelude::*;
 use common::setup::{get_account, setup};
 use endpoint::error::EndpointError;
-use endpoint::state::{AdapterInfo, AttestationInfo, IntegratorChainConfig, IntegratorConfig};
+use endpoint::state::{
+    AdapterInfo, AttestationInfo, IntegratorChainConfig, IntegratorConfig, MessageExpiry,
+    SequenceRecord,
+};
 use solana_program_test::*;
 use solana_sdk::{
     instruction::InstructionError, signature::Keypair, signer::Signer,
@@ -127,6 +131,8 @@ async fn test_recv_message_success() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
+        vec![],
     )
     .await
     .unwrap();
@@ -138,6 +144,7 @@ async fn test_recv_message_success() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
     ));
 
     // Now, receive the message
@@ -150,6 +157,8 @@ async fn test_recv_message_success() {
         sequence,
         dst_chain,
         payload_hash,
+        None,
+        false,
     )
     .await;
 
@@ -208,6 +217,8 @@ async fn test_recv_message_already_executed() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
+        vec![],
     )
     .await
     .unwrap();
@@ -219,6 +230,7 @@ async fn test_recv_message_already_executed() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
     ));
 
     recv_message(
@@ -230,6 +242,8 @@ async fn test_recv_message_already_executed() {
         sequence,
         dst_chain,
         payload_hash,
+        None,
+        false,
     )
     .await
     .unwrap();
@@ -244,6 +258,8 @@ async fn test_recv_message_already_executed() {
         sequence,
         dst_chain,
         payload_hash,
+        None,
+        false,
     )
     .await;
 
@@ -276,6 +292,7 @@ async fn test_recv_message_no_attestation() {
         dst_chain,
         dst_addr,
         payload_hash,
+        None,
     ));
 
     // Try to receive a message that hasn't been attested
@@ -288,6 +305,8 @@ async fn test_recv_message_no_attestation() {
         sequence,
         dst_chain,
         payload_hash,
+        None,
+        false,
     )
     .await;
 
@@ -298,3 +317,271 @@ async fn test_recv_message_no_attestation() {
         TransactionError::InstructionError(_, InstructionError::Custom(3012))
     ));
 }
+
+#[tokio::test]
+async fn test_recv_message_equivocated_sequence() {
+    let (
+        mut context,
+        payer,
+        _,
+        _,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        adapter_pda,
+        chain_id,
+    ) = setup_test_environment().await;
+
+    let src_chain: u16 = chain_id;
+    let src_addr = [1u8; 32];
+    let sequence: u64 = 1;
+    let dst_chain = 1;
+    let dst_addr = mock_integrator::id().to_bytes();
+    let payload_hash = [3u8; 32];
+    let conflicting_payload_hash = [4u8; 32];
+
+    // Attest two different payloads for the same source sequence
+    for hash in [payload_hash, conflicting_payload_hash] {
+        attest_message(
+            &mut context,
+            &payer,
+            adapter_info_pda,
+            adapter_pda,
+            integrator_chain_config_pda,
+            src_chain,
+            src_addr,
+            sequence,
+            dst_chain,
+            dst_addr,
+            hash,
+            None,
+            vec![],
+        )
+        .await
+        .unwrap();
+    }
+
+    // Verify that the sequence is flagged and keeps the first payload hash
+    let (sequence_record_pda, _) =
+        SequenceRecord::pda(&mock_integrator::id(), src_chain, src_addr, sequence);
+    let sequence_record: SequenceRecord =
+        get_account(&mut context.banks_client, sequence_record_pda).await;
+    assert!(sequence_record.equivocated);
+    assert_eq!(sequence_record.first_payload_hash, payload_hash);
+
+    let (attestation_info_pda, _) = AttestationInfo::pda(AttestationInfo::compute_message_hash(
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        dst_addr,
+        payload_hash,
+        None,
+    ));
+
+    // Receiving without the override fails
+    let result = recv_message(
+        &mut context,
+        &payer,
+        attestation_info_pda,
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        payload_hash,
+        None,
+        false,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::SequenceEquivocated.into())
+        )
+    );
+
+    // Receiving with the override succeeds
+    let result = recv_message(
+        &mut context,
+        &payer,
+        attestation_info_pda,
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        payload_hash,
+        None,
+        true,
+    )
+    .await;
+
+    assert!(result.is_ok(), "recv_message failed: {:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_recv_message_before_expiry() {
+    let (
+        mut context,
+        payer,
+        _,
+        _,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        adapter_pda,
+        chain_id,
+    ) = setup_test_environment().await;
+
+    let src_chain: u16 = chain_id;
+    let src_addr = [1u8; 32];
+    let sequence: u64 = 1;
+    let dst_chain = 1;
+    let dst_addr = mock_integrator::id().to_bytes();
+    let payload_hash = [3u8; 32];
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let expiry = Some(MessageExpiry::Slot(clock.slot + 1_000));
+
+    attest_message(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        integrator_chain_config_pda,
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        dst_addr,
+        payload_hash,
+        expiry,
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    // The expiry is committed into the message hash
+    let (attestation_info_pda, _) = AttestationInfo::pda(AttestationInfo::compute_message_hash(
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        dst_addr,
+        payload_hash,
+        expiry,
+    ));
+    let attestation_info: AttestationInfo =
+        get_account(&mut context.banks_client, attestation_info_pda).await;
+    assert_eq!(attestation_info.expiry, expiry);
+
+    let result = recv_message(
+        &mut context,
+        &payer,
+        attestation_info_pda,
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        payload_hash,
+        expiry,
+        false,
+    )
+    .await;
+
+    assert!(result.is_ok(), "recv_message failed: {:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_recv_message_expired() {
+    let (
+        mut context,
+        payer,
+        _,
+        _,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        adapter_pda,
+        chain_id,
+    ) = setup_test_environment().await;
+
+    let src_chain: u16 = chain_id;
+    let src_addr = [1u8; 32];
+    let sequence: u64 = 1;
+    let dst_chain = 1;
+    let dst_addr = mock_integrator::id().to_bytes();
+    let payload_hash = [3u8; 32];
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let deadline = clock.slot + 10;
+    let expiry = Some(MessageExpiry::Slot(deadline));
+
+    attest_message(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        integrator_chain_config_pda,
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        dst_addr,
+        payload_hash,
+        expiry,
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    // Move past the deadline
+    context.warp_to_slot(deadline + 1).unwrap();
+
+    let (attestation_info_pda, _) = AttestationInfo::pda(AttestationInfo::compute_message_hash(
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        dst_addr,
+        payload_hash,
+        expiry,
+    ));
+
+    let result = recv_message(
+        &mut context,
+        &payer,
+        attestation_info_pda,
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        payload_hash,
+        expiry,
+        false,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::MessageExpired.into())
+        )
+    );
+
+    // The integrator can still consume the expired message
+    exec_message(
+        &mut context,
+        &payer,
+        src_chain,
+        src_addr,
+        sequence,
+        dst_chain,
+        dst_addr,
+        payload_hash,
+        expiry,
+    )
+    .await
+    .unwrap();
+
+    let attestation_info: AttestationInfo =
+        get_account(&mut context.banks_client, attestation_info_pda).await;
+    assert!(attestation_info.executed);
+}
@@ -0,0 +1,396 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::attest_message_compressed::attest_message_compressed;
+use crate::instructions::enable_adapter::enable_recv_adapter;
+use crate::instructions::enable_replay_windows::enable_replay_windows;
+use crate::instructions::init_attestation_tree::init_attestation_tree;
+use crate::instructions::recv_message_compressed::recv_message_compressed;
+use crate::instructions::register::register;
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::client::attestation_tree::{AttestationTreeIndex, AttestationTreeProof};
+use endpoint::error::EndpointError;
+use endpoint::instructions::BatchedMessage;
+use endpoint::state::{
+    AdapterInfo, AttestationInfo, AttestationLeaf, AttestationTree, CompressedAttestation,
+    IntegratorChainConfig, IntegratorConfig, ReplayWindow, SequenceRecord,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 2;
+const SRC_ADDR: [u8; 32] = [1u8; 32];
+const PAYLOAD_HASH: [u8; 32] = [3u8; 32];
+
+struct TestEnvironment {
+    context: ProgramTestContext,
+    payer: Keypair,
+    admin: Keypair,
+    attestation_tree: Pubkey,
+    index: AttestationTreeIndex,
+}
+
+async fn setup_test_environment() -> TestEnvironment {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_program_id = mock_adapter::id();
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let attestation_tree = Keypair::new();
+    init_attestation_tree(
+        &mut context,
+        &admin,
+        &payer,
+        &attestation_tree,
+        integrator_program_id,
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+
+    TestEnvironment {
+        context,
+        payer,
+        admin,
+        attestation_tree: attestation_tree.pubkey(),
+        index: AttestationTreeIndex::new(),
+    }
+}
+
+fn message(sequence: u64) -> BatchedMessage {
+    BatchedMessage {
+        src_addr: SRC_ADDR,
+        sequence,
+        payload_hash: PAYLOAD_HASH,
+        expiry: None,
+    }
+}
+
+fn message_hash(sequence: u64) -> [u8; 32] {
+    AttestationInfo::compute_message_hash(
+        CHAIN_ID,
+        SRC_ADDR,
+        sequence,
+        1,
+        mock_integrator::id().to_bytes(),
+        PAYLOAD_HASH,
+        None,
+    )
+}
+
+/// Attests to the message with a new leaf and returns the leaf's index
+async fn attest(env: &mut TestEnvironment, sequence: u64) -> u32 {
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&mock_integrator::id(), CHAIN_ID);
+
+    let proof = AttestationTreeProof {
+        root: env.index.root(),
+        leaf_index: 0,
+        proof: vec![],
+    };
+    attest_message_compressed(
+        &mut env.context,
+        &env.payer,
+        env.attestation_tree,
+        CHAIN_ID,
+        &message(sequence),
+        &proof,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let integrator_chain_config: IntegratorChainConfig =
+        get_account(&mut env.context.banks_client, integrator_chain_config_pda).await;
+    let mut leaf = CompressedAttestation::new(
+        message_hash(sequence),
+        integrator_chain_config.config_epoch,
+        integrator_chain_config.recv_adapter_bitmap,
+    );
+    leaf.attested_adapters.set(0, true).unwrap();
+
+    let leaf_index = env.index.leaf_count();
+    env.index.set_leaf(leaf_index, leaf);
+    leaf_index
+}
+
+async fn recv(
+    env: &mut TestEnvironment,
+    sequence: u64,
+    leaf_index: u32,
+    leaf: CompressedAttestation,
+) -> std::result::Result<(), BanksClientError> {
+    let proof = env.index.proof(leaf_index).unwrap();
+    recv_message_compressed(
+        &mut env.context,
+        &env.payer,
+        env.attestation_tree,
+        CHAIN_ID,
+        &message(sequence),
+        &proof,
+        leaf,
+    )
+    .await
+}
+
+async fn enable_windows(env: &mut TestEnvironment) {
+    enable_replay_windows(
+        &mut env.context,
+        &env.admin,
+        &env.payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+    )
+    .await
+    .unwrap();
+}
+
+async fn tree_root(env: &mut TestEnvironment) -> [u8; 32] {
+    let mut data = env
+        .context
+        .banks_client
+        .get_account(env.attestation_tree)
+        .await
+        .unwrap()
+        .expect("account not found")
+        .data;
+    AttestationTree::load_tree_mut(&mut data)
+        .unwrap()
+        .get_root()
+}
+
+fn assert_endpoint_error(result: std::result::Result<(), BanksClientError>, error: EndpointError) {
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(error.into()))
+    );
+}
+
+#[tokio::test]
+async fn test_recv_message_compressed_success() {
+    let mut env = setup_test_environment().await;
+    enable_windows(&mut env).await;
+    attest(&mut env, 1).await;
+    let leaf_index = attest(&mut env, 2).await;
+    attest(&mut env, 3).await;
+
+    let leaf = *env.index.leaf(leaf_index).unwrap();
+    recv(&mut env, 2, leaf_index, leaf).await.unwrap();
+
+    // The leaf is marked as executed
+    env.index.set_leaf(
+        leaf_index,
+        CompressedAttestation {
+            executed: true,
+            ..leaf
+        },
+    );
+    assert_eq!(tree_root(&mut env).await, env.index.root());
+
+    let (replay_window_pda, _) = ReplayWindow::pda(
+        &mock_integrator::id(),
+        CHAIN_ID,
+        SRC_ADDR,
+        ReplayWindow::window_index(2),
+    );
+    let replay_window: ReplayWindow =
+        get_account(&mut env.context.banks_client, replay_window_pda).await;
+    assert!(replay_window.is_executed(2));
+    assert!(!replay_window.is_executed(1));
+
+    // The leaf and sequence records are closed, as the window now guards against replays
+    for account in [
+        AttestationLeaf::pda(&message_hash(2)).0,
+        SequenceRecord::pda(&mock_integrator::id(), CHAIN_ID, SRC_ADDR, 2).0,
+    ] {
+        assert!(env
+            .context
+            .banks_client
+            .get_account(account)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_recv_message_compressed_already_executed() {
+    let mut env = setup_test_environment().await;
+    enable_windows(&mut env).await;
+    let leaf_index = attest(&mut env, 1).await;
+
+    let leaf = *env.index.leaf(leaf_index).unwrap();
+    recv(&mut env, 1, leaf_index, leaf).await.unwrap();
+    let executed = CompressedAttestation {
+        executed: true,
+        ..leaf
+    };
+    env.index.set_leaf(leaf_index, executed);
+
+    // The message's leaf record is closed, so the executed leaf cannot be passed again
+    let result = recv(&mut env, 1, leaf_index, executed).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ErrorCode::AccountNotInitialized.into())
+        )
+    );
+
+    // A second leaf for the same message is stopped by the replay window
+    let proof = AttestationTreeProof {
+        root: env.index.root(),
+        leaf_index: 0,
+        proof: vec![],
+    };
+    let result = attest_message_compressed(
+        &mut env.context,
+        &env.payer,
+        env.attestation_tree,
+        CHAIN_ID,
+        &message(1),
+        &proof,
+        None,
+    )
+    .await;
+    assert_endpoint_error(result, EndpointError::AlreadyExecuted);
+}
+
+#[tokio::test]
+async fn test_recv_message_compressed_equivocated() {
+    let mut env = setup_test_environment().await;
+    enable_windows(&mut env).await;
+    let leaf_index = attest(&mut env, 1).await;
+
+    // Another payload is attested for the same sequence
+    let proof = AttestationTreeProof {
+        root: env.index.root(),
+        leaf_index: 0,
+        proof: vec![],
+    };
+    attest_message_compressed(
+        &mut env.context,
+        &env.payer,
+        env.attestation_tree,
+        CHAIN_ID,
+        &BatchedMessage {
+            payload_hash: [4u8; 32],
+            ..message(1)
+        },
+        &proof,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let (sequence_record_pda, _) =
+        SequenceRecord::pda(&mock_integrator::id(), CHAIN_ID, SRC_ADDR, 1);
+    let sequence_record: SequenceRecord =
+        get_account(&mut env.context.banks_client, sequence_record_pda).await;
+    assert!(sequence_record.equivocated);
+    assert_eq!(sequence_record.first_payload_hash, PAYLOAD_HASH);
+
+    // The tree now holds the conflicting leaf as well
+    let mut conflicting_leaf = *env.index.leaf(leaf_index).unwrap();
+    conflicting_leaf.message_hash = AttestationInfo::compute_message_hash(
+        CHAIN_ID,
+        SRC_ADDR,
+        1,
+        1,
+        mock_integrator::id().to_bytes(),
+        [4u8; 32],
+        None,
+    );
+    env.index.set_leaf(leaf_index + 1, conflicting_leaf);
+
+    let leaf = *env.index.leaf(leaf_index).unwrap();
+    let result = recv(&mut env, 1, leaf_index, leaf).await;
+    assert_endpoint_error(result, EndpointError::SequenceEquivocated);
+}
+
+#[tokio::test]
+async fn test_recv_message_compressed_invalid_proof() {
+    let mut env = setup_test_environment().await;
+    enable_windows(&mut env).await;
+    let leaf_index = attest(&mut env, 1).await;
+
+    // A leaf claiming a different set of attestations does not match the tree
+    let mut leaf = *env.index.leaf(leaf_index).unwrap();
+    leaf.attested_adapters.set(1, true).unwrap();
+    let result = recv(&mut env, 1, leaf_index, leaf).await;
+    assert_endpoint_error(result, EndpointError::InvalidAttestationProof);
+}
+
+#[tokio::test]
+async fn test_recv_message_compressed_wrong_leaf() {
+    let mut env = setup_test_environment().await;
+    enable_windows(&mut env).await;
+    let leaf_index = attest(&mut env, 1).await;
+    let other_leaf_index = attest(&mut env, 2).await;
+
+    // The leaf of sequence 1 cannot be used for sequence 2
+    let leaf = *env.index.leaf(leaf_index).unwrap();
+    let result = recv(&mut env, 2, other_leaf_index, leaf).await;
+    assert_endpoint_error(result, EndpointError::InvalidMessageHash);
+}
+
+#[tokio::test]
+async fn test_recv_message_compressed_requires_replay_windows() {
+    let mut env = setup_test_environment().await;
+    let leaf_index = attest(&mut env, 1).await;
+
+    let leaf = *env.index.leaf(leaf_index).unwrap();
+    let result = recv(&mut env, 1, leaf_index, leaf).await;
+    assert_endpoint_error(result, EndpointError::ReplayWindowsRequired);
+}
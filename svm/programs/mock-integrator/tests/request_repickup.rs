@@ -0,0 +1,328 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::cancel_message::cancel_message;
+use crate::instructions::close_sent_message_record::close_sent_message_record;
+use crate::instructions::enable_adapter::enable_send_adapter;
+use crate::instructions::pick_up_message::pick_up_message;
+use crate::instructions::register::register;
+use crate::instructions::request_repickup::request_repickup;
+use crate::instructions::send_message::{send_message_with_options, SendMessageOptions};
+
+use anchor_lang::prelude::*;
+use common::error::custom_error;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::state::{
+    AdapterInfo, IntegratorChainConfig, IntegratorConfig, OutboxMessage, SentMessageRecord,
+    SequenceTracker,
+};
+use mock_integrator::InvokeRequestRepickupArgs;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+
+const DST_CHAIN: u16 = 2;
+const DST_ADDR: [u8; 32] = [1u8; 32];
+const PAYLOAD_HASH: [u8; 32] = [2u8; 32];
+
+/// Registers the mock integrator with the mock adapter enabled for sending to `DST_CHAIN`, and
+/// sends a recorded message under sequence 0 that the adapter has picked up
+async fn setup_test_environment() -> (ProgramTestContext, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, DST_CHAIN);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_send_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        DST_CHAIN,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let outbox_message = Keypair::new();
+
+    send_message_with_options(
+        &mut context,
+        &payer,
+        Some(&outbox_message),
+        DST_CHAIN,
+        DST_ADDR,
+        PAYLOAD_HASH,
+        SendMessageOptions {
+            consistency_level: 1,
+            record: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    pick_up(&mut context, &payer, &outbox_message)
+        .await
+        .unwrap();
+
+    (context, payer)
+}
+
+async fn pick_up(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    outbox_message: &Keypair,
+) -> std::result::Result<(), BanksClientError> {
+    let (adapter_info_pda, _) = AdapterInfo::pda(&mock_integrator::id(), &mock_adapter::id());
+    let (adapter_pda, _) = Pubkey::find_program_address(&[b"adapter_pda"], &mock_adapter::id());
+
+    pick_up_message(
+        context,
+        payer,
+        outbox_message.pubkey(),
+        adapter_info_pda,
+        adapter_pda,
+        payer.pubkey(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_request_repickup_success() {
+    let (mut context, payer) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+
+    let (sent_message_record_pda, _) = SentMessageRecord::pda(&integrator_program_id, 0);
+    let sent_message_record: SentMessageRecord =
+        get_account(&mut context.banks_client, sent_message_record_pda).await;
+    assert_eq!(sent_message_record.sequence, 0);
+    assert_eq!(sent_message_record.consistency_level, 1);
+
+    let outbox_message = Keypair::new();
+    request_repickup(
+        &mut context,
+        &payer,
+        &outbox_message,
+        InvokeRequestRepickupArgs {
+            sequence: 0,
+            dst_chain: DST_CHAIN,
+            dst_addr: DST_ADDR,
+            payload_hash: PAYLOAD_HASH,
+            expiry: None,
+            adapters: 0b1,
+        },
+    )
+    .await
+    .unwrap();
+
+    // The message is handed to the adapter again under its original sequence
+    let outbox: OutboxMessage =
+        get_account(&mut context.banks_client, outbox_message.pubkey()).await;
+    assert_eq!(outbox.src_addr, integrator_program_id.to_bytes());
+    assert_eq!(outbox.sequence, 0);
+    assert_eq!(outbox.dst_chain, DST_CHAIN);
+    assert_eq!(outbox.dst_addr, DST_ADDR);
+    assert_eq!(outbox.payload_hash, PAYLOAD_HASH);
+    assert_eq!(outbox.consistency_level, 1);
+    assert_eq!(outbox.outstanding_adapters.as_value(), 0b1);
+    assert_eq!(outbox.refund_recipient, payer.pubkey());
+
+    let (sequence_tracker_pda, _) = SequenceTracker::pda(&integrator_program_id);
+    let sequence_tracker: SequenceTracker =
+        get_account(&mut context.banks_client, sequence_tracker_pda).await;
+    assert_eq!(sequence_tracker.sequence, 1);
+    assert_eq!(sequence_tracker.outbox_messages, 2);
+
+    pick_up(&mut context, &payer, &outbox_message)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_request_repickup_forged_message() {
+    let (mut context, payer) = setup_test_environment().await;
+
+    let result = request_repickup(
+        &mut context,
+        &payer,
+        &Keypair::new(),
+        InvokeRequestRepickupArgs {
+            sequence: 0,
+            dst_chain: DST_CHAIN,
+            dst_addr: DST_ADDR,
+            payload_hash: [3u8; 32],
+            expiry: None,
+            adapters: 0b1,
+        },
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        custom_error(0, EndpointError::SentMessageMismatch)
+    );
+}
+
+#[tokio::test]
+async fn test_request_repickup_cancelled_message() {
+    let (mut context, payer) = setup_test_environment().await;
+    let args = || InvokeRequestRepickupArgs {
+        sequence: 0,
+        dst_chain: DST_CHAIN,
+        dst_addr: DST_ADDR,
+        payload_hash: PAYLOAD_HASH,
+        expiry: None,
+        adapters: 0b1,
+    };
+
+    let outbox_message = Keypair::new();
+    request_repickup(&mut context, &payer, &outbox_message, args())
+        .await
+        .unwrap();
+    cancel_message(
+        &mut context,
+        &payer,
+        outbox_message.pubkey(),
+        payer.pubkey(),
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // The record is marked as cancelled, so the message cannot be handed to adapters again
+    let (sent_message_record_pda, _) = SentMessageRecord::pda(&mock_integrator::id(), 0);
+    let sent_message_record: SentMessageRecord =
+        get_account(&mut context.banks_client, sent_message_record_pda).await;
+    assert!(sent_message_record.cancelled);
+
+    let result = request_repickup(&mut context, &payer, &Keypair::new(), args()).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        custom_error(0, EndpointError::SentMessageCancelled)
+    );
+}
+
+#[tokio::test]
+async fn test_request_repickup_adapter_not_enabled() {
+    let (mut context, payer) = setup_test_environment().await;
+
+    for adapters in [0, 0b10] {
+        let result = request_repickup(
+            &mut context,
+            &payer,
+            &Keypair::new(),
+            InvokeRequestRepickupArgs {
+                sequence: 0,
+                dst_chain: DST_CHAIN,
+                dst_addr: DST_ADDR,
+                payload_hash: PAYLOAD_HASH,
+                expiry: None,
+                adapters: adapters,
+            },
+        )
+        .await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            custom_error(0, EndpointError::AdapterNotEnabled)
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_close_sent_message_record() {
+    let (mut context, payer) = setup_test_environment().await;
+    let (sent_message_record_pda, _) = SentMessageRecord::pda(&mock_integrator::id(), 0);
+
+    // Only the account that paid for the record can receive its rent
+    let result = close_sent_message_record(&mut context, &payer, 0, Keypair::new().pubkey()).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ErrorCode::ConstraintHasOne.into())
+        )
+    );
+
+    let record_lamports = context
+        .banks_client
+        .get_balance(sent_message_record_pda)
+        .await
+        .unwrap();
+    let before = context
+        .banks_client
+        .get_balance(payer.pubkey())
+        .await
+        .unwrap();
+
+    close_sent_message_record(&mut context, &payer, 0, payer.pubkey())
+        .await
+        .unwrap();
+
+    // The payer gets the record's rent back, less the transaction fee
+    let after = context
+        .banks_client
+        .get_balance(payer.pubkey())
+        .await
+        .unwrap();
+    assert!(after > before && after <= before + record_lamports);
+    let account = context
+        .banks_client
+        .get_account(sent_message_record_pda)
+        .await
+        .unwrap();
+    assert!(account.is_none());
+
+    // The message can no longer be handed to adapters again
+    let result = request_repickup(
+        &mut context,
+        &payer,
+        &Keypair::new(),
+        InvokeRequestRepickupArgs {
+            sequence: 0,
+            dst_chain: DST_CHAIN,
+            dst_addr: DST_ADDR,
+            payload_hash: PAYLOAD_HASH,
+            expiry: None,
+            adapters: 0b1,
+        },
+    )
+    .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,357 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::attest_message::attest_message;
+use crate::instructions::enable_adapter::{enable_recv_adapter, enable_send_adapter};
+use crate::instructions::pick_up_message::pick_up_message;
+use crate::instructions::register::register;
+use crate::instructions::retire_adapter::retire_adapter;
+use crate::instructions::send_message::send_message;
+
+use anchor_lang::prelude::*;
+use common::error::custom_error;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::state::{
+    AdapterIndex, AdapterInfo, IntegratorChainConfig, IntegratorConfig, SequenceTracker,
+};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const SEND_CHAIN: u16 = 1;
+const RECV_CHAIN: u16 = 2;
+
+fn chain_config(chain_id: u16) -> Pubkey {
+    IntegratorChainConfig::pda(&mock_integrator::id(), chain_id).0
+}
+
+/// Registers the mock integrator with the mock adapter enabled for sending to `SEND_CHAIN`
+/// and receiving from `RECV_CHAIN`
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_send_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        chain_config(SEND_CHAIN),
+        adapter_info_pda,
+        SEND_CHAIN,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        chain_config(RECV_CHAIN),
+        adapter_info_pda,
+        RECV_CHAIN,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    (context, payer, admin)
+}
+
+#[tokio::test]
+async fn test_retire_adapter_success() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+
+    let send_config: IntegratorChainConfig =
+        get_account(&mut context.banks_client, chain_config(SEND_CHAIN)).await;
+    let recv_config: IntegratorChainConfig =
+        get_account(&mut context.banks_client, chain_config(RECV_CHAIN)).await;
+
+    retire_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        adapter_program_id,
+        &[chain_config(SEND_CHAIN), chain_config(RECV_CHAIN)],
+    )
+    .await
+    .unwrap();
+
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    let adapter_info: AdapterInfo = get_account(&mut context.banks_client, adapter_info_pda).await;
+    assert!(adapter_info.retired);
+
+    // The adapter is disabled on both chains, moving each to a new epoch
+    for (chain_id, before) in [(SEND_CHAIN, send_config), (RECV_CHAIN, recv_config)] {
+        let after: IntegratorChainConfig =
+            get_account(&mut context.banks_client, chain_config(chain_id)).await;
+        assert_eq!(after.send_adapter_bitmap.as_value(), 0);
+        assert_eq!(after.recv_adapter_bitmap.as_value(), 0);
+        assert_eq!(after.config_epoch, before.config_epoch + 1);
+    }
+
+    // The chains are removed from the adapter's reverse index
+    let (adapter_index_pda, _) = AdapterIndex::pda(&adapter_program_id, &integrator_program_id);
+    let adapter_index: AdapterIndex =
+        get_account(&mut context.banks_client, adapter_index_pda).await;
+    assert!(adapter_index.chains.is_empty());
+}
+
+#[tokio::test]
+async fn test_retire_adapter_twice() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+
+    retire_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        mock_adapter::id(),
+        &[chain_config(SEND_CHAIN), chain_config(RECV_CHAIN)],
+    )
+    .await
+    .unwrap();
+
+    let result = retire_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        mock_adapter::id(),
+        &[chain_config(SEND_CHAIN), chain_config(RECV_CHAIN)],
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        custom_error(0, EndpointError::AdapterRetired)
+    );
+}
+
+#[tokio::test]
+async fn test_retire_adapter_non_admin() {
+    let (mut context, payer, _) = setup_test_environment().await;
+    let non_admin = Keypair::new();
+
+    let result = retire_adapter(
+        &mut context,
+        &non_admin,
+        &payer,
+        mock_integrator::id(),
+        mock_adapter::id(),
+        &[chain_config(SEND_CHAIN)],
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        custom_error(0, EndpointError::CallerNotAuthorized)
+    );
+}
+
+#[tokio::test]
+async fn test_enable_retired_adapter() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+
+    retire_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        adapter_program_id,
+        &[chain_config(SEND_CHAIN), chain_config(RECV_CHAIN)],
+    )
+    .await
+    .unwrap();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    let result = enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        chain_config(RECV_CHAIN),
+        adapter_info_pda,
+        RECV_CHAIN,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        custom_error(0, EndpointError::AdapterRetired)
+    );
+}
+
+#[tokio::test]
+async fn test_retire_adapter_missing_chain() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+
+    // Every indexed chain must be passed, so that none keeps the adapter enabled
+    let result = retire_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        mock_adapter::id(),
+        &[chain_config(SEND_CHAIN)],
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        custom_error(0, EndpointError::InvalidIntegratorChainConfig)
+    );
+
+    // In the order of the index
+    let result = retire_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        mock_adapter::id(),
+        &[chain_config(RECV_CHAIN), chain_config(SEND_CHAIN)],
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        custom_error(0, EndpointError::InvalidIntegratorChainConfig)
+    );
+}
+
+#[tokio::test]
+async fn test_attest_message_retired_adapter() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+
+    retire_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        adapter_program_id,
+        &[chain_config(SEND_CHAIN), chain_config(RECV_CHAIN)],
+    )
+    .await
+    .unwrap();
+
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    let (adapter_pda, _) = Pubkey::find_program_address(&[b"adapter_pda"], &adapter_program_id);
+    let result = attest_message(
+        &mut context,
+        &payer,
+        adapter_info_pda,
+        adapter_pda,
+        chain_config(RECV_CHAIN),
+        RECV_CHAIN,
+        [1u8; 32],
+        1,
+        endpoint::CHAIN_ID,
+        integrator_program_id.to_bytes(),
+        [3u8; 32],
+        None,
+        vec![],
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        custom_error(0, EndpointError::AdapterRetired)
+    );
+}
+
+#[tokio::test]
+async fn test_pick_up_message_retired_adapter() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+    let adapter_program_id = mock_adapter::id();
+
+    let outbox_message = Keypair::new();
+    let (integrator_program_pda, _) =
+        Pubkey::find_program_address(&[b"endpoint_integrator"], &integrator_program_id);
+    let (sequence_tracker_pda, _) = SequenceTracker::pda(&integrator_program_id);
+    send_message(
+        &mut context,
+        &payer,
+        integrator_program_pda,
+        chain_config(SEND_CHAIN),
+        &outbox_message,
+        sequence_tracker_pda,
+        SEND_CHAIN,
+        [1u8; 32],
+        [2u8; 32],
+        1,
+        None,
+    )
+    .await
+    .unwrap();
+
+    retire_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        adapter_program_id,
+        &[chain_config(SEND_CHAIN), chain_config(RECV_CHAIN)],
+    )
+    .await
+    .unwrap();
+
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    let (adapter_pda, _) = Pubkey::find_program_address(&[b"adapter_pda"], &adapter_program_id);
+    // The retired adapter can still pick up the message it was outstanding for, which closes it
+    pick_up_message(
+        &mut context,
+        &payer,
+        outbox_message.pubkey(),
+        adapter_info_pda,
+        adapter_pda,
+        payer.pubkey(),
+    )
+    .await
+    .unwrap();
+    assert!(context
+        .banks_client
+        .get_account(outbox_message.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+}
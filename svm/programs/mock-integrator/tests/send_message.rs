@@ -6,13 +6,16 @@ mod instructions;
 use crate::instructions::add_adapter::add_adapter;
 use crate::instructions::enable_adapter::enable_send_adapter;
 use crate::instructions::register::register;
-use crate::instructions::send_message::send_message;
+use crate::instructions::send_message::{
+    send_message, send_message_with_options, SendMessageOptions,
+};
 
 use anchor_lang::prelude::*;
 use common::setup::{get_account, setup};
 use endpoint::error::EndpointError;
 use endpoint::state::{
-    AdapterInfo, IntegratorChainConfig, IntegratorConfig, OutboxMessage, SequenceTracker,
+    AdapterInfo, AttestationInfo, IntegratorChainConfig, IntegratorConfig, MessageExpiry,
+    OutboxMessage, SequenceTracker,
 };
 use solana_program_test::*;
 use solana_sdk::{
@@ -121,6 +124,8 @@ async fn test_send_message_success() {
         chain_id,
         dst_addr,
         payload_hash,
+        1,
+        None,
     )
     .await;
 
@@ -137,6 +142,7 @@ async fn test_send_message_success() {
     assert_eq!(outbox_msg.dst_chain, chain_id);
     assert_eq!(outbox_msg.dst_addr, dst_addr);
     assert_eq!(outbox_msg.payload_hash, payload_hash);
+    assert_eq!(outbox_msg.consistency_level, 1);
     assert_eq!(outbox_msg.outstanding_adapters.as_value(), 1);
 }
 
@@ -172,6 +178,8 @@ async fn test_send_message_increments_sequence() {
         chain_id,
         dst_addr,
         payload_hash,
+        1,
+        None,
     )
     .await
     .unwrap();
@@ -193,6 +201,8 @@ async fn test_send_message_increments_sequence() {
         chain_id,
         dst_addr,
         payload_hash,
+        1,
+        None,
     )
     .await
     .unwrap();
@@ -206,6 +216,21 @@ async fn test_send_message_increments_sequence() {
     let sequence_tracker: SequenceTracker =
         get_account(&mut context.banks_client, sequence_tracker_pda).await;
     assert_eq!(sequence_tracker.sequence, 2); // Next available sequence
+
+    // Verify both messages were folded into the outbound accumulator in order
+    let outbound_accumulator = (0..2).fold([0u8; 32], |outbound_accumulator, sequence| {
+        let message_hash = AttestationInfo::compute_message_hash(
+            endpoint::CHAIN_ID,
+            integrator_program_id.to_bytes(),
+            sequence,
+            chain_id,
+            dst_addr,
+            payload_hash,
+            None,
+        );
+        SequenceTracker::fold_outbound_accumulator(outbound_accumulator, message_hash)
+    });
+    assert_eq!(sequence_tracker.outbound_accumulator, outbound_accumulator);
 }
 
 #[tokio::test]
@@ -250,6 +275,8 @@ async fn test_send_message_no_enabled_adapters() {
         chain_id,
         dst_addr,
         payload_hash,
+        1,
+        None,
     )
     .await;
 
@@ -295,6 +322,8 @@ async fn test_send_message_unregistered_chain() {
         unregistered_chain_id,
         dst_addr,
         payload_hash,
+        1,
+        None,
     )
     .await;
 
@@ -303,3 +332,83 @@ async fn test_send_message_unregistered_chain() {
         TransactionError::InstructionError(0, InstructionError::Custom(3012)) // AccountNotInitialized
     );
 }
+
+#[tokio::test]
+async fn test_send_message_expiry() {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+
+    let (admin, integrator_program_id, integrator_config_pda, _, _, _, _, chain_id) =
+        initialize_test_environment(&mut context).await;
+
+    // Enable a send adapter for another chain
+    let other_chain_id: u16 = 2;
+    let adapter_program_id = Keypair::new().pubkey();
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+    enable_send_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        IntegratorChainConfig::pda(&integrator_program_id, other_chain_id).0,
+        adapter_info_pda,
+        other_chain_id,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let options = || SendMessageOptions {
+        consistency_level: 1,
+        expiry: Some(MessageExpiry::Timestamp(1_000)),
+        ..Default::default()
+    };
+
+    // An expiry is accepted for a message to this chain
+    let outbox_message = Keypair::new();
+    send_message_with_options(
+        &mut context,
+        &payer,
+        Some(&outbox_message),
+        chain_id,
+        [1u8; 32],
+        [2u8; 32],
+        options(),
+    )
+    .await
+    .unwrap();
+    let outbox_msg: OutboxMessage =
+        get_account(&mut context.banks_client, outbox_message.pubkey()).await;
+    assert_eq!(outbox_msg.expiry, Some(MessageExpiry::Timestamp(1_000)));
+
+    // The endpoint of the other chain does not check it
+    let result = send_message_with_options(
+        &mut context,
+        &payer,
+        Some(&Keypair::new()),
+        other_chain_id,
+        [1u8; 32],
+        [2u8; 32],
+        options(),
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::ExpiryNotSupported.into())
+        )
+    );
+}
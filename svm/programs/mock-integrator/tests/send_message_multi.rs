@@ -0,0 +1,250 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::disable_adapter::disable_send_adapter;
+use crate::instructions::enable_adapter::enable_send_adapter;
+use crate::instructions::register::register;
+use crate::instructions::send_message_multi::send_message_multi;
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::instructions::MessageDestination;
+use endpoint::state::{
+    AdapterInfo, AttestationInfo, IntegratorChainConfig, IntegratorConfig, OutboxMessage,
+    SentMessageRecord, SequenceTracker,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+
+const CHAIN_IDS: [u16; 3] = [2, 3, 4];
+const PAYLOAD_HASH: [u8; 32] = [2u8; 32];
+
+/// Registers the integrator with one adapter, send-enabled on every chain in `CHAIN_IDS`
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Keypair, Pubkey) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_program_id = Keypair::new().pubkey();
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    for chain_id in CHAIN_IDS {
+        enable_send_adapter(
+            &mut context,
+            &admin,
+            &payer,
+            integrator_config_pda,
+            IntegratorChainConfig::pda(&integrator_program_id, chain_id).0,
+            adapter_info_pda,
+            chain_id,
+            adapter_program_id,
+            integrator_program_id,
+        )
+        .await
+        .unwrap();
+    }
+
+    (context, payer, admin, adapter_program_id)
+}
+
+fn destinations() -> Vec<MessageDestination> {
+    CHAIN_IDS
+        .iter()
+        .map(|&dst_chain| MessageDestination {
+            dst_chain,
+            dst_addr: [dst_chain as u8; 32],
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_send_message_multi_success() {
+    let (mut context, payer, _, _) = setup_test_environment().await;
+    let outbox_messages: Vec<_> = CHAIN_IDS.iter().map(|_| Keypair::new()).collect();
+
+    send_message_multi(
+        &mut context,
+        &payer,
+        destinations(),
+        &outbox_messages,
+        PAYLOAD_HASH,
+        false,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Each destination gets its own outbox message and the next sequence
+    for (index, (destination, outbox_message)) in
+        destinations().iter().zip(&outbox_messages).enumerate()
+    {
+        let outbox_msg: OutboxMessage =
+            get_account(&mut context.banks_client, outbox_message.pubkey()).await;
+        assert_eq!(outbox_msg.src_addr, mock_integrator::id().to_bytes());
+        assert_eq!(outbox_msg.sequence, index as u64);
+        assert_eq!(outbox_msg.dst_chain, destination.dst_chain);
+        assert_eq!(outbox_msg.dst_addr, destination.dst_addr);
+        assert_eq!(outbox_msg.payload_hash, PAYLOAD_HASH);
+        assert_eq!(outbox_msg.outstanding_adapters.as_value(), 1);
+        assert_eq!(outbox_msg.refund_recipient, payer.pubkey());
+    }
+
+    let (sequence_tracker_pda, _) = SequenceTracker::pda(&mock_integrator::id());
+    let sequence_tracker: SequenceTracker =
+        get_account(&mut context.banks_client, sequence_tracker_pda).await;
+    assert_eq!(sequence_tracker.sequence, CHAIN_IDS.len() as u64);
+}
+
+#[tokio::test]
+async fn test_send_message_multi_record() {
+    let (mut context, payer, _, _) = setup_test_environment().await;
+    let outbox_messages: Vec<_> = CHAIN_IDS.iter().map(|_| Keypair::new()).collect();
+
+    send_message_multi(
+        &mut context,
+        &payer,
+        destinations(),
+        &outbox_messages,
+        PAYLOAD_HASH,
+        true,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Each message is recorded under its sequence, paid for by the payer
+    for (index, outbox_message) in outbox_messages.iter().enumerate() {
+        let outbox_msg: OutboxMessage =
+            get_account(&mut context.banks_client, outbox_message.pubkey()).await;
+        let (sent_message_record_pda, _) =
+            SentMessageRecord::pda(&mock_integrator::id(), index as u64);
+        let sent_message_record: SentMessageRecord =
+            get_account(&mut context.banks_client, sent_message_record_pda).await;
+        assert_eq!(
+            sent_message_record.integrator_program_id,
+            mock_integrator::id()
+        );
+        assert_eq!(sent_message_record.sequence, index as u64);
+        assert_eq!(
+            sent_message_record.message_hash,
+            AttestationInfo::compute_message_hash(
+                endpoint::CHAIN_ID,
+                outbox_msg.src_addr,
+                outbox_msg.sequence,
+                outbox_msg.dst_chain,
+                outbox_msg.dst_addr,
+                outbox_msg.payload_hash,
+                outbox_msg.expiry,
+            )
+        );
+        assert_eq!(sent_message_record.consistency_level, 1);
+        assert_eq!(sent_message_record.payer, payer.pubkey());
+    }
+}
+
+#[tokio::test]
+async fn test_send_message_multi_no_enabled_adapters() {
+    let (mut context, payer, admin, adapter_program_id) = setup_test_environment().await;
+    let integrator_program_id = mock_integrator::id();
+
+    // Disable the only adapter on the last destination chain
+    disable_send_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        IntegratorConfig::pda(&integrator_program_id).0,
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_IDS[2]).0,
+        AdapterInfo::pda(&integrator_program_id, &adapter_program_id).0,
+        CHAIN_IDS[2],
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let outbox_messages: Vec<_> = CHAIN_IDS.iter().map(|_| Keypair::new()).collect();
+    let result = send_message_multi(
+        &mut context,
+        &payer,
+        destinations(),
+        &outbox_messages,
+        PAYLOAD_HASH,
+        false,
+        &[],
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::AdapterNotEnabled.into())
+        )
+    );
+
+    // No outbox message was created
+    let account = context
+        .banks_client
+        .get_account(outbox_messages[0].pubkey())
+        .await
+        .unwrap();
+    assert!(account.is_none());
+}
+
+#[tokio::test]
+async fn test_send_message_multi_missing_accounts() {
+    let (mut context, payer, _, _) = setup_test_environment().await;
+
+    // One outbox message short of the destinations
+    let outbox_messages: Vec<_> = CHAIN_IDS[1..].iter().map(|_| Keypair::new()).collect();
+    let result = send_message_multi(
+        &mut context,
+        &payer,
+        destinations(),
+        &outbox_messages,
+        PAYLOAD_HASH,
+        false,
+        &[],
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::InvalidBatchAccounts.into())
+        )
+    );
+}
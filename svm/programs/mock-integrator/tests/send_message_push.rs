@@ -0,0 +1,232 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::enable_adapter::enable_send_adapter;
+use crate::instructions::register::register;
+use crate::instructions::send_message::{send_message_with_options, SendMessageOptions};
+
+use adapter_interface::OUTBOUND_AUTHORITY_SEED;
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::state::{
+    AdapterInfo, AdapterStats, IntegratorChainConfig, IntegratorConfig, OutboxMessage,
+    SequenceTracker,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 2;
+const DST_ADDR: [u8; 32] = [1u8; 32];
+const PAYLOAD_HASH: [u8; 32] = [2u8; 32];
+
+/// Registers the integrator and adds the mock adapter and `other_adapters`, enabling the
+/// adapters in `enabled` for sending to `CHAIN_ID`
+async fn setup_test_environment(
+    other_adapters: &[Pubkey],
+    enabled: &[Pubkey],
+) -> (ProgramTestContext, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapters = [&[mock_adapter::id()], other_adapters].concat();
+    for adapter_program_id in adapters {
+        let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+        add_adapter(
+            &mut context,
+            &admin,
+            &payer,
+            integrator_config_pda,
+            adapter_info_pda,
+            integrator_program_id,
+            adapter_program_id,
+        )
+        .await
+        .unwrap();
+
+        if enabled.contains(&adapter_program_id) {
+            enable_send_adapter(
+                &mut context,
+                &admin,
+                &payer,
+                integrator_config_pda,
+                IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID).0,
+                adapter_info_pda,
+                CHAIN_ID,
+                adapter_program_id,
+                integrator_program_id,
+            )
+            .await
+            .unwrap();
+        }
+    }
+
+    (context, payer)
+}
+
+async fn push(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    outbox_message: Option<&Keypair>,
+    pushed_adapters: &[Pubkey],
+) -> std::result::Result<(), BanksClientError> {
+    send_message_with_options(
+        context,
+        payer,
+        outbox_message,
+        CHAIN_ID,
+        DST_ADDR,
+        PAYLOAD_HASH,
+        SendMessageOptions {
+            consistency_level: 1,
+            pushed_adapters,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_send_message_push_all_adapters() {
+    let (mut context, payer) = setup_test_environment(&[], &[mock_adapter::id()]).await;
+
+    push(&mut context, &payer, None, &[mock_adapter::id()])
+        .await
+        .unwrap();
+
+    // The message was sent without an outbox message
+    let (sequence_tracker_pda, _) = SequenceTracker::pda(&mock_integrator::id());
+    let sequence_tracker: SequenceTracker =
+        get_account(&mut context.banks_client, sequence_tracker_pda).await;
+    assert_eq!(sequence_tracker.sequence, 1);
+
+    let (adapter_stats_pda, _) = AdapterStats::pda(&mock_integrator::id(), &mock_adapter::id());
+    let adapter_stats: AdapterStats =
+        get_account(&mut context.banks_client, adapter_stats_pda).await;
+    assert_eq!(adapter_stats.messages_picked_up, 1);
+}
+
+#[tokio::test]
+async fn test_send_message_push_some_adapters() {
+    let other_adapter = Keypair::new().pubkey();
+    let (mut context, payer) =
+        setup_test_environment(&[other_adapter], &[mock_adapter::id(), other_adapter]).await;
+    let outbox_message = Keypair::new();
+
+    push(
+        &mut context,
+        &payer,
+        Some(&outbox_message),
+        &[mock_adapter::id()],
+    )
+    .await
+    .unwrap();
+
+    // Only the adapter that was not pushed the message has to pick it up
+    let outbox_msg: OutboxMessage =
+        get_account(&mut context.banks_client, outbox_message.pubkey()).await;
+    let other_adapter_info: AdapterInfo = get_account(
+        &mut context.banks_client,
+        AdapterInfo::pda(&mock_integrator::id(), &other_adapter).0,
+    )
+    .await;
+    assert_eq!(outbox_msg.sequence, 0);
+    assert_eq!(
+        outbox_msg.outstanding_adapters.as_value(),
+        1 << other_adapter_info.index
+    );
+}
+
+#[tokio::test]
+async fn test_send_message_push_unneeded_outbox_message() {
+    let (mut context, payer) = setup_test_environment(&[], &[mock_adapter::id()]).await;
+    let outbox_message = Keypair::new();
+
+    let result = push(
+        &mut context,
+        &payer,
+        Some(&outbox_message),
+        &[mock_adapter::id()],
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::InvalidOutboxMessage.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_send_message_push_adapter_not_enabled() {
+    let other_adapter = Keypair::new().pubkey();
+    let (mut context, payer) = setup_test_environment(&[other_adapter], &[other_adapter]).await;
+    let outbox_message = Keypair::new();
+
+    let result = push(
+        &mut context,
+        &payer,
+        Some(&outbox_message),
+        &[mock_adapter::id()],
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::AdapterNotEnabled.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_send_message_push_shared_outbound_authority() {
+    let (mut context, payer) = setup_test_environment(&[], &[mock_adapter::id()]).await;
+
+    // The endpoint only signs with the outbound authority of the sending integrator
+    let (shared_outbound_authority, _) =
+        Pubkey::find_program_address(&[OUTBOUND_AUTHORITY_SEED], &endpoint::id());
+    let result = send_message_with_options(
+        &mut context,
+        &payer,
+        None,
+        CHAIN_ID,
+        DST_ADDR,
+        PAYLOAD_HASH,
+        SendMessageOptions {
+            consistency_level: 1,
+            pushed_adapters: &[mock_adapter::id()],
+            outbound_authority: Some(shared_outbound_authority),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(2006)) // ConstraintSeeds
+    );
+}
@@ -0,0 +1,246 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::attest_message::attest_message;
+use crate::instructions::enable_adapter::enable_recv_adapter;
+use crate::instructions::recv_message::recv_message;
+use crate::instructions::register::register;
+use crate::instructions::set_ordered_delivery::set_ordered_delivery;
+
+use anchor_lang::prelude::*;
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::state::{
+    AdapterInfo, AttestationInfo, InboundCursor, IntegratorChainConfig, IntegratorConfig,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 2;
+const SRC_ADDR: [u8; 32] = [1u8; 32];
+
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Keypair, Pubkey, Pubkey) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_program_id = mock_adapter::id();
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    (
+        context,
+        payer,
+        admin,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+    )
+}
+
+async fn attest_and_recv(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    integrator_chain_config_pda: Pubkey,
+    adapter_info_pda: Pubkey,
+    sequence: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let (adapter_pda, _) = Pubkey::find_program_address(&[b"adapter_pda"], &mock_adapter::id());
+    let dst_addr = mock_integrator::id().to_bytes();
+    let payload_hash = [3u8; 32];
+
+    attest_message(
+        context,
+        payer,
+        adapter_info_pda,
+        adapter_pda,
+        integrator_chain_config_pda,
+        CHAIN_ID,
+        SRC_ADDR,
+        sequence,
+        1,
+        dst_addr,
+        payload_hash,
+        None,
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let (attestation_info_pda, _) = AttestationInfo::pda(AttestationInfo::compute_message_hash(
+        CHAIN_ID,
+        SRC_ADDR,
+        sequence,
+        1,
+        dst_addr,
+        payload_hash,
+        None,
+    ));
+
+    recv_message(
+        context,
+        payer,
+        attestation_info_pda,
+        CHAIN_ID,
+        SRC_ADDR,
+        sequence,
+        1,
+        payload_hash,
+        None,
+        false,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_set_ordered_delivery_success() {
+    let (mut context, payer, admin, integrator_chain_config_pda, _) =
+        setup_test_environment().await;
+
+    set_ordered_delivery(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+        true,
+    )
+    .await
+    .unwrap();
+
+    let integrator_chain_config: IntegratorChainConfig =
+        get_account(&mut context.banks_client, integrator_chain_config_pda).await;
+    assert!(integrator_chain_config.ordered_delivery);
+
+    set_ordered_delivery(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+        false,
+    )
+    .await
+    .unwrap();
+
+    let integrator_chain_config: IntegratorChainConfig =
+        get_account(&mut context.banks_client, integrator_chain_config_pda).await;
+    assert!(!integrator_chain_config.ordered_delivery);
+}
+
+#[tokio::test]
+async fn test_set_ordered_delivery_non_admin() {
+    let (mut context, payer, _, _, _) = setup_test_environment().await;
+    let non_admin = Keypair::new();
+
+    let result = set_ordered_delivery(
+        &mut context,
+        &non_admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+        true,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::CallerNotAuthorized.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_ordered_delivery_rejects_out_of_order_sequence() {
+    let (mut context, payer, admin, integrator_chain_config_pda, adapter_info_pda) =
+        setup_test_environment().await;
+
+    set_ordered_delivery(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+        true,
+    )
+    .await
+    .unwrap();
+
+    attest_and_recv(
+        &mut context,
+        &payer,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        0,
+    )
+    .await
+    .unwrap();
+
+    // Sequence 2 cannot be received before sequence 1
+    let result = attest_and_recv(
+        &mut context,
+        &payer,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        2,
+    )
+    .await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::OutOfOrderSequence.into())
+        )
+    );
+
+    let (inbound_cursor_pda, _) = InboundCursor::pda(&mock_integrator::id(), CHAIN_ID, SRC_ADDR);
+    let inbound_cursor: InboundCursor =
+        get_account(&mut context.banks_client, inbound_cursor_pda).await;
+    assert_eq!(inbound_cursor.next_sequence, 1);
+}
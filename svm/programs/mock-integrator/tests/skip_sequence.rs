@@ -0,0 +1,189 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+mod instructions;
+
+use crate::instructions::add_adapter::add_adapter;
+use crate::instructions::enable_adapter::enable_recv_adapter;
+use crate::instructions::exec_message::exec_message;
+use crate::instructions::register::register;
+use crate::instructions::set_ordered_delivery::set_ordered_delivery;
+use crate::instructions::skip_sequence::skip_sequence;
+
+use common::setup::{get_account, setup};
+use endpoint::error::EndpointError;
+use endpoint::state::{AdapterInfo, InboundCursor, IntegratorChainConfig, IntegratorConfig};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, transaction::TransactionError,
+};
+
+const CHAIN_ID: u16 = 2;
+const SRC_ADDR: [u8; 32] = [1u8; 32];
+
+async fn setup_test_environment() -> (ProgramTestContext, Keypair, Keypair) {
+    let mut context = setup().await;
+    let payer = context.payer.insecure_clone();
+    let admin = Keypair::new();
+    let integrator_program_id = mock_integrator::id();
+
+    let (integrator_config_pda, _) = IntegratorConfig::pda(&integrator_program_id);
+    let (integrator_chain_config_pda, _) =
+        IntegratorChainConfig::pda(&integrator_program_id, CHAIN_ID);
+
+    register(
+        &mut context,
+        &payer,
+        &admin,
+        integrator_config_pda,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    let adapter_program_id = mock_adapter::id();
+    let (adapter_info_pda, _) = AdapterInfo::pda(&integrator_program_id, &adapter_program_id);
+
+    add_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        adapter_info_pda,
+        integrator_program_id,
+        adapter_program_id,
+    )
+    .await
+    .unwrap();
+
+    enable_recv_adapter(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_config_pda,
+        integrator_chain_config_pda,
+        adapter_info_pda,
+        CHAIN_ID,
+        adapter_program_id,
+        integrator_program_id,
+    )
+    .await
+    .unwrap();
+
+    set_ordered_delivery(
+        &mut context,
+        &admin,
+        &payer,
+        integrator_program_id,
+        CHAIN_ID,
+        true,
+    )
+    .await
+    .unwrap();
+
+    (context, payer, admin)
+}
+
+async fn exec(
+    context: &mut ProgramTestContext,
+    payer: &Keypair,
+    sequence: u64,
+) -> std::result::Result<(), BanksClientError> {
+    exec_message(
+        context,
+        payer,
+        CHAIN_ID,
+        SRC_ADDR,
+        sequence,
+        1,
+        mock_integrator::id().to_bytes(),
+        [3u8; 32],
+        None,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_skip_sequence_success() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+
+    skip_sequence(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+        SRC_ADDR,
+        1,
+    )
+    .await
+    .unwrap();
+
+    // The skipped sequences can no longer be delivered
+    let result = exec(&mut context, &payer, 1).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::OutOfOrderSequence.into())
+        )
+    );
+
+    exec(&mut context, &payer, 2).await.unwrap();
+
+    let (inbound_cursor_pda, _) = InboundCursor::pda(&mock_integrator::id(), CHAIN_ID, SRC_ADDR);
+    let inbound_cursor: InboundCursor =
+        get_account(&mut context.banks_client, inbound_cursor_pda).await;
+    assert_eq!(inbound_cursor.next_sequence, 3);
+}
+
+#[tokio::test]
+async fn test_skip_sequence_already_delivered() {
+    let (mut context, payer, admin) = setup_test_environment().await;
+
+    exec(&mut context, &payer, 0).await.unwrap();
+
+    let result = skip_sequence(
+        &mut context,
+        &admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+        SRC_ADDR,
+        0,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::SequenceAlreadyDelivered.into())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_skip_sequence_non_admin() {
+    let (mut context, payer, _) = setup_test_environment().await;
+    let non_admin = Keypair::new();
+
+    let result = skip_sequence(
+        &mut context,
+        &non_admin,
+        &payer,
+        mock_integrator::id(),
+        CHAIN_ID,
+        SRC_ADDR,
+        0,
+    )
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EndpointError::CallerNotAuthorized.into())
+        )
+    );
+}